@@ -0,0 +1,87 @@
+use colored::*;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use crate::config::{self, Config};
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+
+pub async fn AddAlias(
+    cache: &Cache,
+    appConfig: &Config,
+    configPath: &Path,
+    name: &str,
+    licenseId: &str,
+) -> Result<(), AppError> {
+    let nameLower = name.to_lowercase();
+
+    // An alias named like a real license would make that license unreachable
+    if cache.licenses.contains_key(&nameLower) {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+            "'{}' is already a license ID; pick another alias name",
+            name
+        ))));
+    }
+
+    if appConfig.ResolveAlias(licenseId).is_some() {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+            "'{}' is itself an alias; point '{}' at a license ID instead",
+            licenseId, name
+        ))));
+    }
+
+    // The target may live in a private source that is not synced into this cache yet
+    if !cache.licenses.contains_key(&licenseId.to_lowercase()) {
+        eprintln!(
+            "{} '{}' is not in the current cache; the alias is saved anyway.",
+            "Warning:".yellow(),
+            licenseId
+        );
+    }
+
+    let mut aliases = appConfig.aliases.clone();
+    aliases.retain(|alias, _| !alias.eq_ignore_ascii_case(name));
+    aliases.insert(nameLower.clone(), licenseId.to_string());
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Saving alias {} = {} to {:?}", nameLower, licenseId, configPath);
+    }
+
+    config::SaveAliases(configPath, &aliases)?;
+    println!("Alias '{}' now stands for {}.", nameLower.green(), licenseId.cyan());
+
+    Ok(())
+}
+
+pub async fn RemoveAlias(appConfig: &Config, configPath: &Path, name: &str) -> Result<(), AppError> {
+    let mut aliases = appConfig.aliases.clone();
+    let before = aliases.len();
+    aliases.retain(|alias, _| !alias.eq_ignore_ascii_case(name));
+
+    if aliases.len() == before {
+        println!("No alias named '{}'.", name.yellow());
+        return Ok(());
+    }
+
+    config::SaveAliases(configPath, &aliases)?;
+    println!("Removed alias '{}'.", name.green());
+
+    Ok(())
+}
+
+pub async fn ListAliases(appConfig: &Config) -> Result<(), AppError> {
+    if appConfig.aliases.is_empty() {
+        println!("No aliases defined. Add one with `getlicense alias add <NAME> <SPDX_ID>`.");
+        return Ok(());
+    }
+
+    let mut aliases: Vec<_> = appConfig.aliases.iter().collect();
+    aliases.sort();
+
+    println!("{}", "License Aliases:".bold());
+    for (alias, target) in aliases {
+        println!("  {} -> {}", alias.green(), target.cyan());
+    }
+
+    Ok(())
+}
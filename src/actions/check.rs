@@ -0,0 +1,303 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::config::{self, OrgConfig};
+use crate::constants::{LICENSE_FILE_CANDIDATES, MANIFEST_FILENAMES};
+use crate::dependencies;
+use crate::display;
+use crate::error::{ActionError, AppError, CacheError};
+use crate::manifest;
+use crate::models::{Cache, LicenseEntry};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    // Not configured, or nothing to check against
+    Skip,
+}
+
+// One step of `check`, e.g. "manifest".
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CheckOutcome {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+fn Outcome(name: &'static str, status: CheckStatus, detail: String) -> CheckOutcome {
+    CheckOutcome { name, status, detail }
+}
+
+// Which optional checks run, from .getlicense.toml and the command line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CheckSelection {
+    pub headers: bool,
+    pub dependencies: bool,
+}
+
+fn CheckPlaceholders(text: &str, licenseEntry: &LicenseEntry) -> CheckOutcome {
+    let unfilled: Vec<&str> = licenseEntry
+        .placeholdersInBody
+        .iter()
+        .map(String::as_str)
+        .filter(|placeholder| text.contains(placeholder))
+        .collect();
+
+    if unfilled.is_empty() {
+        Outcome("placeholders", CheckStatus::Pass, "every placeholder is filled".to_string())
+    } else {
+        Outcome("placeholders", CheckStatus::Fail, format!("unfilled: {}", unfilled.join(", ")))
+    }
+}
+
+fn CheckManifests(projectDir: &Path, licenseEntry: &LicenseEntry) -> Result<CheckOutcome, AppError> {
+    let manifestLicenses = manifest::ReadManifestLicenses(projectDir)?;
+
+    if manifestLicenses.is_empty() {
+        return Ok(Outcome(
+            "manifest",
+            CheckStatus::Skip,
+            format!("no license field in {}", MANIFEST_FILENAMES.join(", ")),
+        ));
+    }
+
+    // Same rule as `verify`: the declared expression only has to mention the license
+    let disagreeing: Vec<String> = manifestLicenses
+        .iter()
+        .filter(|declared| {
+            !manifest::SpdxExpressionIds(&declared.expression)
+                .iter()
+                .any(|id| id.eq_ignore_ascii_case(&licenseEntry.spdxId))
+        })
+        .map(|declared| format!("{} declares \"{}\"", declared.manifestPath.display(), declared.expression))
+        .collect();
+
+    Ok(if disagreeing.is_empty() {
+        Outcome("manifest", CheckStatus::Pass, format!("{} manifest(s) declare {}", manifestLicenses.len(), licenseEntry.spdxId))
+    } else {
+        Outcome("manifest", CheckStatus::Fail, disagreeing.join("; "))
+    })
+}
+
+fn CheckSourceHeaders(projectDir: &Path, expectedSpdxId: &str, excludeGlobs: &[String]) -> Result<CheckOutcome, AppError> {
+    let files = super::headers::CollectFilesToCheck(&[projectDir.to_path_buf()], excludeGlobs)?;
+
+    let problems = files
+        .par_iter()
+        .map(|file| super::headers::CheckFileHeader(file, expectedSpdxId))
+        .collect::<Result<Vec<_>, AppError>>()?;
+    let lacking = problems.iter().flatten().count();
+
+    let detail = format!("{} of {} file(s) carry SPDX-License-Identifier: {}", files.len() - lacking, files.len(), expectedSpdxId);
+    let status = if lacking == 0 { CheckStatus::Pass } else { CheckStatus::Fail };
+
+    Ok(Outcome("headers", status, detail))
+}
+
+fn CheckDependencies(projectDir: &Path, org: &OrgConfig) -> Result<CheckOutcome, AppError> {
+    if org.approvedLicenses.is_empty() && org.bannedLicenses.is_empty() {
+        return Ok(Outcome(
+            "dependencies",
+            CheckStatus::Skip,
+            "no approved_licenses or banned_licenses policy configured".to_string(),
+        ));
+    }
+
+    let packages = dependencies::ReadProjectDependencies(projectDir)?;
+
+    // Packages that declare no license are left to `notices`, which warns about them
+    let violations: Vec<String> = packages
+        .iter()
+        .filter_map(|package| {
+            let ids = manifest::SpdxExpressionIds(package.license.as_deref()?);
+            let violates = ids.iter().any(|id| org.IsBanned(id)) || !ids.iter().any(|id| org.IsApproved(id));
+            violates.then(|| format!("{} {} ({})", package.name, package.version, package.license.as_deref().unwrap_or_default()))
+        })
+        .collect();
+
+    Ok(if violations.is_empty() {
+        Outcome("dependencies", CheckStatus::Pass, format!("{} dependencies follow the license policy", packages.len()))
+    } else {
+        Outcome("dependencies", CheckStatus::Fail, format!("outside the policy: {}", violations.join(", ")))
+    })
+}
+
+// Checks that need the license file are skipped when it is missing or unrecognized, so a
+// single problem fails once.
+pub fn RunChecks(
+    cache: &Cache,
+    projectDir: &Path,
+    expectedSpdxId: Option<&str>,
+    selection: CheckSelection,
+    excludeGlobs: &[String],
+    org: &OrgConfig,
+) -> Result<Vec<CheckOutcome>, AppError> {
+    let mut outcomes = Vec::new();
+
+    let licensePath: Option<PathBuf> = super::validate::FindLicenseFile(projectDir);
+    let identified = match &licensePath {
+        None => {
+            outcomes.push(Outcome(
+                "license file",
+                CheckStatus::Fail,
+                format!("none found (looked for {})", LICENSE_FILE_CANDIDATES.join(", ")),
+            ));
+            None
+        }
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, path.clone())))?;
+
+            match super::validate::IdentifyLicenseText(cache, &text) {
+                None => {
+                    outcomes.push(Outcome(
+                        "license file",
+                        CheckStatus::Fail,
+                        format!("{} does not match any known license template", path.display()),
+                    ));
+                    None
+                }
+                Some(entry) if expectedSpdxId.is_some_and(|expected| !entry.spdxId.eq_ignore_ascii_case(expected)) => {
+                    outcomes.push(Outcome(
+                        "license file",
+                        CheckStatus::Fail,
+                        format!("{} contains {}, expected {}", path.display(), entry.spdxId, expectedSpdxId.unwrap_or_default()),
+                    ));
+                    Some((entry, text))
+                }
+                Some(entry) => {
+                    outcomes.push(Outcome("license file", CheckStatus::Pass, format!("{} contains {}", path.display(), entry.spdxId)));
+                    Some((entry, text))
+                }
+            }
+        }
+    };
+
+    let unidentified = || "needs a recognized license file".to_string();
+
+    match &identified {
+        Some((entry, text)) => {
+            outcomes.push(CheckPlaceholders(text, entry));
+            outcomes.push(CheckManifests(projectDir, entry)?);
+        }
+        None => {
+            outcomes.push(Outcome("placeholders", CheckStatus::Skip, unidentified()));
+            outcomes.push(Outcome("manifest", CheckStatus::Skip, unidentified()));
+        }
+    }
+
+    if selection.headers {
+        let expected = expectedSpdxId.map(str::to_string).or_else(|| identified.as_ref().map(|(entry, _)| entry.spdxId.clone()));
+
+        outcomes.push(match expected {
+            Some(expected) => CheckSourceHeaders(projectDir, &expected, excludeGlobs)?,
+            None => Outcome("headers", CheckStatus::Skip, format!("{}, or pass --license", unidentified())),
+        });
+    } else {
+        outcomes.push(Outcome("headers", CheckStatus::Skip, "not enabled (pass --headers or set headers = true under [check])".to_string()));
+    }
+
+    if selection.dependencies {
+        outcomes.push(CheckDependencies(projectDir, org)?);
+    } else {
+        outcomes.push(Outcome(
+            "dependencies",
+            CheckStatus::Skip,
+            "not enabled (pass --dependencies or set dependencies = true under [check])".to_string(),
+        ));
+    }
+
+    Ok(outcomes)
+}
+
+// The project in the current directory, like `validate` and `check-headers`.
+pub async fn CheckProject(
+    cache: &Cache,
+    expectedSpdxId: Option<&str>,
+    selection: CheckSelection,
+    org: &OrgConfig,
+    asJson: bool,
+) -> Result<(), AppError> {
+    let projectDir = Path::new(".");
+    let projectConfig = config::LoadProjectConfig(projectDir)?;
+    let selection = CheckSelection {
+        headers: selection.headers || projectConfig.check.headers,
+        dependencies: selection.dependencies || projectConfig.check.dependencies,
+    };
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Running compliance checks ({:?})", selection);
+    }
+
+    let outcomes = RunChecks(cache, projectDir, expectedSpdxId, selection, &projectConfig.headers.exclude, org)?;
+
+    if asJson {
+        let json = serde_json::to_string_pretty(&outcomes)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintCheckSummary(&outcomes);
+    }
+
+    let failed = outcomes.iter().filter(|outcome| outcome.status == CheckStatus::Fail).count();
+
+    if failed > 0 {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} of {} check(s) failed",
+            failed,
+            outcomes.len()
+        ))));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::sync::Arc;
+
+    #[test]
+    fn TestRunChecks() {
+        let mut cache = Cache::default();
+        cache.licenses.insert(
+            "mit".into(),
+            Arc::new(LicenseEntry::ForTest(
+                "MIT",
+                "---\ntitle: MIT License\n---\n\nCopyright (c) [year] [fullname]\n\nPermission is hereby granted.\n",
+            )),
+        );
+
+        let dir = TempDir::New("check");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("LICENSE"), "Copyright (c) [year] Jane Doe\n\nPermission is hereby granted.\n").unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\nlicense = \"Apache-2.0\"\n").unwrap();
+        fs::write(dir.join("src/lib.rs"), "// SPDX-License-Identifier: MIT\n").unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let selection = CheckSelection { headers: true, dependencies: false };
+        let outcomes = RunChecks(&cache, &dir, None, selection, &[], &OrgConfig::default()).unwrap();
+
+        let statuses: Vec<(&str, CheckStatus)> = outcomes.iter().map(|o| (o.name, o.status)).collect();
+        assert_eq!(statuses, [
+            ("license file", CheckStatus::Pass),
+            ("placeholders", CheckStatus::Fail),
+            ("manifest", CheckStatus::Fail),
+            ("headers", CheckStatus::Fail),
+            ("dependencies", CheckStatus::Skip),
+        ]);
+        assert_eq!(outcomes[1].detail, "unfilled: [year]");
+        assert!(outcomes[3].detail.starts_with("1 of 2 file(s)"));
+
+        fs::remove_file(dir.join("LICENSE")).unwrap();
+        let outcomes = RunChecks(&cache, &dir, None, CheckSelection::default(), &[], &OrgConfig::default()).unwrap();
+        assert_eq!(outcomes[0].status, CheckStatus::Fail);
+        assert!(outcomes[1..].iter().all(|o| o.status == CheckStatus::Skip));
+    }
+}
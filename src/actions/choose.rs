@@ -0,0 +1,166 @@
+use colored::*;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::actions::find::{FilterLicensesByRules, FindRanking, RankMatches, RankingFor};
+use crate::config::{self, ChooseAnswers, Config};
+use crate::constants::DEFAULT_CHOOSE_RESULTS;
+use crate::display;
+use crate::error::{ActionError, AppError};
+use crate::models::{Cache, LicenseEntry};
+use crate::permissiveness::PermissivenessWeights;
+
+fn Questions(answers: &mut ChooseAnswers) -> [(&'static str, &mut Option<bool>); 4] {
+    [
+        (
+            "May others ship modified versions of your code without publishing their source?",
+            &mut answers.closedSourceUse,
+        ),
+        ("Should the license grant patent rights explicitly?", &mut answers.patentGrant),
+        (
+            "Should running a modified version as a network service require publishing its source?",
+            &mut answers.networkCopyleft,
+        ),
+        ("Must modified files state what was changed?", &mut answers.documentChanges),
+    ]
+}
+
+// Rule tags to require and to disallow for a set of answers
+pub fn RulesFor(answers: &ChooseAnswers) -> (Vec<String>, Vec<String>) {
+    let mut require = Vec::new();
+    let mut disallow = Vec::new();
+    let mut pick = |wanted: Option<bool>, tag: &str| match wanted {
+        Some(true) => require.push(tag.to_string()),
+        Some(false) => disallow.push(tag.to_string()),
+        None => {}
+    };
+
+    pick(answers.closedSourceUse.map(|allowed| !allowed), "disclose-source");
+    // Network copyleft only matters when modified source has to be published at all
+    if answers.closedSourceUse != Some(true) {
+        pick(answers.networkCopyleft, "network-use-disclose");
+    }
+    pick(answers.documentChanges, "document-changes");
+
+    (require, disallow)
+}
+
+// y/n sets an answer, "-" clears it, Enter keeps the current one
+fn AskQuestion(question: &str, current: Option<bool>) -> Result<Option<bool>, AppError> {
+    let shown = match current {
+        Some(true) => "y",
+        Some(false) => "n",
+        None => "-",
+    };
+
+    loop {
+        print!("{} [y/n/-, Enter keeps {}] ", question, shown.bold());
+        io::stdout()
+            .flush()
+            .map_err(|e| AppError::Io(e, PathBuf::from("stdout")))?;
+
+        let mut answer = String::new();
+        let read = io::stdin()
+            .read_line(&mut answer)
+            .map_err(|e| AppError::Io(e, PathBuf::from("stdin")))?;
+
+        match answer.trim().to_lowercase().as_str() {
+            _ if read == 0 => return Ok(current),
+            "" => return Ok(current),
+            "y" | "yes" => return Ok(Some(true)),
+            "n" | "no" => return Ok(Some(false)),
+            "-" => return Ok(None),
+            _ => println!("Please answer y, n or - (no preference)."),
+        }
+    }
+}
+
+// Asks the questionnaire (or reuses saved answers) and recommends the best fitting licenses.
+// Answers come from, and are saved to, `answersFile` when given, else the [choose] table.
+pub async fn ChooseLicense(
+    cache: &Cache,
+    appConfig: &Config,
+    configPath: &Path,
+    answersFile: Option<&Path>,
+    fromSaved: bool,
+    best: Option<usize>,
+) -> Result<(), AppError> {
+    let mut answers = match answersFile {
+        Some(path) if fromSaved || path.exists() => config::LoadChooseAnswers(path)?,
+        _ => appConfig.choose.clone(),
+    };
+
+    if fromSaved {
+        if answers.IsEmpty() {
+            return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+                "no saved answers; run `getlicense choose` once to answer the questions".to_string(),
+            )));
+        }
+    } else {
+        if !io::stdin().is_terminal() {
+            return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+                "`choose` asks its questions on a terminal; pass --from-saved to reuse saved answers".to_string(),
+            )));
+        }
+
+        for (question, answer) in Questions(&mut answers) {
+            *answer = AskQuestion(question, *answer)?;
+        }
+
+        match answersFile {
+            Some(path) => config::SaveChooseAnswersFile(path, &answers)?,
+            None => config::SaveChooseAnswers(configPath, &answers)?,
+        }
+
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Action] Saved answers to {:?}", answersFile.unwrap_or(configPath));
+        }
+        println!();
+    }
+
+    let (require, disallow) = RulesFor(&answers);
+    let weights = PermissivenessWeights::WithOverrides(&appConfig.permissivenessWeights);
+
+    let mut matches: Vec<(&LicenseEntry, Option<f32>, FindRanking)> = FilterLicensesByRules(cache, &require, &disallow)?
+        .into_iter()
+        // Required as a permission: a patent-use limitation is the opposite of a grant
+        .filter(|entry| answers.patentGrant != Some(true) || entry.permissions.iter().any(|tag| tag == "patent-use"))
+        .map(|entry| (entry.as_ref(), None, RankingFor(entry, &require, &weights)))
+        .collect();
+
+    RankMatches(&mut matches);
+
+    let totalMatches = matches.len();
+    matches.truncate(best.unwrap_or(DEFAULT_CHOOSE_RESULTS));
+
+    display::PrintFindResults(&matches, totalMatches, None, &require, &disallow);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestRulesFor() {
+        let copyleft = ChooseAnswers {
+            closedSourceUse: Some(false),
+            networkCopyleft: Some(true),
+            documentChanges: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            RulesFor(&copyleft),
+            (
+                vec!["disclose-source".to_string(), "network-use-disclose".to_string()],
+                vec!["document-changes".to_string()]
+            )
+        );
+
+        // Network copyleft is moot once closed-source use is allowed
+        let permissive = ChooseAnswers { closedSourceUse: Some(true), networkCopyleft: Some(true), ..Default::default() };
+        assert_eq!(RulesFor(&permissive), (Vec::new(), vec!["disclose-source".to_string()]));
+    }
+}
@@ -1,11 +1,256 @@
 use crate::display;
-use crate::error::AppError;
-use crate::models::{Cache, LicenseEntry, RulesDataContent};
+use crate::drift;
+use crate::error::{ActionError, AppError, CacheError};
+use crate::models::{Cache, LicenseEntry, RuleSource, RulesDataContent};
+use crate::stats;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 
+// How one license's rules differ from the baseline's in one category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleDelta {
+    pub category: &'static str,
+    // Tags the license has and the baseline does not
+    pub added: Vec<String>,
+    // Tags the baseline has and the license does not
+    pub dropped: Vec<String>,
+}
+
+pub fn RuleDeltas(baseline: &LicenseEntry, other: &LicenseEntry) -> Vec<RuleDelta> {
+    [
+        ("permissions", &baseline.permissions, &other.permissions),
+        ("conditions", &baseline.conditions, &other.conditions),
+        ("limitations", &baseline.limitations, &other.limitations),
+    ]
+    .into_iter()
+    .map(|(category, base, theirs)| RuleDelta {
+        category,
+        added: theirs.iter().filter(|tag| !base.contains(tag)).cloned().collect(),
+        dropped: base.iter().filter(|tag| !theirs.contains(tag)).cloned().collect(),
+    })
+    .collect()
+}
+
+// One value per rule category, serialized in the order rules.yml uses.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct PerCategory<T> {
+    pub permissions: T,
+    pub conditions: T,
+    pub limitations: T,
+}
+
+impl<T> PerCategory<T> {
+    fn Get(&mut self, category: &str) -> &mut T {
+        match category {
+            "permissions" => &mut self.permissions,
+            "conditions" => &mut self.conditions,
+            _ => &mut self.limitations,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct DeltaJson {
+    pub added: Vec<String>,
+    pub dropped: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ComparedLicenseJson {
+    // The SPDX ID, or "<file> (<SPDX ID>)" for --files
+    pub label: String,
+    #[serde(rename = "spdx_id")]
+    pub spdxId: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    pub rules: PerCategory<Vec<RuleSource>>,
+    #[serde(rename = "vs_baseline", skip_serializing_if = "Option::is_none")]
+    pub vsBaseline: Option<PerCategory<DeltaJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<stats::LicenseStats>,
+}
+
+// Which compared licenses carry one rule tag
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RuleAgreement {
+    pub label: String,
+    pub description: String,
+    pub with: Vec<String>,
+    pub without: Vec<String>,
+    // Every compared license agrees, i.e. `without` is empty
+    pub unanimous: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ComparisonJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+    pub licenses: Vec<ComparedLicenseJson>,
+    // Category -> rule tag -> agreement, covering every tag any compared license carries
+    pub agreement: PerCategory<BTreeMap<String, RuleAgreement>>,
+}
+
+// Label and description from rules.yml; tags it does not define keep the tag as label
+fn RuleObject(rulesData: &Option<RulesDataContent>, category: &str, tag: &str) -> RuleSource {
+    rulesData
+        .as_ref()
+        .and_then(|rules| {
+            let definitions = match category {
+                "permissions" => &rules.permissions,
+                "conditions" => &rules.conditions,
+                _ => &rules.limitations,
+            };
+            definitions.iter().find(|rule| rule.tag == tag).cloned()
+        })
+        .unwrap_or_else(|| RuleSource {
+            tag: tag.to_string(),
+            label: tag.to_string(),
+            description: String::new(),
+        })
+}
+
+pub fn BuildComparisonJson(
+    licenses: &[(String, &LicenseEntry, Option<PathBuf>)],
+    rulesData: &Option<RulesDataContent>,
+    baseline: bool,
+    withStats: bool,
+) -> ComparisonJson {
+    let mut agreement: PerCategory<BTreeMap<String, RuleAgreement>> = PerCategory::default();
+    let categoryTags = |entry: &LicenseEntry| {
+        [
+            ("permissions", entry.permissions.clone()),
+            ("conditions", entry.conditions.clone()),
+            ("limitations", entry.limitations.clone()),
+        ]
+    };
+
+    for (_, entry, _) in licenses {
+        for (category, tags) in categoryTags(entry) {
+            for tag in tags {
+                agreement.Get(category).entry(tag.clone()).or_insert_with(|| {
+                    let rule = RuleObject(rulesData, category, &tag);
+                    RuleAgreement {
+                        label: rule.label,
+                        description: rule.description,
+                        with: Vec::new(),
+                        without: Vec::new(),
+                        unanimous: false,
+                    }
+                });
+            }
+        }
+    }
+
+    for (label, entry, _) in licenses {
+        for (category, tags) in categoryTags(entry) {
+            for (tag, rule) in agreement.Get(category).iter_mut() {
+                if tags.contains(tag) {
+                    rule.with.push(label.clone());
+                } else {
+                    rule.without.push(label.clone());
+                }
+            }
+        }
+    }
+
+    for category in ["permissions", "conditions", "limitations"] {
+        for rule in agreement.Get(category).values_mut() {
+            rule.unanimous = rule.without.is_empty();
+        }
+    }
+
+    let baselineEntry = licenses.first().filter(|_| baseline).map(|(_, entry, _)| *entry);
+
+    let compared = licenses
+        .iter()
+        .enumerate()
+        .map(|(index, (label, entry, file))| {
+            let mut rules: PerCategory<Vec<RuleSource>> = PerCategory::default();
+            for (category, tags) in categoryTags(entry) {
+                *rules.Get(category) = tags.iter().map(|tag| RuleObject(rulesData, category, tag)).collect();
+            }
+
+            let vsBaseline = baselineEntry.filter(|_| index > 0).map(|base| {
+                let mut deltas: PerCategory<DeltaJson> = PerCategory::default();
+                for delta in RuleDeltas(base, entry) {
+                    *deltas.Get(delta.category) = DeltaJson { added: delta.added, dropped: delta.dropped };
+                }
+                deltas
+            });
+
+            ComparedLicenseJson {
+                label: label.clone(),
+                spdxId: entry.spdxId.clone(),
+                title: entry.title.clone(),
+                file: file.clone(),
+                rules,
+                vsBaseline,
+                stats: withStats.then(|| stats::LicenseTextStats(entry)),
+            }
+        })
+        .collect();
+
+    ComparisonJson {
+        baseline: baselineEntry.map(|entry| entry.spdxId.clone()),
+        licenses: compared,
+        agreement,
+    }
+}
+
+struct DetectedFile<'a> {
+    path: PathBuf,
+    text: String,
+    entry: &'a LicenseEntry,
+}
+
+fn DetectLicenseFiles<'a>(cache: &'a Cache, paths: &[PathBuf]) -> Result<Vec<DetectedFile<'a>>, AppError> {
+    let mut detected = Vec::new();
+
+    for path in paths {
+        let text = fs::read_to_string(path).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, path.clone()))
+        })?;
+
+        let (entry, similarity) = super::validate::IdentifyOrApproximate(cache, &text).ok_or_else(|| {
+            AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+                "{} does not resemble any cached license template",
+                path.display()
+            )))
+        })?;
+
+        if similarity < 1.0 {
+            eprintln!(
+                "Note: {} is closest to {} ({:.0}% similar) but does not match it exactly.",
+                path.display(),
+                entry.spdxId,
+                similarity * 100.0
+            );
+        } else if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Action] Detected {} in {:?}", entry.spdxId, path);
+        }
+
+        detected.push(DetectedFile {
+            path: path.clone(),
+            text,
+            entry,
+        });
+    }
+
+    Ok(detected)
+}
+
 pub async fn CompareLicenses(
     cache: &Cache,
     requestedIds: Option<Vec<String>>,
+    files: &[PathBuf],
+    showDiff: bool,
+    baseline: Option<String>,
+    showStats: bool,
+    asJson: bool,
 ) -> Result<(), AppError> {
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!(
@@ -14,7 +259,7 @@ pub async fn CompareLicenses(
         );
     }
 
-    let targetKeysLower: Vec<String> = match requestedIds {
+    let mut targetKeysLower: Vec<String> = match requestedIds {
         Some(ids) if !ids.is_empty() => ids
             .into_iter()
             .filter_map(|idStr| {
@@ -32,6 +277,7 @@ pub async fn CompareLicenses(
                 }
             })
             .collect(),
+        _ if !files.is_empty() => Vec::new(),
         _ => {
             let mut allKeys: Vec<String> = cache.licenses.keys().cloned().collect();
             allKeys.sort();
@@ -39,20 +285,41 @@ pub async fn CompareLicenses(
         }
     };
 
-    if targetKeysLower.len() < 2 {
+    // The baseline goes first, so it is also the first column of the table
+    if let Some(baselineId) = &baseline {
+        let baselineLower = baselineId.to_lowercase();
+
+        if !cache.licenses.contains_key(&baselineLower) {
+            return Err(super::LicenseNotFoundError(baselineLower));
+        }
+
+        targetKeysLower.retain(|key| *key != baselineLower);
+        targetKeysLower.insert(0, baselineLower);
+    }
+
+    let detectedFiles = DetectLicenseFiles(cache, files)?;
+
+    if targetKeysLower.len() + detectedFiles.len() < 2 {
         println!("Need at least two licenses to compare. Found {} valid licenses from request (or in cache if all).", targetKeysLower.len());
 
         return Ok(());
     }
 
-    let mut licensesToCompare: Vec<&LicenseEntry> = Vec::new();
+    let mut licensesToCompare: Vec<(String, &LicenseEntry)> = Vec::new();
 
     for key in &targetKeysLower {
         if let Some(entry) = cache.licenses.get(key) {
-            licensesToCompare.push(entry);
+            licensesToCompare.push((entry.spdxId.clone(), entry));
         }
     }
 
+    for file in &detectedFiles {
+        licensesToCompare.push((
+            format!("{} ({})", file.path.display(), file.entry.spdxId),
+            file.entry,
+        ));
+    }
+
     if licensesToCompare.len() < 2 {
         println!(
             "After filtering, only {} licenses are available for comparison. Need at least two.",
@@ -62,12 +329,111 @@ pub async fn CompareLicenses(
         return Ok(());
     }
 
-    let rulesDataContent: Option<RulesDataContent> = cache
-        .dataFiles // dataFiles is correct
-        .get(crate::constants::RULES_YML_KEY)
-        .and_then(|entry| serde_yaml::from_value(entry.content.clone()).ok());
+    let rulesDataContent: Option<RulesDataContent> = cache.RulesData();
+
+    if asJson {
+        let labelled: Vec<(String, &LicenseEntry, Option<PathBuf>)> = licensesToCompare
+            .iter()
+            .enumerate()
+            .map(|(index, (label, entry))| {
+                // Files come after the requested IDs
+                let file = index
+                    .checked_sub(targetKeysLower.len())
+                    .map(|fileIndex| detectedFiles[fileIndex].path.clone());
+                (label.clone(), *entry, file)
+            })
+            .collect();
+
+        let document = BuildComparisonJson(&labelled, &rulesDataContent, baseline.is_some(), showStats);
+        let json = serde_json::to_string_pretty(&document)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+
+        return Ok(());
+    }
+
+    match licensesToCompare.split_first() {
+        Some((baselineLicense, others)) if baseline.is_some() => {
+            let deltas: Vec<(&str, Vec<RuleDelta>)> = others
+                .iter()
+                .map(|(label, entry)| (label.as_str(), RuleDeltas(baselineLicense.1, entry)))
+                .collect();
+
+            display::PrintBaselineComparison(&baselineLicense.0, &deltas, &rulesDataContent);
+        }
+        _ => display::PrintComparisonTable(&licensesToCompare, &rulesDataContent),
+    }
 
-    display::PrintComparisonTable(&licensesToCompare, &rulesDataContent);
+    if showStats {
+        let rows: Vec<(String, stats::LicenseStats)> = licensesToCompare
+            .iter()
+            .map(|(label, entry)| (label.clone(), stats::LicenseTextStats(entry)))
+            .collect();
+
+        display::PrintStatsTable(&rows);
+    }
+
+    if showDiff {
+        if let Some((first, rest)) = detectedFiles.split_first() {
+            for other in rest {
+                println!();
+                display::PrintUnifiedDiff(&drift::LineDiff(
+                    &first.text,
+                    &other.text,
+                    &first.path.display().to_string(),
+                    &other.path.display().to_string(),
+                ));
+            }
+        }
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Strings;
+
+    #[test]
+    fn TestRuleDeltas() {
+        let license = |permissions: &[&str], conditions: &[&str]| LicenseEntry {
+            permissions: Strings(permissions),
+            conditions: Strings(conditions),
+            ..LicenseEntry::ForTest("", "")
+        };
+
+        let mit = license(&["commercial-use"], &["include-copyright"]);
+        let mpl = license(&["commercial-use", "patent-use"], &["disclose-source"]);
+
+        let deltas = RuleDeltas(&mit, &mpl);
+
+        assert_eq!(deltas[0].added, ["patent-use"]);
+        assert!(deltas[0].dropped.is_empty());
+        assert_eq!(deltas[1].added, ["disclose-source"]);
+        assert_eq!(deltas[1].dropped, ["include-copyright"]);
+        assert!(deltas[2].added.is_empty() && deltas[2].dropped.is_empty());
+
+        let rules = Some(RulesDataContent {
+            permissions: vec![RuleSource {
+                tag: "patent-use".to_string(),
+                label: "Patent use".to_string(),
+                description: "Grants patent rights.".to_string(),
+            }],
+            ..Default::default()
+        });
+        let document = BuildComparisonJson(
+            &[("MIT".to_string(), &mit, None), ("MPL-2.0".to_string(), &mpl, None)],
+            &rules,
+            true,
+            false,
+        );
+
+        let patentUse = &document.agreement.permissions["patent-use"];
+        assert_eq!(patentUse.label, "Patent use");
+        assert_eq!(patentUse.with, ["MPL-2.0"]);
+        assert_eq!(patentUse.without, ["MIT"]);
+        assert!(document.agreement.permissions["commercial-use"].unanimous);
+        assert_eq!(document.licenses[1].vsBaseline.as_ref().unwrap().conditions.dropped, ["include-copyright"]);
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use crate::actions::tags;
+use crate::constants::{CORPUS_STATS_NEWEST_ADDITIONS, CORPUS_STATS_TOP_CONDITIONS, LICENSE_CATEGORIES};
+use crate::display;
+use crate::error::{AppError, CacheError};
+use crate::history::{self, SyncRecord};
+use crate::models::Cache;
+use crate::stats;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CategoryCount {
+    pub category: &'static str,
+    pub count: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ConditionCount {
+    pub tag: String,
+    // From rules.yml; tags only seen on licenses have none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub count: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LicenseAddition {
+    #[serde(rename = "spdx_id")]
+    pub spdxId: String,
+    #[serde(rename = "added_at")]
+    pub addedAt: DateTime<Utc>,
+}
+
+// Figures about the cached corpus as a whole, for writing about the dataset.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CorpusStats {
+    #[serde(rename = "license_count")]
+    pub licenseCount: usize,
+    pub categories: Vec<CategoryCount>,
+    // Most licenses first
+    #[serde(rename = "top_conditions")]
+    pub topConditions: Vec<ConditionCount>,
+    #[serde(rename = "average_placeholders")]
+    pub averagePlaceholders: f64,
+    // Newest first. Empty until a sync after the first one brings in a new license.
+    #[serde(rename = "newest_additions")]
+    pub newestAdditions: Vec<LicenseAddition>,
+}
+
+pub fn CorpusStatsFor(cache: &Cache, records: &[SyncRecord]) -> CorpusStats {
+    let licenseCount = cache.licenses.len();
+
+    let categories = LICENSE_CATEGORIES
+        .iter()
+        .map(|&category| CategoryCount {
+            category,
+            count: cache
+                .licenses
+                .values()
+                .filter(|license| stats::LicenseCategory(license) == category)
+                .count(),
+        })
+        .collect();
+
+    let mut topConditions: Vec<ConditionCount> = tags::CountTagUsage(cache)
+        .into_iter()
+        .filter(|usage| usage.conditions > 0)
+        .map(|usage| ConditionCount {
+            tag: usage.tag,
+            label: usage.label,
+            count: usage.conditions,
+        })
+        .collect();
+    // CountTagUsage sorts by tag, and the sort is stable, so ties stay alphabetical
+    topConditions.sort_by_key(|condition| std::cmp::Reverse(condition.count));
+    topConditions.truncate(CORPUS_STATS_TOP_CONDITIONS);
+
+    let placeholderTotal: usize = cache.licenses.values().map(|license| license.placeholdersInBody.len()).sum();
+    let averagePlaceholders = if licenseCount == 0 { 0.0 } else { placeholderTotal as f64 / licenseCount as f64 };
+
+    CorpusStats {
+        licenseCount,
+        categories,
+        topConditions,
+        averagePlaceholders,
+        newestAdditions: NewestAdditions(cache, records),
+    }
+}
+
+// Licenses first fetched as new by a sync after the first one: the first sync fetches the
+// whole corpus, and --refresh syncs record every file as new, so neither says anything.
+fn NewestAdditions(cache: &Cache, records: &[SyncRecord]) -> Vec<LicenseAddition> {
+    let spdxIdByFile: HashMap<&str, &str> = cache
+        .licenses
+        .values()
+        .map(|license| (license.filename.as_str(), license.spdxId.as_str()))
+        .collect();
+
+    let mut seen: HashMap<&str, Option<DateTime<Utc>>> = HashMap::new();
+
+    for (index, record) in records.iter().enumerate() {
+        for file in &record.fetched {
+            seen.entry(file.name.as_str()).or_insert_with(|| {
+                (index > 0 && !record.forced && file.previousSha.is_none()).then_some(record.syncedAt)
+            });
+        }
+    }
+
+    let mut additions: Vec<LicenseAddition> = seen
+        .into_iter()
+        .filter_map(|(name, addedAt)| {
+            Some(LicenseAddition {
+                spdxId: spdxIdByFile.get(name)?.to_string(),
+                addedAt: addedAt?,
+            })
+        })
+        .collect();
+
+    additions.sort_by(|a, b| b.addedAt.cmp(&a.addedAt).then_with(|| a.spdxId.cmp(&b.spdxId)));
+    additions.truncate(CORPUS_STATS_NEWEST_ADDITIONS);
+    additions
+}
+
+// `None` means an in-memory cache, which keeps no history to date additions by.
+pub async fn ShowCorpusStats(cache: &Cache, historyPath: Option<&Path>, asJson: bool) -> Result<(), AppError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Summarizing {} cached licenses", cache.licenses.len());
+    }
+
+    let records = match historyPath {
+        Some(path) => history::LoadSyncHistory(path)?,
+        None => Vec::new(),
+    };
+
+    let corpusStats = CorpusStatsFor(cache, &records);
+
+    if asJson {
+        let json = serde_json::to_string_pretty(&corpusStats)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintCorpusStats(&corpusStats, historyPath.is_some());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LicenseEntry;
+    use crate::test_support::Strings;
+    use chrono::TimeZone;
+    use std::sync::Arc;
+
+    fn License(spdxId: &str, conditions: &[&str], placeholders: usize) -> LicenseEntry {
+        LicenseEntry {
+            conditions: Strings(conditions),
+            placeholdersInBody: (0..placeholders).map(|i| format!("[p{}]", i)).collect(),
+            ..LicenseEntry::ForTest(spdxId, "")
+        }
+    }
+
+    fn Sync(day: u32, forced: bool, files: &[(&str, Option<&str>)]) -> SyncRecord {
+        let mut record = SyncRecord::New(forced);
+        record.syncedAt = Utc.with_ymd_and_hms(2026, 1, day, 0, 0, 0).unwrap();
+        for (name, previousSha) in files {
+            record.RecordFetch(name, "new", *previousSha, 0);
+        }
+        record
+    }
+
+    #[test]
+    fn TestCorpusStatsFor() {
+        let mut cache = Cache::default();
+        for license in [
+            License("MIT", &["include-copyright"], 2),
+            License("GPL-3.0", &["include-copyright", "disclose-source", "same-license"], 0),
+            License("MPL-2.0", &["disclose-source", "same-license--file"], 1),
+            License("0BSD", &[], 0),
+        ] {
+            cache.licenses.insert(license.spdxId.to_lowercase(), Arc::new(license));
+        }
+
+        let records = [
+            Sync(1, false, &[("mit.txt", None), ("gpl-3.0.txt", None)]),
+            Sync(2, false, &[("mpl-2.0.txt", None), ("mit.txt", Some("old"))]),
+            Sync(3, true, &[("0bsd.txt", None)]),
+            Sync(4, false, &[("removed.txt", None)]),
+        ];
+
+        let corpusStats = CorpusStatsFor(&cache, &records);
+
+        assert_eq!(corpusStats.licenseCount, 4);
+        let counts: Vec<usize> = corpusStats.categories.iter().map(|c| c.count).collect();
+        assert_eq!(counts, [2, 1, 1]);
+        let conditions: Vec<(&str, usize)> =
+            corpusStats.topConditions.iter().map(|c| (c.tag.as_str(), c.count)).collect();
+        assert_eq!(
+            conditions,
+            [("disclose-source", 2), ("include-copyright", 2), ("same-license", 1), ("same-license--file", 1)]
+        );
+        assert_eq!(corpusStats.averagePlaceholders, 0.75);
+        assert_eq!(corpusStats.newestAdditions, [LicenseAddition {
+            spdxId: "MPL-2.0".to_string(),
+            addedAt: Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap(),
+        }]);
+    }
+}
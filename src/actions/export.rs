@@ -0,0 +1,111 @@
+use colored::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+use crate::parser;
+
+fn WriteNewFile(path: &Path, content: &str, force: bool) -> Result<(), AppError> {
+    if path.exists() && !force {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+            "{} already exists. Use --force to overwrite it.",
+            path.display()
+        ))));
+    }
+
+    fs::write(path, content)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, path.to_path_buf())))
+}
+
+// The template exactly as upstream ships it minus the front matter, placeholders
+// untouched. `-` writes to stdout.
+pub async fn ExportTemplate(
+    cache: &Cache,
+    spdxIdStr: &str,
+    outputPath: Option<PathBuf>,
+    force: bool,
+) -> Result<(), AppError> {
+    let spdxIdLower = spdxIdStr.to_lowercase();
+    let licenseEntry = cache
+        .licenses
+        .get(&spdxIdLower)
+        .ok_or_else(|| super::LicenseNotFoundError(spdxIdLower.clone()))?;
+
+    let templateBody = parser::LicenseTemplateBody(&licenseEntry.fileContentCached);
+
+    if outputPath.as_deref() == Some(Path::new("-")) {
+        print!("{}", templateBody);
+        return Ok(());
+    }
+
+    let outputPath = outputPath.unwrap_or_else(|| PathBuf::from(format!("{}.txt", licenseEntry.spdxId)));
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Exporting {} template to {:?}", licenseEntry.spdxId, outputPath);
+    }
+
+    WriteNewFile(&outputPath, &templateBody, force)?;
+
+    println!(
+        "Exported the {} template to {} ({} placeholder(s) left unfilled)",
+        licenseEntry.spdxId.cyan(),
+        outputPath.display().to_string().green(),
+        licenseEntry.placeholdersInBody.len()
+    );
+
+    Ok(())
+}
+
+// Every cached license as <dir>/<SPDX_ID>.txt. Files that already exist are left alone
+// unless `force`, so a re-run only adds what is new.
+pub async fn ExportAllTemplates(
+    cache: &Cache,
+    outputDir: &Path,
+    keepFrontMatter: bool,
+    force: bool,
+) -> Result<(), AppError> {
+    fs::create_dir_all(outputDir).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, outputDir.to_path_buf()))
+    })?;
+
+    let mut licenses: Vec<_> = cache.licenses.values().collect();
+    licenses.sort_by(|a, b| a.spdxId.cmp(&b.spdxId));
+
+    let (mut written, mut skipped) = (0, 0);
+
+    for licenseEntry in licenses {
+        let outputPath = outputDir.join(format!("{}.txt", licenseEntry.spdxId));
+
+        if outputPath.exists() && !force {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[Action] Skipping existing {:?}", outputPath);
+            }
+            skipped += 1;
+            continue;
+        }
+
+        let content = if keepFrontMatter {
+            licenseEntry.fileContentCached.clone()
+        } else {
+            parser::LicenseTemplateBody(&licenseEntry.fileContentCached)
+        };
+
+        WriteNewFile(&outputPath, &content, true)?;
+        written += 1;
+    }
+
+    println!(
+        "Exported {} license template(s) to {}{}",
+        written,
+        outputDir.display().to_string().green(),
+        if skipped > 0 {
+            format!(" ({} existing file(s) skipped; use --force to overwrite)", skipped)
+        } else {
+            String::new()
+        }
+    );
+
+    Ok(())
+}
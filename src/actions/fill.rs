@@ -1,117 +1,606 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::ffi::OsStr;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
-use crate::cli::{Cli as FullCliArgs, LicenseFillArgs};
-use crate::constants::{CACHABLE_PLACEHOLDER_KEYS, CLI_ARG_TO_CACHE_KEY_TUPLES};
+use crate::appendix::{self, AppendixValues};
+use crate::cli::{Cli as FullCliArgs, LicenseFillArgs, YearSource};
+use crate::config::{self, Config};
+use crate::constants::{
+    CACHABLE_PLACEHOLDER_KEYS, CLI_ARG_TO_CACHE_KEY_TUPLES, DEFAULT_LICENSE_FILENAME, LICENSES_DIR_NAME,
+    RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES, TEMPLATE_LOCK_FILENAME,
+};
 use crate::display;
 use crate::error::{ActionError, AppError};
-use crate::models::Cache;
+use crate::integrity::GitBlobSha;
+use crate::journal::{FillJournal, FillRecord, JournaledFile};
+use crate::lockfile::{self, TemplateLock};
+use crate::models::{Cache, LicenseEntry};
 use crate::parser;
-use chrono::Datelike;
+use crate::provenance::{self, ProvenanceRecord};
+use crate::resolve::{self, PlaceholderSource};
+use crate::store::FillRequest;
+use super::validate;
 use colored::*;
 use std::sync::atomic::Ordering;
 
+// An existing COPYING, LICENSE.txt, LICENCE, ... is overwritten in place rather than
+// ending up next to a second, new LICENSE file.
+fn ResolveOutputPath(args: &LicenseFillArgs) -> PathBuf {
+    if let Some(output) = &args.output {
+        return output.clone();
+    }
+
+    if let Some(filename) = &args.filename {
+        return PathBuf::from(filename);
+    }
+
+    match validate::FindLicenseFile(Path::new(".")) {
+        Some(existing) if existing.file_name() != Some(OsStr::new(DEFAULT_LICENSE_FILENAME)) => {
+            let existing = PathBuf::from(existing.file_name().unwrap_or_default());
+            eprintln!(
+                "{} Found existing {}; writing to it instead of creating {}. Use --filename to override.",
+                "Warning:".yellow().bold(),
+                existing.display(),
+                DEFAULT_LICENSE_FILENAME
+            );
+            existing
+        }
+        _ => PathBuf::from(DEFAULT_LICENSE_FILENAME),
+    }
+}
+
+// Adds every source `license` consults besides its own flags to `request`.
+pub fn WithContextLayers<'a>(
+    request: FillRequest<'a>,
+    cache: &Cache,
+    appConfig: &Config,
+    profile: Option<&str>,
+    currentDir: &Path,
+) -> Result<FillRequest<'a>, AppError> {
+    let projectValues = config::LoadProjectConfig(currentDir)?.placeholders;
+    let profileValues = appConfig.ProfilePlaceholders(profile)?;
+
+    let cachedPreferences: HashMap<String, String> = cache
+        .userPlaceholders
+        .iter()
+        .filter(|(k, _)| CACHABLE_PLACEHOLDER_KEYS.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    // Skip spawning git when auto-detection is disabled in the config
+    let autoValues = if appConfig.PlaceholderPrecedence().contains(&PlaceholderSource::Auto) {
+        resolve::DetectGitPlaceholders()
+    } else {
+        HashMap::new()
+    };
+
+    let defaultValues = appConfig
+        .org
+        .defaultHolder
+        .iter()
+        .map(|holder| ("fullname".to_string(), holder.clone()))
+        .collect();
+
+    Ok(request
+        .layer(PlaceholderSource::Project, projectValues)
+        .layer(PlaceholderSource::Profile, profileValues)
+        .layer(PlaceholderSource::Cache, cachedPreferences)
+        .layer(PlaceholderSource::Auto, autoValues)
+        .layer(PlaceholderSource::Default, defaultValues))
+}
+
+// --year as given, or the year (range) --year-from derives from the project. An explicit
+// --year then starts the range.
+fn DerivedYear(args: &LicenseFillArgs, currentDir: &Path) -> Result<Option<String>, AppError> {
+    let Some(source) = args.yearFrom else {
+        return Ok(args.year.clone());
+    };
+
+    let path = args.yearPath.as_deref().unwrap_or(currentDir);
+    let (derivedStart, end) = match source {
+        YearSource::Git => resolve::CommitYearRange(path).ok_or_else(|| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "--year-from git needs a git repository with at least one commit at {}",
+                path.display()
+            )))
+        })?,
+        YearSource::Mtime => {
+            let year = resolve::NewestModifiedYear(path).ok_or_else(|| {
+                AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                    "Could not read modification times under {}",
+                    path.display()
+                )))
+            })?;
+            (year, year)
+        }
+    };
+
+    let start = match &args.year {
+        Some(year) => year.trim().parse::<i32>().map_err(|_| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "--year must be a single year when combined with --year-from, got '{}'",
+                year
+            )))
+        })?,
+        None => derivedStart,
+    };
+
+    Ok(Some(resolve::FormatYearRange(start, end)))
+}
+
+// One file written by a multi-license `license` run, for the combined summary
+pub struct WrittenLicense<'a> {
+    pub entry: &'a LicenseEntry,
+    pub path: PathBuf,
+    // Placeholders still in the written text
+    pub unfilled: Vec<String>,
+}
+
+// LICENSES/<SPDX_ID>.txt, or LICENSE-<SPDX_ID> in the current directory with --suffixed
+fn MultiLicenseOutputPath(args: &LicenseFillArgs, licenseEntry: &LicenseEntry) -> PathBuf {
+    if args.suffixed {
+        PathBuf::from(format!("{}-{}", DEFAULT_LICENSE_FILENAME, licenseEntry.spdxId))
+    } else {
+        Path::new(LICENSES_DIR_NAME).join(format!("{}.txt", licenseEntry.spdxId))
+    }
+}
+
+// Refuses banned licenses and warns about unapproved ones before anything is written
+fn CheckOrgPolicy(licenseEntry: &LicenseEntry, appConfig: &Config) -> Result<(), AppError> {
+    let org = &appConfig.org;
+
+    if org.IsBanned(&licenseEntry.spdxId) {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+            "{} is banned by your organization's license policy",
+            licenseEntry.spdxId
+        ))));
+    }
+    if !org.IsApproved(&licenseEntry.spdxId) {
+        eprintln!(
+            "{} {} is not on your organization's approved license list ({}).",
+            "Warning:".yellow(),
+            licenseEntry.spdxId,
+            org.approvedLicenses.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+// Asks on the terminal for a value; None when not interactive or left blank
+fn PromptValue(label: &str) -> Result<Option<String>, AppError> {
+    if !io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    print!("{}: ", label);
+    io::stdout()
+        .flush()
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdout")))?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdin")))?;
+
+    let answer = answer.trim();
+    Ok((!answer.is_empty()).then(|| answer.to_string()))
+}
+
+// What the user answered in --interactive
+struct PromptedValues {
+    // Values they typed, which override every other source
+    typed: HashMap<String, String>,
+    // Values to save as preferences: typed ones, and defaults accepted from a profile or the
+    // saved preferences. Accepted flags are saved anyway; project and git values are not.
+    remembered: HashMap<String, String>,
+}
+
+impl PromptedValues {
+    fn Record(&mut self, key: &str, answer: Option<String>, default: Option<&resolve::ResolvedValue>) {
+        match (answer, default) {
+            (Some(answer), _) => {
+                self.remembered.insert(key.to_string(), answer.clone());
+                self.typed.insert(key.to_string(), answer);
+            }
+            // Accepted defaults keep their source, so they are not layered again
+            (None, Some(default)) if matches!(default.source, PlaceholderSource::Profile | PlaceholderSource::Cache) => {
+                self.remembered.insert(key.to_string(), default.value.clone());
+            }
+            (None, _) => {}
+        }
+    }
+}
+
+// The value each placeholder key would get, from the first selected license that resolves it
+fn DefaultsAcrossEntries(
+    request: &FillRequest,
+    licenseEntries: &[&LicenseEntry],
+) -> Result<HashMap<String, resolve::ResolvedValue>, AppError> {
+    let mut defaults = HashMap::new();
+
+    for licenseEntry in licenseEntries {
+        for (key, resolved) in request.for_license(&licenseEntry.spdxId).render()?.resolved {
+            defaults.entry(key).or_insert(resolved);
+        }
+    }
+
+    Ok(defaults)
+}
+
+// Asks for every placeholder the templates use, in order of first appearance. The value the
+// sources would give (flag, saved preference, git config, ...) is shown and kept on Enter.
+fn PromptPlaceholderValues(
+    request: &FillRequest,
+    licenseEntries: &[&LicenseEntry],
+) -> Result<PromptedValues, AppError> {
+    if !io::stdin().is_terminal() {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "--interactive asks for values on a terminal; pass them as flags instead".to_string(),
+        )));
+    }
+
+    let rawToStdMap: HashMap<&str, &str> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES.iter().cloned().collect();
+    let mut keys: Vec<&str> = Vec::new();
+
+    for placeholder in licenseEntries.iter().flat_map(|entry| &entry.placeholdersInBody) {
+        if let Some(key) = rawToStdMap.get(parser::PlaceholderName(placeholder).as_str()) {
+            if !keys.contains(key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    let defaults = DefaultsAcrossEntries(request, licenseEntries)?;
+    let mut prompted = PromptedValues { typed: HashMap::new(), remembered: HashMap::new() };
+
+    for key in keys {
+        let label = match defaults.get(key) {
+            Some(default) => format!(
+                "{} [{}] {}",
+                key,
+                default.value.cyan(),
+                format!("({})", default.source.Name()).dimmed()
+            ),
+            None => key.to_string(),
+        };
+
+        prompted.Record(key, PromptValue(&label)?, defaults.get(key));
+    }
+
+    println!();
+    Ok(prompted)
+}
+
+// Resolved placeholder values (flags included) first, then the terminal
+fn AppendixValuesFor(
+    resolved: &HashMap<String, resolve::ResolvedValue>,
+) -> Result<AppendixValues, AppError> {
+    let value = |key: &str| resolved.get(key).map(|r| r.value.clone());
+
+    let program = match value("project") {
+        Some(program) => Some(program),
+        None => PromptValue("Program name")?,
+    };
+    let description = match value("description") {
+        Some(description) => Some(description),
+        None => PromptValue("One-line description of what it does")?,
+    };
+    let author = match value("fullname") {
+        Some(author) => Some(author),
+        None => PromptValue("Author")?,
+    };
+
+    let values = AppendixValues {
+        program,
+        description,
+        year: value("year"),
+        author,
+    };
+
+    if values.program.is_none() || values.description.is_none() || values.author.is_none() {
+        eprintln!(
+            "{} Some appendix fields have no value; their <...> placeholders are kept.",
+            "Warning:".yellow()
+        );
+    }
+
+    Ok(values)
+}
+
+fn WriteLicenseFile(outputPath: &Path, body: &str) -> Result<(), AppError> {
+    if let Some(parent) = outputPath.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, parent.to_path_buf()))
+        })?;
+    }
+
+    fs::write(outputPath, body.to_string() + "\n").map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, outputPath.to_path_buf()))
+    })
+}
+
+// The files a run has written so far. The record is appended to the journal when this is
+// dropped, so a run that fails halfway through can still undo what it did write.
+struct PendingFillRecord<'a> {
+    journal: Option<&'a FillJournal>,
+    files: Vec<JournaledFile>,
+}
+
+impl Drop for PendingFillRecord<'_> {
+    fn drop(&mut self) {
+        let (Some(journal), false) = (self.journal, self.files.is_empty()) else {
+            return;
+        };
+
+        let record = FillRecord {
+            writtenAt: crate::clock::Now(),
+            files: std::mem::take(&mut self.files),
+        };
+
+        // The files are already written; losing the undo record is not worth failing over
+        if let Err(e) = journal.Append(&record) {
+            eprintln!("Warning: could not record this run for undo: {}", e);
+        }
+    }
+}
+
+// Backs up what the file held before, so `undo` can put it back
+fn WriteJournaledFile(pending: &mut PendingFillRecord, outputPath: &Path, body: &str, spdxId: Option<&str>) -> Result<(), AppError> {
+    let previousSha = match pending.journal {
+        Some(journal) => journal.BackUp(outputPath)?,
+        None => None,
+    };
+
+    WriteLicenseFile(outputPath, body)?;
+
+    if pending.journal.is_some() {
+        pending.files.push(JournaledFile {
+            path: std::path::absolute(outputPath).unwrap_or_else(|_| outputPath.to_path_buf()),
+            spdxId: spdxId.map(str::to_string),
+            previousSha,
+            writtenSha: GitBlobSha(format!("{}\n", body).as_bytes()),
+        });
+    }
+
+    Ok(())
+}
+
 pub async fn FillLicenseTemplateAction(
     cache: &mut Cache,
     args: &LicenseFillArgs,
     cliAllArgs: &FullCliArgs,
+    appConfig: &Config,
+    journal: Option<&FillJournal>,
+    templateLock: Option<TemplateLock>,
 ) -> Result<bool, AppError> {
-    let spdxIdLower = args.licenseId.to_lowercase();
+    let mut spdxIdsLower: Vec<String> = Vec::new();
+
+    if args.licenseIds.is_empty() {
+        spdxIdsLower.push(super::LicenseIdOrPick(cache, None)?);
+    }
+    for id in &args.licenseIds {
+        let idLower = id.to_lowercase();
+        if !spdxIdsLower.contains(&idLower) {
+            spdxIdsLower.push(idLower);
+        }
+    }
+
+    let multiple = spdxIdsLower.len() > 1;
+
+    if multiple && (args.output.is_some() || args.filename.is_some()) {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "--output and --filename name a single file; with several licenses, files go to LICENSES/ (or use --suffixed)"
+                .to_string(),
+        )));
+    }
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
-        eprintln!("[Action] Filling license template for: {}", spdxIdLower);
+        eprintln!("[Action] Filling license template(s) for: {}", spdxIdsLower.join(", "));
     }
 
-    let licenseEntry = cache.licenses.get(&spdxIdLower).ok_or_else(|| {
-        AppError::ActionErrorVariant(ActionError::LicenseNotFound(spdxIdLower.clone()))
-    })?;
+    // Every ID is checked before the first file is written
+    let mut licenseEntries: Vec<&LicenseEntry> = Vec::new();
 
-    let templateBody = &licenseEntry.fileContentCached;
+    for spdxIdLower in &spdxIdsLower {
+        let licenseEntry = cache
+            .licenses
+            .get(spdxIdLower)
+            .ok_or_else(|| super::LicenseNotFoundError(spdxIdLower.clone()))?;
 
+        CheckOrgPolicy(licenseEntry, appConfig)?;
+        licenseEntries.push(licenseEntry);
+    }
+
+    let titles: Vec<String> = licenseEntries
+        .iter()
+        .map(|entry| format!("{} ({})", entry.title.cyan().bold(), entry.spdxId.cyan()))
+        .collect();
     println!(
-        "\nUsing license: {} ({})",
-        licenseEntry.title.cyan().bold(),
-        licenseEntry.spdxId.cyan()
+        "\nUsing license{}: {}",
+        if multiple { "s" } else { "" },
+        titles.join(", ")
     );
 
-    let cachedPlaceholdersAtStart = cache.userPlaceholders.clone();
     let mut userProvidedForCaching: HashMap<String, String> = HashMap::new();
 
     // Collect CLI args for cachable placeholders
     let cliArgToCacheKeyMap: HashMap<&str, &str> =
         CLI_ARG_TO_CACHE_KEY_TUPLES.iter().cloned().collect();
 
-    if let Some(name) = &args.fullname {
-        if let Some(key) = cliArgToCacheKeyMap.get("fullname") {
-            userProvidedForCaching.insert(key.to_string(), name.clone());
+    for (argName, argValue) in [
+        ("fullname", &args.fullname),
+        ("project", &args.project),
+        ("email", &args.email),
+        ("projecturl", &args.projecturl),
+        ("login", &args.login),
+    ] {
+        if let (Some(value), Some(key)) = (argValue, cliArgToCacheKeyMap.get(argName)) {
+            userProvidedForCaching.insert(key.to_string(), value.clone());
         }
     }
 
-    if let Some(proj) = &args.project {
-        if let Some(key) = cliArgToCacheKeyMap.get("project") {
-            userProvidedForCaching.insert(key.to_string(), proj.clone());
-        }
-    }
+    // --- Determine Final Replacements for Template Filling ---
+    let precedence = appConfig.PlaceholderPrecedence();
 
-    if let Some(mail) = &args.email {
-        if let Some(key) = cliArgToCacheKeyMap.get("email") {
-            userProvidedForCaching.insert(key.to_string(), mail.clone());
-        }
+    // 'year' is never cached, so it only enters through the CLI layer
+    let mut cliValues = userProvidedForCaching.clone();
+
+    let currentDir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+    if let Some(year) = DerivedYear(args, &currentDir)? {
+        cliValues.insert("year".to_string(), year);
     }
+    // Describes this project only, so it is not saved as a preference either
+    if let Some(description) = &args.description {
+        cliValues.insert("description".to_string(), description.clone());
+    }
+
+    // Sources are gathered (and git consulted) once, then applied to every license
+    let request = FillRequest::from_cache(cache, &spdxIdsLower[0]).layer(PlaceholderSource::Cli, cliValues);
+    let request = WithContextLayers(request, cache, appConfig, cliAllArgs.profile.as_deref(), &currentDir)?
+        .precedence(precedence.clone())
+        .strip_unfilled(args.stripUnfilled)
+        .lang(args.lang.as_deref());
+
+    // Typed answers become saved preferences, like the same values given as flags
+    let request = if args.interactive {
+        let prompted = PromptPlaceholderValues(&request, &licenseEntries)?;
+        userProvidedForCaching.extend(
+            prompted
+                .remembered
+                .into_iter()
+                .filter(|(key, _)| CACHABLE_PLACEHOLDER_KEYS.contains(&key.as_str())),
+        );
+        request.layer(PlaceholderSource::Cli, prompted.typed)
+    } else {
+        request
+    };
+
+    let mut written: Vec<WrittenLicense> = Vec::new();
+    let mut resolvedPlaceholders = HashMap::new();
+    let mut filledLicenseBody = String::new();
+    // Asked for once, then shared by every GNU license in the run
+    let mut appendixValues: Option<AppendixValues> = None;
+    let mut separateNotices: Vec<String> = Vec::new();
+    let mut journaled = PendingFillRecord { journal, files: Vec::new() };
+    // Only projects with a getlicense.lock (or asking for one) pin template versions
+    let mut templateLock = templateLock.or_else(|| args.updateLock.then(TemplateLock::default));
+    let mut lockChanged = false;
+
+    for licenseEntry in &licenseEntries {
+        let outputPath = if multiple {
+            MultiLicenseOutputPath(args, licenseEntry)
+        } else {
+            ResolveOutputPath(args)
+        };
 
-    if let Some(url) = &args.projecturl {
-        if let Some(key) = cliArgToCacheKeyMap.get("projecturl") {
-            userProvidedForCaching.insert(key.to_string(), url.clone());
+        let pinnedSha = templateLock
+            .as_ref()
+            .filter(|_| !args.updateLock)
+            .and_then(|lock| lock.PinFor(&outputPath, &licenseEntry.spdxId))
+            .map(|pinned| pinned.templateSha.clone());
+
+        if let Some(sha) = pinnedSha.as_ref().filter(|sha| **sha != licenseEntry.sha) {
+            println!(
+                "{} keeps the {} template pinned in {} ({}); pass --update-lock for the current one.",
+                outputPath.display(),
+                licenseEntry.spdxId,
+                TEMPLATE_LOCK_FILENAME,
+                sha.chars().take(7).collect::<String>()
+            );
         }
-    }
 
-    // --- Determine Final Replacements for Template Filling ---
-    let mut finalTemplateReplacements: HashMap<String, String> = HashMap::new();
+        let templateSha = pinnedSha.unwrap_or_else(|| licenseEntry.sha.clone());
+        let filled = request
+            .for_license(&licenseEntry.spdxId)
+            .template_sha(Some(&templateSha))
+            .render()?;
+
+        let mut fileContent = filled.body.clone();
 
-    // 1. Start with cached preferences (non-year)
+        if args.withAppendix && appendix::HasAppendix(&licenseEntry.spdxId) {
+            if appendixValues.is_none() {
+                appendixValues = Some(AppendixValuesFor(&filled.resolved)?);
+            }
 
-    for keyStr in CACHABLE_PLACEHOLDER_KEYS.iter() {
-        // CACHABLE_PLACEHOLDER_KEYS is an array of &str
+            let notice = appendixValues
+                .as_ref()
+                .and_then(|values| appendix::RenderAppendix(&licenseEntry.spdxId, values))
+                .unwrap_or_default();
 
-        if let Some(val) = cachedPlaceholdersAtStart.get(*keyStr) {
-            finalTemplateReplacements.insert(keyStr.to_string(), val.clone());
+            match &args.appendixOutput {
+                Some(_) => separateNotices.push(notice),
+                None => fileContent = format!("{}\n\n{}", fileContent.trim_end(), notice),
+            }
+        } else if args.withAppendix {
+            eprintln!(
+                "{} {} has no \"How to Apply\" appendix; --with-appendix only applies to GPL, AGPL and LGPL.",
+                "Warning:".yellow(),
+                licenseEntry.spdxId
+            );
         }
-    }
 
-    // 2. Override with current CLI arguments (non-year)
-    finalTemplateReplacements.extend(userProvidedForCaching.clone());
+        WriteJournaledFile(&mut journaled, &outputPath, &fileContent, Some(&licenseEntry.spdxId))?;
 
-    // 3. Handle 'year' (default or CLI, not from cache)
-    let currentYearStr = chrono::Local::now().year().to_string();
-    let year_to_use = args.year.as_ref().unwrap_or(&currentYearStr);
-    finalTemplateReplacements.insert("year".to_string(), year_to_use.clone());
+        if args.provenance {
+            let usedValues: HashMap<String, String> = filled
+                .resolved
+                .iter()
+                .map(|(k, resolved)| (k.clone(), resolved.value.clone()))
+                .collect();
+            let mut record = ProvenanceRecord::New(licenseEntry, &usedValues, args.stripUnfilled);
+            record.templateSha = templateSha.clone();
+            provenance::SaveProvenance(&outputPath, &record)?;
 
-    // For summary: user_provided_for_filling_summary includes explicit CLI args + year used
-    let mut userProvidedForFillingSummary = userProvidedForCaching.clone();
-    userProvidedForFillingSummary.insert("year".to_string(), year_to_use.clone());
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!(
+                    "[Action] Recorded provenance in {:?}",
+                    provenance::ProvenancePathFor(&outputPath)
+                );
+            }
+        }
 
-    // Pass the extracted placeholders from the license entry
-    let filledLicenseBody = parser::FillLicenseTemplateBody(
-        templateBody,
-        &finalTemplateReplacements,
-        &licenseEntry.placeholdersInBody,
-    );
+        if let Some(lock) = &mut templateLock {
+            lockChanged |= lock.Pin(&outputPath, &licenseEntry.spdxId, &templateSha);
+        }
 
-    let outputPath = args
-        .output
-        .clone()
-        .unwrap_or_else(|| PathBuf::from("LICENSE"));
+        resolvedPlaceholders = filled.resolved;
+        filledLicenseBody = filled.body;
+        written.push(WrittenLicense {
+            entry: licenseEntry,
+            path: outputPath,
+            unfilled: filled.unfilled,
+        });
+    }
 
-    if let Some(parent) = outputPath.parent() {
-        fs::create_dir_all(parent).map_err(|e| {
-            AppError::ActionErrorVariant(ActionError::FileOperation(e, parent.to_path_buf()))
-        })?;
+    if let (Some(appendixPath), false) = (&args.appendixOutput, separateNotices.is_empty()) {
+        WriteJournaledFile(&mut journaled, appendixPath, &separateNotices.join("\n\n"), None)?;
+        println!("\nWrote the \"How to Apply\" notice to {}", appendixPath.display().to_string().green());
+    }
+
+    if let (Some(lock), true) = (&templateLock, lockChanged) {
+        lockfile::SaveTemplateLock(Path::new("."), lock)?;
+        println!("Pinned the template version(s) in {}", TEMPLATE_LOCK_FILENAME.green());
     }
 
-    fs::write(&outputPath, filledLicenseBody.clone() + "\n").map_err(|e| {
-        AppError::ActionErrorVariant(ActionError::FileOperation(e, outputPath.clone()))
-    })?;
+    drop(journaled);
+
+    if multiple {
+        display::DisplayMultiLicenseSummaryAfterWrite(&written, &resolvedPlaceholders, &precedence);
+    } else if let Some(single) = written.first() {
+        // Pass the whole cache for access to fields.yml etc. for summary display
+        display::DisplayLicenseSummaryAfterWrite(
+            single.entry,
+            cache,
+            &single.path,
+            &resolvedPlaceholders,
+            &precedence,
+            &filledLicenseBody,
+        );
+    }
 
     let mut placeholderCacheModified = false;
 
@@ -124,17 +613,54 @@ pub async fn FillLicenseTemplateAction(
         }
     }
 
-    // Pass the whole cache for access to fields.yml etc. for summary display
-    // Pass all CLI args for context for the summary display
-    display::DisplayLicenseSummaryAfterWrite(
-        licenseEntry,
-        cache,
-        &outputPath,
-        &userProvidedForFillingSummary,
-        &cachedPlaceholdersAtStart,
-        &filledLicenseBody,
-        cliAllArgs,
-    );
-
     Ok(placeholderCacheModified)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestFailedRunJournalsWhatItWrote() {
+        let dir = TempDir::New("fill-journal");
+        let journal = FillJournal::ForCache(&dir.join("license_cache_rs.json"));
+        let licensePath = dir.join("LICENSE");
+        fs::write(&licensePath, "old text\n").unwrap();
+
+        let outcome = (|| {
+            let mut pending = PendingFillRecord { journal: Some(&journal), files: Vec::new() };
+            WriteJournaledFile(&mut pending, &licensePath, "MIT text", Some("MIT"))?;
+            // A directory cannot be created below a file, so the second write fails
+            WriteJournaledFile(&mut pending, &licensePath.join("LICENSE-APACHE"), "Apache text", Some("Apache-2.0"))
+        })();
+
+        assert!(outcome.is_err());
+        let records = journal.Load().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].files.len(), 1);
+        assert_eq!(records[0].files[0].spdxId.as_deref(), Some("MIT"));
+        assert_eq!(records[0].files[0].previousSha, Some(GitBlobSha(b"old text\n")));
+    }
+
+    #[test]
+    fn TestAcceptedDefaultsKeepTheirSource() {
+        let default = |value: &str, source| resolve::ResolvedValue { value: value.to_string(), source };
+        let mut prompted = PromptedValues { typed: HashMap::new(), remembered: HashMap::new() };
+
+        prompted.Record("fullname", Some("Jane Doe".to_string()), Some(&default("Git Name", PlaceholderSource::Auto)));
+        prompted.Record("email", None, Some(&default("jane@example.com", PlaceholderSource::Auto)));
+        prompted.Record("project", None, Some(&default("demo", PlaceholderSource::Project)));
+        prompted.Record("login", None, Some(&default("jdoe", PlaceholderSource::Profile)));
+        prompted.Record("projecturl", None, None);
+
+        assert_eq!(prompted.typed, HashMap::from([("fullname".to_string(), "Jane Doe".to_string())]));
+        assert_eq!(
+            prompted.remembered,
+            HashMap::from([
+                ("fullname".to_string(), "Jane Doe".to_string()),
+                ("login".to_string(), "jdoe".to_string()),
+            ])
+        );
+    }
+}
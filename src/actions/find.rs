@@ -1,13 +1,79 @@
+use crate::config::Config;
 use crate::display;
 use crate::error::{ActionError, AppError};
 use crate::models::{Cache, LicenseEntry, RulesDataContent};
+use crate::permissiveness::PermissivenessWeights;
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::sync::atomic::Ordering;
 
+// Jaccard index of the two licenses' rule sets. A tag counts separately per category, so
+// patent-use as a permission and as a limitation are different rules.
+pub fn RuleSimilarity(a: &LicenseEntry, b: &LicenseEntry) -> f32 {
+    let rules = |entry: &LicenseEntry| -> HashSet<(&'static str, String)> {
+        let mut rules = HashSet::new();
+        rules.extend(entry.permissions.iter().map(|tag| ("permissions", tag.clone())));
+        rules.extend(entry.conditions.iter().map(|tag| ("conditions", tag.clone())));
+        rules.extend(entry.limitations.iter().map(|tag| ("limitations", tag.clone())));
+        rules
+    };
+
+    let (rulesA, rulesB) = (rules(a), rules(b));
+    let union = rulesA.union(&rulesB).count();
+
+    if union == 0 {
+        return 1.0;
+    }
+
+    rulesA.intersection(&rulesB).count() as f32 / union as f32
+}
+
+// How well a match fits a find query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FindRanking {
+    // Conditions the license imposes that --require did not ask for
+    pub extraConditions: Vec<String>,
+    pub score: u8,
+    // Notable projects choosealicense.com lists as using the license
+    pub popularity: usize,
+}
+
+pub fn RankingFor(entry: &LicenseEntry, requireTags: &[String], weights: &PermissivenessWeights) -> FindRanking {
+    FindRanking {
+        extraConditions: entry
+            .conditions
+            .iter()
+            .filter(|tag| !requireTags.contains(tag))
+            .cloned()
+            .collect(),
+        score: weights.Score(entry),
+        popularity: entry.infoComponents.usingInfo.as_ref().map_or(0, |using| using.len()),
+    }
+}
+
+// Best fit first: fewest extra conditions, then most permissive, then most used
+pub fn RankMatches(matches: &mut [(&LicenseEntry, Option<f32>, FindRanking)]) {
+    matches.sort_by(|(a, _, rankA), (b, _, rankB)| {
+        rankA
+            .extraConditions
+            .len()
+            .cmp(&rankB.extraConditions.len())
+            .then(rankB.score.cmp(&rankA.score))
+            .then(rankB.popularity.cmp(&rankA.popularity))
+            .then(a.spdxId.cmp(&b.spdxId))
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn FindMatchingLicenses(
     cache: &Cache,
     requireTagsOpt: Option<Vec<String>>,
     disallowTagsOpt: Option<Vec<String>>,
+    similarTo: Option<String>,
+    minSimilarity: Option<u8>,
+    minScore: Option<u8>,
+    best: Option<usize>,
+    appConfig: &Config,
 ) -> Result<(), AppError> {
     let requireTags = requireTagsOpt.unwrap_or_default();
     let disallowTags = disallowTagsOpt.unwrap_or_default();
@@ -19,17 +85,81 @@ pub async fn FindMatchingLicenses(
         );
     }
 
-    if requireTags.is_empty() && disallowTags.is_empty() {
+    if requireTags.is_empty() && disallowTags.is_empty() && similarTo.is_none() && minScore.is_none() && best.is_none() {
         return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
-            "Please provide at least one --require or --disallow tag for finding licenses."
+            "Please provide at least one --require or --disallow tag, --similar-to, --min-score or --best, for finding licenses."
                 .to_string(),
         )));
     }
 
+    let reference = match &similarTo {
+        Some(id) => {
+            let idLower = id.to_lowercase();
+            Some(
+                cache
+                    .licenses
+                    .get(&idLower)
+                    .ok_or_else(|| super::LicenseNotFoundError(idLower))?,
+            )
+        }
+        None => None,
+    };
+
+    let matches = FilterLicensesByRules(cache, &requireTags, &disallowTags)?;
+    let weights = PermissivenessWeights::WithOverrides(&appConfig.permissivenessWeights);
+
+    let mut matchedEntries: Vec<(&LicenseEntry, Option<f32>, FindRanking)> = matches
+        .iter()
+        .map(|entry| {
+            (
+                entry.as_ref(),
+                reference.map(|r| RuleSimilarity(r, entry)),
+                RankingFor(entry, &requireTags, &weights),
+            )
+        })
+        .filter(|(_, _, ranking)| minScore.is_none_or(|minScore| ranking.score >= minScore))
+        .collect();
+
+    RankMatches(&mut matchedEntries);
+
+    // Most similar first, ties in fit order; the reference itself is not a result
+    if let Some(reference) = reference {
+        let minimum = f32::from(minSimilarity.unwrap_or(0)) / 100.0;
+
+        matchedEntries.retain(|(entry, similarity, _)| {
+            entry.spdxId != reference.spdxId && similarity.unwrap_or(0.0) >= minimum
+        });
+        matchedEntries.sort_by(|a, b| b.1.unwrap_or(0.0).total_cmp(&a.1.unwrap_or(0.0)));
+    }
+
+    let totalMatches = matchedEntries.len();
+    if let Some(best) = best {
+        matchedEntries.truncate(best);
+    }
+
+    display::PrintFindResults(
+        &matchedEntries,
+        totalMatches,
+        reference.map(|r| r.spdxId.as_str()),
+        &requireTags,
+        &disallowTags,
+    );
+
+    Ok(())
+}
+
+// Validates the tags against rules.yml and returns the licenses carrying every required
+// tag and none of the disallowed ones, sorted by SPDX ID.
+pub fn FilterLicensesByRules<'a, S: AsRef<str>>(
+    cache: &'a Cache,
+    requireTags: &[S],
+    disallowTags: &[S],
+) -> Result<Vec<&'a Arc<LicenseEntry>>, AppError> {
+    let requireTags: Vec<String> = requireTags.iter().map(|t| t.as_ref().to_string()).collect();
+    let disallowTags: Vec<String> = disallowTags.iter().map(|t| t.as_ref().to_string()).collect();
+
     let rulesDataContent: RulesDataContent = cache
-        .dataFiles // dataFiles is correct
-        .get(crate::constants::RULES_YML_KEY)
-        .and_then(|entry| serde_yaml::from_value(entry.content.clone()).ok())
+        .RulesData()
         .ok_or_else(|| {
             AppError::ActionErrorVariant(ActionError::MissingData(
                 // Corrected this line based on other similar changes
@@ -84,7 +214,7 @@ pub async fn FindMatchingLicenses(
         )));
     }
 
-    let mut matches: Vec<&LicenseEntry> = Vec::new();
+    let mut matches: Vec<&Arc<LicenseEntry>> = Vec::new();
 
     for licenseEntry in cache.licenses.values() {
         // The raw tags are directly available in LicenseEntry
@@ -104,7 +234,59 @@ pub async fn FindMatchingLicenses(
     // Sort matches by SPDX ID for consistent output
     matches.sort_by_key(|entry| &entry.spdxId);
 
-    display::PrintFindResults(&matches, &requireTags, &disallowTags);
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Strings;
 
-    Ok(())
+    #[test]
+    fn TestRuleSimilarity() {
+        let license = |permissions: &[&str], limitations: &[&str]| LicenseEntry {
+            permissions: Strings(permissions),
+            limitations: Strings(limitations),
+            ..LicenseEntry::ForTest("", "")
+        };
+
+        let mpl = license(&["commercial-use", "patent-use"], &["liability"]);
+        let mit = license(&["commercial-use"], &["liability"]);
+        let patentLimited = license(&["commercial-use"], &["liability", "patent-use"]);
+
+        assert_eq!(RuleSimilarity(&mpl, &mpl), 1.0);
+        assert!((RuleSimilarity(&mpl, &mit) - 2.0 / 3.0).abs() < 1e-6);
+        // The same tag in another category is a different rule
+        assert_eq!(RuleSimilarity(&mpl, &patentLimited), 0.5);
+    }
+
+    #[test]
+    fn TestRankMatches() {
+        let license = |spdxId: &str, conditions: &[&str], projects: usize| LicenseEntry {
+            permissions: Strings(&["commercial-use"]),
+            conditions: Strings(conditions),
+            infoComponents: crate::models::InfoComponents {
+                usingInfo: Some((0..projects).map(|i| (i.to_string(), String::new())).collect()),
+                ..Default::default()
+            },
+            ..LicenseEntry::ForTest(spdxId, "")
+        };
+
+        let mit = license("MIT", &["include-copyright"], 3);
+        let isc = license("ISC", &["include-copyright"], 1);
+        let gpl = license("GPL-3.0", &["include-copyright", "disclose-source", "same-license"], 9);
+        let weights = PermissivenessWeights::default();
+        let require = vec!["include-copyright".to_string()];
+
+        let mut matches: Vec<_> = [&gpl, &isc, &mit]
+            .into_iter()
+            .map(|entry| (entry, None, RankingFor(entry, &require, &weights)))
+            .collect();
+        RankMatches(&mut matches);
+
+        let order: Vec<&str> = matches.iter().map(|(entry, _, _)| entry.spdxId.as_str()).collect();
+        assert_eq!(order, ["MIT", "ISC", "GPL-3.0"]);
+        assert_eq!(matches[2].2.extraConditions, ["disclose-source", "same-license"]);
+        assert_eq!(matches[0].2.popularity, 3);
+    }
 }
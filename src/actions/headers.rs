@@ -0,0 +1,718 @@
+use colored::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{WalkBuilder, WalkState};
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use regex::Regex;
+
+use crate::config::{self, HeaderStyle};
+use crate::constants::{
+    HEADER_CHECK_EXTENSIONS, HEADER_CHECK_SKIPPED_DIRS, HEADER_COMMENT_PREFIX_TUPLES,
+    HEADER_LANGUAGE_TUPLES, HEADER_SCAN_LINES, LICENSE_IGNORE_FILENAME, SPDX_HEADER_TAG,
+};
+use crate::display;
+use crate::drift;
+use crate::error::{ActionError, AppError, CacheError};
+use crate::models::Cache;
+
+// "Copyright (c) 2019" or "Copyright 2019-2023": the lead-in, the first year and the last
+static HEADER_COPYRIGHT_YEAR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(copyright\b[^\d\n]*?)(\d{4})(?:\s*[-\u{2013}]\s*(\d{4}))?").unwrap()
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderProblem {
+    Missing,
+    // Holds the identifier that was found instead
+    WrongId(String),
+}
+
+// Header coverage of one directory or one language
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CoverageGroup {
+    pub name: String,
+    pub files: usize,
+    pub covered: usize,
+    pub percent: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct UncoveredFile {
+    pub path: PathBuf,
+    // "missing" or "wrong-id"
+    pub problem: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub found: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct HeaderCoverageReport {
+    pub expected: String,
+    pub files: usize,
+    pub covered: usize,
+    pub percent: f64,
+    pub directories: Vec<CoverageGroup>,
+    pub languages: Vec<CoverageGroup>,
+    pub uncovered: Vec<UncoveredFile>,
+}
+
+fn CoveragePercent(covered: usize, files: usize) -> f64 {
+    if files == 0 { 100.0 } else { covered as f64 * 100.0 / files as f64 }
+}
+
+fn LanguageOf(path: &Path) -> String {
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    HEADER_LANGUAGE_TUPLES
+        .iter()
+        .find(|(known, _)| *known == ext)
+        .map(|(_, language)| language.to_string())
+        .unwrap_or_else(|| if ext.is_empty() { "other".to_string() } else { ext })
+}
+
+fn DirectoryOf(path: &Path) -> String {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let parent = parent.strip_prefix(".").unwrap_or(parent);
+
+    if parent.as_os_str().is_empty() { ".".to_string() } else { parent.display().to_string() }
+}
+
+// Sorted by name; each file counts toward its own directory only, not its ancestors
+fn GroupCoverage(files: &[PathBuf], uncovered: &HashSet<&PathBuf>, keyOf: fn(&Path) -> String) -> Vec<CoverageGroup> {
+    let mut groups: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+
+    for file in files {
+        let entry = groups.entry(keyOf(file)).or_default();
+        entry.0 += 1;
+        if !uncovered.contains(file) {
+            entry.1 += 1;
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, (files, covered))| CoverageGroup {
+            name,
+            files,
+            covered,
+            percent: CoveragePercent(covered, files),
+        })
+        .collect()
+}
+
+pub fn BuildCoverageReport(expectedSpdxId: &str, files: &[PathBuf], problems: &[(PathBuf, HeaderProblem)]) -> HeaderCoverageReport {
+    let covered = files.len() - problems.len();
+    let uncovered: HashSet<&PathBuf> = problems.iter().map(|(path, _)| path).collect();
+
+    HeaderCoverageReport {
+        expected: expectedSpdxId.to_string(),
+        files: files.len(),
+        covered,
+        percent: CoveragePercent(covered, files.len()),
+        directories: GroupCoverage(files, &uncovered, DirectoryOf),
+        languages: GroupCoverage(files, &uncovered, LanguageOf),
+        uncovered: problems
+            .iter()
+            .map(|(path, problem)| UncoveredFile {
+                path: path.clone(),
+                problem: match problem {
+                    HeaderProblem::Missing => "missing",
+                    HeaderProblem::WrongId(_) => "wrong-id",
+                },
+                found: match problem {
+                    HeaderProblem::Missing => None,
+                    HeaderProblem::WrongId(found) => Some(found.clone()),
+                },
+            })
+            .collect(),
+    }
+}
+
+// Reads the SPDX identifier from the first few lines, without trailing comment closers.
+pub fn ReadSpdxHeader(content: &str) -> Option<String> {
+    content.lines().take(HEADER_SCAN_LINES).find_map(|line| {
+        let idx = line.find(SPDX_HEADER_TAG)?;
+        let id = line[idx + SPDX_HEADER_TAG.len()..]
+            .trim()
+            .trim_end_matches("*/")
+            .trim_end_matches("-->")
+            .trim();
+
+        if id.is_empty() { None } else { Some(id.to_string()) }
+    })
+}
+
+fn IsCheckedSourceFile(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| HEADER_CHECK_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+// The [headers] exclude globs. Override globs normally whitelist; the ! makes them excludes.
+fn ExcludeMatcher(globs: &[String]) -> Result<Override, AppError> {
+    let projectDir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut builder = OverrideBuilder::new(projectDir);
+
+    for glob in globs {
+        builder.add(&format!("!{}", glob)).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput(format!("invalid [headers] exclude glob '{}': {}", glob, e)))
+        })?;
+    }
+
+    builder.build().map_err(|e| AppError::ActionErrorVariant(ActionError::InvalidInput(e.to_string())))
+}
+
+// Hidden entries, .gitignore (inside a git repository), .ignore and .licenseignore files are
+// honored like ripgrep does. Directories are walked on as many threads as the current rayon
+// pool has, so --jobs caps both.
+fn CollectSourceFiles(dir: &Path, excludes: &Override) -> Vec<PathBuf> {
+    let files = Mutex::new(Vec::new());
+
+    WalkBuilder::new(dir)
+        .add_custom_ignore_filename(LICENSE_IGNORE_FILENAME)
+        .overrides(excludes.clone())
+        .threads(rayon::current_num_threads())
+        .filter_entry(|entry| {
+            !entry.file_type().is_some_and(|fileType| fileType.is_dir())
+                || !HEADER_CHECK_SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+        })
+        .build_parallel()
+        .run(|| {
+            let files = &files;
+            Box::new(move |result| {
+                match result {
+                    Ok(entry) => {
+                        let isDir = entry.file_type().is_some_and(|fileType| fileType.is_dir());
+                        if !isDir && IsCheckedSourceFile(entry.path()) {
+                            files.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(entry.into_path());
+                        }
+                    }
+                    // An unreadable directory or a broken ignore file should not stop the walk
+                    Err(e) => eprintln!("{} {}", "Warning:".yellow(), e),
+                }
+                WalkState::Continue
+            })
+        });
+
+    files.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Directories are walked for known source extensions, skipping ignored and excluded files;
+// files given explicitly are always checked.
+pub fn CollectFilesToCheck(paths: &[PathBuf], excludeGlobs: &[String]) -> Result<Vec<PathBuf>, AppError> {
+    let excludes = ExcludeMatcher(excludeGlobs)?;
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            files.extend(CollectSourceFiles(path, &excludes));
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+pub fn CheckFileHeader(path: &Path, expectedSpdxId: &str) -> Result<Option<HeaderProblem>, AppError> {
+    let bytes = fs::read(path).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, path.to_path_buf()))
+    })?;
+
+    // Lossy decoding: a non-UTF-8 file simply ends up reported as missing a header
+    Ok(match ReadSpdxHeader(&String::from_utf8_lossy(&bytes)) {
+        None => Some(HeaderProblem::Missing),
+        Some(found) if !found.eq_ignore_ascii_case(expectedSpdxId) => {
+            Some(HeaderProblem::WrongId(found))
+        }
+        Some(_) => None,
+    })
+}
+
+// Without --license, expect whatever the project's license file contains
+fn ExpectedSpdxId(cache: &Cache, licenseIdOpt: Option<&str>) -> Result<String, AppError> {
+    match licenseIdOpt {
+        Some(id) => Ok(id.to_string()),
+        None => {
+            let licensePath = super::validate::FindLicenseFile(Path::new(".")).ok_or_else(|| {
+                AppError::ActionErrorVariant(ActionError::InvalidInput(
+                    "No license file found to infer the expected SPDX ID; pass --license."
+                        .to_string(),
+                ))
+            })?;
+
+            Ok(super::validate::ReadAndIdentifyLicenseFile(cache, &licensePath)?
+                .spdxId
+                .clone())
+        }
+    }
+}
+
+pub async fn CheckHeaders(
+    cache: &Cache,
+    paths: &[PathBuf],
+    licenseIdOpt: Option<&str>,
+    report: bool,
+    asJson: bool,
+    jobs: Option<usize>,
+) -> Result<(), AppError> {
+    let expectedSpdxId = ExpectedSpdxId(cache, licenseIdOpt)?;
+    let projectConfig = config::LoadProjectConfig(Path::new("."))?;
+
+    // 0 lets rayon pick one thread per CPU
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::InvalidInput(format!("cannot start worker threads: {}", e))))?;
+
+    let files = pool.install(|| CollectFilesToCheck(paths, &projectConfig.headers.exclude))?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Checking {} file(s) for SPDX-License-Identifier: {}",
+            files.len(),
+            expectedSpdxId
+        );
+    }
+
+    // Collecting keeps the sorted file order
+    let problems: Vec<(PathBuf, HeaderProblem)> = pool.install(|| {
+        files
+            .par_iter()
+            .filter_map(|file| match CheckFileHeader(file, &expectedSpdxId) {
+                Ok(problem) => problem.map(|problem| Ok((file.clone(), problem))),
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<Result<_, AppError>>()
+    })?;
+
+    // A report describes coverage for dashboards rather than gating, so it always succeeds
+    if report {
+        let coverage = BuildCoverageReport(&expectedSpdxId, &files, &problems);
+
+        if asJson {
+            let json = serde_json::to_string_pretty(&coverage)
+                .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+            println!("{}", json);
+        } else {
+            display::PrintHeaderCoverageReport(&coverage);
+        }
+
+        return Ok(());
+    }
+
+    display::PrintHeaderCheckResults(&expectedSpdxId, files.len(), &problems);
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} of {} file(s) lack a correct SPDX header",
+            problems.len(),
+            files.len()
+        ))))
+    }
+}
+
+pub fn CommentPrefixFor(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+
+    HEADER_COMMENT_PREFIX_TUPLES
+        .iter()
+        .find(|(known, _)| *known == ext)
+        .map(|(_, prefix)| *prefix)
+}
+
+// The comment block for one file, without the blank line that separates it from the code
+pub fn RenderHeader(
+    prefix: &str,
+    spdxExpression: &str,
+    style: HeaderStyle,
+    copyrightLine: Option<&str>,
+    licenseTitle: &str,
+) -> String {
+    let mut lines = vec![format!("{} {} {}", prefix, SPDX_HEADER_TAG, spdxExpression)];
+
+    if let Some(copyright) = copyrightLine {
+        lines.push(format!("{} {}", prefix, copyright));
+    }
+
+    if style == HeaderStyle::Notice {
+        lines.push(prefix.to_string());
+        lines.push(format!("{} Licensed under the {}.", prefix, licenseTitle));
+        lines.push(format!("{} See the LICENSE file in the project root for the full license text.", prefix));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// The header goes below a shebang, a Python encoding declaration or a PHP open tag, which
+// must stay on the first lines.
+pub fn InsertHeader(content: &str, header: &str) -> String {
+    let mut keptLines = 0;
+
+    for line in content.lines().take(2) {
+        let pinned = (keptLines == 0 && (line.starts_with("#!") || line.trim_start().starts_with("<?php")))
+            || (line.starts_with('#') && line.contains("coding") && (line.contains(':') || line.contains('=')));
+
+        if !pinned {
+            break;
+        }
+        keptLines += 1;
+    }
+
+    let splitAt: usize = content.split_inclusive('\n').take(keptLines).map(str::len).sum();
+    let (pinned, rest) = content.split_at(splitAt);
+    let pinned = if !pinned.is_empty() && !pinned.ends_with('\n') { format!("{}\n", pinned) } else { pinned.to_string() };
+
+    if rest.trim().is_empty() {
+        format!("{}{}", pinned, header)
+    } else {
+        format!("{}{}\n{}", pinned, header, rest)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn AddHeaders(
+    cache: &Cache,
+    paths: &[PathBuf],
+    licenseIdOpt: Option<&str>,
+    styleOpt: Option<HeaderStyle>,
+    withCopyright: bool,
+    fullnameOpt: Option<&str>,
+    dryRun: bool,
+) -> Result<(), AppError> {
+    let spdxExpression = ExpectedSpdxId(cache, licenseIdOpt)?;
+    let projectConfig = config::LoadProjectConfig(Path::new("."))?;
+    let headersConfig = &projectConfig.headers;
+
+    let licenseTitle = cache
+        .licenses
+        .get(&spdxExpression.to_lowercase())
+        .map(|entry| entry.title.clone())
+        .unwrap_or_else(|| spdxExpression.clone());
+
+    let copyrightLine = if withCopyright || headersConfig.copyright.unwrap_or(false) {
+        let fullname = fullnameOpt
+            .map(str::to_string)
+            .or_else(|| projectConfig.placeholders.get("fullname").cloned())
+            .or_else(|| cache.userPlaceholders.get("fullname").cloned())
+            .ok_or_else(|| {
+                AppError::ActionErrorVariant(ActionError::InvalidInput(
+                    "A copyright line needs a name: pass --fullname or save one with set-placeholder fullname."
+                        .to_string(),
+                ))
+            })?;
+
+        Some(format!("Copyright (c) {} {}", crate::clock::CurrentYear(), fullname))
+    } else {
+        None
+    };
+
+    let files = CollectFilesToCheck(paths, &headersConfig.exclude)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Adding SPDX-License-Identifier: {} to up to {} file(s)", spdxExpression, files.len());
+    }
+
+    let mut added: Vec<PathBuf> = Vec::new();
+    let mut alreadyPresent = 0;
+    let mut unsupported = 0;
+
+    for file in &files {
+        let Some(prefix) = CommentPrefixFor(file) else {
+            unsupported += 1;
+            continue;
+        };
+
+        let content = fs::read_to_string(file).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, file.clone()))
+        })?;
+
+        // Wrong or outdated headers are fix-headers' job; never stack a second one
+        if ReadSpdxHeader(&content).is_some() {
+            alreadyPresent += 1;
+            continue;
+        }
+
+        let style = styleOpt.unwrap_or_else(|| headersConfig.StyleFor(file));
+        let header = RenderHeader(prefix, &spdxExpression, style, copyrightLine.as_deref(), &licenseTitle);
+
+        if !dryRun {
+            fs::write(file, InsertHeader(&content, &header)).map_err(|e| {
+                AppError::ActionErrorVariant(ActionError::FileOperation(e, file.clone()))
+            })?;
+        }
+
+        added.push(file.clone());
+    }
+
+    display::PrintAddedHeaders(&spdxExpression, &added, alreadyPresent, unsupported, dryRun);
+
+    Ok(())
+}
+
+// What fix-headers changes in the header lines of a file.
+pub struct HeaderFixes<'a> {
+    pub spdxExpression: &'a str,
+    // Copyright years are extended to end in this one
+    pub year: i32,
+    // Copyright holders replaced by `newHolder`
+    pub oldHolders: &'a [String],
+    pub newHolder: Option<&'a str>,
+}
+
+fn FixSpdxLine(line: &str, expected: &str) -> Option<String> {
+    let idx = line.find(SPDX_HEADER_TAG)? + SPDX_HEADER_TAG.len();
+    let found = ReadSpdxHeader(line)?;
+
+    if found.eq_ignore_ascii_case(expected) {
+        return None;
+    }
+
+    let (lead, rest) = line.split_at(idx);
+    Some(format!("{}{}", lead, rest.replacen(&found, expected, 1)))
+}
+
+fn FixCopyrightLine(line: &str, fixes: &HeaderFixes) -> Option<String> {
+    if !line.to_lowercase().contains("copyright") {
+        return None;
+    }
+
+    let mut fixed = HEADER_COPYRIGHT_YEAR_RE
+        .replace(line, |caps: &regex::Captures| {
+            let first: i32 = caps[2].parse().unwrap_or(fixes.year);
+            let last: i32 = caps.get(3).and_then(|m| m.as_str().parse().ok()).unwrap_or(first);
+
+            if last >= fixes.year || first > fixes.year {
+                caps[0].to_string()
+            } else {
+                format!("{}{}-{}", &caps[1], first, fixes.year)
+            }
+        })
+        .into_owned();
+
+    if let Some(newHolder) = fixes.newHolder {
+        for oldHolder in fixes.oldHolders.iter().filter(|holder| !holder.is_empty()) {
+            fixed = fixed.replace(oldHolder.as_str(), newHolder);
+        }
+    }
+
+    (fixed != line).then_some(fixed)
+}
+
+// The content with its header lines fixed, or None if the header needs no change. Only the
+// lines scanned for the SPDX tag are touched; line endings are preserved.
+pub fn FixHeaderText(content: &str, fixes: &HeaderFixes) -> Option<String> {
+    let mut changed = false;
+    let mut fixed = String::with_capacity(content.len());
+
+    for (index, rawLine) in content.split_inclusive('\n').enumerate() {
+        let line = rawLine.trim_end_matches(['\r', '\n']);
+        let ending = &rawLine[line.len()..];
+
+        let replacement = if index < HEADER_SCAN_LINES {
+            FixSpdxLine(line, fixes.spdxExpression).or_else(|| FixCopyrightLine(line, fixes))
+        } else {
+            None
+        };
+
+        match replacement {
+            Some(newLine) => {
+                changed = true;
+                fixed.push_str(&newLine);
+                fixed.push_str(ending);
+            }
+            None => fixed.push_str(rawLine),
+        }
+    }
+
+    changed.then_some(fixed)
+}
+
+pub async fn FixHeaders(
+    cache: &Cache,
+    paths: &[PathBuf],
+    licenseIdOpt: Option<&str>,
+    oldHolders: &[String],
+    fullnameOpt: Option<&str>,
+    dryRun: bool,
+) -> Result<(), AppError> {
+    let spdxExpression = ExpectedSpdxId(cache, licenseIdOpt)?;
+    let projectConfig = config::LoadProjectConfig(Path::new("."))?;
+
+    let newHolder = match fullnameOpt {
+        Some(name) => Some(name.to_string()),
+        None if !oldHolders.is_empty() => Some(
+            projectConfig
+                .placeholders
+                .get("fullname")
+                .or_else(|| cache.userPlaceholders.get("fullname"))
+                .cloned()
+                .ok_or_else(|| {
+                    AppError::ActionErrorVariant(ActionError::InvalidInput(
+                        "--old-holder needs a replacement: pass --fullname or save one with set-placeholder fullname."
+                            .to_string(),
+                    ))
+                })?,
+        ),
+        None => None,
+    };
+
+    let fixes = HeaderFixes {
+        spdxExpression: &spdxExpression,
+        year: crate::clock::CurrentYear(),
+        oldHolders,
+        newHolder: newHolder.as_deref(),
+    };
+
+    let files = CollectFilesToCheck(paths, &projectConfig.headers.exclude)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Fixing headers of {} file(s) for SPDX-License-Identifier: {}", files.len(), spdxExpression);
+    }
+
+    let mut fixed: Vec<PathBuf> = Vec::new();
+    let mut missing = 0;
+
+    for file in &files {
+        let bytes = fs::read(file).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, file.clone()))
+        })?;
+
+        // Rewriting would mangle a file that is not text
+        let Ok(content) = String::from_utf8(bytes) else {
+            continue;
+        };
+
+        if ReadSpdxHeader(&content).is_none() {
+            missing += 1;
+            continue;
+        }
+
+        let Some(newContent) = FixHeaderText(&content, &fixes) else {
+            continue;
+        };
+
+        if dryRun {
+            let label = file.display().to_string();
+            display::PrintUnifiedDiff(&drift::LineDiff(&content, &newContent, &label, &label));
+        } else {
+            fs::write(file, &newContent).map_err(|e| {
+                AppError::ActionErrorVariant(ActionError::FileOperation(e, file.clone()))
+            })?;
+        }
+
+        fixed.push(file.clone());
+    }
+
+    display::PrintFixedHeaders(&fixed, missing, dryRun);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestReadSpdxHeader() {
+        assert_eq!(
+            ReadSpdxHeader("// SPDX-License-Identifier: MIT\nfn main() {}"),
+            Some("MIT".to_string())
+        );
+        assert_eq!(
+            ReadSpdxHeader("/* SPDX-License-Identifier: Apache-2.0 */\n"),
+            Some("Apache-2.0".to_string())
+        );
+        assert_eq!(ReadSpdxHeader("fn main() {}\n"), None);
+    }
+
+    #[test]
+    fn TestFixHeaderText() {
+        let oldHolders = ["Acme Inc".to_string()];
+        let fixes = HeaderFixes {
+            spdxExpression: "Apache-2.0",
+            year: 2026,
+            oldHolders: &oldHolders,
+            newHolder: Some("NewCo"),
+        };
+
+        let content = "/* SPDX-License-Identifier: MIT */\r\n// Copyright (c) 2019-2023 Acme Inc\r\nfn main() {}\r\n";
+        assert_eq!(
+            FixHeaderText(content, &fixes).as_deref(),
+            Some("/* SPDX-License-Identifier: Apache-2.0 */\r\n// Copyright (c) 2019-2026 NewCo\r\nfn main() {}\r\n")
+        );
+        assert_eq!(FixHeaderText("# SPDX-License-Identifier: Apache-2.0\n# Copyright 2026 NewCo\n", &fixes), None);
+    }
+
+    #[test]
+    fn TestInsertHeader() {
+        let header = RenderHeader("#", "MIT", HeaderStyle::Spdx, Some("Copyright (c) 2024 Jane Doe"), "MIT License");
+        assert_eq!(header, "# SPDX-License-Identifier: MIT\n# Copyright (c) 2024 Jane Doe\n");
+
+        assert_eq!(
+            InsertHeader("#!/usr/bin/env python\n# -*- coding: utf-8 -*-\nprint(1)\n", &header),
+            "#!/usr/bin/env python\n# -*- coding: utf-8 -*-\n# SPDX-License-Identifier: MIT\n# Copyright (c) 2024 Jane Doe\n\nprint(1)\n"
+        );
+
+        let notice = RenderHeader("//", "MIT", HeaderStyle::Notice, None, "MIT License");
+        assert_eq!(InsertHeader("fn main() {}\n", &notice).lines().nth(2), Some("// Licensed under the MIT License."));
+    }
+
+    #[test]
+    fn TestBuildCoverageReport() {
+        let files = [
+            PathBuf::from("./main.py"),
+            PathBuf::from("./src/lib.rs"),
+            PathBuf::from("./src/util.rs"),
+        ];
+        let problems = [(PathBuf::from("./src/util.rs"), HeaderProblem::Missing)];
+
+        let report = BuildCoverageReport("MIT", &files, &problems);
+
+        assert_eq!(report.covered, 2);
+        let directories: Vec<(&str, usize, usize)> =
+            report.directories.iter().map(|g| (g.name.as_str(), g.files, g.covered)).collect();
+        assert_eq!(directories, [(".", 1, 1), ("src", 2, 1)]);
+        let languages: Vec<(&str, f64)> = report.languages.iter().map(|g| (g.name.as_str(), g.percent)).collect();
+        assert_eq!(languages, [("Python", 100.0), ("Rust", 50.0)]);
+    }
+
+    #[test]
+    fn TestCollectFilesToCheck() {
+        let dir = TempDir::New("headers");
+        for subdir in ["src/a", "src/b", "src/third_party", "target", ".git"] {
+            fs::create_dir_all(dir.join(subdir)).unwrap();
+        }
+        for file in [
+            "src/a/x.rs", "src/a/x_generated.rs", "src/b/y.py", "src/b/notes.txt", "src/third_party/z.c",
+            "target/gen.rs", ".git/hook.sh", "main.rs",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        fs::write(dir.join("src").join(LICENSE_IGNORE_FILENAME), "third_party/\n").unwrap();
+
+        let files = CollectFilesToCheck(&[dir.to_path_buf(), dir.join("src/b/notes.txt")], &["*_generated.rs".to_string()]).unwrap();
+
+        let expected: Vec<PathBuf> = ["main.rs", "src/a/x.rs", "src/b/notes.txt", "src/b/y.py"]
+            .iter()
+            .map(|file| dir.join(file))
+            .collect();
+        assert_eq!(files, expected);
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use crate::display;
+use crate::error::{AppError, CacheError};
+use crate::history;
+
+// `None` means an in-memory cache, which keeps no history.
+pub async fn ShowSyncHistory(historyPath: Option<&Path>, limit: usize, asJson: bool) -> Result<(), AppError> {
+    let mut records = match historyPath {
+        Some(path) => {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[Action] Reading sync history from {:?}", path);
+            }
+            history::LoadSyncHistory(path)?
+        }
+        None => {
+            eprintln!("No sync history is kept with --cache none.");
+            Vec::new()
+        }
+    };
+
+    records.reverse();
+    records.truncate(limit);
+
+    if asJson {
+        let json = serde_json::to_string_pretty(&records)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintSyncHistory(&records);
+    }
+
+    Ok(())
+}
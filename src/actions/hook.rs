@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::Ordering;
+
+use colored::*;
+
+use crate::constants::{HOOK_MARKER, PRE_COMMIT_CONFIG_FILENAME};
+use crate::error::{ActionError, AppError};
+
+fn HookCommands(skipHeaders: bool) -> Vec<(&'static str, &'static str)> {
+    let mut commands = vec![("getlicense-validate", "getlicense --offline validate")];
+
+    if !skipHeaders {
+        commands.push(("getlicense-check-headers", "getlicense --offline check-headers"));
+    }
+
+    commands
+}
+
+pub fn BuildPreCommitScript(skipHeaders: bool) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n{}\n# Blocks commits that remove or corrupt the license file.\n",
+        HOOK_MARKER
+    );
+
+    for (_, command) in HookCommands(skipHeaders) {
+        script.push_str(&format!("{} || exit 1\n", command));
+    }
+
+    script
+}
+
+pub fn BuildPreCommitConfig(skipHeaders: bool) -> String {
+    let mut config = "repos:\n  - repo: local\n    hooks:\n".to_string();
+
+    for (id, command) in HookCommands(skipHeaders) {
+        config.push_str(&format!(
+            "      - id: {id}\n        name: {id}\n        entry: {command}\n        language: system\n        pass_filenames: false\n        always_run: true\n"
+        ));
+    }
+
+    config
+}
+
+// Honours core.hooksPath and worktrees by asking git instead of assuming .git/hooks
+fn GitHooksDir() -> Result<PathBuf, AppError> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .map_err(|e| AppError::Io(e, PathBuf::from("git")))?;
+
+    if !output.status.success() {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "Not inside a git repository.".to_string(),
+        )));
+    }
+
+    Ok(PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+fn WriteFile(path: &PathBuf, content: &str) -> Result<(), AppError> {
+    fs::write(path, content)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, path.clone())))
+}
+
+pub async fn InstallHook(force: bool, usePreCommitFramework: bool, skipHeaders: bool) -> Result<(), AppError> {
+    if usePreCommitFramework {
+        let configPath = PathBuf::from(PRE_COMMIT_CONFIG_FILENAME);
+        let config = BuildPreCommitConfig(skipHeaders);
+
+        // Merging into an existing YAML file could reorder or drop the user's comments
+        if configPath.exists() && !force {
+            println!(
+                "{} already exists. Add these hooks to it:\n\n{}",
+                PRE_COMMIT_CONFIG_FILENAME.cyan(),
+                config
+            );
+            return Ok(());
+        }
+
+        WriteFile(&configPath, &config)?;
+        println!("Wrote {}. Run `pre-commit install` to activate it.", PRE_COMMIT_CONFIG_FILENAME.green());
+
+        return Ok(());
+    }
+
+    let hooksDir = GitHooksDir()?;
+    let hookPath = hooksDir.join("pre-commit");
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Installing pre-commit hook at {:?}", hookPath);
+    }
+
+    if let Ok(existing) = fs::read_to_string(&hookPath) {
+        if !existing.contains(HOOK_MARKER) && !force {
+            return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "{} already exists and was not written by getlicense. Use --force to replace it.",
+                hookPath.display()
+            ))));
+        }
+    }
+
+    fs::create_dir_all(&hooksDir).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, hooksDir.clone()))
+    })?;
+    WriteFile(&hookPath, &BuildPreCommitScript(skipHeaders))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(&hookPath, fs::Permissions::from_mode(0o755)).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, hookPath.clone()))
+        })?;
+    }
+
+    println!("Installed pre-commit hook at {}", hookPath.display().to_string().green());
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+
+use crate::constants::DRIFT_MIN_SIMILARITY;
+use crate::display;
+use crate::drift;
+use crate::error::{ActionError, AppError, CacheError};
+use crate::models::Cache;
+use crate::parser;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IdentifyCandidate {
+    #[serde(rename = "spdx_id")]
+    pub spdxId: String,
+    pub title: String,
+    // 1.0 for an exact template match, otherwise word-level similarity
+    pub score: f32,
+    pub exact: bool,
+}
+
+// Every cached license scored against `text`, best first. Exact matches always rank
+// above approximate ones, longer templates first (BSD-3-Clause before BSD-2-Clause).
+pub fn RankLicenseCandidates(cache: &Cache, text: &str) -> Vec<IdentifyCandidate> {
+    let mut scored: Vec<(IdentifyCandidate, usize)> = cache
+        .licenses
+        .values()
+        .map(|entry| {
+            let templateBody = parser::LicenseTemplateBody(&entry.fileContentCached);
+            let exact = parser::TemplateMatchesText(&templateBody, &entry.placeholdersInBody, text);
+            let score = if exact { 1.0 } else { drift::WordSimilarity(text, &templateBody) };
+
+            (
+                IdentifyCandidate {
+                    spdxId: entry.spdxId.clone(),
+                    title: entry.title.clone(),
+                    score,
+                    exact,
+                },
+                templateBody.len(),
+            )
+        })
+        .collect();
+
+    scored.sort_by(|(a, aLen), (b, bLen)| {
+        b.exact
+            .cmp(&a.exact)
+            .then(b.score.total_cmp(&a.score))
+            .then(bLen.cmp(aLen))
+            .then(a.spdxId.cmp(&b.spdxId))
+    });
+
+    scored.into_iter().map(|(candidate, _)| candidate).collect()
+}
+
+// A file path, or "-" for stdin
+pub fn ReadInput(source: &str) -> Result<String, AppError> {
+    if source == "-" {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| AppError::Io(e, PathBuf::from("stdin")))?;
+        return Ok(text);
+    }
+
+    fs::read_to_string(source).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, PathBuf::from(source)))
+    })
+}
+
+pub async fn IdentifyLicense(
+    cache: &Cache,
+    source: &str,
+    limit: usize,
+    asJson: bool,
+) -> Result<(), AppError> {
+    if cache.licenses.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(
+            "No license templates are cached. Run getlicense once without --offline first."
+                .to_string(),
+        )));
+    }
+
+    let text = ReadInput(source)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Identifying {} bytes of license text from {}",
+            text.len(),
+            if source == "-" { "stdin" } else { source }
+        );
+    }
+
+    let mut candidates = RankLicenseCandidates(cache, &text);
+    candidates.truncate(limit);
+
+    if asJson {
+        let json = serde_json::to_string_pretty(&candidates)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintIdentifyCandidates(&candidates);
+    }
+
+    match candidates.first() {
+        Some(best) if best.score >= DRIFT_MIN_SIMILARITY => Ok(()),
+        _ => Err(AppError::ActionErrorVariant(ActionError::CheckFailed(
+            "The text does not resemble any cached license template".to_string(),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LicenseEntry;
+    use std::sync::Arc;
+
+    fn Entry(spdxId: &str, body: &str) -> Arc<LicenseEntry> {
+        Arc::new(LicenseEntry::ForTest(spdxId, body))
+    }
+
+    #[test]
+    fn TestRankLicenseCandidates() {
+        let mut cache = Cache::default();
+        cache.licenses.insert("short".into(), Entry("SHORT", "Copyright [fullname]\n\nYou may use this."));
+        cache.licenses.insert(
+            "long".into(),
+            Entry("LONG", "Copyright [fullname]\n\nYou may use this.\n\nNo warranty of any kind."),
+        );
+        cache.licenses.insert("other".into(), Entry("OTHER", "Entirely different terms apply here."));
+
+        let ranked = RankLicenseCandidates(&cache, "Copyright Jane\n\nYou may use this.\n\nNo warranty of any kind.");
+
+        assert_eq!(ranked[0].spdxId, "LONG");
+        assert!(ranked[0].exact);
+        assert_eq!(ranked[2].spdxId, "OTHER");
+        assert!(ranked[2].score < ranked[1].score);
+    }
+}
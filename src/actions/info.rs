@@ -1,9 +1,20 @@
+use crate::config::Config;
 use crate::display;
-use crate::error::{ActionError, AppError};
+use crate::error::AppError;
 use crate::models::{Cache, FieldsDataContent};
+use crate::permissiveness::PermissivenessWeights;
+use crate::stats;
+use crate::store::FillRequest;
+use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 
-pub async fn DisplayLicenseInfo(cache: &Cache, spdxIdStr: &str) -> Result<(), AppError> {
+pub async fn DisplayLicenseInfo(
+    cache: &Cache,
+    spdxIdStr: &str,
+    fullText: bool,
+    showStats: bool,
+    appConfig: &Config,
+) -> Result<(), AppError> {
     let spdxIdLower = spdxIdStr.to_lowercase();
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
@@ -12,21 +23,39 @@ pub async fn DisplayLicenseInfo(cache: &Cache, spdxIdStr: &str) -> Result<(), Ap
 
     match cache.licenses.get(&spdxIdLower) {
         Some(licenseEntry) => {
-            let fieldsDataContent: Option<FieldsDataContent> = cache
-                .dataFiles // dataFiles is correct
-                .get(crate::constants::FIELDS_YML_KEY)
-                .and_then(|entry| serde_yaml::from_value(entry.content.clone()).ok());
+            let fieldsDataContent: Option<FieldsDataContent> = cache.FieldsData();
+            let licenseStats = showStats.then(|| stats::LicenseTextStats(licenseEntry));
+            let score = PermissivenessWeights::WithOverrides(&appConfig.permissivenessWeights).Score(licenseEntry);
 
-            display::PrintLicenseInfoPanel(licenseEntry, &fieldsDataContent);
+            if fullText {
+                display::PrintLicenseInfoWithText(licenseEntry, &fieldsDataContent, score, licenseStats.as_ref());
+            } else {
+                display::PrintLicenseInfoPanel(licenseEntry, &fieldsDataContent, score, licenseStats.as_ref());
+            }
 
             Ok(())
         }
-        None => Err(AppError::ActionErrorVariant(ActionError::LicenseNotFound(
-            spdxIdLower,
-        ))),
+        None => Err(super::LicenseNotFoundError(spdxIdLower)),
     }
 }
 
+pub async fn ShowLicenseText(cache: &Cache, spdxIdStr: &str, annotate: bool) -> Result<(), AppError> {
+    let spdxIdLower = spdxIdStr.to_lowercase();
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Showing text of license: {}", spdxIdLower);
+    }
+
+    let licenseEntry = cache
+        .licenses
+        .get(&spdxIdLower)
+        .ok_or_else(|| super::LicenseNotFoundError(spdxIdLower.clone()))?;
+
+    display::PrintLicenseBody(licenseEntry, &cache.FieldsData(), annotate);
+
+    Ok(())
+}
+
 pub async fn ShowPlaceholdersForLicense(cache: &Cache, spdxIdStr: &str) -> Result<(), AppError> {
     let spdxIdLower = spdxIdStr.to_lowercase();
 
@@ -36,17 +65,43 @@ pub async fn ShowPlaceholdersForLicense(cache: &Cache, spdxIdStr: &str) -> Resul
 
     match cache.licenses.get(&spdxIdLower) {
         Some(licenseEntry) => {
-            let fieldsDataContent: Option<FieldsDataContent> = cache
-                .dataFiles // dataFiles is correct
-                .get(crate::constants::FIELDS_YML_KEY)
-                .and_then(|entry| serde_yaml::from_value(entry.content.clone()).ok());
+            let fieldsDataContent: Option<FieldsDataContent> = cache.FieldsData();
 
             display::PrintPlaceholderList(licenseEntry, &fieldsDataContent);
 
             Ok(())
         }
-        None => Err(AppError::ActionErrorVariant(ActionError::LicenseNotFound(
-            spdxIdLower,
-        ))),
+        None => Err(super::LicenseNotFoundError(spdxIdLower)),
     }
 }
+
+// Dry run of placeholder resolution: the same sources and precedence as `license`, minus
+// its flags, with nothing written.
+pub async fn ShowResolvedPlaceholders(
+    cache: &Cache,
+    spdxIdStr: &str,
+    appConfig: &Config,
+    profile: Option<&str>,
+) -> Result<(), AppError> {
+    let spdxIdLower = spdxIdStr.to_lowercase();
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Resolving placeholders for license: {}", spdxIdLower);
+    }
+
+    let licenseEntry = cache
+        .licenses
+        .get(&spdxIdLower)
+        .ok_or_else(|| super::LicenseNotFoundError(spdxIdLower.clone()))?;
+
+    let currentDir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let precedence = appConfig.PlaceholderPrecedence();
+    let request = FillRequest::from_cache(cache, &spdxIdLower);
+    let filled = super::fill::WithContextLayers(request, cache, appConfig, profile, &currentDir)?
+        .precedence(precedence.clone())
+        .render()?;
+
+    display::PrintResolvedPlaceholders(licenseEntry, &filled.resolved, &precedence);
+
+    Ok(())
+}
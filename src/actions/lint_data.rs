@@ -0,0 +1,200 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::Ordering;
+
+use crate::constants::{FIELDS_YML_KEY, RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES, RULES_YML_KEY};
+use crate::display;
+use crate::error::{ActionError, AppError, CacheError};
+use crate::models::{Cache, FrontMatter, LicenseEntry, RulesDataContent};
+use crate::parser;
+use crate::spdx_markup;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    // Still usable, but some information is missing or will be ignored
+    Warning,
+    // Sync would skip or misread the file
+    Error,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DataLintIssue {
+    // Upstream file name, e.g. "mit.txt" or "rules.yml"
+    pub file: String,
+    pub severity: LintSeverity,
+    // Short machine-friendly kind, e.g. "unknown-rule-tag"
+    pub kind: &'static str,
+    pub message: String,
+}
+
+fn Issue(file: &str, severity: LintSeverity, kind: &'static str, message: String) -> DataLintIssue {
+    DataLintIssue {
+        file: file.to_string(),
+        severity,
+        kind,
+        message,
+    }
+}
+
+fn LintDataFiles(cache: &Cache, issues: &mut Vec<DataLintIssue>) {
+    if !cache.dataFiles.contains_key(RULES_YML_KEY) {
+        issues.push(Issue("rules.yml", LintSeverity::Error, "missing-data-file", "rules.yml is not cached; rule labels and descriptions are unavailable".to_string()));
+    } else if cache.RulesData().is_none() {
+        issues.push(Issue("rules.yml", LintSeverity::Error, "unexpected-shape", "rules.yml does not have the expected permissions/conditions/limitations lists".to_string()));
+    }
+
+    if !cache.dataFiles.contains_key(FIELDS_YML_KEY) {
+        issues.push(Issue("fields.yml", LintSeverity::Warning, "missing-data-file", "fields.yml is not cached; placeholder descriptions are unavailable".to_string()));
+    } else if cache.FieldsData().is_none() {
+        issues.push(Issue("fields.yml", LintSeverity::Warning, "unexpected-shape", "fields.yml does not have the expected list of fields".to_string()));
+    }
+}
+
+fn LintRuleTags(file: &str, frontMatter: &FrontMatter, rules: &RulesDataContent, issues: &mut Vec<DataLintIssue>) {
+    let categories = [
+        ("permissions", &frontMatter.permissions, &rules.permissions),
+        ("conditions", &frontMatter.conditions, &rules.conditions),
+        ("limitations", &frontMatter.limitations, &rules.limitations),
+    ];
+
+    for (category, tags, known) in categories {
+        let knownTags: HashSet<&str> = known.iter().map(|rule| rule.tag.as_str()).collect();
+
+        for tag in tags.iter().filter(|tag| !knownTags.contains(tag.as_str())) {
+            issues.push(Issue(file, LintSeverity::Warning, "unknown-rule-tag", format!("{} tag '{}' is not defined in rules.yml", category, tag)));
+        }
+    }
+}
+
+// Re-parses the raw upstream file the way sync does, but collects every problem instead
+// of stopping at the first or only logging it.
+fn LintLicense(entry: &LicenseEntry, rules: &Option<RulesDataContent>, issues: &mut Vec<DataLintIssue>) {
+    let file = entry.filename.as_str();
+    let (frontMatterText, body) = parser::SplitFrontMatterAndBody(&entry.fileContentCached);
+
+    let frontMatter: FrontMatter = match frontMatterText {
+        None => {
+            issues.push(Issue(file, LintSeverity::Warning, "missing-front-matter", "no YAML front matter; metadata and rules are unavailable".to_string()));
+            FrontMatter::default()
+        }
+        Some(text) => match serde_yaml::from_str(&text) {
+            Ok(frontMatter) => frontMatter,
+            Err(e) => {
+                issues.push(Issue(file, LintSeverity::Error, "invalid-front-matter", format!("front matter does not parse: {}", e)));
+                return;
+            }
+        },
+    };
+
+    if frontMatter.spdxId.as_deref().is_none_or(|id| id.trim().is_empty()) {
+        match parser::GuessSpdxFromFilename(file) {
+            Some(guess) => issues.push(Issue(file, LintSeverity::Warning, "missing-spdx-id", format!("no spdx-id; guessed '{}' from the file name", guess))),
+            None => issues.push(Issue(file, LintSeverity::Error, "missing-spdx-id", "no spdx-id and none can be guessed from the file name".to_string())),
+        }
+    }
+
+    if frontMatter.title.as_deref().is_none_or(|title| title.trim().is_empty()) {
+        issues.push(Issue(file, LintSeverity::Warning, "missing-title", "no title; the SPDX ID is shown instead".to_string()));
+    }
+
+    if let Some(rules) = rules {
+        LintRuleTags(file, &frontMatter, rules, issues);
+    }
+
+    if spdx_markup::IsSpdxTemplate(&body) {
+        if let Err(e) = spdx_markup::ParseSpdxTemplate(&body) {
+            issues.push(Issue(file, LintSeverity::Warning, "invalid-spdx-markup", format!("{}; the template is treated as plain text", e)));
+        }
+    }
+
+    let rawToStdMap: HashMap<&str, &str> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES
+        .iter()
+        .cloned()
+        .collect();
+
+    for placeholder in parser::FindPlaceholdersInBody(&body) {
+        if !rawToStdMap.contains_key(parser::PlaceholderName(&placeholder).as_str()) {
+            issues.push(Issue(file, LintSeverity::Warning, "unmapped-placeholder", format!("placeholder {} has no standard key and can never be filled", placeholder)));
+        }
+    }
+}
+
+// Every problem found in the cached upstream files, data files first, then the files the
+// last sync could not parse at all, then licenses by file name.
+pub fn LintCachedData(cache: &Cache) -> Vec<DataLintIssue> {
+    let mut issues = Vec::new();
+    let rules = cache.RulesData();
+
+    LintDataFiles(cache, &mut issues);
+
+    for (file, error) in &cache.parseFailures {
+        issues.push(Issue(file, LintSeverity::Error, "parse-failed", format!("the last sync could not parse it, so it is not cached: {}", error)));
+    }
+
+    let mut licenses: Vec<&LicenseEntry> = cache.licenses.values().map(|entry| entry.as_ref()).collect();
+    licenses.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    for entry in licenses {
+        LintLicense(entry, &rules, &mut issues);
+    }
+
+    issues
+}
+
+pub async fn LintData(cache: &Cache, asJson: bool) -> Result<(), AppError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Re-parsing {} cached license files and {} data files",
+            cache.licenses.len(),
+            cache.dataFiles.len()
+        );
+    }
+
+    let issues = LintCachedData(cache);
+
+    if asJson {
+        let json = serde_json::to_string_pretty(&issues)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintDataLintIssues(&issues, cache.licenses.len());
+    }
+
+    let errors = issues.iter().filter(|issue| issue.severity == LintSeverity::Error).count();
+
+    if errors > 0 {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} cached file(s) could not be parsed correctly",
+            errors
+        ))));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn TestLintCachedData() {
+        let mut cache = Cache::default();
+        cache.licenses.insert(
+            "odd".into(),
+            Arc::new(LicenseEntry::ForTest(
+                "ODD",
+                "---\npermissions:\n  - teleportation\n---\nCopyright [year] [mystery]",
+            )),
+        );
+        cache.parseFailures.insert("broken.txt".into(), "did not find expected node content".into());
+
+        let issues = LintCachedData(&cache);
+        let kinds: Vec<&str> = issues.iter().filter(|i| i.file == "odd.txt").map(|i| i.kind).collect();
+
+        assert_eq!(kinds, ["missing-spdx-id", "missing-title", "unmapped-placeholder"]);
+        assert!(issues.iter().any(|i| i.file == "rules.yml" && i.severity == LintSeverity::Error));
+        assert!(issues.iter().any(|i| i.file == "broken.txt" && i.kind == "parse-failed"));
+    }
+}
@@ -1,13 +1,32 @@
+use crate::cli::{ListArgs, ListColumn, ListSort};
+use crate::config::Config;
 use crate::display;
-use crate::error::AppError;
-use crate::models::Cache;
+use crate::error::{ActionError, AppError, CacheError};
+use crate::filter;
+use crate::models::{Cache, LicenseEntry};
+use crate::permissiveness::PermissivenessWeights;
+use crate::stats;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
 use std::sync::atomic::Ordering;
 
-fn GetTargetLicenseKeys(cache: &Cache, requestedIds: Option<Vec<String>>) -> Vec<String> {
+// What --json shows when no --columns are given
+const LIST_DEFAULT_COLUMNS: [ListColumn; 2] = [ListColumn::Id, ListColumn::Title];
+const DETAILED_LIST_DEFAULT_COLUMNS: [ListColumn; 7] = [
+    ListColumn::Id,
+    ListColumn::Title,
+    ListColumn::Nickname,
+    ListColumn::Score,
+    ListColumn::Permissions,
+    ListColumn::Conditions,
+    ListColumn::Limitations,
+];
+
+fn GetTargetLicenseKeys(cache: &Cache, requestedIds: &Option<Vec<String>>) -> Vec<String> {
     match requestedIds {
         // requestedIds is correct
         Some(ids) if !ids.is_empty() => ids
-            .into_iter()
+            .iter()
             .filter_map(|idStr| {
                 let idLower = idStr.to_lowercase();
 
@@ -31,24 +50,111 @@ fn GetTargetLicenseKeys(cache: &Cache, requestedIds: Option<Vec<String>>) -> Vec
     }
 }
 
-pub async fn ListLicenses(
+fn ApplyFilter(
     cache: &Cache,
-    requestedIds: Option<Vec<String>>,
+    targetKeys: &mut Vec<String>,
+    filterOpt: Option<&str>,
+    weights: &PermissivenessWeights,
 ) -> Result<(), AppError> {
+    if let Some(filterText) = filterOpt {
+        let filter = filter::ParseFilter(filterText).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput(format!("invalid --filter: {}", e)))
+        })?;
+        targetKeys.retain(|key| filter.Matches(&cache.licenses[key], weights));
+    }
+
+    Ok(())
+}
+
+// Keys are already sorted by ID; sorting by score is stable, so ties stay alphabetical
+fn SortLicenseKeys(cache: &Cache, targetKeys: &mut [String], sort: ListSort, weights: &PermissivenessWeights) {
+    if sort == ListSort::Score {
+        targetKeys.sort_by_key(|key| std::cmp::Reverse(weights.Score(&cache.licenses[key])));
+    }
+}
+
+// The table cells and the JSON fields are both built from this, so they always agree
+pub fn ColumnValue(licenseEntry: &LicenseEntry, column: ListColumn, weights: &PermissivenessWeights) -> Value {
+    let rules = &licenseEntry.infoComponents.parsedRules;
+    let labels = |list: &[crate::models::RuleDetail]| {
+        Value::from(list.iter().map(|rule| rule.label.clone()).collect::<Vec<_>>())
+    };
+
+    match column {
+        ListColumn::Id => Value::from(licenseEntry.spdxId.clone()),
+        ListColumn::Title => Value::from(licenseEntry.title.clone()),
+        ListColumn::Nickname => licenseEntry.nickname.clone().map_or(Value::Null, Value::from),
+        ListColumn::Description => licenseEntry.description.clone().map_or(Value::Null, Value::from),
+        ListColumn::Score => Value::from(weights.Score(licenseEntry)),
+        ListColumn::Category => Value::from(stats::LicenseCategory(licenseEntry)),
+        ListColumn::Permissions => labels(&rules.permissions),
+        ListColumn::Conditions => labels(&rules.conditions),
+        ListColumn::Limitations => labels(&rules.limitations),
+        ListColumn::PermissionsCount => Value::from(rules.permissions.len()),
+        ListColumn::ConditionsCount => Value::from(rules.conditions.len()),
+        ListColumn::LimitationsCount => Value::from(rules.limitations.len()),
+    }
+}
+
+// One JSON object per license, keys in the order the columns were asked for
+struct ColumnRow<'a> {
+    columns: &'a [ListColumn],
+    values: Vec<Value>,
+}
+
+impl Serialize for ColumnRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (column, value) in self.columns.iter().zip(&self.values) {
+            map.serialize_entry(&column.Name(), value)?;
+        }
+        map.end()
+    }
+}
+
+fn PrintColumns(
+    cache: &Cache,
+    targetKeys: &[String],
+    columns: &[ListColumn],
+    asJson: bool,
+    weights: &PermissivenessWeights,
+) -> Result<(), AppError> {
+    let rows: Vec<Vec<Value>> = targetKeys
+        .iter()
+        .map(|key| columns.iter().map(|column| ColumnValue(&cache.licenses[key], *column, weights)).collect())
+        .collect();
+
+    if asJson {
+        let objects: Vec<ColumnRow> = rows.into_iter().map(|values| ColumnRow { columns, values }).collect();
+        let json = serde_json::to_string_pretty(&objects)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintColumnTable(columns, &rows);
+    }
+
+    Ok(())
+}
+
+pub async fn ListLicenses(cache: &Cache, args: &ListArgs, appConfig: &Config) -> Result<(), AppError> {
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!(
             "[Action] Listing licenses. Requested IDs: {:?}",
-            requestedIds
+            args.licenseIds
         );
     }
 
-    let targetKeys = GetTargetLicenseKeys(cache, requestedIds);
+    let weights = PermissivenessWeights::WithOverrides(&appConfig.permissivenessWeights);
+    let mut targetKeys = GetTargetLicenseKeys(cache, &args.licenseIds);
+    ApplyFilter(cache, &mut targetKeys, args.filter.as_deref(), &weights)?;
 
     if targetKeys.is_empty() {
         // targetKeys is correct
 
         if cache.licenses.is_empty() {
             println!("No licenses found in the cache.");
+        } else if args.filter.is_some() {
+            println!("No licenses match the --filter expression.");
         } else {
             println!("No matching licenses found for the specified IDs, or no IDs provided and cache is empty.");
         }
@@ -56,27 +162,36 @@ pub async fn ListLicenses(
         return Ok(());
     }
 
-    display::PrintSimpleLicenseList(cache, &targetKeys);
+    SortLicenseKeys(cache, &mut targetKeys, args.sort, &weights);
+
+    if args.json || !args.columns.is_empty() {
+        let columns = if args.columns.is_empty() { &LIST_DEFAULT_COLUMNS[..] } else { &args.columns };
+        return PrintColumns(cache, &targetKeys, columns, args.json, &weights);
+    }
+
+    // Scores are shown when they decide the order
+    display::PrintSimpleLicenseList(cache, &targetKeys, (args.sort == ListSort::Score).then_some(&weights));
 
     return Ok(());
 }
 
-pub async fn DetailedListLicenses(
-    cache: &Cache,
-    requestedIds: Option<Vec<String>>,
-) -> Result<(), AppError> {
+pub async fn DetailedListLicenses(cache: &Cache, args: &ListArgs, appConfig: &Config) -> Result<(), AppError> {
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!(
             "[Action] Detailed listing of licenses. Requested IDs: {:?}",
-            requestedIds
+            args.licenseIds
         );
     }
 
-    let targetKeys = GetTargetLicenseKeys(cache, requestedIds);
+    let weights = PermissivenessWeights::WithOverrides(&appConfig.permissivenessWeights);
+    let mut targetKeys = GetTargetLicenseKeys(cache, &args.licenseIds);
+    ApplyFilter(cache, &mut targetKeys, args.filter.as_deref(), &weights)?;
 
     if targetKeys.is_empty() {
         if cache.licenses.is_empty() {
             println!("No licenses found in the cache for detailed listing.");
+        } else if args.filter.is_some() {
+            println!("No licenses match the --filter expression.");
         } else {
             println!("No matching licenses found for detailed listing with specified IDs, or no IDs provided and cache is empty.");
         }
@@ -85,12 +200,16 @@ pub async fn DetailedListLicenses(
     }
 
     // The display function will need access to rules.yml for labels
-    let rulesDataContent = cache
-        .dataFiles
-        .get(crate::constants::RULES_YML_KEY)
-        .and_then(|entry| serde_yaml::from_value(entry.content.clone()).ok());
+    let rulesDataContent = cache.RulesData();
+
+    SortLicenseKeys(cache, &mut targetKeys, args.sort, &weights);
+
+    if args.json || !args.columns.is_empty() {
+        let columns = if args.columns.is_empty() { &DETAILED_LIST_DEFAULT_COLUMNS[..] } else { &args.columns };
+        return PrintColumns(cache, &targetKeys, columns, args.json, &weights);
+    }
 
-    display::PrintDetailedLicenseList(cache, &targetKeys, &rulesDataContent);
+    display::PrintDetailedLicenseList(cache, &targetKeys, &rulesDataContent, &weights);
 
     return Ok(());
 }
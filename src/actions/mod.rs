@@ -1,6 +1,65 @@
 pub mod list;
 pub mod info;
 pub mod fill;
+pub mod export;
 pub mod compare;
 pub mod find;
+pub mod tags;
+pub mod history;
+pub mod lint_data;
 pub mod placeholder_management;
+pub mod validate;
+pub mod verify;
+pub mod notices;
+pub mod headers;
+pub mod hook;
+pub mod outdated;
+pub mod plugin;
+pub mod upgrade;
+pub mod unfill;
+pub mod identify;
+pub mod normalize;
+pub mod vendor;
+pub mod snippets;
+pub mod undo;
+pub mod alias;
+pub mod choose;
+pub mod check;
+pub mod corpus;
+#[cfg(feature = "net")]
+pub mod sync;
+#[cfg(feature = "self-update")]
+pub mod self_update;
+
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+use std::sync::atomic::Ordering;
+
+// The SPDX ID given on the command line, lowercased, or one picked interactively when it
+// was omitted and a terminal is attached.
+pub fn LicenseIdOrPick(cache: &Cache, licenseIdOpt: Option<&str>) -> Result<String, AppError> {
+    if let Some(licenseId) = licenseIdOpt {
+        return Ok(licenseId.to_lowercase());
+    }
+
+    #[cfg(any(unix, windows))]
+    if crate::picker::CanPick() {
+        return crate::picker::PickLicense(cache)?.ok_or_else(|| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput("No license selected.".to_string()))
+        });
+    }
+
+    let _ = cache;
+    Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+        "No SPDX ID given. Pass one, or run in a terminal to pick one interactively.".to_string(),
+    )))
+}
+
+// Distinguishes "does not exist" from "was never fetched because of --offline".
+pub fn LicenseNotFoundError(spdxIdLower: String) -> AppError {
+    if crate::OFFLINE.load(Ordering::SeqCst) {
+        AppError::ActionErrorVariant(ActionError::LicenseNotCachedOffline(spdxIdLower))
+    } else {
+        AppError::ActionErrorVariant(ActionError::LicenseNotFound(spdxIdLower))
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::atomic::Ordering;
+
+use crate::error::AppError;
+use crate::normalize;
+
+// Prints the text the way matching sees it, so external tools can diff or deduplicate
+// license texts with the same rules identify and verify use.
+pub fn NormalizeInput(source: &str) -> Result<(), AppError> {
+    let text = super::identify::ReadInput(source)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Normalizing {} bytes of license text from {}",
+            text.len(),
+            if source == "-" { "stdin" } else { source }
+        );
+    }
+
+    println!("{}", normalize::NormalizeLicenseText(&text));
+
+    Ok(())
+}
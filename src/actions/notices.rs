@@ -0,0 +1,183 @@
+use colored::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::config::OrgConfig;
+use crate::constants::NOTICES_FILENAME;
+use crate::dependencies::{self, DependencyPackage};
+use crate::error::{ActionError, AppError};
+use crate::manifest;
+use crate::models::Cache;
+use crate::parser;
+
+const RULE: &str = "================================================================================";
+const UNDECLARED_LICENSE: &str = "No license declared";
+
+fn AttributionLine(package: &DependencyPackage) -> String {
+    let mut line = format!("  {} {}", package.name, package.version);
+
+    if !package.authors.is_empty() {
+        line.push_str(&format!(" - {}", package.authors.join(", ")));
+    }
+    if let Some(repository) = &package.repository {
+        line.push_str(&format!(" <{}>", repository));
+    }
+
+    line
+}
+
+// Packages grouped by license expression, then the full text of every license those
+// expressions mention, each once. Also returns the IDs whose text is not cached.
+pub fn RenderNotices(cache: &Cache, projectName: &str, packages: &[DependencyPackage]) -> (String, Vec<String>) {
+    let mut groups: BTreeMap<&str, Vec<&DependencyPackage>> = BTreeMap::new();
+
+    for package in packages {
+        groups
+            .entry(package.license.as_deref().unwrap_or(UNDECLARED_LICENSE))
+            .or_default()
+            .push(package);
+    }
+
+    let mut out = String::new();
+    out.push_str("THIRD-PARTY SOFTWARE NOTICES\n\n");
+    out.push_str(&format!(
+        "{} uses the following {} third-party package(s).\nGenerated by getlicense on {}.\n",
+        projectName,
+        packages.len(),
+        crate::clock::Now().format("%Y-%m-%d")
+    ));
+
+    let mut referencedIds: BTreeSet<String> = BTreeSet::new();
+
+    for (expression, mut groupPackages) in groups {
+        groupPackages.sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+
+        out.push_str(&format!("\n{}\n{} ({})\n{}\n\n", RULE, expression, groupPackages.len(), RULE));
+        for package in groupPackages {
+            out.push_str(&AttributionLine(package));
+            out.push('\n');
+        }
+
+        if expression != UNDECLARED_LICENSE {
+            referencedIds.extend(manifest::SpdxExpressionIds(expression));
+        }
+    }
+
+    let mut missingIds = Vec::new();
+
+    if !referencedIds.is_empty() {
+        out.push_str(&format!("\n\n{}\nLICENSE TEXTS\n{}\n", RULE, RULE));
+    }
+
+    for id in referencedIds {
+        match cache.licenses.get(&id.to_lowercase()) {
+            Some(entry) => {
+                out.push_str(&format!("\n--- {} ({}) ---\n\n", entry.title, entry.spdxId));
+                out.push_str(parser::LicenseTemplateBody(&entry.fileContentCached).trim_end());
+                out.push('\n');
+            }
+            None => missingIds.push(id),
+        }
+    }
+
+    (out, missingIds)
+}
+
+fn ProjectName(projectDir: &Path) -> String {
+    manifest::ReadCargoPackage(&projectDir.join("Cargo.toml"))
+        .ok()
+        .flatten()
+        .and_then(|package| package.name)
+        .or_else(|| {
+            projectDir
+                .canonicalize()
+                .ok()
+                .and_then(|dir| dir.file_name().map(|name| name.to_string_lossy().to_string()))
+        })
+        .unwrap_or_else(|| "This project".to_string())
+}
+
+// `-` as the output writes to stdout.
+pub async fn GenerateNotices(
+    cache: &Cache,
+    projectDir: &Path,
+    outputPath: Option<PathBuf>,
+    org: &OrgConfig,
+) -> Result<(), AppError> {
+    let packages = dependencies::ReadProjectDependencies(projectDir)?;
+
+    if packages.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "No dependencies found in {} (looked for Cargo.toml, package-lock.json, pnpm-lock.yaml, poetry.lock and requirements.txt)",
+            projectDir.display()
+        ))));
+    }
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Generating notices for {} dependencies", packages.len());
+    }
+
+    let (notices, missingIds) = RenderNotices(cache, &ProjectName(projectDir), &packages);
+
+    for id in &missingIds {
+        eprintln!(
+            "{} the text of {} is not cached, so it is not included.",
+            "Warning:".yellow(),
+            id.cyan()
+        );
+    }
+
+    let undeclared = packages.iter().filter(|p| p.license.is_none()).count();
+    if undeclared > 0 {
+        eprintln!(
+            "{} {} package(s) declare no license; check their license files by hand.",
+            "Warning:".yellow(),
+            undeclared
+        );
+    }
+
+    for package in &packages {
+        let Some(expression) = &package.license else {
+            continue;
+        };
+        let ids = manifest::SpdxExpressionIds(expression);
+
+        if let Some(banned) = ids.iter().find(|id| org.IsBanned(id)) {
+            eprintln!(
+                "{} {} {} uses {}, which your organization bans.",
+                "Warning:".yellow(),
+                package.name,
+                package.version,
+                banned.red()
+            );
+        } else if !ids.iter().any(|id| org.IsApproved(id)) {
+            eprintln!(
+                "{} {} {} is licensed under {}, which is not on the approved list.",
+                "Warning:".yellow(),
+                package.name,
+                package.version,
+                expression
+            );
+        }
+    }
+
+    let outputPath = outputPath.unwrap_or_else(|| projectDir.join(NOTICES_FILENAME));
+
+    if outputPath == Path::new("-") {
+        print!("{}", notices);
+        return Ok(());
+    }
+
+    fs::write(&outputPath, &notices)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, outputPath.clone())))?;
+
+    println!(
+        "Wrote notices for {} package(s) to {}",
+        packages.len(),
+        outputPath.display().to_string().green()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use crate::constants::LICENSE_FILE_CANDIDATES;
+use crate::display;
+use crate::drift;
+use crate::error::{ActionError, AppError};
+use crate::models::{Cache, LicenseEntry};
+use crate::provenance;
+
+// The license file, its text, and the template it was generated from. An edited upstream
+// text no longer matches exactly, so this falls back to the closest template.
+pub fn LocateLicenseForDrift<'a>(
+    cache: &'a Cache,
+    pathOpt: Option<PathBuf>,
+    licenseIdOpt: Option<&str>,
+) -> Result<(PathBuf, String, &'a Arc<LicenseEntry>), AppError> {
+    let licensePath = match pathOpt {
+        Some(path) => path,
+        None => super::validate::FindLicenseFile(Path::new(".")).ok_or_else(|| {
+            AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+                "No license file found (looked for {})",
+                LICENSE_FILE_CANDIDATES.join(", ")
+            )))
+        })?,
+    };
+
+    let localText = fs::read_to_string(&licensePath).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, licensePath.clone()))
+    })?;
+
+    // A provenance record names the template outright, even if upstream wording moved on
+    let recordedId = match licenseIdOpt {
+        Some(_) => None,
+        None => provenance::LoadProvenance(&licensePath)?
+            .map(|record| record.spdxId.to_lowercase())
+            .filter(|id| cache.licenses.contains_key(id)),
+    };
+
+    let licenseEntry = match licenseIdOpt.map(str::to_string).or(recordedId) {
+        Some(id) => cache
+            .licenses
+            .get(&id.to_lowercase())
+            .ok_or_else(|| super::LicenseNotFoundError(id.to_lowercase()))?,
+        None => match super::validate::IdentifyOrApproximate(cache, &localText) {
+            Some((entry, _)) => entry,
+            None => {
+                return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+                    "{} does not resemble any cached license template; pass --license",
+                    licensePath.display()
+                ))));
+            }
+        },
+    };
+
+    Ok((licensePath, localText, licenseEntry))
+}
+
+pub async fn CheckOutdated(
+    cache: &Cache,
+    pathOpt: Option<PathBuf>,
+    licenseIdOpt: Option<&str>,
+) -> Result<(), AppError> {
+    let (licensePath, localText, licenseEntry) =
+        LocateLicenseForDrift(cache, pathOpt, licenseIdOpt)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Comparing {:?} with the current {} template",
+            licensePath, licenseEntry.spdxId
+        );
+    }
+
+    let mut report = drift::CompareWithTemplate(
+        &crate::parser::LicenseTemplateBody(&licenseEntry.fileContentCached),
+        &licenseEntry.placeholdersInBody,
+        &localText,
+    );
+
+    // With a record, staleness is a template SHA comparison rather than a text heuristic
+    if let Some(record) = provenance::LoadProvenance(&licensePath)?
+        .filter(|record| record.spdxId.eq_ignore_ascii_case(&licenseEntry.spdxId))
+    {
+        display::PrintProvenanceNote(&record, licenseEntry);
+        report.upToDate = record.templateSha == licenseEntry.sha;
+    }
+
+    display::PrintDriftReport(&licensePath, licenseEntry, &report);
+
+    if report.upToDate {
+        Ok(())
+    } else {
+        Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} differs from the current {} template",
+            licensePath.display(),
+            licenseEntry.spdxId
+        ))))
+    }
+}
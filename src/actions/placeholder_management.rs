@@ -1,6 +1,15 @@
-use crate::error::AppError;
+use crate::constants::{CACHABLE_PLACEHOLDER_KEYS_ARRAY, DEFAULT_EDITOR};
+use crate::error::{ActionError, AppError, CacheError};
+use crate::manifest;
 use crate::models::Cache;
+use crate::resolve;
 use colored::*;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::atomic::Ordering;
 
 pub async fn SetPlaceholder(cache: &mut Cache, key: &str, value: &str) -> Result<(), AppError> {
@@ -20,11 +29,29 @@ pub async fn SetPlaceholder(cache: &mut Cache, key: &str, value: &str) -> Result
     Ok(())
 }
 
-pub async fn GetPlaceholder(cache: &Cache, keyOpt: Option<&str>) -> Result<(), AppError> {
+pub async fn GetPlaceholder(
+    cache: &Cache,
+    keyOpt: Option<&str>,
+    asJson: bool,
+) -> Result<(), AppError> {
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!("[Action] Getting placeholder(s). Key: {:?}", keyOpt);
     }
 
+    if asJson {
+        // BTreeMap for stable key order; a missing single key yields an empty object
+        let selected: BTreeMap<&String, &String> = cache
+            .userPlaceholders
+            .iter()
+            .filter(|(k, _)| keyOpt.is_none_or(|key| k.as_str() == key))
+            .collect();
+        let json = serde_json::to_string_pretty(&selected)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+
+        return Ok(());
+    }
+
     if cache.userPlaceholders.is_empty() {
         println!("No saved placeholder preferences found.");
 
@@ -91,3 +118,269 @@ pub async fn ClearPlaceholders(
 
     Ok(())
 }
+
+// (key, value, where it came from). git config wins for fullname/email; the first
+// package.authors entry only fills in what it lacks.
+fn ImportedValues(
+    gitValues: &HashMap<String, String>,
+    package: Option<&manifest::CargoPackage>,
+    manifestLabel: &str,
+) -> Vec<(String, String, String)> {
+    let mut imported: Vec<(String, String, String)> = Vec::new();
+    let (authorName, authorEmail) = package
+        .and_then(|package| package.authors.first())
+        .map(|author| manifest::SplitAuthor(author))
+        .unwrap_or_default();
+
+    for (key, gitKey, fromAuthor) in [("fullname", "user.name", authorName), ("email", "user.email", authorEmail)] {
+        if let Some(value) = gitValues.get(key) {
+            imported.push((key.to_string(), value.clone(), format!("git config {}", gitKey)));
+        } else if let Some(value) = fromAuthor {
+            imported.push((key.to_string(), value, format!("{} package.authors", manifestLabel)));
+        }
+    }
+
+    let Some(package) = package else {
+        return imported;
+    };
+
+    if let Some(name) = &package.name {
+        imported.push(("project".to_string(), name.clone(), format!("{} package.name", manifestLabel)));
+    }
+
+    if let Some(url) = &package.repository {
+        imported.push(("projecturl".to_string(), url.clone(), format!("{} package.repository", manifestLabel)));
+    } else if let Some(url) = &package.homepage {
+        imported.push(("projecturl".to_string(), url.clone(), format!("{} package.homepage", manifestLabel)));
+    }
+
+    imported
+}
+
+pub async fn ImportPlaceholders(cache: &mut Cache, manifestPath: &Path) -> Result<bool, AppError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Importing placeholders from git config and {:?}",
+            manifestPath
+        );
+    }
+
+    let package = manifest::ReadCargoPackage(manifestPath)?;
+
+    if package.is_none() && crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] No [package] found at {:?}. Skipping cargo metadata.", manifestPath);
+    }
+
+    let imported = ImportedValues(
+        &resolve::DetectGitPlaceholders(),
+        package.as_ref(),
+        &manifestPath.display().to_string(),
+    );
+
+    if imported.is_empty() {
+        println!("Nothing to import: no git identity or Cargo package metadata found.");
+
+        return Ok(false);
+    }
+
+    println!("{}", "Imported Placeholder Preferences:".bold());
+
+    for (key, value, origin) in imported {
+        println!("  {}: {} {}", key.green(), value.cyan(), format!("(from {})", origin).dimmed());
+        cache.userPlaceholders.insert(key, value);
+    }
+
+    Ok(true)
+}
+
+// The saved preferences as the TOML document `edit-placeholders` opens. Unset keys are
+// listed commented out so they are easy to fill in.
+pub fn PlaceholdersDocument(placeholders: &HashMap<String, String>) -> String {
+    let mut document = String::from(
+        "# Saved placeholder preferences for getlicense. Save and close the editor to apply.\n\
+         # Remove a line (or leave its value empty) to clear that preference.\n\n",
+    );
+
+    for key in CACHABLE_PLACEHOLDER_KEYS_ARRAY {
+        match placeholders.get(key) {
+            Some(value) => document.push_str(&format!("{} = {}\n", key, toml::Value::String(value.clone()))),
+            None => document.push_str(&format!("# {} = \"\"\n", key)),
+        }
+    }
+
+    document
+}
+
+// Empty values are dropped, so clearing a value clears the preference
+pub fn ParsePlaceholdersDocument(document: &str) -> Result<HashMap<String, String>, String> {
+    let table: toml::Table = document.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+    let mut placeholders = HashMap::new();
+
+    for (key, value) in table {
+        if !CACHABLE_PLACEHOLDER_KEYS_ARRAY.contains(&key.as_str()) {
+            return Err(format!(
+                "unknown key '{}'; expected one of: {}",
+                key,
+                CACHABLE_PLACEHOLDER_KEYS_ARRAY.join(", ")
+            ));
+        }
+
+        let toml::Value::String(value) = value else {
+            return Err(format!("the value of '{}' must be a quoted string", key));
+        };
+
+        if !value.trim().is_empty() {
+            placeholders.insert(key, value);
+        }
+    }
+
+    Ok(placeholders)
+}
+
+// $VISUAL, then $EDITOR, may carry arguments such as "code --wait"
+fn RunEditor(path: &Path) -> Result<(), AppError> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.trim().is_empty()))
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Opening {:?} with {}", path, editor);
+    }
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, PathBuf::from(program))))?;
+
+    if !status.success() {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+            "{} exited with {}; preferences left unchanged",
+            program, status
+        ))));
+    }
+
+    Ok(())
+}
+
+fn ConfirmReedit(problem: &str) -> Result<bool, AppError> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    eprintln!("{} {}", "Invalid placeholder preferences:".red(), problem);
+
+    print!("Edit again? [Y/n] ");
+    io::stdout()
+        .flush()
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdout")))?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdin")))?;
+
+    Ok(!matches!(answer.trim().to_lowercase().as_str(), "n" | "no"))
+}
+
+// Returns whether the preferences changed
+pub async fn EditPlaceholders(cache: &mut Cache) -> Result<bool, AppError> {
+    let path = env::temp_dir().join(format!("getlicense-placeholders-{}.toml", std::process::id()));
+    fs::write(&path, PlaceholdersDocument(&cache.userPlaceholders))
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, path.clone())))?;
+
+    // An invalid document is reopened as the user left it, so no edit is lost
+    let result = loop {
+        if let Err(e) = RunEditor(&path) {
+            break Err(e);
+        }
+
+        let document = match fs::read_to_string(&path) {
+            Ok(document) => document,
+            Err(e) => break Err(AppError::ActionErrorVariant(ActionError::FileOperation(e, path.clone()))),
+        };
+
+        match ParsePlaceholdersDocument(&document) {
+            Ok(placeholders) => break Ok(placeholders),
+            Err(problem) => match ConfirmReedit(&problem) {
+                Ok(true) => continue,
+                Ok(false) => {
+                    break Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                        "{}; preferences left unchanged",
+                        problem
+                    ))));
+                }
+                Err(e) => break Err(e),
+            },
+        }
+    };
+
+    let _ = fs::remove_file(&path);
+    let placeholders = result?;
+
+    if placeholders == cache.userPlaceholders {
+        println!("No changes to saved placeholder preferences.");
+        return Ok(false);
+    }
+
+    println!("{}", "Updated Placeholder Preferences:".bold());
+
+    for key in CACHABLE_PLACEHOLDER_KEYS_ARRAY {
+        match (cache.userPlaceholders.get(key), placeholders.get(key)) {
+            (old, Some(new)) if old != Some(new) => println!("  {}: {}", key.green(), new.cyan()),
+            (Some(_), None) => println!("  {}: {}", key.green(), "(cleared)".dimmed()),
+            _ => {}
+        }
+    }
+
+    cache.userPlaceholders = placeholders;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestPlaceholdersDocumentRoundTrip() {
+        let saved = HashMap::from([("fullname".to_string(), "Jane \"JD\" Doe".to_string())]);
+        let document = PlaceholdersDocument(&saved);
+
+        assert!(document.contains("# email = \"\"\n"));
+        assert_eq!(ParsePlaceholdersDocument(&document).unwrap(), saved);
+
+        let edited = ParsePlaceholdersDocument("fullname = \"\"\nproject = \"demo\"\n").unwrap();
+        assert_eq!(edited, HashMap::from([("project".to_string(), "demo".to_string())]));
+
+        assert!(ParsePlaceholdersDocument("name = \"x\"").unwrap_err().contains("unknown key 'name'"));
+        assert!(ParsePlaceholdersDocument("email = 3").is_err());
+    }
+
+    #[test]
+    fn TestImportedValues() {
+        let package = manifest::CargoPackage {
+            name: Some("demo".to_string()),
+            repository: None,
+            homepage: Some("https://demo.example.com".to_string()),
+            authors: vec!["Jane Doe <jane@example.com>".to_string()],
+        };
+        let keysAndOrigins = |imported: Vec<(String, String, String)>| {
+            imported.into_iter().map(|(key, value, origin)| format!("{}={} ({})", key, value, origin)).collect::<Vec<_>>()
+        };
+
+        let gitValues = HashMap::from([("fullname".to_string(), "J. Doe".to_string())]);
+        assert_eq!(keysAndOrigins(ImportedValues(&gitValues, Some(&package), "Cargo.toml")), [
+            "fullname=J. Doe (git config user.name)",
+            "email=jane@example.com (Cargo.toml package.authors)",
+            "project=demo (Cargo.toml package.name)",
+            "projecturl=https://demo.example.com (Cargo.toml package.homepage)",
+        ]);
+
+        assert_eq!(keysAndOrigins(ImportedValues(&gitValues, None, "Cargo.toml")), ["fullname=J. Doe (git config user.name)"]);
+        assert!(ImportedValues(&HashMap::new(), None, "Cargo.toml").is_empty());
+    }
+}
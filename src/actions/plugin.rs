@@ -0,0 +1,36 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::Ordering;
+
+use crate::constants::PLUGIN_PREFIX;
+use crate::error::{ActionError, AppError};
+
+// Runs `getlicense-<name>` from PATH with the remaining arguments, cargo/git style, and
+// returns its exit code. `env` tells the plugin where getlicense keeps its files.
+pub fn RunPlugin(args: &[String], env: &[(&str, String)]) -> Result<i32, AppError> {
+    let Some((name, rest)) = args.split_first() else {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput("missing command".to_string())));
+    };
+
+    let program = format!("{}{}", PLUGIN_PREFIX, name);
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Running plugin {} with {:?}", program, rest);
+    }
+
+    let status = Command::new(&program)
+        .args(rest)
+        .envs(env.iter().map(|(key, value)| (*key, value)))
+        .status()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "unknown command '{}' (no {} executable found on PATH)",
+                name, program
+            ))),
+            _ => AppError::ActionErrorVariant(ActionError::FileOperation(e, PathBuf::from(&program))),
+        })?;
+
+    // Killed by a signal: report failure like a shell would
+    Ok(status.code().unwrap_or(1))
+}
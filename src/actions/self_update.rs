@@ -0,0 +1,155 @@
+use colored::*;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::sync::atomic::Ordering;
+
+use crate::constants::{RELEASE_CHECKSUMS_ASSET, SELF_RELEASE_OWNER, SELF_RELEASE_REPO};
+use crate::error::{ActionError, AppError};
+use crate::models::GitHubRelease;
+use crate::update::ParseVersion;
+
+fn PlatformAssetName() -> String {
+    let extension = if cfg!(windows) { ".exe" } else { "" };
+
+    format!(
+        "getlicense-{}-{}{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        extension
+    )
+}
+
+// SHA256SUMS lines look like "<hex>  <filename>" (sha256sum output, optionally with '*').
+fn FindExpectedChecksum(checksums: &str, assetName: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let hash = fields.next()?;
+        let name = fields.next()?.trim_start_matches('*');
+
+        (name == assetName).then(|| hash.to_lowercase())
+    })
+}
+
+fn SelfUpdateError(message: String) -> AppError {
+    AppError::ActionErrorVariant(ActionError::SelfUpdate(message))
+}
+
+pub async fn SelfUpdate(checkOnly: bool) -> Result<(), AppError> {
+    let currentVersion = env!("CARGO_PKG_VERSION");
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Checking for a newer release than {}", currentVersion);
+    }
+
+    let release: GitHubRelease =
+        crate::api::FetchLatestRelease(SELF_RELEASE_OWNER, SELF_RELEASE_REPO).await?;
+
+    let isNewer = match (ParseVersion(&release.tagName), ParseVersion(currentVersion)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => {
+            return Err(SelfUpdateError(format!(
+                "Could not compare release tag '{}' with version {}",
+                release.tagName, currentVersion
+            )));
+        }
+    };
+
+    if !isNewer {
+        println!(
+            "getlicense {} is up to date (latest release: {}).",
+            currentVersion.green(),
+            release.tagName
+        );
+
+        return Ok(());
+    }
+
+    println!(
+        "A newer version is available: {} -> {}",
+        currentVersion.yellow(),
+        release.tagName.green().bold()
+    );
+
+    if checkOnly {
+        return Ok(());
+    }
+
+    let assetName = PlatformAssetName();
+    let binaryAsset = release
+        .assets
+        .iter()
+        .find(|a| a.name == assetName)
+        .ok_or_else(|| {
+            SelfUpdateError(format!(
+                "Release {} has no binary for this platform ({})",
+                release.tagName, assetName
+            ))
+        })?;
+    let checksumAsset = release
+        .assets
+        .iter()
+        .find(|a| a.name == RELEASE_CHECKSUMS_ASSET)
+        .ok_or_else(|| {
+            SelfUpdateError(format!(
+                "Release {} has no {} file; refusing to install an unverified binary",
+                release.tagName, RELEASE_CHECKSUMS_ASSET
+            ))
+        })?;
+
+    let checksums = crate::api::FetchFileContent(&checksumAsset.browserDownloadUrl).await?;
+    let expectedHash = FindExpectedChecksum(&checksums, &assetName).ok_or_else(|| {
+        SelfUpdateError(format!("{} has no entry for {}", RELEASE_CHECKSUMS_ASSET, assetName))
+    })?;
+
+    println!("Downloading {}...", assetName.cyan());
+    let binary = crate::api::FetchBinaryContent(&binaryAsset.browserDownloadUrl).await?;
+
+    let actualHash = format!("{:x}", Sha256::digest(&binary));
+
+    if actualHash != expectedHash {
+        return Err(SelfUpdateError(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            assetName, expectedHash, actualHash
+        )));
+    }
+
+    let currentExe = std::env::current_exe()
+        .map_err(|e| AppError::Io(e, "current executable".into()))?;
+    let stagedPath = currentExe.with_extension("update");
+
+    fs::write(&stagedPath, &binary)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, stagedPath.clone())))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&stagedPath, fs::Permissions::from_mode(0o755)).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, stagedPath.clone()))
+        })?;
+    }
+
+    let replaceResult = self_replace::self_replace(&stagedPath);
+    let _ = fs::remove_file(&stagedPath);
+    replaceResult
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, currentExe.clone())))?;
+
+    println!(
+        "Updated {} to {} (checksum verified).",
+        currentExe.display(),
+        release.tagName.green().bold()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestFindExpectedChecksum() {
+        let sums = "abc123  getlicense-x86_64-linux\nDEF456 *getlicense-aarch64-macos\n";
+        assert_eq!(FindExpectedChecksum(sums, "getlicense-aarch64-macos").as_deref(), Some("def456"));
+        assert_eq!(FindExpectedChecksum(sums, "getlicense-x86_64-windows.exe"), None);
+    }
+}
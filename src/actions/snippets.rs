@@ -0,0 +1,265 @@
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::appendix::{self, AppendixValues};
+use crate::cli::SnippetFormat;
+use crate::constants::{HEADER_COMMENT_PREFIX_TUPLES, SNIPPET_SCOPE_TUPLES, SPDX_HEADER_TAG};
+use crate::error::{ActionError, AppError, CacheError};
+use crate::models::{Cache, LicenseEntry};
+
+// Stand-ins for the tab stops while the literal text is escaped
+const PROGRAM_MARKER: &str = "\u{0}program\u{0}";
+const DESCRIPTION_MARKER: &str = "\u{0}description\u{0}";
+const YEAR_MARKER: &str = "\u{0}year\u{0}";
+const AUTHOR_MARKER: &str = "\u{0}author\u{0}";
+
+#[derive(Serialize, Debug)]
+struct VsCodeSnippet {
+    prefix: String,
+    scope: String,
+    description: String,
+    body: Vec<String>,
+}
+
+fn EscapeSnippetText(text: &str, format: SnippetFormat) -> String {
+    match format {
+        SnippetFormat::Vscode => text.replace('\\', "\\\\").replace('$', "\\$").replace('}', "\\}"),
+        SnippetFormat::Ultisnips => text.replace('\\', "\\\\").replace('$', "\\$").replace('`', "\\`"),
+    }
+}
+
+fn TriggerFor(licenseEntry: &LicenseEntry) -> String {
+    format!("license-{}", licenseEntry.spdxId.to_lowercase())
+}
+
+// The header as comment lines with tab stops. GNU licenses get their how-to-apply notice
+// (program, description, year, author); the rest a copyright line (year, author).
+pub fn SnippetBodyLines(licenseEntry: &LicenseEntry, prefix: &str, format: SnippetFormat, author: &str) -> Vec<String> {
+    let noticeValues = AppendixValues {
+        program: Some(PROGRAM_MARKER.to_string()),
+        description: Some(DESCRIPTION_MARKER.to_string()),
+        year: Some(YEAR_MARKER.to_string()),
+        author: Some(AUTHOR_MARKER.to_string()),
+    };
+
+    let (text, tabStops) = match appendix::RenderAppendix(&licenseEntry.spdxId, &noticeValues) {
+        // A blank comment line sets the notice apart from the SPDX line
+        Some(notice) => (format!("\n{}", notice), [PROGRAM_MARKER, DESCRIPTION_MARKER, YEAR_MARKER, AUTHOR_MARKER].as_slice()),
+        None => (format!("Copyright (c) {} {}", YEAR_MARKER, AUTHOR_MARKER), [YEAR_MARKER, AUTHOR_MARKER].as_slice()),
+    };
+
+    let currentYear = match format {
+        SnippetFormat::Vscode => "$CURRENT_YEAR",
+        SnippetFormat::Ultisnips => "`!v strftime(\"%Y\")`",
+    };
+
+    let mut commented = format!("{} {} {}\n", prefix, SPDX_HEADER_TAG, licenseEntry.spdxId);
+    for line in text.lines() {
+        if line.is_empty() {
+            commented.push_str(prefix);
+        } else {
+            commented.push_str(&format!("{} {}", prefix, line));
+        }
+        commented.push('\n');
+    }
+
+    let mut body = EscapeSnippetText(&commented, format);
+    for (index, marker) in tabStops.iter().enumerate() {
+        let default = match *marker {
+            PROGRAM_MARKER => "program",
+            DESCRIPTION_MARKER => "what it does",
+            YEAR_MARKER => currentYear,
+            _ => author,
+        };
+        body = body.replace(marker, &format!("${{{}:{}}}", index + 1, default));
+    }
+
+    let mut lines: Vec<String> = body.lines().map(str::to_string).collect();
+    // Leaves the cursor below the header, where the code starts
+    lines.push("$0".to_string());
+    lines
+}
+
+// One snippet per license and comment style, scoped to the languages using that style
+pub fn RenderVsCodeSnippets(licenses: &[&LicenseEntry], author: &str) -> Result<String, AppError> {
+    let mut scopesByPrefix: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (ext, languageId, _) in SNIPPET_SCOPE_TUPLES {
+        let Some((_, prefix)) = HEADER_COMMENT_PREFIX_TUPLES.iter().find(|(known, _)| *known == ext) else {
+            continue;
+        };
+        let scopes = scopesByPrefix.entry(prefix).or_default();
+        if !scopes.contains(&languageId) {
+            scopes.push(languageId);
+        }
+    }
+
+    let mut snippets: BTreeMap<String, VsCodeSnippet> = BTreeMap::new();
+    for licenseEntry in licenses {
+        for (prefix, scopes) in &scopesByPrefix {
+            snippets.insert(
+                format!("{} header ({} comments)", licenseEntry.spdxId, prefix),
+                VsCodeSnippet {
+                    prefix: TriggerFor(licenseEntry),
+                    scope: scopes.join(","),
+                    description: format!("{} header", licenseEntry.title),
+                    body: SnippetBodyLines(licenseEntry, prefix, SnippetFormat::Vscode, author),
+                },
+            );
+        }
+    }
+
+    serde_json::to_string_pretty(&snippets).map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))
+}
+
+// Filetype -> content of its UltiSnips file
+pub fn RenderUltiSnips(licenses: &[&LicenseEntry], author: &str) -> BTreeMap<String, String> {
+    let mut files: BTreeMap<String, String> = BTreeMap::new();
+
+    for (ext, _, filetype) in SNIPPET_SCOPE_TUPLES {
+        if files.contains_key(filetype) {
+            continue;
+        }
+        let Some((_, prefix)) = HEADER_COMMENT_PREFIX_TUPLES.iter().find(|(known, _)| *known == ext) else {
+            continue;
+        };
+
+        let mut content = String::new();
+        for licenseEntry in licenses {
+            // "b": only expands at the start of a line
+            content.push_str(&format!(
+                "snippet {} \"{} header\" b\n",
+                TriggerFor(licenseEntry),
+                licenseEntry.title.replace('"', "'")
+            ));
+            for line in SnippetBodyLines(licenseEntry, prefix, SnippetFormat::Ultisnips, author) {
+                content.push_str(&line);
+                content.push('\n');
+            }
+            content.push_str("endsnippet\n\n");
+        }
+
+        files.insert(filetype.to_string(), content);
+    }
+
+    files
+}
+
+fn WriteSnippetFile(path: &Path, content: &str) -> Result<(), AppError> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, parent.to_path_buf()))
+        })?;
+    }
+
+    fs::write(path, content)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, path.to_path_buf())))
+}
+
+pub async fn ExportSnippets(
+    cache: &Cache,
+    spdxIds: &[String],
+    format: SnippetFormat,
+    outputPath: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let mut licenses: Vec<&LicenseEntry> = if spdxIds.is_empty() {
+        cache.licenses.values().map(|entry| entry.as_ref()).collect()
+    } else {
+        spdxIds
+            .iter()
+            .map(|id| {
+                let spdxIdLower = id.to_lowercase();
+                cache
+                    .licenses
+                    .get(&spdxIdLower)
+                    .map(|entry| entry.as_ref())
+                    .ok_or_else(|| super::LicenseNotFoundError(spdxIdLower))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    licenses.sort_by(|a, b| a.spdxId.cmp(&b.spdxId));
+    licenses.dedup_by(|a, b| a.spdxId == b.spdxId);
+
+    if licenses.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::MissingData(
+            "no cached licenses to export snippets for".to_string(),
+        )));
+    }
+
+    // Snippets are shared across machines, so the saved name is only a default
+    let author = cache
+        .userPlaceholders
+        .get("fullname")
+        .map(|name| EscapeSnippetText(name, format))
+        .unwrap_or_else(|| "author".to_string());
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Exporting {:?} header snippets for {} license(s)", format, licenses.len());
+    }
+
+    match format {
+        SnippetFormat::Vscode => {
+            let json = RenderVsCodeSnippets(&licenses, &author)?;
+
+            match outputPath.filter(|path| path != Path::new("-")) {
+                None => println!("{}", json),
+                Some(path) => {
+                    WriteSnippetFile(&path, &(json + "\n"))?;
+                    println!(
+                        "Wrote VS Code header snippets for {} license(s) to {}",
+                        licenses.len(),
+                        path.display().to_string().green()
+                    );
+                }
+            }
+        }
+        SnippetFormat::Ultisnips => {
+            let outputDir = outputPath.ok_or_else(|| {
+                AppError::ActionErrorVariant(ActionError::InvalidInput(
+                    "UltiSnips snippets are one file per filetype; pass --output <DIR>, e.g. ~/.vim/UltiSnips."
+                        .to_string(),
+                ))
+            })?;
+
+            let files = RenderUltiSnips(&licenses, &author);
+            for (filetype, content) in &files {
+                WriteSnippetFile(&outputDir.join(format!("{}.snippets", filetype)), content)?;
+            }
+
+            println!(
+                "Wrote UltiSnips header snippets for {} license(s) and {} filetype(s) to {}",
+                licenses.len(),
+                files.len(),
+                outputDir.display().to_string().green()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn License(spdxId: &str) -> LicenseEntry {
+        LicenseEntry::ForTest(spdxId, "")
+    }
+
+    #[test]
+    fn TestSnippetBodyLines() {
+        let mit = SnippetBodyLines(&License("MIT"), "#", SnippetFormat::Vscode, "Jane Doe");
+        assert_eq!(
+            mit,
+            ["# SPDX-License-Identifier: MIT", "# Copyright (c) ${1:$CURRENT_YEAR} ${2:Jane Doe}", "$0"]
+        );
+
+        let gpl = SnippetBodyLines(&License("GPL-3.0"), "//", SnippetFormat::Ultisnips, "author");
+        assert_eq!(gpl[2], "// ${1:program} - ${2:what it does}");
+        assert!(gpl[3].starts_with("// Copyright (C) ${3:`!v strftime(\"%Y\")`}  ${4:author}"));
+        assert!(gpl.contains(&"//".to_string()));
+    }
+}
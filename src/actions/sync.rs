@@ -0,0 +1,131 @@
+use chrono::Utc;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cache;
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+
+// "1d", "12h", "30m" or "90s", whichever is the largest exact unit
+pub fn FormatInterval(interval: Duration) -> String {
+    let secs = interval.as_secs();
+
+    [(86400, "d"), (3600, "h"), (60, "m")]
+        .iter()
+        .find(|(unit, _)| secs.is_multiple_of(*unit))
+        .map(|(unit, suffix)| format!("{}{}", secs / unit, suffix))
+        .unwrap_or_else(|| format!("{}s", secs))
+}
+
+fn SyncStatusLine(cacheData: &Cache) -> String {
+    let (fetched, failures) = cacheData
+        .lastSync
+        .as_ref()
+        .map_or((0, 0), |record| (record.fetched.len(), record.failures.len()));
+
+    format!(
+        "{} licenses, {} data files cached; fetched {} file(s), {} failure(s)",
+        cacheData.licenses.len(),
+        cacheData.dataFiles.len(),
+        fetched,
+        failures
+    )
+}
+
+// The sync itself already ran at startup; this reports what it did.
+pub async fn ReportSync(cacheData: &Cache) -> Result<(), AppError> {
+    if crate::OFFLINE.load(std::sync::atomic::Ordering::SeqCst) {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "sync needs network access; drop --offline".to_string(),
+        )));
+    }
+
+    println!("Synced: {}", SyncStatusLine(cacheData));
+
+    let missing = cache::MissingFromListing(cacheData);
+    if !missing.is_empty() {
+        println!(
+            "{} file(s) of the listing are still missing; run `getlicense sync --resume` to fetch just those.",
+            missing.len()
+        );
+    }
+
+    Ok(())
+}
+
+// SIGINT everywhere, plus SIGTERM on Unix since that is how systemd and launchd stop services
+async fn ShutdownSignal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        if let Ok(mut sigterm) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
+            }
+            return;
+        }
+    }
+
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+// Re-syncs every `interval` until stopped. `cacheData` always holds the latest sync so
+// whatever runs after the daemon stops sees current data. A failed sync is logged and
+// retried at the next tick instead of ending the daemon.
+pub async fn RunSyncDaemon(
+    cacheData: &mut Cache,
+    cachePath: &Path,
+    only: &[String],
+    interval: Duration,
+    persist: bool,
+) -> Result<(), AppError> {
+    if crate::OFFLINE.load(std::sync::atomic::Ordering::SeqCst) {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "sync --daemon needs network access; drop --offline".to_string(),
+        )));
+    }
+
+    eprintln!(
+        "[{}] Synced: {}. Next sync in {}.",
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        SyncStatusLine(cacheData),
+        FormatInterval(interval)
+    );
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = ShutdownSignal() => {
+                eprintln!("Stopping sync daemon.");
+                return Ok(());
+            }
+        }
+
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
+
+        let fetcher = crate::api::AllowlistFetcher::New(&crate::api::GithubFetcher, only);
+
+        match cache::UpdateAndLoadLicenseCache(&fetcher, Some(cachePath), false, persist).await {
+            Ok((mut synced, updated)) => {
+                synced.userPlaceholders = std::mem::take(&mut cacheData.userPlaceholders);
+
+                if persist {
+                    if updated {
+                        if let Err(e) = cache::SaveCache(cachePath, &synced).await {
+                            eprintln!("[{}] Could not save the cache: {}", timestamp, e);
+                        }
+                    }
+                    if let Err(e) = cache::SaveSyncHistory(cachePath, &synced) {
+                        eprintln!("[{}] Could not record the sync in the history log: {}", timestamp, e);
+                    }
+                }
+
+                eprintln!("[{}] Synced: {}", timestamp, SyncStatusLine(&synced));
+                *cacheData = synced;
+            }
+            Err(e) => eprintln!("[{}] Sync failed, retrying in {}: {}", timestamp, FormatInterval(interval), e),
+        }
+    }
+}
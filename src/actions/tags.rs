@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering;
+
+use crate::display;
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+
+// How many cached licenses carry a rule tag, per category.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagUsage {
+    pub tag: String,
+    // From rules.yml; tags only seen on licenses have none
+    pub label: Option<String>,
+    pub permissions: usize,
+    pub conditions: usize,
+    pub limitations: usize,
+}
+
+// Every tag known from rules.yml or carried by a cached license, sorted by tag.
+pub fn CountTagUsage(cache: &Cache) -> Vec<TagUsage> {
+    let mut usage: BTreeMap<String, TagUsage> = BTreeMap::new();
+
+    if let Some(rules) = cache.RulesData() {
+        for rule in rules
+            .permissions
+            .iter()
+            .chain(&rules.conditions)
+            .chain(&rules.limitations)
+        {
+            usage.entry(rule.tag.clone()).or_insert_with(|| TagUsage {
+                tag: rule.tag.clone(),
+                label: Some(rule.label.clone()),
+                ..Default::default()
+            });
+        }
+    }
+
+    fn UsageFor<'a>(usage: &'a mut BTreeMap<String, TagUsage>, tag: &str) -> &'a mut TagUsage {
+        usage.entry(tag.to_string()).or_insert_with(|| TagUsage {
+            tag: tag.to_string(),
+            ..Default::default()
+        })
+    }
+
+    for license in cache.licenses.values() {
+        for tag in &license.permissions {
+            UsageFor(&mut usage, tag).permissions += 1;
+        }
+        for tag in &license.conditions {
+            UsageFor(&mut usage, tag).conditions += 1;
+        }
+        for tag in &license.limitations {
+            UsageFor(&mut usage, tag).limitations += 1;
+        }
+    }
+
+    usage.into_values().collect()
+}
+
+pub async fn ListTags(cache: &Cache) -> Result<(), AppError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Counting rule tags across {} cached licenses",
+            cache.licenses.len()
+        );
+    }
+
+    display::PrintTagUsage(&CountTagUsage(cache), cache.licenses.len());
+
+    Ok(())
+}
+
+// One category a rule tag is defined in. A tag can appear in more than one category
+// (e.g. patent-use is both a permission and a limitation), with a different meaning in each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagDefinition {
+    pub category: &'static str,
+    // None when the tag is only seen on licenses, not in rules.yml
+    pub label: Option<String>,
+    pub description: Option<String>,
+    // SPDX IDs of cached licenses carrying the tag in this category, sorted
+    pub licenses: Vec<String>,
+}
+
+pub fn ExplainTagDefinitions(cache: &Cache, tag: &str) -> Vec<TagDefinition> {
+    let rules = cache.RulesData();
+    let tag = tag.trim().to_lowercase();
+    let mut definitions = Vec::new();
+
+    for category in ["permissions", "conditions", "limitations"] {
+        let rule = rules.as_ref().and_then(|rules| {
+            let list = match category {
+                "permissions" => &rules.permissions,
+                "conditions" => &rules.conditions,
+                _ => &rules.limitations,
+            };
+            list.iter().find(|rule| rule.tag == tag).cloned()
+        });
+
+        let mut licenses: Vec<String> = cache
+            .licenses
+            .values()
+            .filter(|license| {
+                let tags = match category {
+                    "permissions" => &license.permissions,
+                    "conditions" => &license.conditions,
+                    _ => &license.limitations,
+                };
+                tags.contains(&tag)
+            })
+            .map(|license| license.spdxId.clone())
+            .collect();
+        licenses.sort_by_key(|id| id.to_lowercase());
+
+        if rule.is_some() || !licenses.is_empty() {
+            definitions.push(TagDefinition {
+                category,
+                label: rule.as_ref().map(|r| r.label.clone()),
+                description: rule.map(|r| r.description),
+                licenses,
+            });
+        }
+    }
+
+    definitions
+}
+
+pub async fn ExplainTag(cache: &Cache, tag: &str) -> Result<(), AppError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Looking up rule tag '{}' in rules.yml and {} cached licenses", tag, cache.licenses.len());
+    }
+
+    let definitions = ExplainTagDefinitions(cache, tag);
+
+    if definitions.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+            "unknown rule tag '{}'; run `getlicense tags` to list them",
+            tag
+        ))));
+    }
+
+    display::PrintTagExplanation(&tag.trim().to_lowercase(), &definitions);
+
+    Ok(())
+}
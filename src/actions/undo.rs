@@ -0,0 +1,82 @@
+use colored::*;
+use std::fs;
+use std::sync::atomic::Ordering;
+
+use crate::error::{ActionError, AppError};
+use crate::integrity::GitBlobSha;
+use crate::journal::FillJournal;
+
+// Puts back what the most recent `license` run replaced: earlier content is restored from
+// its backup, files the run created are removed. Running it again steps further back.
+pub async fn UndoLastFill(journal: Option<&FillJournal>, force: bool) -> Result<(), AppError> {
+    let journal = journal.ok_or_else(|| {
+        AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "nothing to undo: `license` runs are not recorded with --cache none or --no-cache-write".to_string(),
+        ))
+    })?;
+
+    let records = journal.Load()?;
+    let Some(last) = records.last() else {
+        println!("Nothing to undo: no `license` runs recorded.");
+        return Ok(());
+    };
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Undoing the license run of {} ({} file(s)), recorded in {:?}",
+            last.writtenAt, last.files.len(), journal.journalPath
+        );
+    }
+
+    // Check everything first, so a refusal leaves every file as it was
+    for file in &last.files {
+        let current = fs::read(&file.path).ok();
+
+        if !force && current.as_deref().map(GitBlobSha).as_ref() != Some(&file.writtenSha) {
+            return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "{} changed after getlicense wrote it; pass --force to undo anyway",
+                file.path.display()
+            ))));
+        }
+
+        if let Some(sha) = &file.previousSha {
+            if !journal.BackupPath(sha).is_file() {
+                return Err(AppError::ActionErrorVariant(ActionError::MissingData(format!(
+                    "backup of {} ({})",
+                    file.path.display(),
+                    sha
+                ))));
+            }
+        }
+    }
+
+    for file in &last.files {
+        let label = file.spdxId.as_deref().unwrap_or("notice");
+
+        match &file.previousSha {
+            Some(sha) => {
+                let backupPath = journal.BackupPath(sha);
+                fs::copy(&backupPath, &file.path).map_err(|e| {
+                    AppError::ActionErrorVariant(ActionError::FileOperation(e, file.path.clone()))
+                })?;
+                println!(
+                    "Restored {} to its content before the {} fill",
+                    file.path.display().to_string().green(),
+                    label.cyan()
+                );
+            }
+            None => {
+                if file.path.exists() {
+                    fs::remove_file(&file.path).map_err(|e| {
+                        AppError::ActionErrorVariant(ActionError::FileOperation(e, file.path.clone()))
+                    })?;
+                }
+                println!("Removed {}, created by the {} fill", file.path.display().to_string().green(), label.cyan());
+            }
+        }
+    }
+
+    journal.RemoveLast()?;
+
+    Ok(())
+}
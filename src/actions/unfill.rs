@@ -0,0 +1,177 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+
+use colored::*;
+
+use crate::constants::{CACHABLE_PLACEHOLDER_KEYS, PROVENANCE_FILENAME};
+use crate::display;
+use crate::drift;
+use crate::error::{AppError, CacheError};
+use crate::models::Cache;
+use crate::parser;
+use crate::provenance::{self, ProvenanceRecord};
+
+// The values an existing license file holds where its template has placeholders.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct UnfilledLicense {
+    pub path: PathBuf,
+    #[serde(rename = "spdx_id")]
+    pub spdxId: String,
+    // Standard key -> value
+    pub placeholders: BTreeMap<String, String>,
+}
+
+pub fn UnfillLicenseFile(
+    cache: &Cache,
+    pathOpt: Option<PathBuf>,
+    licenseIdOpt: Option<&str>,
+) -> Result<UnfilledLicense, AppError> {
+    let (licensePath, localText, licenseEntry) =
+        super::outdated::LocateLicenseForDrift(cache, pathOpt, licenseIdOpt)?;
+
+    let templateBody = parser::LicenseTemplateBody(&licenseEntry.fileContentCached);
+    let recovered =
+        drift::RecoverPlaceholderValues(&templateBody, &licenseEntry.placeholdersInBody, &localText);
+
+    Ok(UnfilledLicense {
+        path: licensePath,
+        spdxId: licenseEntry.spdxId.clone(),
+        placeholders: recovered.into_iter().collect(),
+    })
+}
+
+// Only keys that make sense across projects are saved; the year belongs to this file
+fn SavableValues(unfilled: &UnfilledLicense) -> Vec<(&String, &String)> {
+    unfilled
+        .placeholders
+        .iter()
+        .filter(|(key, _)| CACHABLE_PLACEHOLDER_KEYS.contains(&key.as_str()))
+        .collect()
+}
+
+// Without a terminal nothing is asked, and the answer is no
+fn AskYesNo(question: &str) -> Result<bool, AppError> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    print!("{} [y/N] ", question);
+    io::stdout()
+        .flush()
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdout")))?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdin")))?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+// Returns whether saved placeholder preferences changed. `save` and `recordProvenance` skip
+// the corresponding question; without them, both are offered in a terminal.
+pub async fn UnfillLicense(
+    cache: &mut Cache,
+    pathOpt: Option<PathBuf>,
+    licenseIdOpt: Option<&str>,
+    save: bool,
+    recordProvenance: bool,
+    asJson: bool,
+) -> Result<bool, AppError> {
+    let unfilled = UnfillLicenseFile(cache, pathOpt, licenseIdOpt)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] Values recovered from {:?} ({}): {:?}",
+            unfilled.path, unfilled.spdxId, unfilled.placeholders
+        );
+    }
+
+    if asJson {
+        let json = serde_json::to_string_pretty(&unfilled)
+            .map_err(|e| AppError::CacheErrorVariant(CacheError::Serialization(e)))?;
+        println!("{}", json);
+    } else {
+        display::PrintUnfilledLicense(&unfilled);
+    }
+
+    if unfilled.placeholders.is_empty() {
+        return Ok(false);
+    }
+
+    // JSON output is for scripts, which pass the flags instead of answering questions
+    let offer = !asJson && !save && !recordProvenance;
+    let savable = SavableValues(&unfilled);
+    let mut modifiedPlaceholders = false;
+
+    if !savable.is_empty() && (save || (offer && AskYesNo("\nSave these values as placeholder preferences?")?)) {
+        for (key, value) in &savable {
+            if cache.userPlaceholders.get(*key) != Some(*value) {
+                cache.userPlaceholders.insert(key.to_string(), value.to_string());
+                modifiedPlaceholders = true;
+            }
+        }
+
+        if !asJson {
+            println!("Saved {} placeholder preference(s).", savable.len().to_string().green());
+        }
+    }
+
+    if recordProvenance || (offer && AskYesNo(&format!("Record them in {}?", PROVENANCE_FILENAME))?) {
+        let licenseEntry = cache
+            .licenses
+            .get(&unfilled.spdxId.to_lowercase())
+            .ok_or_else(|| super::LicenseNotFoundError(unfilled.spdxId.to_lowercase()))?;
+        let values = unfilled.placeholders.clone().into_iter().collect();
+
+        provenance::SaveProvenance(&unfilled.path, &ProvenanceRecord::New(licenseEntry, &values, false))?;
+
+        if !asJson {
+            println!(
+                "Recorded the provenance of {} in {}",
+                unfilled.path.display(),
+                provenance::ProvenancePathFor(&unfilled.path).display().to_string().green()
+            );
+        }
+    }
+
+    Ok(modifiedPlaceholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LicenseEntry;
+    use crate::test_support::TempDir;
+    use std::fs;
+    use std::sync::Arc;
+
+    #[test]
+    fn TestUnfillLicenseFile() {
+        let mut cache = Cache::default();
+        cache.licenses.insert(
+            "mit".into(),
+            Arc::new(LicenseEntry::ForTest(
+                "MIT",
+                "---\ntitle: MIT License\n---\n\nCopyright (c) [year] [fullname]\n\nPermission is hereby granted.\n",
+            )),
+        );
+
+        let dir = TempDir::New("unfill");
+        let licensePath = dir.join("LICENSE");
+        fs::write(&licensePath, "Copyright (c) 2018 Jane Doe\n\nPermission is hereby granted.\n").unwrap();
+
+        let unfilled = UnfillLicenseFile(&cache, Some(licensePath.clone()), None).unwrap();
+        assert_eq!(unfilled.spdxId, "MIT");
+        assert_eq!(unfilled.placeholders["year"], "2018");
+        assert_eq!(unfilled.placeholders["fullname"], "Jane Doe");
+        assert_eq!(SavableValues(&unfilled), [(&"fullname".to_string(), &"Jane Doe".to_string())]);
+
+        fs::write(&licensePath, "Copyright (c) [year] Jane Doe\n\nPermission is hereby granted.\n").unwrap();
+        let unfilled = UnfillLicenseFile(&cache, Some(licensePath), Some("mit")).unwrap();
+        assert!(!unfilled.placeholders.contains_key("year"));
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use colored::*;
+
+use crate::display;
+use crate::drift;
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+use crate::parser;
+use crate::provenance::{self, ProvenanceRecord};
+use crate::store::FillRequest;
+
+fn ConfirmWrite(licensePath: &Path) -> Result<bool, AppError> {
+    if !io::stdin().is_terminal() {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "Refusing to overwrite without confirmation; pass --yes.".to_string(),
+        )));
+    }
+
+    print!("\nWrite these changes to {}? [y/N] ", licensePath.display());
+    io::stdout()
+        .flush()
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdout")))?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| AppError::Io(e, PathBuf::from("stdin")))?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+pub async fn UpgradeLicense(
+    cache: &Cache,
+    pathOpt: Option<PathBuf>,
+    licenseIdOpt: Option<&str>,
+    assumeYes: bool,
+) -> Result<(), AppError> {
+    let (licensePath, localText, licenseEntry) =
+        super::outdated::LocateLicenseForDrift(cache, pathOpt, licenseIdOpt)?;
+
+    let templateBody = parser::LicenseTemplateBody(&licenseEntry.fileContentCached);
+    let mut preserved =
+        drift::RecoverPlaceholderValues(&templateBody, &licenseEntry.placeholdersInBody, &localText);
+
+    // Recorded values are exact; recovered ones only fill what the record lacks
+    let record = provenance::LoadProvenance(&licensePath)?
+        .filter(|record| record.spdxId.eq_ignore_ascii_case(&licenseEntry.spdxId));
+
+    if let Some(record) = &record {
+        preserved.extend(record.placeholders.clone());
+    }
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Values recovered from {:?}: {:?}", licensePath, preserved);
+    }
+
+    let stripUnfilled = record.as_ref().is_some_and(|record| record.stripUnfilled);
+    let mut request =
+        FillRequest::from_cache(cache, &licenseEntry.spdxId).strip_unfilled(stripUnfilled);
+
+    for (key, value) in &preserved {
+        request = request.value(key, value);
+    }
+
+    let upgradedText = request.render()?.body + "\n";
+
+    if upgradedText.trim_end() == localText.trim_end() {
+        println!(
+            "{} {} already matches the current {} template",
+            "ok".green().bold(),
+            licensePath.display(),
+            licenseEntry.spdxId.cyan()
+        );
+        return Ok(());
+    }
+
+    let pathLabel = licensePath.display().to_string();
+    display::PrintPreservedValues(&preserved);
+    display::PrintUnifiedDiff(&drift::LineDiff(
+        &localText,
+        &upgradedText,
+        &pathLabel,
+        &format!("{} ({} template)", pathLabel, licenseEntry.spdxId),
+    ));
+
+    if !assumeYes && !ConfirmWrite(&licensePath)? {
+        println!("Left {} unchanged.", licensePath.display());
+        return Ok(());
+    }
+
+    fs::write(&licensePath, upgradedText).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, licensePath.clone()))
+    })?;
+
+    if record.is_some() {
+        provenance::SaveProvenance(
+            &licensePath,
+            &ProvenanceRecord::New(licenseEntry, &preserved, stripUnfilled),
+        )?;
+    }
+
+    println!(
+        "Upgraded {} to the current {} template",
+        licensePath.display().to_string().green(),
+        licenseEntry.spdxId.cyan()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use crate::constants::{DRIFT_MIN_SIMILARITY, LICENSE_FILE_CANDIDATES};
+use crate::display;
+use crate::drift;
+use crate::error::{ActionError, AppError};
+use crate::models::{Cache, LicenseEntry};
+use crate::parser;
+use crate::provenance;
+use crate::store::FillRequest;
+
+pub fn FindLicenseFile(dir: &Path) -> Option<PathBuf> {
+    LICENSE_FILE_CANDIDATES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+// Returns the cached license whose template matches `text`. When several match
+// (e.g. BSD-2-Clause inside BSD-3-Clause), the longest template wins.
+pub fn IdentifyLicenseText<'a>(cache: &'a Cache, text: &str) -> Option<&'a Arc<LicenseEntry>> {
+    cache
+        .licenses
+        .values()
+        .filter(|entry| {
+            parser::TemplateMatchesText(
+                &parser::LicenseTemplateBody(&entry.fileContentCached),
+                &entry.placeholdersInBody,
+                text,
+            )
+        })
+        .max_by_key(|entry| entry.fileContentCached.len())
+}
+
+// Exact template match first (similarity 1.0), then the closest template if it is similar
+// enough, for files whose wording was edited or comes from an older template.
+pub fn IdentifyOrApproximate<'a>(cache: &'a Cache, text: &str) -> Option<(&'a Arc<LicenseEntry>, f32)> {
+    match IdentifyLicenseText(cache, text) {
+        Some(entry) => Some((entry, 1.0)),
+        None => drift::ClosestTemplate(cache, text)
+            .filter(|(_, similarity)| *similarity >= DRIFT_MIN_SIMILARITY),
+    }
+}
+
+pub fn ReadAndIdentifyLicenseFile<'a>(
+    cache: &'a Cache,
+    licensePath: &Path,
+) -> Result<&'a Arc<LicenseEntry>, AppError> {
+    if cache.licenses.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(
+            "No license templates are cached. Run getlicense once without --offline first."
+                .to_string(),
+        )));
+    }
+
+    let text = fs::read_to_string(licensePath).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, licensePath.to_path_buf()))
+    })?;
+
+    IdentifyLicenseText(cache, &text).ok_or_else(|| {
+        AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} does not match any known license template (modified or corrupted?)",
+            licensePath.display()
+        )))
+    })
+}
+
+// Re-renders the recorded template with the recorded values. Only possible while the
+// cached template is the one the file was generated from.
+fn CheckAgainstProvenance(
+    cache: &Cache,
+    licensePath: &Path,
+    licenseEntry: &LicenseEntry,
+    record: &provenance::ProvenanceRecord,
+) -> Result<(), AppError> {
+    if !licenseEntry.spdxId.eq_ignore_ascii_case(&record.spdxId) {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} contains {}, but its provenance record says {}",
+            licensePath.display(),
+            licenseEntry.spdxId,
+            record.spdxId
+        ))));
+    }
+
+    if record.templateSha != licenseEntry.sha {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!(
+                "[Action] Template for {} changed since {:?} was generated; skipping exact comparison",
+                licenseEntry.spdxId, licensePath
+            );
+        }
+        return Ok(());
+    }
+
+    let mut request = FillRequest::from_cache(cache, &licenseEntry.spdxId)
+        .strip_unfilled(record.stripUnfilled);
+
+    for (key, value) in &record.placeholders {
+        request = request.value(key, value);
+    }
+
+    let expected = request.render()?.body;
+    let text = fs::read_to_string(licensePath).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, licensePath.to_path_buf()))
+    })?;
+
+    if text.trim_end() != expected.trim_end() {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} was modified since it was generated on {}",
+            licensePath.display(),
+            record.filledAt.format("%Y-%m-%d")
+        ))));
+    }
+
+    Ok(())
+}
+
+pub async fn ValidateLicenseFile(
+    cache: &Cache,
+    pathOpt: Option<PathBuf>,
+    expectedSpdxId: Option<&str>,
+) -> Result<(), AppError> {
+    let licensePath = match pathOpt {
+        Some(path) => path,
+        None => FindLicenseFile(Path::new(".")).ok_or_else(|| {
+            AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+                "No license file found (looked for {})",
+                LICENSE_FILE_CANDIDATES.join(", ")
+            )))
+        })?,
+    };
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Validating license file {:?}", licensePath);
+    }
+
+    let licenseEntry = ReadAndIdentifyLicenseFile(cache, &licensePath)?;
+
+    if let Some(expected) = expectedSpdxId {
+        if !licenseEntry.spdxId.eq_ignore_ascii_case(expected) {
+            return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+                "{} contains {}, expected {}",
+                licensePath.display(),
+                licenseEntry.spdxId,
+                expected
+            ))));
+        }
+    }
+
+    if let Some(record) = provenance::LoadProvenance(&licensePath)? {
+        CheckAgainstProvenance(cache, &licensePath, licenseEntry, &record)?;
+    }
+
+    display::PrintValidationResult(&licensePath, licenseEntry);
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use colored::*;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use crate::constants::{DATA_PATH_STR, LICENSES_PATH_STR, VENDOR_MANIFEST_FILENAME};
+use crate::error::{ActionError, AppError};
+use crate::models::Cache;
+
+fn WriteVendoredFile(path: &Path, content: &str) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::FileOperation(e, parent.to_path_buf()))
+        })?;
+    }
+
+    fs::write(path, content)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, path.to_path_buf())))
+}
+
+// Removes the files a previous vendor run recorded in its manifest. Anything else under
+// `_licenses/` and `_data/` was put there by the user and is left alone.
+fn RemovePreviouslyVendored(outputDir: &Path) -> Result<(), AppError> {
+    let manifestPath = outputDir.join(VENDOR_MANIFEST_FILENAME);
+    let Ok(manifest) = fs::read_to_string(&manifestPath) else {
+        return Ok(());
+    };
+
+    for relative in manifest.lines().filter(|line| !line.is_empty()) {
+        // Only paths vendor itself writes; a hand-edited manifest cannot reach elsewhere
+        let inVendoredDir = relative.starts_with(&format!("{}/", LICENSES_PATH_STR))
+            || relative.starts_with(&format!("{}/", DATA_PATH_STR));
+        if !inVendoredDir || relative.split('/').any(|part| part == "..") {
+            continue;
+        }
+
+        let path = outputDir.join(relative);
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(AppError::ActionErrorVariant(ActionError::FileOperation(e, path))),
+        }
+    }
+
+    Ok(())
+}
+
+// The cached templates and data files, laid out like the template repository so
+// `--source-dir <DIR>` can sync from them. License files are written exactly as upstream
+// shipped them, so their git blob SHAs match the cached ones.
+pub async fn VendorTemplates(cache: &Cache, outputDir: &Path) -> Result<(), AppError> {
+    if cache.licenses.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::MissingData(
+            "no cached licenses to vendor; run once without --offline first".to_string(),
+        )));
+    }
+
+    // Files left over from an older snapshot would otherwise be synced alongside the new ones
+    RemovePreviouslyVendored(outputDir)?;
+
+    let mut written: Vec<String> = Vec::new();
+
+    for licenseEntry in cache.licenses.values() {
+        let relative = format!("{}/{}", LICENSES_PATH_STR, licenseEntry.filename);
+        let path = outputDir.join(&relative);
+
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Action] Vendoring {} to {:?}", licenseEntry.spdxId, path);
+        }
+
+        WriteVendoredFile(&path, &licenseEntry.fileContentCached)?;
+        written.push(relative);
+    }
+    let licenseCount = written.len();
+
+    // Only the parsed YAML is cached, so data files are written back out from it
+    for (key, entry) in &cache.dataFiles {
+        let Some(name) = key.strip_prefix("data:") else {
+            continue;
+        };
+
+        let content = serde_yaml::to_string(&entry.content).map_err(|e| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "could not serialize {}: {}",
+                name, e
+            )))
+        })?;
+
+        let relative = format!("{}/{}", DATA_PATH_STR, name);
+        WriteVendoredFile(&outputDir.join(&relative), &content)?;
+        written.push(relative);
+    }
+
+    let mut manifest = written.join("\n");
+    manifest.push('\n');
+    WriteVendoredFile(&outputDir.join(VENDOR_MANIFEST_FILENAME), &manifest)?;
+
+    println!(
+        "Vendored {} license template(s) and {} data file(s) into {}. Use them with --source-dir {}.",
+        licenseCount,
+        written.len() - licenseCount,
+        outputDir.display().to_string().green(),
+        outputDir.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataFileEntry, LicenseEntry};
+    use crate::test_support::TempDir;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn TestRevendorRemovesOnlyWhatItWrote() {
+        let dir = TempDir::New("vendor");
+        let userTemplate = dir.join(LICENSES_PATH_STR).join("in-house.txt");
+        WriteVendoredFile(&userTemplate, "---\ntitle: In-house\n---\nText\n").unwrap();
+
+        let mut cache = Cache::default();
+        cache.licenses.insert("mit".to_string(), Arc::new(LicenseEntry::ForTest("MIT", "MIT text")));
+        cache.dataFiles.insert(
+            "data:rules.yml".to_string(),
+            Arc::new(DataFileEntry { sha: "abc".to_string(), content: serde_yaml::Value::Null }),
+        );
+        cache.dataFiles.insert(
+            "meta:listing".to_string(),
+            Arc::new(DataFileEntry { sha: "def".to_string(), content: serde_yaml::Value::Null }),
+        );
+        VendorTemplates(&cache, &dir).await.unwrap();
+
+        let manifest = fs::read_to_string(dir.join(VENDOR_MANIFEST_FILENAME)).unwrap();
+        assert_eq!(manifest, "_licenses/mit.txt\n_data/rules.yml\n");
+
+        // A template dropped from the cache is removed on the next run, the user's file is not
+        cache.licenses.clear();
+        cache.licenses.insert("isc".to_string(), Arc::new(LicenseEntry::ForTest("ISC", "ISC text")));
+        VendorTemplates(&cache, &dir).await.unwrap();
+
+        assert!(userTemplate.exists());
+        assert!(!dir.join(LICENSES_PATH_STR).join("mit.txt").exists());
+        assert!(dir.join(LICENSES_PATH_STR).join("isc.txt").exists());
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use crate::constants::{LICENSE_FILE_CANDIDATES, MANIFEST_FILENAMES};
+use crate::display;
+use crate::error::{ActionError, AppError};
+use crate::manifest;
+use crate::models::Cache;
+
+// A manifest agrees when its expression mentions the detected license, so a dual-licensed
+// "MIT OR Apache-2.0" crate with an MIT LICENSE file passes.
+pub async fn VerifyManifestLicense(cache: &Cache, projectDir: &Path) -> Result<(), AppError> {
+    let licensePath = super::validate::FindLicenseFile(projectDir).ok_or_else(|| {
+        AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "No license file found in {} (looked for {})",
+            projectDir.display(),
+            LICENSE_FILE_CANDIDATES.join(", ")
+        )))
+    })?;
+
+    let licenseEntry = super::validate::ReadAndIdentifyLicenseFile(cache, &licensePath)?;
+    let manifestLicenses = manifest::ReadManifestLicenses(projectDir)?;
+
+    if manifestLicenses.is_empty() {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "No license field found in {}",
+            MANIFEST_FILENAMES.join(", ")
+        ))));
+    }
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!(
+            "[Action] {:?} detected as {}; checking {} manifest(s)",
+            licensePath,
+            licenseEntry.spdxId,
+            manifestLicenses.len()
+        );
+    }
+
+    let results: Vec<(manifest::ManifestLicense, bool)> = manifestLicenses
+        .into_iter()
+        .map(|declared| {
+            let agrees = manifest::SpdxExpressionIds(&declared.expression)
+                .iter()
+                .any(|id| id.eq_ignore_ascii_case(&licenseEntry.spdxId));
+            (declared, agrees)
+        })
+        .collect();
+
+    display::PrintVerifyResults(&licensePath, licenseEntry, &results);
+
+    let mismatches = results.iter().filter(|(_, agrees)| !agrees).count();
+
+    if mismatches > 0 {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "{} manifest(s) disagree with {} ({})",
+            mismatches,
+            licensePath.display(),
+            licenseEntry.spdxId
+        ))));
+    }
+
+    Ok(())
+}
@@ -1,29 +1,370 @@
-use reqwest::header::{ACCEPT, AUTHORIZATION};
+use once_cell::sync::{Lazy, OnceCell};
+use reqwest::header::{ACCEPT, AUTHORIZATION, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
 use reqwest::Client;
 use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
-use crate::error::ApiError;
+use crate::config::AuthConfig;
+use crate::error::{ApiError, CacheError};
+use crate::http_cache::{ResponseCache, Validators};
 // For specific deserialization
-use crate::constants::{APP_USER_AGENT, GITHUB_API_BASE_URL, GITHUB_API_VERSION_HEADER};
-use crate::models::GitHubFile;
+use crate::constants::{
+    APP_USER_AGENT, BRANCH_CONST, DATA_PATH_STR, DEFAULT_CONNECT_TIMEOUT_SECS,
+    DEFAULT_REQUEST_TIMEOUT_SECS, GHE_API_PATH, GHE_RAW_PATH, GITHUB_API_BASE_URL,
+    GITHUB_API_VERSION_HEADER, GITHUB_PUBLIC_HOST, LICENSES_PATH_STR, OWNER_CONST,
+    RAW_CONTENT_BASE_URL, REPO_CONST,
+};
+use crate::models::{GitHubBlob, GitHubFile, GitHubRelease};
 
-fn GetHttpClient() -> Result<Client, reqwest::Error> {
-    Client::builder().user_agent(APP_USER_AGENT).build()
+// The repository templates are synced from.
+#[derive(Debug, Clone)]
+pub struct SourceRepo {
+    pub apiBaseUrl: String,
+    pub rawBaseUrl: String,
+    pub owner: String,
+    pub repo: String,
+    pub branch: String,
+}
+
+impl Default for SourceRepo {
+    fn default() -> Self {
+        SourceRepo {
+            apiBaseUrl: GITHUB_API_BASE_URL.to_string(),
+            rawBaseUrl: RAW_CONTENT_BASE_URL.to_string(),
+            owner: OWNER_CONST.to_string(),
+            repo: REPO_CONST.to_string(),
+            branch: BRANCH_CONST.to_string(),
+        }
+    }
+}
+
+impl SourceRepo {
+    // "github.com" keeps the public endpoints; any other host is treated as GitHub Enterprise.
+    pub fn ForHost(host: &str) -> Self {
+        let host = host
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_end_matches('/');
+
+        if host.eq_ignore_ascii_case(GITHUB_PUBLIC_HOST) {
+            return SourceRepo::default();
+        }
+
+        SourceRepo {
+            apiBaseUrl: format!("https://{}{}", host, GHE_API_PATH),
+            rawBaseUrl: format!("https://{}{}", host, GHE_RAW_PATH),
+            ..SourceRepo::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkSettings {
+    pub connectTimeout: Duration,
+    pub requestTimeout: Duration,
+    // URL templates for raw downloads, tried in order after the original URL
+    pub rawMirrors: Vec<String>,
+    pub source: SourceRepo,
+    // None with --cache none or http_cache_max_mb = 0
+    pub responseCache: Option<ResponseCache>,
+    // Per-host credentials from [auth], applied to API requests and raw downloads
+    pub auth: HashMap<String, AuthConfig>,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        NetworkSettings {
+            connectTimeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            requestTimeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            rawMirrors: Vec::new(),
+            source: SourceRepo::default(),
+            responseCache: None,
+            auth: HashMap::new(),
+        }
+    }
+}
+
+// Where UpdateAndLoadLicenseCache gets directory listings and file bodies from.
+pub trait LicenseFetcher {
+    // Files directly under `path` ("_data" or "_licenses") of the template repository
+    fn ListDirectory(&self, path: &str) -> impl Future<Output = Result<Vec<GitHubFile>, ApiError>> + Send;
+    // Body of a listed file; `blobSha` is the SHA the listing reported for it
+    fn FetchFile(&self, downloadUrl: &str, blobSha: &str) -> impl Future<Output = Result<String, ApiError>> + Send;
+    // Sources that never touch the network are still synced from with --offline
+    fn IsLocal(&self) -> bool {
+        false
+    }
+}
+
+// The configured GitHub (or GitHub Enterprise) source, with mirrors and the response cache.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GithubFetcher;
+
+impl LicenseFetcher for GithubFetcher {
+    async fn ListDirectory(&self, path: &str) -> Result<Vec<GitHubFile>, ApiError> {
+        let source = &Settings().source;
+        FetchGithubDirListing(&source.owner, &source.repo, path, &source.branch).await
+    }
+
+    async fn FetchFile(&self, downloadUrl: &str, blobSha: &str) -> Result<String, ApiError> {
+        FetchBlobContent(downloadUrl, blobSha).await
+    }
+}
+
+// Serves a fixed set of files without any I/O, e.g. for tests. Listings report real git
+// blob SHAs, so integrity checks behave as they do against GitHub.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFetcher {
+    // Repository path, e.g. "_licenses/mit.txt" -> content
+    files: BTreeMap<String, String>,
+}
+
+const MEMORY_URL_PREFIX: &str = "memory:///";
+
+impl MemoryFetcher {
+    pub fn New() -> Self {
+        MemoryFetcher::default()
+    }
+
+    pub fn WithFile(mut self, path: &str, content: &str) -> Self {
+        self.files.insert(path.trim_start_matches('/').to_string(), content.to_string());
+        self
+    }
+
+    // Every file under _licenses/ and _data/ of a directory laid out like the template
+    // repository, e.g. one written by `vendor`
+    pub fn FromDirectory(dir: &Path) -> Result<Self, CacheError> {
+        let mut fetcher = MemoryFetcher::New();
+
+        for subdir in [LICENSES_PATH_STR, DATA_PATH_STR] {
+            let subdirPath = dir.join(subdir);
+            // A snapshot without data files still gives usable templates
+            if subdir == DATA_PATH_STR && !subdirPath.exists() {
+                continue;
+            }
+
+            let entries = fs::read_dir(&subdirPath).map_err(|e| CacheError::Io(e, subdirPath.clone()))?;
+
+            for entry in entries {
+                let path = entry.map_err(|e| CacheError::Io(e, subdirPath.clone()))?.path();
+
+                if !path.is_file() {
+                    continue;
+                }
+
+                let content = fs::read_to_string(&path).map_err(|e| CacheError::Io(e, path.clone()))?;
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                fetcher = fetcher.WithFile(&format!("{}/{}", subdir, name), &content);
+            }
+        }
+
+        Ok(fetcher)
+    }
+}
+
+impl LicenseFetcher for MemoryFetcher {
+    async fn ListDirectory(&self, path: &str) -> Result<Vec<GitHubFile>, ApiError> {
+        let prefix = format!("{}/", path.trim_matches('/'));
+        let listing: Vec<GitHubFile> = self
+            .files
+            .iter()
+            .filter_map(|(filePath, content)| {
+                let name = filePath.strip_prefix(&prefix).filter(|name| !name.contains('/'))?;
+                Some(GitHubFile {
+                    name: name.to_string(),
+                    fileType: "file".to_string(),
+                    sha: crate::integrity::GitBlobSha(content.as_bytes()),
+                    downloadUrl: Some(format!("{}{}", MEMORY_URL_PREFIX, filePath)),
+                })
+            })
+            .collect();
+
+        if listing.is_empty() {
+            return Err(ApiError::HttpError {
+                status: reqwest::StatusCode::NOT_FOUND,
+                body: format!("no files under {}", path),
+            });
+        }
+
+        Ok(listing)
+    }
+
+    async fn FetchFile(&self, downloadUrl: &str, _blobSha: &str) -> Result<String, ApiError> {
+        downloadUrl
+            .strip_prefix(MEMORY_URL_PREFIX)
+            .and_then(|path| self.files.get(path))
+            .cloned()
+            .ok_or_else(|| ApiError::HttpError {
+                status: reqwest::StatusCode::NOT_FOUND,
+                body: format!("no file at {}", downloadUrl),
+            })
+    }
+
+    fn IsLocal(&self) -> bool {
+        true
+    }
+}
+
+// Answers listings from those an earlier sync saved (Cache::listings) and fetches bodies
+// through `inner`, so `sync --resume` only downloads what that sync did not finish.
+pub struct SavedListingFetcher<'a, F> {
+    inner: &'a F,
+    listings: HashMap<String, Vec<GitHubFile>>,
+}
+
+impl<'a, F: LicenseFetcher> SavedListingFetcher<'a, F> {
+    pub fn New(inner: &'a F, listings: HashMap<String, Vec<GitHubFile>>) -> Self {
+        SavedListingFetcher { inner, listings }
+    }
+}
+
+impl<F: LicenseFetcher + Sync> LicenseFetcher for SavedListingFetcher<'_, F> {
+    async fn ListDirectory(&self, path: &str) -> Result<Vec<GitHubFile>, ApiError> {
+        self.listings.get(path).cloned().ok_or_else(|| ApiError::HttpError {
+            status: reqwest::StatusCode::NOT_FOUND,
+            body: format!("the last sync saved no listing of {}", path),
+        })
+    }
+
+    async fn FetchFile(&self, downloadUrl: &str, blobSha: &str) -> Result<String, ApiError> {
+        self.inner.FetchFile(downloadUrl, blobSha).await
+    }
+
+    fn IsLocal(&self) -> bool {
+        self.inner.IsLocal()
+    }
+}
+
+// Narrows the _licenses listing of `inner` to the allowlisted IDs (matched against file
+// names), so a sync fetches and caches only those. An empty allowlist changes nothing.
+pub struct AllowlistFetcher<'a, F> {
+    inner: &'a F,
+    only: Vec<String>,
+}
+
+impl<'a, F: LicenseFetcher> AllowlistFetcher<'a, F> {
+    pub fn New(inner: &'a F, only: &[String]) -> Self {
+        AllowlistFetcher { inner, only: only.iter().map(|id| id.to_lowercase()).collect() }
+    }
+}
+
+impl<F: LicenseFetcher + Sync> LicenseFetcher for AllowlistFetcher<'_, F> {
+    async fn ListDirectory(&self, path: &str) -> Result<Vec<GitHubFile>, ApiError> {
+        let mut listing = self.inner.ListDirectory(path).await?;
+
+        if path != LICENSES_PATH_STR || self.only.is_empty() {
+            return Ok(listing);
+        }
+
+        let licenseId = |file: &GitHubFile| file.name.trim_end_matches(".txt").to_lowercase();
+        listing.retain(|file| self.only.contains(&licenseId(file)));
+
+        for id in self.only.iter().filter(|id| !listing.iter().any(|file| &licenseId(file) == *id)) {
+            eprintln!("Warning: '{}' is not an upstream license; nothing to sync for it.", id);
+        }
+
+        Ok(listing)
+    }
+
+    async fn FetchFile(&self, downloadUrl: &str, blobSha: &str) -> Result<String, ApiError> {
+        self.inner.FetchFile(downloadUrl, blobSha).await
+    }
+
+    fn IsLocal(&self) -> bool {
+        self.inner.IsLocal()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MirrorHealth {
+    pub successes: u32,
+    pub failures: u32,
+    pub lastError: Option<String>,
+}
+
+static NETWORK_SETTINGS: OnceCell<NetworkSettings> = OnceCell::new();
+// Built on first use, after ConfigureNetwork, and shared by every request so parallel
+// downloads reuse pooled connections instead of each doing a TLS handshake
+static HTTP_CLIENT: OnceCell<Client> = OnceCell::new();
+
+// One raw download; `body` is None when the server answered 304 Not Modified
+#[derive(Debug, Clone)]
+struct RawDownload {
+    body: Option<String>,
+    validators: Validators,
+}
+
+type InFlightDownload = Arc<tokio::sync::OnceCell<Option<RawDownload>>>;
+
+// Raw downloads currently in flight, keyed by URL. A request for a URL that is already being
+// fetched waits for that response instead of sending its own; None means it failed.
+static IN_FLIGHT: Lazy<Mutex<HashMap<String, InFlightDownload>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+// Keyed by host; filled as raw downloads succeed or fail during this run
+static MIRROR_HEALTH: Lazy<Mutex<BTreeMap<String, MirrorHealth>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+// Called once from main before any request; later calls are ignored.
+pub fn ConfigureNetwork(settings: NetworkSettings) {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[API] Network settings: {:?}", settings);
+    }
+
+    let _ = NETWORK_SETTINGS.set(settings);
+}
+
+pub fn Settings() -> &'static NetworkSettings {
+    NETWORK_SETTINGS.get_or_init(NetworkSettings::default)
+}
+
+fn GetHttpClient() -> Result<&'static Client, reqwest::Error> {
+    HTTP_CLIENT.get_or_try_init(|| {
+        let settings = Settings();
+
+        Client::builder()
+            .user_agent(APP_USER_AGENT)
+            .connect_timeout(settings.connectTimeout)
+            .timeout(settings.requestTimeout)
+            .build()
+    })
+}
+
+fn TokenFor(url: &str) -> Option<String> {
+    crate::credentials::TokenForHost(&Settings().auth, &HostOf(url))
+}
+
+fn EnsureOnline(url: &str) -> Result<(), ApiError> {
+    if crate::OFFLINE.load(Ordering::SeqCst) {
+        return Err(ApiError::OfflineError(url.to_string()));
+    }
+
+    Ok(())
 }
 
 async fn GetGithubApiGeneric<T: DeserializeOwned>(
     client: &Client,
+    apiBaseUrl: &str,
     endpoint: &str,
 ) -> Result<T, ApiError> {
-    let token = env::var("GITHUB_TOKEN").ok();
-    let url = format!("{}{}", GITHUB_API_BASE_URL, endpoint);
+    let url = format!("{}{}", apiBaseUrl, endpoint);
+    EnsureOnline(&url)?;
+
+    // GITHUB_TOKEN is the fallback for API hosts without an [auth] entry
+    let configuredToken = TokenFor(&url);
+    let usingGithubToken = configuredToken.is_none();
+    let token = configuredToken.or_else(|| env::var("GITHUB_TOKEN").ok());
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!("API Request: GET {}", url);
 
-        if token.is_some() {
+        if token.is_some() && usingGithubToken {
             eprintln!("Using GITHUB_TOKEN.");
         }
     }
@@ -77,27 +418,319 @@ pub async fn FetchGithubDirListing(
     let client = GetHttpClient().map_err(ApiError::ReqwestError)?;
     let endpoint = format!("/repos/{}/{}/contents/{}?ref={}", owner, repo, path, branch);
 
-    return GetGithubApiGeneric::<Vec<GitHubFile>>(&client, &endpoint).await;
+    return GetGithubApiGeneric::<Vec<GitHubFile>>(client, &Settings().source.apiBaseUrl, &endpoint)
+        .await;
 }
 
-pub async fn FetchFileContent(downloadUrl: &str) -> Result<String, ApiError> {
+// Any version of a file in the template repository, including ones no longer listed
+pub async fn FetchGitBlob(sha: &str) -> Result<String, ApiError> {
+    use base64::Engine;
+
+    let client = GetHttpClient().map_err(ApiError::ReqwestError)?;
+    let source = &Settings().source;
+    let endpoint = format!("/repos/{}/{}/git/blobs/{}", source.owner, source.repo, sha);
+
+    let blob = GetGithubApiGeneric::<GitHubBlob>(client, &source.apiBaseUrl, &endpoint).await?;
+
+    if blob.encoding != "base64" {
+        return Ok(blob.content);
+    }
+
+    // The API wraps the encoded content at 60 columns
+    let encoded: String = blob.content.split_whitespace().collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| ApiError::MalformedBlob(sha.to_string()))
+}
+
+// Releases of getlicense itself always live on public GitHub, regardless of --github-host.
+pub async fn FetchLatestRelease(owner: &str, repo: &str) -> Result<GitHubRelease, ApiError> {
+    let client = GetHttpClient().map_err(ApiError::ReqwestError)?;
+    let endpoint = format!("/repos/{}/{}/releases/latest", owner, repo);
+
+    GetGithubApiGeneric::<GitHubRelease>(client, GITHUB_API_BASE_URL, &endpoint).await
+}
+
+#[cfg(feature = "self-update")]
+pub async fn FetchBinaryContent(downloadUrl: &str) -> Result<Vec<u8>, ApiError> {
+    EnsureOnline(downloadUrl)?;
     let client = GetHttpClient().map_err(ApiError::ReqwestError)?;
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
-        eprintln!("Fetching file content from: {}", downloadUrl);
+        eprintln!("Downloading binary content from: {}", downloadUrl);
     }
 
     let response = client
         .get(downloadUrl)
-        // No need for GitHub API specific headers for raw download_url
         .send()
         .await
         .map_err(ApiError::ReqwestError)?;
 
+    if !response.status().is_success() {
+        return Err(ApiError::HttpError {
+            status: response.status(),
+            body: response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string()),
+        });
+    }
+
+    let bytes = response.bytes().await.map_err(ApiError::ReqwestError)?;
+
+    Ok(bytes.to_vec())
+}
+
+// Maps a raw content URL onto a mirror template. Returns None for URLs
+// outside rawBaseUrl, which cannot be decomposed into owner/repo/branch/path.
+pub fn RewriteRawUrl(downloadUrl: &str, rawBaseUrl: &str, mirrorTemplate: &str) -> Option<String> {
+    let rest = downloadUrl
+        .strip_prefix(rawBaseUrl)?
+        .trim_start_matches('/');
+    let mut parts = rest.splitn(4, '/');
+    let owner = parts.next()?;
+    let repo = parts.next()?;
+    let branch = parts.next()?;
+    let path = parts.next()?;
+
+    Some(
+        mirrorTemplate
+            .replace("{owner}", owner)
+            .replace("{repo}", repo)
+            .replace("{branch}", branch)
+            .replace("{path}", path),
+    )
+}
+
+fn HostOf(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn RecordMirrorResult<T>(url: &str, result: &Result<T, ApiError>) {
+    if let Ok(mut health) = MIRROR_HEALTH.lock() {
+        let entry = health.entry(HostOf(url)).or_default();
+
+        match result {
+            Ok(_) => entry.successes += 1,
+            Err(e) => {
+                entry.failures += 1;
+                entry.lastError = Some(e.to_string());
+            }
+        }
+    }
+}
+
+pub fn MirrorHealthReport() -> Vec<(String, MirrorHealth)> {
+    MIRROR_HEALTH
+        .lock()
+        .map(|health| health.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
+pub async fn FetchFileContent(downloadUrl: &str) -> Result<String, ApiError> {
+    FetchRawContent(downloadUrl, None).await?.body.ok_or(ApiError::HttpError {
+        status: reqwest::StatusCode::NOT_MODIFIED,
+        body: String::new(),
+    })
+}
+
+// Tries downloadUrl, then each mirror. Only the primary host gets the validators, since
+// they were issued by it; a 304 leaves `body` empty.
+async fn FetchRawContent(downloadUrl: &str, validators: Option<&Validators>) -> Result<RawDownload, ApiError> {
+    let settings = Settings();
+    let candidateUrls: Vec<String> = std::iter::once(downloadUrl.to_string())
+        .chain(
+            settings
+                .rawMirrors
+                .iter()
+                .filter_map(|m| RewriteRawUrl(downloadUrl, &settings.source.rawBaseUrl, m)),
+        )
+        .collect();
+    let mut lastError: Option<ApiError> = None;
+
+    for url in &candidateUrls {
+        let result = FetchFileContentFrom(url, validators.filter(|_| url == downloadUrl)).await;
+        RecordMirrorResult(url, &result);
+
+        match result {
+            Ok(download) => return Ok(download),
+            Err(ApiError::OfflineError(u)) => return Err(ApiError::OfflineError(u)),
+            Err(e) => {
+                if crate::VERBOSE.load(Ordering::SeqCst) && candidateUrls.len() > 1 {
+                    eprintln!("[API] Download from {} failed: {}. Trying next host.", url, e);
+                }
+
+                lastError = Some(e);
+            }
+        }
+    }
+
+    // candidateUrls always contains the original URL, so at least one error was recorded
+    Err(lastError.expect("no download candidates"))
+}
+
+// For listed files: the blob SHA identifies the exact content, so a response cached under
+// it can be reused without a request, even across --refresh runs and retries. When only an
+// older response is cached, the request is conditional and a 304 keeps that body.
+pub async fn FetchBlobContent(downloadUrl: &str, blobSha: &str) -> Result<String, ApiError> {
+    FetchBlobContentWith(Settings().responseCache.as_ref(), downloadUrl, blobSha, |validators| async move {
+        FetchRawContent(downloadUrl, validators.as_ref()).await
+    })
+    .await
+}
+
+// FetchBlobContent with the download passed in, so the 304 handling can be tested offline
+async fn FetchBlobContentWith<F, Fut>(
+    responseCache: Option<&ResponseCache>,
+    downloadUrl: &str,
+    blobSha: &str,
+    download: F,
+) -> Result<String, ApiError>
+where
+    F: Fn(Option<Validators>) -> Fut,
+    Fut: Future<Output = Result<RawDownload, ApiError>>,
+{
+    if let Some(content) = responseCache.and_then(|c| c.Get(downloadUrl, blobSha)) {
+        return Ok(content);
+    }
+
+    let previous = responseCache.and_then(|c| c.Latest(downloadUrl));
+    let fetched = download(previous.as_ref().map(|(_, validators)| validators.clone())).await?;
+
+    let (content, validators) = match (fetched.body, previous) {
+        (Some(body), _) => (body, fetched.validators),
+        // The listing names the exact content, so a 304 is only believed when the kept body
+        // is that content; otherwise the server or a proxy in between is stale
+        (None, Some((body, validators))) if crate::integrity::GitBlobSha(body.as_bytes()) == blobSha => {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[HTTP Cache] Not modified, keeping the cached body of {}", downloadUrl);
+            }
+            (body, validators)
+        }
+        (None, _) => {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[HTTP Cache] 304 for {} does not match SHA {}; fetching it again", downloadUrl, blobSha);
+            }
+
+            let refetched = download(None).await?;
+            let body = refetched.body.ok_or(ApiError::HttpError {
+                status: reqwest::StatusCode::NOT_MODIFIED,
+                body: String::new(),
+            })?;
+            (body, refetched.validators)
+        }
+    };
+
+    if let Some(c) = responseCache {
+        c.Put(downloadUrl, blobSha, &content, &validators);
+    }
+
+    Ok(content)
+}
+
+async fn FetchFileContentFrom(downloadUrl: &str, validators: Option<&Validators>) -> Result<RawDownload, ApiError> {
+    EnsureOnline(downloadUrl)?;
+
+    CoalescedDownload(downloadUrl, validators, || DownloadFileContent(downloadUrl, validators)).await
+}
+
+// Runs `download` unless a download of the same URL is already in flight, in which case
+// the response of that one is shared
+async fn CoalescedDownload<F, Fut>(
+    downloadUrl: &str,
+    validators: Option<&Validators>,
+    download: F,
+) -> Result<RawDownload, ApiError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<RawDownload, ApiError>>,
+{
+    let inFlight = {
+        let mut requests = IN_FLIGHT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        requests.entry(downloadUrl.to_string()).or_default().clone()
+    };
+
+    // Whoever gets here first downloads; the rest wait on the cell. Only the downloader
+    // sees its error, since ApiError cannot be shared.
+    let mut ownError = None;
+    let errorSlot = &mut ownError;
+    let shared = inFlight
+        .get_or_init(|| async {
+            download()
+                .await
+                .map_err(|e| *errorSlot = Some(e))
+                .ok()
+        })
+        .await
+        .clone();
+
+    // Finished requests leave the map so a later call downloads afresh
+    if let Ok(mut requests) = IN_FLIGHT.lock() {
+        if requests.get(downloadUrl).is_some_and(|cell| Arc::ptr_eq(cell, &inFlight)) {
+            requests.remove(downloadUrl);
+        }
+    }
+
+    match (shared, ownError) {
+        // A 304 answers someone else's validators, so it is no use to a caller without them
+        (Some(download), _) if download.body.is_some() || validators.is_some() => Ok(download),
+        (None, Some(e)) => Err(e),
+        // The download this call waited on failed; try once on its own to get the error
+        _ => download().await,
+    }
+}
+
+async fn DownloadFileContent(downloadUrl: &str, validators: Option<&Validators>) -> Result<RawDownload, ApiError> {
+    let client = GetHttpClient().map_err(ApiError::ReqwestError)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("Fetching file content from: {}", downloadUrl);
+    }
+
+    // No need for GitHub API specific headers for raw download_url
+    let mut requestBuilder = client.get(downloadUrl);
+
+    // Only hosts with an [auth] entry get a token, so public mirrors stay anonymous
+    if let Some(token) = TokenFor(downloadUrl) {
+        requestBuilder = requestBuilder.header(AUTHORIZATION, format!("token {}", token));
+    }
+
+    if let Some(validators) = validators {
+        if let Some(etag) = &validators.etag {
+            requestBuilder = requestBuilder.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(lastModified) = &validators.lastModified {
+            requestBuilder = requestBuilder.header(IF_MODIFIED_SINCE, lastModified);
+        }
+    }
+
+    let response = requestBuilder.send().await.map_err(ApiError::ReqwestError)?;
+
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!("File Content Response Status: {}", response.status());
     }
 
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let responseValidators = Validators {
+        etag: header(ETAG),
+        lastModified: header(LAST_MODIFIED),
+    };
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED && validators.is_some() {
+        return Ok(RawDownload { body: None, validators: responseValidators });
+    }
+
     if !response.status().is_success() {
         return Err(ApiError::HttpError {
             status: response.status(),
@@ -108,5 +741,95 @@ pub async fn FetchFileContent(downloadUrl: &str) -> Result<String, ApiError> {
         });
     }
 
-    response.text().await.map_err(ApiError::ReqwestError)
+    let body = response.text().await.map_err(ApiError::ReqwestError)?;
+
+    Ok(RawDownload { body: Some(body), validators: responseValidators })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestRewriteRawUrlToJsDelivr() {
+        let url = "https://raw.githubusercontent.com/github/choosealicense.com/gh-pages/_licenses/mit.txt";
+        let rewritten = RewriteRawUrl(
+            url,
+            RAW_CONTENT_BASE_URL,
+            "https://cdn.jsdelivr.net/gh/{owner}/{repo}@{branch}/{path}",
+        );
+        assert_eq!(
+            rewritten.as_deref(),
+            Some("https://cdn.jsdelivr.net/gh/github/choosealicense.com@gh-pages/_licenses/mit.txt")
+        );
+    }
+
+    #[test]
+    fn TestRewriteRawUrlIgnoresOtherHosts() {
+        assert_eq!(
+            RewriteRawUrl("https://example.com/a/b/c/d.txt", RAW_CONTENT_BASE_URL, "{path}"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn TestStaleNotModifiedIsFetchedAgain() {
+        let dir = TempDir::New("stale-304");
+        let responseCache = ResponseCache { dir: dir.to_path_buf(), maxBytes: 1 << 20, writable: true };
+        let url = "https://example.com/gpl-3.0.txt";
+        let validators = Validators { etag: Some("\"v1\"".to_string()), lastModified: None };
+        responseCache.Put(url, &crate::integrity::GitBlobSha(b"v1"), "v1", &validators);
+
+        // Answers every conditional request with 304, as a stale proxy would
+        let requests: Mutex<Vec<Option<Validators>>> = Mutex::new(Vec::new());
+        let download = |sent: Option<Validators>| {
+            let notModified = sent.is_some();
+            requests.lock().unwrap().push(sent);
+            async move {
+                Ok(RawDownload {
+                    body: (!notModified).then(|| "v2".to_string()),
+                    validators: Validators::default(),
+                })
+            }
+        };
+
+        let v2Sha = crate::integrity::GitBlobSha(b"v2");
+        let content = FetchBlobContentWith(Some(&responseCache), url, &v2Sha, download).await.unwrap();
+
+        assert_eq!(content, "v2");
+        assert_eq!(*requests.lock().unwrap(), [Some(validators), None]);
+        assert_eq!(responseCache.Get(url, &v2Sha).as_deref(), Some("v2"));
+    }
+
+    #[tokio::test]
+    async fn TestConcurrentDownloadsOfOneUrlAreCoalesced() {
+        let url = "https://example.com/coalesced/mit.txt";
+        let downloads = std::sync::atomic::AtomicUsize::new(0);
+        let download = || {
+            downloads.fetch_add(1, Ordering::SeqCst);
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(RawDownload { body: Some("MIT".to_string()), validators: Validators::default() })
+            }
+        };
+
+        let (first, second) = tokio::join!(CoalescedDownload(url, None, download), CoalescedDownload(url, None, download));
+
+        assert_eq!(first.unwrap().body.as_deref(), Some("MIT"));
+        assert_eq!(second.unwrap().body.as_deref(), Some("MIT"));
+        assert_eq!(downloads.load(Ordering::SeqCst), 1);
+
+        // Once finished, the next request downloads again
+        CoalescedDownload(url, None, download).await.unwrap();
+        assert_eq!(downloads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn TestSourceRepoForEnterpriseHost() {
+        let source = SourceRepo::ForHost("https://github.example.com/");
+        assert_eq!(source.apiBaseUrl, "https://github.example.com/api/v3");
+        assert_eq!(source.rawBaseUrl, "https://github.example.com/raw");
+        assert_eq!(SourceRepo::ForHost("github.com").apiBaseUrl, GITHUB_API_BASE_URL);
+    }
 }
@@ -0,0 +1,127 @@
+// The "How to Apply These Terms" notice the GNU licenses ask to be placed at the start of
+// each source file, filled in for one program.
+
+// Upstream's wording for each family, with {program_line}, {year} and {author} to fill in.
+// The LGPL-3.0 text has no notice of its own and refers to the GPL's.
+const GPL_NOTICE: &str = "\
+{program_line}
+Copyright (C) {year}  {author}
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU {license_name} as published by
+the Free Software Foundation, either version {version} of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU {license_name} for more details.
+
+You should have received a copy of the GNU {license_name}
+along with this program.  If not, see <https://www.gnu.org/licenses/>.";
+
+const GPL2_NOTICE: &str = "\
+{program_line}
+Copyright (C) {year}  {author}
+
+This program is free software; you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation; either version 2 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along
+with this program; if not, write to the Free Software Foundation, Inc.,
+51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.";
+
+const LGPL21_NOTICE: &str = "\
+{program_line}
+Copyright (C) {year}  {author}
+
+This library is free software; you can redistribute it and/or
+modify it under the terms of the GNU Lesser General Public
+License as published by the Free Software Foundation; either
+version 2.1 of the License, or (at your option) any later version.
+
+This library is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+Lesser General Public License for more details.
+
+You should have received a copy of the GNU Lesser General Public
+License along with this library; if not, write to the Free Software
+Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA";
+
+// Values for the notice. Missing ones keep upstream's <...> wording.
+#[derive(Debug, Clone, Default)]
+pub struct AppendixValues {
+    pub program: Option<String>,
+    pub description: Option<String>,
+    pub year: Option<String>,
+    pub author: Option<String>,
+}
+
+fn NoticeTemplate(spdxId: &str) -> Option<String> {
+    let gpl = |licenseName: &str, version: &str| {
+        GPL_NOTICE
+            .replace("{license_name}", licenseName)
+            .replace("{version}", version)
+    };
+
+    match spdxId.to_lowercase().as_str() {
+        "gpl-2.0" => Some(GPL2_NOTICE.to_string()),
+        "gpl-3.0" => Some(gpl("General Public License", "3")),
+        "agpl-3.0" => Some(gpl("Affero General Public License", "3")),
+        "lgpl-2.1" => Some(LGPL21_NOTICE.to_string()),
+        "lgpl-3.0" => Some(gpl("Lesser General Public License", "3")),
+        _ => None,
+    }
+}
+
+pub fn HasAppendix(spdxId: &str) -> bool {
+    NoticeTemplate(spdxId).is_some()
+}
+
+// None for licenses without a how-to-apply notice
+pub fn RenderAppendix(spdxId: &str, values: &AppendixValues) -> Option<String> {
+    let template = NoticeTemplate(spdxId)?;
+
+    let programLine = match (&values.program, &values.description) {
+        (Some(program), Some(description)) => format!("{} - {}", program, description),
+        (Some(program), None) => program.clone(),
+        (None, Some(description)) => description.clone(),
+        (None, None) => "<one line to give the program's name and a brief idea of what it does.>".to_string(),
+    };
+
+    Some(
+        template
+            .replace("{program_line}", &programLine)
+            .replace("{year}", values.year.as_deref().unwrap_or("<year>"))
+            .replace("{author}", values.author.as_deref().unwrap_or("<name of author>")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestRenderAppendix() {
+        let values = AppendixValues {
+            program: Some("frobnicate".to_string()),
+            description: Some("frobnicates widgets".to_string()),
+            year: Some("2024".to_string()),
+            author: None,
+        };
+
+        let notice = RenderAppendix("AGPL-3.0", &values).unwrap();
+
+        assert!(notice.starts_with("frobnicate - frobnicates widgets\nCopyright (C) 2024  <name of author>\n"));
+        assert!(notice.contains("GNU Affero General Public License as published by"));
+        assert!(RenderAppendix("MIT", &values).is_none());
+    }
+}
@@ -1,18 +1,47 @@
 use indicatif::{ProgressBar, ProgressStyle};
-use serde_json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
-use std::sync::atomic::Ordering;
+use std::io::{BufWriter, Write};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::api::LicenseFetcher;
 use crate::constants::{
-    BRANCH_CONST, DATA_PATH_STR, LICENSES_PATH_STR, OWNER_CONST, REPO_CONST, RULES_YML_KEY,
+    DATA_PATH_STR, LICENSES_PATH_STR, RULES_YML_KEY, SYNC_CHECKPOINT_INTERVAL, UNREFERENCED_BODY_GRACE_HOURS,
 };
 use crate::error::CacheError;
+use crate::history::{self, SyncRecord};
 use crate::models::{Cache, DataFileEntry, GitHubFile, LicenseEntry, RulesDataContent};
 
-pub fn LoadCache(cachePath: &Path) -> Result<Cache, CacheError> {
-    if !cachePath.exists() {
+// Distinguishes temp files of concurrent saves within one process
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// A sibling of `path` to write before renaming it into place, so readers and crashes only
+// ever see the old or the new file. Same directory, so the rename never crosses filesystems.
+fn TempPathFor(path: &Path) -> PathBuf {
+    let fileName = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cache".to_string());
+
+    path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        fileName,
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+fn JoinErrorToCacheError(e: tokio::task::JoinError, path: &Path) -> CacheError {
+    CacheError::Io(std::io::Error::other(e), path.to_path_buf())
+}
+
+// Without `writable`, a corrupt cache is reported but left where it is.
+pub async fn LoadCache(cachePath: &Path, writable: bool) -> Result<Cache, CacheError> {
+    if !tokio::fs::try_exists(cachePath).await.unwrap_or(false) {
         // exists() is fine
         if crate::VERBOSE.load(Ordering::SeqCst) {
             eprintln!(
@@ -24,10 +53,11 @@ pub fn LoadCache(cachePath: &Path) -> Result<Cache, CacheError> {
         return Ok(Cache::default());
     }
 
-    let content =
-        fs::read_to_string(cachePath).map_err(|e| CacheError::Io(e, cachePath.to_path_buf()))?;
+    let content = tokio::fs::read(cachePath)
+        .await
+        .map_err(|e| CacheError::Io(e, cachePath.to_path_buf()))?;
 
-    if content.trim().is_empty() {
+    if content.iter().all(|b| b.is_ascii_whitespace()) {
         if crate::VERBOSE.load(Ordering::SeqCst) {
             eprintln!(
                 "[Cache] Cache file at {:?} is empty. Starting fresh.",
@@ -38,16 +68,321 @@ pub fn LoadCache(cachePath: &Path) -> Result<Cache, CacheError> {
         return Ok(Cache::default());
     }
 
-    serde_json::from_str(&content)
-        .map_err(|e| CacheError::Deserialization(e, cachePath.to_path_buf()))
+    // Parsing a multi-megabyte cache is CPU-bound; keep it off the async workers
+    let parsePath = cachePath.to_path_buf();
+    let (parsed, content) = tokio::task::spawn_blocking(move || {
+        let parsed = serde_json::from_slice::<Cache>(&content)
+            .map_err(|e| CacheError::Deserialization(e, parsePath));
+        (parsed, content)
+    })
+    .await
+    .map_err(|e| JoinErrorToCacheError(e, cachePath))?;
+
+    let mut cache = match parsed {
+        Ok(cache) => cache,
+        Err(err) => return Ok(RecoverFromCorruptCache(cachePath, &content, &err, writable)),
+    };
+    AttachLicenseBodies(&mut cache, &BodiesDirFor(cachePath)).await;
+
+    Ok(cache)
+}
+
+pub fn CorruptBackupPathFor(cachePath: &Path) -> PathBuf {
+    let fileName = cachePath
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cache".to_string());
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+
+    cachePath.with_file_name(format!("{}.corrupt-{}", fileName, timestamp))
+}
+
+// Preferences embedded by older versions are the only part of a cache that cannot be
+// re-synced, so salvage them from JSON that is valid but misshapen, or failing that
+// from the raw text of a truncated file.
+pub fn RecoverUserPlaceholders(content: &[u8]) -> HashMap<String, String> {
+    const KEYS: [&str; 2] = ["userPlaceholders", "user_placeholders_cache"];
+
+    if let Ok(value) = serde_json::from_slice::<serde_json::Value>(content) {
+        return KEYS
+            .iter()
+            .find_map(|key| value.get(key).and_then(|v| v.as_object()))
+            .map(|object| {
+                object
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    let text = String::from_utf8_lossy(content);
+    let objectRe = regex::Regex::new(r#""(?:userPlaceholders|user_placeholders_cache)"\s*:\s*(\{[^{}]*\})"#).unwrap();
+
+    objectRe
+        .captures(&text)
+        .and_then(|caps| serde_json::from_str(&caps[1]).ok())
+        .unwrap_or_default()
+}
+
+// The broken file is kept next to the cache for inspection; the caller starts from an
+// empty cache that only carries whatever preferences could be salvaged.
+fn RecoverFromCorruptCache(cachePath: &Path, content: &[u8], err: &CacheError, writable: bool) -> Cache {
+    let backupPath = CorruptBackupPathFor(cachePath);
+
+    eprintln!("Warning: {}", err);
+
+    match writable.then(|| fs::rename(cachePath, &backupPath)) {
+        None => eprintln!(
+            "Warning: the corrupt cache at {:?} is left in place since writes are disabled; starting with a fresh cache.",
+            cachePath
+        ),
+        Some(Ok(())) => eprintln!(
+            "Warning: moved the corrupt cache to {:?} and starting with a fresh cache.",
+            backupPath
+        ),
+        Some(Err(e)) => eprintln!(
+            "Warning: could not move the corrupt cache to {:?} ({}); starting with a fresh cache.",
+            backupPath, e
+        ),
+    }
+
+    let userPlaceholders = RecoverUserPlaceholders(content);
+
+    if !userPlaceholders.is_empty() {
+        eprintln!(
+            "Recovered {} saved placeholder preference(s) from the corrupt cache.",
+            userPlaceholders.len()
+        );
+    }
+
+    Cache {
+        userPlaceholders,
+        ..Cache::default()
+    }
+}
+
+// License bodies are stored outside the JSON as <cache stem>.bodies/<git blob sha>,
+// so identical texts are stored once and an updated license only writes one file.
+pub fn BodiesDirFor(cachePath: &Path) -> PathBuf {
+    let stem = cachePath
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cache".to_string());
+
+    cachePath.with_file_name(format!("{}.bodies", stem))
+}
+
+fn BodyPathFor(bodiesDir: &Path, sha: &str) -> Option<PathBuf> {
+    // The sha comes from the cache/API; never let it escape the bodies directory
+    if sha.is_empty()
+        || sha.starts_with('.')
+        || !sha.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return None;
+    }
+
+    Some(bodiesDir.join(sha))
 }
 
-pub fn SaveCache(cachePath: &Path, cacheData: &Cache) -> Result<(), CacheError> {
+// Checks the SHA-256 recorded at sync time. Entries cached before hashes were recorded
+// are checked against the git blob SHA from the listing instead, like ReadBodyFile does.
+fn BodyMatchesRecordedHash(key: &str, entry: &LicenseEntry, body: &str) -> bool {
+    let matches = match &entry.sha256 {
+        Some(expected) => crate::integrity::Sha256Hex(body.as_bytes()) == *expected,
+        None => crate::integrity::GitBlobSha(body.as_bytes()) == entry.sha,
+    };
+
+    if !matches {
+        eprintln!(
+            "[Cache] Warning: body of '{}' does not match its recorded hash and was discarded. It will be refetched.",
+            key
+        );
+    }
+    matches
+}
+
+// Content whose git blob SHA differs from the one in the directory listing was altered
+// in transit or by a mirror, so it is never cached.
+fn MatchesListedBlobSha(ghFileInfo: &GitHubFile, content: &str) -> bool {
+    let actual = crate::integrity::GitBlobSha(content.as_bytes());
+
+    if actual == ghFileInfo.sha {
+        return true;
+    }
+
+    eprintln!(
+        "[Cache] Error: {} does not match the git blob SHA reported by the API (expected {}, got {}). Not caching it.",
+        ghFileInfo.name, ghFileInfo.sha, actual
+    );
+    false
+}
+
+// The stored body with this git blob SHA, if it is there and intact
+pub async fn ReadBodyFile(bodiesDir: &Path, sha: &str) -> Option<String> {
+    let body = tokio::fs::read_to_string(BodyPathFor(bodiesDir, sha)?).await.ok()?;
+
+    (crate::integrity::GitBlobSha(body.as_bytes()) == sha).then_some(body)
+}
+
+async fn AttachLicenseBodies(cache: &mut Cache, bodiesDir: &Path) {
+    let mut missingKeys: Vec<String> = Vec::new();
+
+    for (key, entry) in cache.licenses.iter_mut() {
+        // Caches written before body storage existed carry the body inline
+        let body = if !entry.fileContentCached.is_empty() {
+            Some(entry.fileContentCached.clone())
+        } else {
+            match BodyPathFor(bodiesDir, &entry.sha) {
+                Some(path) => tokio::fs::read_to_string(path).await.ok(),
+                None => None,
+            }
+        };
+
+        let verified = body.filter(|body| BodyMatchesRecordedHash(key, entry, body));
+
+        match (verified, Arc::get_mut(entry)) {
+            (Some(body), Some(entryMut)) => {
+                // Entries cached before hashes were recorded get one now
+                if entryMut.sha256.is_none() {
+                    entryMut.sha256 = Some(crate::integrity::Sha256Hex(body.as_bytes()));
+                }
+                entryMut.fileContentCached = body;
+            }
+            _ => {
+                if crate::VERBOSE.load(Ordering::SeqCst) {
+                    eprintln!("[Cache] Body for '{}' ({}) missing or altered. It will be refetched.", key, entry.sha);
+                }
+                missingKeys.push(key.clone());
+            }
+        }
+    }
+
+    for key in missingKeys {
+        cache.licenses.remove(&key);
+    }
+}
+
+async fn SaveLicenseBodies(cacheData: &Cache, bodiesDir: &Path) -> Result<(), CacheError> {
+    tokio::fs::create_dir_all(bodiesDir)
+        .await
+        .map_err(|e| CacheError::Io(e, bodiesDir.to_path_buf()))?;
+    let mut referenced: HashSet<PathBuf> = HashSet::new();
+
+    for entry in cacheData.licenses.values() {
+        let Some(bodyPath) = BodyPathFor(bodiesDir, &entry.sha) else {
+            continue;
+        };
+
+        if !tokio::fs::try_exists(&bodyPath).await.unwrap_or(false) {
+            WriteBodyFile(&bodyPath, &entry.fileContentCached).await?;
+        }
+
+        referenced.insert(bodyPath);
+    }
+
+    // Pinned versions are kept for as long as the cache lists them
+    for sha in &cacheData.pinnedTemplates {
+        let Some(bodyPath) = BodyPathFor(bodiesDir, sha) else {
+            continue;
+        };
+
+        if let Some(content) = cacheData.pinnedTemplateBodies.get(sha) {
+            if !tokio::fs::try_exists(&bodyPath).await.unwrap_or(false) {
+                WriteBodyFile(&bodyPath, content).await?;
+            }
+        }
+
+        referenced.insert(bodyPath);
+    }
+
+    RemoveUnreferencedBodies(bodiesDir, &referenced).await;
+
+    Ok(())
+}
+
+// Via a temp file, so a concurrent reader never sees a partly written body
+async fn WriteBodyFile(bodyPath: &Path, content: &str) -> Result<(), CacheError> {
+    let tempPath = TempPathFor(bodyPath);
+    let written = match tokio::fs::write(&tempPath, content).await {
+        Ok(()) => tokio::fs::rename(&tempPath, bodyPath).await,
+        Err(e) => Err(e),
+    };
+
+    if written.is_err() {
+        let _ = tokio::fs::remove_file(&tempPath).await;
+    }
+    written.map_err(|e| CacheError::Io(e, bodyPath.to_path_buf()))
+}
+
+// Superseded upstream versions, and temp files left by a crash. Other processes may share
+// the directory with a different cache generation, so recent files are always kept.
+async fn RemoveUnreferencedBodies(bodiesDir: &Path, referenced: &HashSet<PathBuf>) {
+    let grace = Duration::from_secs(UNREFERENCED_BODY_GRACE_HOURS * 60 * 60);
+    let Ok(mut dirEntries) = tokio::fs::read_dir(bodiesDir).await else {
+        return;
+    };
+
+    while let Ok(Some(dirEntry)) = dirEntries.next_entry().await {
+        let path = dirEntry.path();
+        if referenced.contains(&path) {
+            continue;
+        }
+
+        let Ok(metadata) = dirEntry.metadata().await else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default();
+
+        if metadata.is_file() && age >= grace {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[Cache] Removing unreferenced body {:?}", path);
+            }
+            let _ = tokio::fs::remove_file(&path).await;
+        }
+    }
+}
+
+fn WriteCacheJson(path: &Path, cacheData: &Cache) -> Result<(), CacheError> {
+    let file = fs::File::create(path).map_err(|e| CacheError::Io(e, path.to_path_buf()))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, cacheData).map_err(CacheError::Serialization)?;
+    writer.flush().map_err(|e| CacheError::Io(e, path.to_path_buf()))?;
+    // On disk before the rename, so a crash cannot leave an empty file in place of the cache
+    writer.get_ref().sync_all().map_err(|e| CacheError::Io(e, path.to_path_buf()))
+}
+
+pub async fn SaveCache(cachePath: &Path, cacheData: &Cache) -> Result<(), CacheError> {
+    let started = Instant::now();
     if let Some(parent) = cachePath.parent() {
-        fs::create_dir_all(parent).map_err(|e| CacheError::Io(e, parent.to_path_buf()))?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| CacheError::Io(e, parent.to_path_buf()))?;
     }
-    let content = serde_json::to_string_pretty(cacheData).map_err(CacheError::Serialization)?;
-    fs::write(cachePath, content).map_err(|e| CacheError::Io(e, cachePath.to_path_buf()))?;
+    SaveLicenseBodies(cacheData, &BodiesDirFor(cachePath)).await?;
+
+    // Stream the JSON straight into a temp file on a blocking thread instead of building
+    // the whole document in memory. Cloning the cache only bumps Arc counts.
+    let snapshot = cacheData.clone();
+    let writePath = cachePath.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<(), CacheError> {
+        let tempPath = TempPathFor(&writePath);
+        let written = WriteCacheJson(&tempPath, &snapshot)
+            .and_then(|_| fs::rename(&tempPath, &writePath).map_err(|e| CacheError::Io(e, writePath.clone())));
+
+        if written.is_err() {
+            let _ = fs::remove_file(&tempPath);
+        }
+        written
+    })
+    .await
+    .map_err(|e| JoinErrorToCacheError(e, cachePath))??;
+
+    crate::timings::Record("cache save", None, started);
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!("[Cache] Cache saved to {:?}", cachePath);
@@ -56,28 +391,162 @@ pub fn SaveCache(cachePath: &Path, cacheData: &Cache) -> Result<(), CacheError>
     Ok(())
 }
 
+pub struct PlaceholdersLoad {
+    pub placeholders: HashMap<String, String>,
+    // Legacy values were adopted and the placeholders file still needs writing
+    pub migrated: bool,
+    // The cache still embeds a (now stale) copy that should be dropped on next save
+    pub legacyCopyFound: bool,
+}
+
+// The placeholders file wins whenever it exists; otherwise values embedded in the
+// legacy combined cache are adopted.
+pub fn LoadUserPlaceholders(
+    placeholdersPath: &Path,
+    legacyPlaceholders: HashMap<String, String>,
+) -> Result<PlaceholdersLoad, CacheError> {
+    let legacyCopyFound = !legacyPlaceholders.is_empty();
+
+    if placeholdersPath.exists() {
+        let content = fs::read_to_string(placeholdersPath)
+            .map_err(|e| CacheError::Io(e, placeholdersPath.to_path_buf()))?;
+        let placeholders = if content.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str(&content)
+                .map_err(|e| CacheError::Deserialization(e, placeholdersPath.to_path_buf()))?
+        };
+
+        return Ok(PlaceholdersLoad {
+            placeholders,
+            migrated: false,
+            legacyCopyFound,
+        });
+    }
+
+    if legacyCopyFound {
+        eprintln!(
+            "[Cache] Migrating saved placeholder preferences to {:?}",
+            placeholdersPath
+        );
+    }
+
+    Ok(PlaceholdersLoad {
+        placeholders: legacyPlaceholders,
+        migrated: legacyCopyFound,
+        legacyCopyFound,
+    })
+}
+
+pub fn SaveUserPlaceholders(
+    placeholdersPath: &Path,
+    placeholders: &HashMap<String, String>,
+) -> Result<(), CacheError> {
+    if let Some(parent) = placeholdersPath.parent() {
+        fs::create_dir_all(parent).map_err(|e| CacheError::Io(e, parent.to_path_buf()))?;
+    }
+    // BTreeMap keeps the small file diff-friendly
+    let sorted: BTreeMap<&String, &String> = placeholders.iter().collect();
+    let content = serde_json::to_string_pretty(&sorted).map_err(CacheError::Serialization)?;
+    fs::write(placeholdersPath, content)
+        .map_err(|e| CacheError::Io(e, placeholdersPath.to_path_buf()))?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Cache] Placeholder preferences saved to {:?}", placeholdersPath);
+    }
+
+    Ok(())
+}
+
 fn NewProgressBar(totalItems: u64, message: &str) -> ProgressBar {
+    if !crate::terminal::ANIMATIONS.load(Ordering::SeqCst) {
+        return ProgressBar::hidden();
+    }
+
+    let mut style = ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {msg}")
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("#>-");
+
+    // The default spinner frames are braille characters
+    if !crate::terminal::SpinnerGlyphsSupported() {
+        style = style.tick_chars("|/-\\ ");
+    }
+
     let pb = ProgressBar::new(totalItems);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {msg}")
-            .unwrap_or_else(|_| ProgressStyle::default_bar())
-            .progress_chars("#>-"),
-    );
+    pb.set_style(style);
     pb.set_message(message.to_string());
 
     pb
 }
 
-pub async fn UpdateAndLoadLicenseCache(
+// Appends the record of the sync that produced `cacheData`, if it did anything.
+pub fn SaveSyncHistory(cachePath: &Path, cacheData: &Cache) -> Result<(), CacheError> {
+    match &cacheData.lastSync {
+        Some(record) => history::AppendSyncRecord(&history::HistoryPathFor(cachePath), record),
+        None => Ok(()),
+    }
+}
+
+// `None` means an in-memory cache (`--cache none`): nothing is read from disk, so a sync
+// always starts from scratch.
+async fn LoadCacheIfAny(cachePath: Option<&Path>, writable: bool) -> Result<Cache, CacheError> {
+    let started = Instant::now();
+    let loaded = match cachePath {
+        Some(path) => LoadCache(path, writable).await,
+        None => Ok(Cache::default()),
+    };
+    crate::timings::Record("cache load", None, started);
+    loaded
+}
+
+// What a sync has fetched so far on top of `base`, written to cachePath. A rerun after an
+// interruption then only fetches what the checkpoint does not have yet.
+async fn SaveSyncCheckpoint(
     cachePath: &Path,
+    base: &Cache,
+    newLicenses: &HashMap<String, Arc<LicenseEntry>>,
+    newDataFiles: &HashMap<String, Arc<DataFileEntry>>,
+) -> Result<(), CacheError> {
+    let mut snapshot = base.clone();
+    snapshot.dataFiles.extend(newDataFiles.iter().map(|(key, entry)| (key.clone(), Arc::clone(entry))));
+    snapshot.licenses.extend(newLicenses.iter().map(|(key, entry)| (key.clone(), Arc::clone(entry))));
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Cache] Checkpoint: saving {} licenses to {:?}", snapshot.licenses.len(), cachePath);
+    }
+
+    SaveCache(cachePath, &snapshot).await
+}
+
+// With `writable`, the cache at cachePath is rewritten every SYNC_CHECKPOINT_INTERVAL
+// fetched licenses and a corrupt one is moved aside; only pass true when the caller is
+// going to save it anyway.
+pub async fn UpdateAndLoadLicenseCache(
+    fetcher: &impl LicenseFetcher,
+    cachePath: Option<&Path>,
     forceRefresh: bool,
+    writable: bool,
 ) -> Result<(Cache, bool), CacheError> {
     if crate::VERBOSE.load(Ordering::SeqCst) {
-        eprintln!(
-            "[Cache] Updating and loading license cache from {:?}...",
-            cachePath
-        );
+        match cachePath {
+            Some(path) => eprintln!("[Cache] Updating and loading license cache from {:?}...", path),
+            None => eprintln!("[Cache] Syncing license cache into memory only..."),
+        }
+    }
+
+    if crate::OFFLINE.load(Ordering::SeqCst) && !fetcher.IsLocal() {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Cache] Offline mode. Skipping remote checks.");
+        }
+
+        let offlineCache = LoadCacheIfAny(cachePath, writable).await?;
+
+        if offlineCache.licenses.is_empty() && offlineCache.dataFiles.is_empty() {
+            eprintln!("Warning: cache is empty and --offline prevents syncing. Run once without --offline first.");
+        }
+
+        return Ok((offlineCache, false));
     }
 
     let mut currentCache = if forceRefresh {
@@ -88,7 +557,7 @@ pub async fn UpdateAndLoadLicenseCache(
         }
         Cache::default()
     } else {
-        LoadCache(cachePath).unwrap_or_else(|err| {
+        LoadCacheIfAny(cachePath, writable).await.unwrap_or_else(|err| {
             if crate::VERBOSE.load(Ordering::SeqCst) {
                 eprintln!(
                     "[Cache] Warning: Failed to load cache ({:?}), starting fresh: {}",
@@ -99,25 +568,37 @@ pub async fn UpdateAndLoadLicenseCache(
         })
     };
 
+    let mut syncRecord = SyncRecord::New(forceRefresh);
+    // Files that still do not parse are fetched and recorded again; a failed listing keeps
+    // what was recorded for its directory
+    let previousParseFailures = std::mem::take(&mut currentCache.parseFailures);
+    let mut parseFailures = BTreeMap::new();
+
     let userPlaceholdersBackup = if !forceRefresh {
-        currentCache.userPlaceholders.clone()
+        std::mem::take(&mut currentCache.userPlaceholders)
     } else {
-        let diskCacheForPlaceholders = LoadCache(cachePath).unwrap_or_default();
-        diskCacheForPlaceholders.userPlaceholders
+        // Neither comes from upstream, so a refresh keeps them
+        let diskCache = LoadCacheIfAny(cachePath, writable).await.unwrap_or_default();
+        currentCache.pinnedTemplates = diskCache.pinnedTemplates;
+        diskCache.userPlaceholders
     };
 
     let mut cacheUpdatedByFetch = false;
-    let mut newLicensesCache: HashMap<String, LicenseEntry> = HashMap::new();
-    let mut newDataFilesCache: HashMap<String, DataFileEntry> = HashMap::new();
+    let mut newLicensesCache: HashMap<String, Arc<LicenseEntry>> = HashMap::new();
+    let mut newDataFilesCache: HashMap<String, Arc<DataFileEntry>> = HashMap::new();
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!("[Cache] Checking _data files...");
     }
 
-    match crate::api::FetchGithubDirListing(OWNER_CONST, REPO_CONST, DATA_PATH_STR, BRANCH_CONST)
-        .await
-    {
+    let started = Instant::now();
+    let dataListing = fetcher.ListDirectory(DATA_PATH_STR).await;
+    crate::timings::Record("listing", Some(DATA_PATH_STR), started);
+
+    match dataListing {
         Ok(ghDataFiles) => {
+            currentCache.listings.insert(DATA_PATH_STR.to_string(), ghDataFiles.clone());
+
             for ghFileInfo in ghDataFiles
                 .iter()
                 .filter(|f| f.fileType == "file" && f.name.ends_with(".yml"))
@@ -125,13 +606,23 @@ pub async fn UpdateAndLoadLicenseCache(
                 let cacheKey = format!("data:{}", ghFileInfo.name);
                 let existingEntry = currentCache.dataFiles.get(&cacheKey);
 
-                if forceRefresh || existingEntry.map_or(true, |e| e.sha != ghFileInfo.sha) {
+                if forceRefresh || existingEntry.is_none_or(|e| e.sha != ghFileInfo.sha) {
                     if crate::VERBOSE.load(Ordering::SeqCst) {
                         eprintln!("[Cache] Fetching data file: {}", ghFileInfo.name);
                     }
 
                     if let Some(url) = &ghFileInfo.downloadUrl {
-                        match crate::api::FetchFileContent(url).await {
+                        let started = Instant::now();
+                        let fetched = fetcher.FetchFile(url, &ghFileInfo.sha).await;
+                        crate::timings::Record("fetch", Some(&ghFileInfo.name), started);
+
+                        match fetched {
+                            Ok(content) if !MatchesListedBlobSha(ghFileInfo, &content) => {
+                                syncRecord.failures.push(format!("{}: content does not match its git blob SHA", ghFileInfo.name));
+                                if let Some(entry) = existingEntry {
+                                    newDataFilesCache.insert(cacheKey.clone(), Arc::clone(entry));
+                                }
+                            }
                             Ok(content) => {
                                 if crate::VERBOSE.load(Ordering::SeqCst) {
                                     eprintln!(
@@ -139,17 +630,24 @@ pub async fn UpdateAndLoadLicenseCache(
                                         ghFileInfo.name
                                     );
                                 }
-                                match crate::parser::ParseDataFileToValue(
-                                    &ghFileInfo.name,
-                                    &content,
-                                ) {
+                                let started = Instant::now();
+                                let parsed = crate::parser::ParseDataFileToValue(&ghFileInfo.name, &content);
+                                crate::timings::Record("parse", Some(&ghFileInfo.name), started);
+
+                                match parsed {
                                     Ok(parsed_content) => {
+                                        syncRecord.RecordFetch(
+                                            &ghFileInfo.name,
+                                            &ghFileInfo.sha,
+                                            existingEntry.map(|e| e.sha.as_str()),
+                                            content.len(),
+                                        );
                                         newDataFilesCache.insert(
                                             cacheKey.clone(),
-                                            DataFileEntry {
+                                            Arc::new(DataFileEntry {
                                                 sha: ghFileInfo.sha.clone(),
                                                 content: parsed_content,
-                                            },
+                                            }),
                                         );
                                         cacheUpdatedByFetch = true;
                                         if crate::VERBOSE.load(Ordering::SeqCst) {
@@ -159,49 +657,51 @@ pub async fn UpdateAndLoadLicenseCache(
                                             );
                                         }
                                     }
-                                    Err(e) => eprintln!(
-                                        "[Cache] Error parsing data file {}: {}",
-                                        ghFileInfo.name, e
-                                    ),
+                                    Err(e) => {
+                                        eprintln!("[Cache] Error parsing data file {}: {}", ghFileInfo.name, e);
+                                        syncRecord.failures.push(format!("{}: {}", ghFileInfo.name, e));
+                                        parseFailures.insert(ghFileInfo.name.clone(), e.to_string());
+                                    }
                                 }
                             }
-                            Err(e) => eprintln!(
-                                "[Cache] Error fetching content for data file {}: {}",
-                                ghFileInfo.name, e
-                            ),
+                            Err(e) => {
+                                eprintln!("[Cache] Error fetching content for data file {}: {}", ghFileInfo.name, e);
+                                syncRecord.failures.push(format!("{}: {}", ghFileInfo.name, e));
+                            }
                         }
                     }
                 } else if let Some(entry) = existingEntry {
                     if crate::VERBOSE.load(Ordering::SeqCst) {
                         eprintln!("[Cache] Using cached data file: {}", ghFileInfo.name);
                     }
-                    newDataFilesCache.insert(cacheKey.clone(), entry.clone());
+                    newDataFilesCache.insert(cacheKey.clone(), Arc::clone(entry));
                 }
             }
         }
         Err(e) => {
             eprintln!("[Cache] Warning: Could not fetch _data directory listing: {}. Using cached data files if available.", e);
-            newDataFilesCache.extend(currentCache.dataFiles.clone());
+            syncRecord.failures.push(format!("{} listing: {}", DATA_PATH_STR, e));
+            parseFailures.extend(previousParseFailures.iter().filter(|(name, _)| name.ends_with(".yml")).map(|(k, v)| (k.clone(), v.clone())));
+            newDataFilesCache.extend(std::mem::take(&mut currentCache.dataFiles));
         }
     }
 
     let rulesDataContent: Option<RulesDataContent> = newDataFilesCache
         .get(RULES_YML_KEY)
-        .and_then(|entry| serde_yaml::from_value(entry.content.clone()).ok());
+        .and_then(|entry| RulesDataContent::deserialize(&entry.content).ok());
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!("[Cache] Checking _licenses files...");
     }
 
-    match crate::api::FetchGithubDirListing(
-        OWNER_CONST,
-        REPO_CONST,
-        LICENSES_PATH_STR,
-        BRANCH_CONST,
-    )
-    .await
-    {
+    let started = Instant::now();
+    let licensesListing = fetcher.ListDirectory(LICENSES_PATH_STR).await;
+    crate::timings::Record("listing", Some(LICENSES_PATH_STR), started);
+
+    match licensesListing {
         Ok(ghLicenseFilesInfo) => {
+            currentCache.listings.insert(LICENSES_PATH_STR.to_string(), ghLicenseFilesInfo.clone());
+
             let filesToProcess: Vec<&GitHubFile> = ghLicenseFilesInfo
                 .iter()
                 .filter(|f| f.fileType == "file" && f.name.ends_with(".txt"))
@@ -209,37 +709,61 @@ pub async fn UpdateAndLoadLicenseCache(
 
             if !filesToProcess.is_empty() {
                 let pb = NewProgressBar(filesToProcess.len() as u64, "Syncing licenses");
+                let checkpointPath = cachePath.filter(|_| writable);
+                // A refresh starts from an empty cache, but its checkpoints must not drop
+                // what is on disk and has not been refetched yet
+                let checkpointBase = match checkpointPath {
+                    Some(path) if forceRefresh => Cache {
+                        listings: currentCache.listings.clone(),
+                        ..LoadCacheIfAny(Some(path), writable).await.unwrap_or_default()
+                    },
+                    _ => Cache::default(),
+                };
+                let mut fetchedSinceCheckpoint = 0;
+                let cachedByFilename: HashMap<&str, &Arc<LicenseEntry>> = currentCache
+                    .licenses
+                    .values()
+                    .map(|entry| (entry.filename.as_str(), entry))
+                    .collect();
 
                 for ghFileInfo in filesToProcess {
                     pb.set_message(format!("Processing {}", ghFileInfo.name));
 
-                    let mut existingEntryKey: Option<String> = None;
-                    let mut existingEntrySha: Option<String> = None;
+                    let existingEntry = cachedByFilename.get(ghFileInfo.name.as_str());
 
-                    for (key, entry) in &currentCache.licenses {
-                        if entry.filename == ghFileInfo.name {
-                            existingEntryKey = Some(key.clone());
-                            existingEntrySha = Some(entry.sha.clone());
-                            break;
-                        }
-                    }
-
-                    if forceRefresh || existingEntrySha.map_or(true, |s| s != ghFileInfo.sha) {
+                    if forceRefresh || existingEntry.is_none_or(|e| e.sha != ghFileInfo.sha) {
                         if crate::VERBOSE.load(Ordering::SeqCst) {
                             eprintln!("[Cache] Fetching license file: {}", ghFileInfo.name);
                         }
 
                         if let Some(url) = &ghFileInfo.downloadUrl {
-                            match crate::api::FetchFileContent(url).await {
+                            let started = Instant::now();
+                            let fetched = fetcher.FetchFile(url, &ghFileInfo.sha).await;
+                            crate::timings::Record("fetch", Some(&ghFileInfo.name), started);
+
+                            match fetched {
+                                Ok(content) if !MatchesListedBlobSha(ghFileInfo, &content) => {
+                                    syncRecord.failures.push(format!("{}: content does not match its git blob SHA", ghFileInfo.name));
+                                    if let Some(entry) = existingEntry {
+                                        newLicensesCache.insert(entry.spdxId.to_lowercase(), Arc::clone(entry));
+                                    }
+                                }
                                 Ok(content) => {
                                     if crate::VERBOSE.load(Ordering::SeqCst) {
                                         eprintln!("[Cache] Successfully fetched license file: {}", ghFileInfo.name);
                                     }
-                                    match crate::parser::ParseLicenseFile(
-                                        &ghFileInfo.name,
-                                        &content,
-                                    ) {
+                                    let started = Instant::now();
+                                    let parsed = crate::parser::ParseLicenseFile(&ghFileInfo.name, &content);
+                                    crate::timings::Record("parse", Some(&ghFileInfo.name), started);
+
+                                    match parsed {
                                         Ok((spdxId, fm, body)) => {
+                                            syncRecord.RecordFetch(
+                                                &ghFileInfo.name,
+                                                &ghFileInfo.sha,
+                                                existingEntry.map(|e| e.sha.as_str()),
+                                                content.len(),
+                                            );
                                             let placeholders =
                                                 crate::parser::FindPlaceholdersInBody(&body);
                                             let infoComponents = crate::parser::BuildInfoComponents(
@@ -253,16 +777,18 @@ pub async fn UpdateAndLoadLicenseCache(
                                                 description: fm.description,
                                                 filename: ghFileInfo.name.clone(),
                                                 sha: ghFileInfo.sha.clone(),
+                                                sha256: Some(crate::integrity::Sha256Hex(content.as_bytes())),
                                                 permissions: fm.permissions,
                                                 conditions: fm.conditions,
                                                 limitations: fm.limitations,
                                                 fileContentCached: content,
                                                 placeholdersInBody: placeholders,
-                                                infoComponents: infoComponents,
+                                                infoComponents,
                                             };
                                             newLicensesCache
-                                                .insert(spdxId.to_lowercase(), licenseEntry);
+                                                .insert(spdxId.to_lowercase(), Arc::new(licenseEntry));
                                             cacheUpdatedByFetch = true;
+                                            fetchedSinceCheckpoint += 1;
                                             if crate::VERBOSE.load(Ordering::SeqCst) {
                                                 eprintln!(
                                                     "[Cache] Parsed and cached license file: {} (SPDX: {})",
@@ -270,27 +796,34 @@ pub async fn UpdateAndLoadLicenseCache(
                                                 );
                                             }
                                         }
-                                        Err(e) => eprintln!(
-                                            "[Cache] Error parsing license file {}: {}",
-                                            ghFileInfo.name, e
-                                        ),
+                                        Err(e) => {
+                                            eprintln!("[Cache] Error parsing license file {}: {}", ghFileInfo.name, e);
+                                            syncRecord.failures.push(format!("{}: {}", ghFileInfo.name, e));
+                                            parseFailures.insert(ghFileInfo.name.clone(), e.to_string());
+                                        }
                                     }
                                 }
-                                Err(e) => eprintln!(
-                                    "[Cache] Error fetching content for license {}: {}",
-                                    ghFileInfo.name, e
-                                ),
+                                Err(e) => {
+                                    eprintln!("[Cache] Error fetching content for license {}: {}", ghFileInfo.name, e);
+                                    syncRecord.failures.push(format!("{}: {}", ghFileInfo.name, e));
+                                }
                             }
                         }
-                    } else if let Some(key) = existingEntryKey {
-                        if let Some(entry) = currentCache.licenses.get(&key) {
-                            if crate::VERBOSE.load(Ordering::SeqCst) {
-                                eprintln!("[Cache] Using cached license file: {}", ghFileInfo.name);
-                            }
-                            newLicensesCache.insert(entry.spdxId.to_lowercase(), entry.clone());
+                    } else if let Some(entry) = existingEntry {
+                        if crate::VERBOSE.load(Ordering::SeqCst) {
+                            eprintln!("[Cache] Using cached license file: {}", ghFileInfo.name);
                         }
+                        newLicensesCache.insert(entry.spdxId.to_lowercase(), Arc::clone(entry));
                     }
                     pb.inc(1);
+
+                    if let Some(path) = checkpointPath.filter(|_| fetchedSinceCheckpoint >= SYNC_CHECKPOINT_INTERVAL) {
+                        let base = if forceRefresh { &checkpointBase } else { &currentCache };
+                        if let Err(e) = SaveSyncCheckpoint(path, base, &newLicensesCache, &newDataFilesCache).await {
+                            pb.println(format!("Warning: could not save sync progress: {}", e));
+                        }
+                        fetchedSinceCheckpoint = 0;
+                    }
                 }
 
                 pb.finish_with_message("License sync complete.");
@@ -302,13 +835,26 @@ pub async fn UpdateAndLoadLicenseCache(
         }
         Err(e) => {
             eprintln!("[Cache] Warning: Could not fetch _licenses directory listing: {}. Using cached licenses if available.", e);
-            newLicensesCache.extend(currentCache.licenses.clone());
+            syncRecord.failures.push(format!("{} listing: {}", LICENSES_PATH_STR, e));
+            parseFailures.extend(previousParseFailures.iter().filter(|(name, _)| name.ends_with(".txt")).map(|(k, v)| (k.clone(), v.clone())));
+            newLicensesCache.extend(std::mem::take(&mut currentCache.licenses));
         }
     }
 
+    let mirrorReport = crate::api::MirrorHealthReport();
+
+    // Only worth showing when a fallback actually happened (or was configured and verbose)
+    if mirrorReport.iter().any(|(_, h)| h.failures > 0)
+        || (mirrorReport.len() > 1 && crate::VERBOSE.load(Ordering::SeqCst))
+    {
+        crate::display::PrintMirrorHealth(&mirrorReport);
+    }
+
     currentCache.licenses = newLicensesCache;
     currentCache.dataFiles = newDataFilesCache;
     currentCache.userPlaceholders = userPlaceholdersBackup;
+    currentCache.parseFailures = parseFailures;
+    currentCache.lastSync = (!syncRecord.IsEmpty()).then_some(syncRecord);
 
     if crate::VERBOSE.load(Ordering::SeqCst) {
         eprintln!(
@@ -329,3 +875,339 @@ pub async fn UpdateAndLoadLicenseCache(
 
     Ok((currentCache, cacheUpdatedByFetch))
 }
+
+// Files of the saved listings that the cache does not hold at the listed SHA: never
+// fetched because the sync was interrupted, or failed to fetch or parse.
+pub fn MissingFromListing(cache: &Cache) -> Vec<String> {
+    let cachedShas: HashMap<&str, &str> = cache
+        .licenses
+        .values()
+        .map(|entry| (entry.filename.as_str(), entry.sha.as_str()))
+        .collect();
+
+    let mut missing = Vec::new();
+
+    for (path, extension) in [(DATA_PATH_STR, ".yml"), (LICENSES_PATH_STR, ".txt")] {
+        for file in cache
+            .listings
+            .get(path)
+            .into_iter()
+            .flatten()
+            .filter(|f| f.fileType == "file" && f.name.ends_with(extension))
+        {
+            let cachedSha = if path == DATA_PATH_STR {
+                cache.dataFiles.get(&format!("data:{}", file.name)).map(|entry| entry.sha.as_str())
+            } else {
+                cachedShas.get(file.name.as_str()).copied()
+            };
+
+            if cachedSha != Some(file.sha.as_str()) {
+                missing.push(format!("{}/{}", path, file.name));
+            }
+        }
+    }
+
+    missing
+}
+
+// `sync --resume`: replays the listings the last sync saved, so only the files it did not
+// finish are fetched and upstream is not listed again. None if no listing was saved.
+pub async fn ResumeSync(
+    fetcher: &(impl LicenseFetcher + Sync),
+    cachePath: &Path,
+    writable: bool,
+) -> Result<Option<(Cache, bool)>, CacheError> {
+    let saved = LoadCacheIfAny(Some(cachePath), writable).await?;
+
+    if saved.listings.is_empty() {
+        return Ok(None);
+    }
+
+    let missing = MissingFromListing(&saved);
+    if missing.is_empty() {
+        println!("Nothing to resume: every file of the last listing is cached.");
+        return Ok(Some((saved, false)));
+    }
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Cache] Resuming sync: {} file(s) missing: {}", missing.len(), missing.join(", "));
+    }
+
+    let savedListingFetcher = crate::api::SavedListingFetcher::New(fetcher, saved.listings);
+    UpdateAndLoadLicenseCache(&savedListingFetcher, Some(cachePath), false, writable)
+        .await
+        .map(Some)
+}
+
+// What every command but `sync` (and --refresh) uses: the cache as it is on disk, without
+// listing upstream. Only a cache that was never synced is filled first, so a fresh install
+// still works.
+pub async fn LoadLicenseCache(
+    fetcher: &impl LicenseFetcher,
+    cachePath: &Path,
+    writable: bool,
+) -> Result<(Cache, bool), CacheError> {
+    let cache = LoadCacheIfAny(Some(cachePath), writable).await?;
+
+    if !cache.licenses.is_empty() || crate::OFFLINE.load(Ordering::SeqCst) {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!(
+                "[Cache] Loaded {} licenses from {:?} without syncing; run `getlicense sync` to update.",
+                cache.licenses.len(),
+                cachePath
+            );
+        }
+
+        if cache.licenses.is_empty() {
+            eprintln!("Warning: cache is empty and --offline prevents syncing. Run `getlicense sync` first.");
+        }
+
+        return Ok((cache, false));
+    }
+
+    eprintln!("No license cache yet at {:?}; syncing once. Run `getlicense sync` to update it later.", cachePath);
+    UpdateAndLoadLicenseCache(fetcher, Some(cachePath), false, writable).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestRecoverUserPlaceholders() {
+        let misshapen = br#"{"licenses": 7, "userPlaceholders": {"fullname": "Jane Doe"}}"#;
+        assert_eq!(RecoverUserPlaceholders(misshapen)["fullname"], "Jane Doe");
+
+        let truncated = br#"{"user_placeholders_cache": {"email": "jane@example.com"}, "licenses": {"mit": {"spdxI"#;
+        assert_eq!(RecoverUserPlaceholders(truncated)["email"], "jane@example.com");
+
+        assert!(RecoverUserPlaceholders(b"\x00garbage").is_empty());
+    }
+
+    #[tokio::test]
+    async fn TestCorruptCacheMovedAsideOnlyWhenWritable() {
+        let dir = TempDir::New("corrupt");
+        let cachePath = dir.join("license_cache_rs.json");
+        fs::write(&cachePath, r#"{"licenses": 3, "userPlaceholders": {"fullname": "Jane Doe"}}"#).unwrap();
+
+        let readOnly = LoadCache(&cachePath, false).await.unwrap();
+        assert_eq!(readOnly.userPlaceholders["fullname"], "Jane Doe");
+        assert!(cachePath.exists());
+
+        let recovered = LoadCache(&cachePath, true).await.unwrap();
+        assert_eq!(recovered.userPlaceholders["fullname"], "Jane Doe");
+        assert!(!cachePath.exists());
+        assert_eq!(fs::read_dir(&*dir).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn TestUpdateWithMemoryFetcher() {
+        let fetcher = crate::api::MemoryFetcher::New()
+            .WithFile("_data/rules.yml", "permissions:\n- tag: commercial-use\n  label: Commercial use\n  description: Sell it.\nconditions: []\nlimitations: []\n")
+            .WithFile(
+                "_licenses/mit.txt",
+                "---\ntitle: MIT License\nspdx-id: MIT\npermissions:\n- commercial-use\nconditions: []\nlimitations: []\n---\n\nCopyright (c) [year] [fullname]\n",
+            )
+            .WithFile("_licenses/broken.txt", "---\ntitle: [unclosed\n---\n");
+
+        let (synced, updated) = UpdateAndLoadLicenseCache(&fetcher, None, false, false).await.unwrap();
+
+        assert!(updated);
+        assert_eq!(synced.licenses["mit"].title, "MIT License");
+        assert_eq!(synced.licenses["mit"].placeholdersInBody, ["[fullname]", "[year]"]);
+        assert!(synced.dataFiles.contains_key(RULES_YML_KEY));
+
+        let failures = &synced.lastSync.as_ref().unwrap().failures;
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("broken.txt"));
+        assert_eq!(synced.parseFailures.keys().collect::<Vec<_>>(), ["broken.txt"]);
+    }
+
+    #[tokio::test]
+    async fn TestSyncOnlyAllowlistedLicenses() {
+        let license = |spdxId: &str| {
+            format!("---\ntitle: {0}\nspdx-id: {0}\npermissions: []\nconditions: []\nlimitations: []\n---\n\nText\n", spdxId)
+        };
+        let fetcher = crate::api::MemoryFetcher::New()
+            .WithFile("_data/rules.yml", "permissions: []\nconditions: []\nlimitations: []\n")
+            .WithFile("_licenses/mit.txt", &license("MIT"))
+            .WithFile("_licenses/apache-2.0.txt", &license("Apache-2.0"))
+            .WithFile("_licenses/gpl-3.0.txt", &license("GPL-3.0"));
+        let only = vec!["MIT".to_string(), "gpl-3.0".to_string()];
+
+        let allowlisted = crate::api::AllowlistFetcher::New(&fetcher, &only);
+        let (synced, _) = UpdateAndLoadLicenseCache(&allowlisted, None, false, false).await.unwrap();
+
+        let mut ids: Vec<&str> = synced.licenses.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, ["gpl-3.0", "mit"]);
+        assert!(synced.dataFiles.contains_key(RULES_YML_KEY));
+        assert_eq!(synced.listings[LICENSES_PATH_STR].len(), 2);
+    }
+
+    #[tokio::test]
+    async fn TestUpdateFromVendoredDirectory() {
+        let dir = TempDir::New("vendor");
+        std::fs::create_dir_all(dir.join("_licenses")).unwrap();
+        std::fs::write(
+            dir.join("_licenses/mit.txt"),
+            "---\ntitle: MIT License\nspdx-id: MIT\npermissions: []\nconditions: []\nlimitations: []\n---\n\nCopyright (c) [year] [fullname]\n",
+        )
+        .unwrap();
+
+        let fetcher = crate::api::MemoryFetcher::FromDirectory(&dir).unwrap();
+        let (synced, _) = UpdateAndLoadLicenseCache(&fetcher, None, false, false).await.unwrap();
+
+        assert_eq!(synced.licenses.len(), 1);
+        assert_eq!(synced.licenses["mit"].title, "MIT License");
+    }
+
+    #[tokio::test]
+    async fn TestLoadLicenseCacheSyncsOnlyWhenEmpty() {
+        let dir = TempDir::New("load");
+        let cachePath = dir.join("license_cache_rs.json");
+        let fetcher = crate::api::MemoryFetcher::New().WithFile(
+            "_licenses/mit.txt",
+            "---\ntitle: MIT License\nspdx-id: MIT\npermissions: []\nconditions: []\nlimitations: []\n---\n\nCopyright (c) [year] [fullname]\n",
+        );
+
+        let (first, updated) = LoadLicenseCache(&fetcher, &cachePath, false).await.unwrap();
+        assert!(updated);
+        assert_eq!(first.licenses.len(), 1);
+        SaveCache(&cachePath, &first).await.unwrap();
+
+        // Upstream changes are not picked up until an explicit sync
+        let newer = fetcher.WithFile(
+            "_licenses/isc.txt",
+            "---\ntitle: ISC License\nspdx-id: ISC\npermissions: []\nconditions: []\nlimitations: []\n---\n\nCopyright (c) [year] [fullname]\n",
+        );
+        let (second, updated) = LoadLicenseCache(&newer, &cachePath, false).await.unwrap();
+
+        assert!(!updated);
+        assert_eq!(second.licenses.len(), 1);
+        assert!(second.lastSync.is_none());
+    }
+
+    #[tokio::test]
+    async fn TestResumeSyncReusesSavedListing() {
+        let dir = TempDir::New("resume");
+        let cachePath = dir.join("license_cache_rs.json");
+        let license = |id: &str| format!("---\ntitle: {0} License\nspdx-id: {0}\n---\n\nText\n", id);
+        let fetcher = crate::api::MemoryFetcher::New()
+            .WithFile("_licenses/mit.txt", &license("MIT"))
+            .WithFile("_licenses/0bsd.txt", &license("0BSD"));
+
+        // As if the sync had been interrupted before 0bsd.txt
+        let (mut interrupted, _) = UpdateAndLoadLicenseCache(&fetcher, Some(&cachePath), false, false).await.unwrap();
+        interrupted.licenses.remove("0bsd");
+        SaveCache(&cachePath, &interrupted).await.unwrap();
+        assert_eq!(MissingFromListing(&interrupted), ["_licenses/0bsd.txt"]);
+
+        // Files added upstream since are not listed again
+        let newer = fetcher.WithFile("_licenses/isc.txt", &license("ISC"));
+        let (resumed, updated) = ResumeSync(&newer, &cachePath, false).await.unwrap().unwrap();
+
+        assert!(updated);
+        assert!(resumed.licenses.contains_key("0bsd"));
+        assert!(!resumed.licenses.contains_key("isc"));
+        assert!(MissingFromListing(&resumed).is_empty());
+    }
+
+    #[tokio::test]
+    async fn TestSaveCacheReplacesFileAtomically() {
+        let dir = TempDir::New("atomic-save");
+        let cachePath = dir.join("license_cache_rs.json");
+        let fetcher = crate::api::MemoryFetcher::New().WithFile(
+            "_licenses/mit.txt",
+            "---\ntitle: MIT License\nspdx-id: MIT\n---\n\nCopyright (c) [year] [fullname]\n",
+        );
+        let (synced, _) = UpdateAndLoadLicenseCache(&fetcher, None, false, false).await.unwrap();
+
+        SaveCache(&cachePath, &Cache::default()).await.unwrap();
+        SaveCache(&cachePath, &synced).await.unwrap();
+        let onDisk = LoadCache(&cachePath, true).await.unwrap();
+        let leftovers: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+
+        assert_eq!(onDisk.licenses.len(), 1);
+        assert!(leftovers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn TestUnreferencedBodiesOutliveGracePeriod() {
+        let dir = TempDir::New("body-gc");
+        let bodiesDir = dir.join("license_cache_rs.bodies");
+        fs::create_dir_all(&bodiesDir).unwrap();
+        let (recent, stale, pinned) = (bodiesDir.join("recent"), bodiesDir.join("stale"), bodiesDir.join("pinned"));
+        fs::write(&recent, "another process's body").unwrap();
+        for old in [&stale, &pinned] {
+            fs::write(old, "superseded body").unwrap();
+            fs::File::options()
+                .append(true)
+                .open(old)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH)
+                .unwrap();
+        }
+
+        let cache = Cache { pinnedTemplates: ["pinned".to_string()].into(), ..Cache::default() };
+        SaveCache(&dir.join("license_cache_rs.json"), &cache).await.unwrap();
+        let (recentKept, staleKept, pinnedKept) = (recent.exists(), stale.exists(), pinned.exists());
+
+        assert!(recentKept);
+        assert!(!staleKept);
+        assert!(pinnedKept);
+    }
+
+    #[tokio::test]
+    async fn TestAttachLicenseBodiesVerifiesEveryBody() {
+        let dir = TempDir::New("attach-bodies");
+        let body = "Copyright (c) [year] [fullname]";
+        let entry = |spdxId: &str, inline: &str, sha256: Option<String>| {
+            Arc::new(LicenseEntry {
+                sha: crate::integrity::GitBlobSha(body.as_bytes()),
+                sha256,
+                ..LicenseEntry::ForTest(spdxId, inline)
+            })
+        };
+        let mut cache = Cache::default();
+        cache.licenses.insert("inline".into(), entry("INLINE", body, None));
+        cache.licenses.insert("tampered".into(), entry("TAMPERED", "Anything goes", None));
+        cache.licenses.insert("rehashed".into(), entry("REHASHED", body, Some("0".repeat(64))));
+        cache.licenses.insert("onDisk".into(), entry("ONDISK", "", None));
+        fs::write(dir.join(crate::integrity::GitBlobSha(body.as_bytes())), body).unwrap();
+
+        AttachLicenseBodies(&mut cache, &dir).await;
+
+        let mut kept: Vec<&str> = cache.licenses.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, ["inline", "onDisk"]);
+        // Entries cached before hashes were recorded get one once their body checks out
+        assert_eq!(cache.licenses["inline"].sha256, Some(crate::integrity::Sha256Hex(body.as_bytes())));
+        assert_eq!(cache.licenses["onDisk"].fileContentCached, body);
+    }
+
+    #[tokio::test]
+    async fn TestSyncWritesCheckpoints() {
+        let dir = TempDir::New("checkpoint");
+        let cachePath = dir.join("license_cache_rs.json");
+        let mut fetcher = crate::api::MemoryFetcher::New();
+        for index in 0..SYNC_CHECKPOINT_INTERVAL + 1 {
+            fetcher = fetcher.WithFile(
+                &format!("_licenses/l{}.txt", index),
+                &format!("---\ntitle: License {0}\nspdx-id: L{0}\n---\n\nText {0}\n", index),
+            );
+        }
+
+        // Nothing saves the result, so whatever is on disk came from a checkpoint
+        let (synced, _) = UpdateAndLoadLicenseCache(&fetcher, Some(&cachePath), true, true).await.unwrap();
+        let onDisk = LoadCache(&cachePath, true).await.unwrap();
+
+        assert_eq!(synced.licenses.len(), SYNC_CHECKPOINT_INTERVAL + 1);
+        assert_eq!(onDisk.licenses.len(), SYNC_CHECKPOINT_INTERVAL);
+    }
+}
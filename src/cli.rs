@@ -1,9 +1,11 @@
 use clap::builder::TypedValueParser;
 use clap::{Args as ClapArgs, Parser, Subcommand, crate_version};
 use std::path::PathBuf;
+use std::time::Duration;
 
 pub use clap_complete::Shell;
 
+use crate::config::HeaderStyle;
 use crate::constants::CACHABLE_PLACEHOLDER_KEYS_ARRAY;
 
 #[derive(Parser, Debug)]
@@ -14,18 +16,93 @@ pub struct Cli {
     #[clap(subcommand)]
     pub command: Option<Commands>,
 
-    /// Force refresh of the local license and data cache from GitHub.
+    /// Re-download the whole license and data cache from GitHub before running the command.
+    /// Without it, only `sync` contacts GitHub.
     #[clap(long, global = true)]
     pub refresh: bool,
 
+    /// Exit non-zero if any file failed to fetch or parse during the sync, after listing every failure.
+    #[clap(long, global = true, conflicts_with = "offline", env = "GETLICENSE_STRICT_SYNC")]
+    pub strictSync: bool,
+
+    /// Never access the network; operate solely on the cached data.
+    #[clap(long, global = true, conflicts_with = "refresh", env = "GETLICENSE_OFFLINE")]
+    pub offline: bool,
+
+    /// Seconds to wait for a connection to be established.
+    #[clap(long, global = true, value_name = "SECS", env = "GETLICENSE_CONNECT_TIMEOUT")]
+    pub connectTimeout: Option<u64>,
+
+    /// Seconds a whole request may take, from connecting to the end of the response body.
+    #[clap(long, global = true, value_name = "SECS", env = "GETLICENSE_REQUEST_TIMEOUT")]
+    pub requestTimeout: Option<u64>,
+
+    /// GitHub Enterprise host to sync templates from (e.g. github.example.com).
+    #[clap(long, global = true, value_name = "HOST", env = "GETLICENSE_GITHUB_HOST")]
+    pub githubHost: Option<String>,
+
+    /// Fallback URL template for raw file downloads, tried in order after the primary host.
+    /// Supports {owner}, {repo}, {branch} and {path}. May be repeated.
+    #[clap(long = "mirror", global = true, value_name = "URL_TEMPLATE")]
+    pub mirrors: Vec<String>,
+
     /// Path to the license cache file.
     #[clap(long, global = true, value_name = "FILE_PATH")]
     pub cacheFile: Option<PathBuf>,
 
+    /// Read templates from a directory written by `vendor` instead of syncing from GitHub.
+    /// The license cache file is neither read nor written.
+    #[clap(long, global = true, value_name = "DIR", env = "GETLICENSE_SOURCE_DIR")]
+    pub sourceDir: Option<PathBuf>,
+
+    /// Use a separate named cache (e.g. one synced from company templates). Configure its
+    /// file and source under [cache_profiles.NAME] in the config file.
+    #[clap(long, global = true, value_name = "NAME", conflicts_with = "cacheFile", env = "GETLICENSE_CACHE_PROFILE")]
+    pub cacheProfile: Option<String>,
+
+    /// Where the license cache lives. `none` syncs into memory on every run and never touches disk.
+    #[clap(long = "cache", global = true, value_enum, value_name = "MODE", default_value = "file", env = "GETLICENSE_CACHE")]
+    pub cacheMode: CacheMode,
+
+    /// Never write the cache or placeholder preferences files (e.g. on a read-only or shared cache).
+    #[clap(long, global = true, env = "GETLICENSE_NO_CACHE_WRITE")]
+    pub noCacheWrite: bool,
+
+    /// Path to the config file. Defaults to <config dir>/getlicense/config.toml.
+    #[clap(long, global = true, value_name = "FILE_PATH", env = "GETLICENSE_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Placeholder profile from the config file to use when filling templates.
+    #[clap(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Path to the saved placeholder preferences. Defaults to a file next to the cache.
+    #[clap(long, global = true, value_name = "FILE_PATH")]
+    pub placeholdersFile: Option<PathBuf>,
+
+    /// Output width in columns for wrapping and tables. Defaults to the terminal width.
+    #[clap(long, global = true, value_name = "COLUMNS", value_parser = clap::value_parser!(u16).range(20..))]
+    pub width: Option<u16>,
+
+    /// Use only ASCII characters for indicators and progress bars. On by default when the
+    /// locale is not UTF-8.
+    #[clap(long, global = true, env = "GETLICENSE_ASCII")]
+    pub ascii: bool,
+
     /// Print detailed status messages during execution (to stderr).
     #[clap(short, long, global = true)]
     pub verbose: bool,
 
+    /// Date to use as "now" for copyright years, notice dates and provenance timestamps
+    /// (RFC 3339, YYYY-MM-DD or Unix seconds). Overrides SOURCE_DATE_EPOCH.
+    #[clap(long, global = true, value_name = "DATE", value_parser = crate::clock::ParseNow, env = "GETLICENSE_NOW")]
+    pub now: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Print how long each phase took (cache load, listings, fetches, parsing, the command
+    /// itself, cache save) to stderr when done.
+    #[clap(long, global = true, env = "GETLICENSE_TIMINGS")]
+    pub timings: bool,
+
     /// Generate shell completion script.
     #[clap(
         long = "generate-completion",
@@ -36,6 +113,24 @@ pub struct Cli {
     pub generateCompletion: Option<Shell>,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YearSource {
+    /// Years of the first and latest commit
+    Git,
+    /// Newest file modification time, for projects without git history
+    Mtime,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Cache file on disk (see --cache-file)
+    File,
+    /// In-memory only; nothing is read from or written to disk
+    None,
+}
+
+// Parsed once per run, so boxing the large argument structs would buy nothing
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// List available licenses. If IDs provided, lists only those. Otherwise, lists all.
@@ -45,15 +140,45 @@ pub enum Commands {
     DetailedList(ListArgs),
     /// Show detailed metadata for a specific license.
     Info(InfoArgs),
+    /// Print a license template's text with its placeholders highlighted.
+    Show(ShowArgs),
     /// Show placeholders for a specific license.
     #[clap(name = "show-placeholders")]
-    ShowPlaceholders(InfoArgs),
+    ShowPlaceholders(ShowPlaceholdersArgs),
     /// Compare specified licenses. If no IDs, compares all available licenses.
     Compare(CompareArgs),
     /// Find licenses matching specified criteria.
     Find(FindArgs),
+    /// Answer a few questions about your project and get the best fitting licenses.
+    Choose(ChooseArgs),
+    /// List every rule tag with how many cached licenses carry it in each category.
+    Tags,
+    /// Explain one rule tag: its label, description and category, and which cached licenses carry it.
+    Explain(ExplainArgs),
+    /// Summarize the cached corpus: permissive vs copyleft, most common conditions, average
+    /// placeholder count and the newest additions.
+    Stats(StatsArgs),
+    /// Sync the license cache from upstream; other commands only read it. With --daemon, keep it fresh on a schedule.
+    Sync(SyncArgs),
+    /// Show recent syncs: when they ran, which upstream files they fetched, and what failed.
+    History(HistoryArgs),
+    /// Re-parse the cached upstream files and report missing IDs, unknown rule tags and unmapped placeholders.
+    #[clap(name = "lint-data")]
+    LintData(LintDataArgs),
     /// Fill a license template with user-provided values and save it.
     License(LicenseFillArgs),
+    /// Undo the most recent `license` run: restore the files it overwrote, remove those it created.
+    Undo(UndoArgs),
+    /// Write the unfilled template body (placeholders kept) to a file, default <ID>.txt, or - for stdout.
+    Export(ExportArgs),
+    /// Write every cached license template to a directory, one <SPDX_ID>.txt per license.
+    #[clap(name = "export-all")]
+    ExportAll(ExportAllArgs),
+    /// Copy the cached templates and data files into a directory that --source-dir can read,
+    /// pinning the exact template versions a project uses.
+    Vendor(VendorArgs),
+    /// Export license header snippets for editors, one per license and comment style.
+    Snippets(SnippetsArgs),
     /// Save a placeholder value for future use.
     #[clap(
         name = "set-placeholder",
@@ -66,24 +191,352 @@ pub enum Commands {
     /// Clear saved placeholder(s). Clears all if no KEY.
     #[clap(name = "clear-placeholders")]
     ClearPlaceholders(ClearPlaceholdersArgs),
+    /// Save fullname/email from git config and project/projecturl from Cargo.toml as preferences.
+    #[clap(name = "import-placeholders")]
+    ImportPlaceholders(ImportPlaceholdersArgs),
+    /// Open the saved placeholder preferences as TOML in $VISUAL/$EDITOR and save the result.
+    #[clap(name = "edit-placeholders")]
+    EditPlaceholders,
+    /// Check that the license file matches a known license template.
+    Validate(ValidateArgs),
+    /// Rank cached licenses by how closely they match the given text (a file, or - for stdin).
+    Identify(IdentifyArgs),
+    /// Print a license text in the normalized form used for matching (copyright lines
+    /// dropped, case folded, punctuation and whitespace unified).
+    Normalize(NormalizeArgs),
+    /// Check that the license file agrees with the license field of Cargo.toml, package.json or pyproject.toml.
+    Verify(VerifyArgs),
+    /// Run every compliance check for CI in one go: license file, unfilled placeholders,
+    /// manifests, and optionally source headers and dependency licenses.
+    Check(CheckArgs),
+    /// Write a THIRD-PARTY-NOTICES file listing dependencies by license, with the full license texts.
+    Notices(NoticesArgs),
+    /// Report whether the license file still matches the current upstream template text.
+    Outdated(OutdatedArgs),
+    /// Regenerate the license file from the current template, keeping its filled-in values.
+    Upgrade(UpgradeArgs),
+    /// Recover the values an existing license file filled its placeholders with (holder,
+    /// year, project), to save as preferences or record as provenance.
+    Unfill(UnfillArgs),
+    /// Check that source files carry the expected SPDX-License-Identifier header.
+    #[clap(name = "check-headers")]
+    CheckHeaders(CheckHeadersArgs),
+    /// Add an SPDX-License-Identifier header to source files that have none.
+    #[clap(name = "add-headers")]
+    AddHeaders(AddHeadersArgs),
+    /// Rewrite outdated SPDX headers in place: wrong SPDX ID, copyright year, or old holder name.
+    #[clap(name = "fix-headers")]
+    FixHeaders(FixHeadersArgs),
+    /// Manage git hooks that run validate and check-headers before each commit.
+    Hook(HookArgs),
+    /// Manage shorthands for license IDs (e.g. agpl -> agpl-3.0), accepted wherever an ID is.
+    Alias(AliasArgs),
+    /// Download and install the latest getlicense release for this platform.
+    #[cfg(feature = "self-update")]
+    #[clap(name = "self-update")]
+    SelfUpdate(SelfUpdateArgs),
+    /// Any other command runs the getlicense-<command> executable from PATH.
+    #[clap(external_subcommand)]
+    External(Vec<String>),
+}
+
+impl Commands {
+    // Every license ID argument of the command, so aliases can be expanded in one place.
+    // Commands that take a license ID must be listed here.
+    pub fn LicenseIdsMut(&mut self) -> Vec<&mut String> {
+        match self {
+            Commands::List(args) | Commands::DetailedList(args) => {
+                args.licenseIds.iter_mut().flatten().collect()
+            }
+            Commands::Info(InfoArgs { licenseId, .. })
+            | Commands::Show(ShowArgs { licenseId, .. })
+            | Commands::ShowPlaceholders(ShowPlaceholdersArgs { licenseId, .. }) => {
+                licenseId.iter_mut().collect()
+            }
+            Commands::Compare(args) => args
+                .licenseIds
+                .iter_mut()
+                .flatten()
+                .chain(args.baseline.iter_mut())
+                .collect(),
+            Commands::Find(args) => args.similarTo.iter_mut().collect(),
+            Commands::Sync(args) => args.only.iter_mut().collect(),
+            Commands::License(args) => args.licenseIds.iter_mut().collect(),
+            Commands::Export(args) => vec![&mut args.licenseId],
+            Commands::Snippets(args) => args.licenseIds.iter_mut().collect(),
+            Commands::Validate(ValidateArgs { license, .. })
+            | Commands::Outdated(OutdatedArgs { license, .. })
+            | Commands::Upgrade(UpgradeArgs { license, .. })
+            | Commands::Unfill(UnfillArgs { license, .. })
+            | Commands::Check(CheckArgs { license, .. })
+            | Commands::CheckHeaders(CheckHeadersArgs { license, .. })
+            | Commands::AddHeaders(AddHeadersArgs { license, .. })
+            | Commands::FixHeaders(FixHeadersArgs { license, .. }) => license.iter_mut().collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(ClapArgs, Debug)]
 pub struct ListArgs {
     /// SPDX IDs of the licenses to list/detail. Lists all if omitted.
     pub licenseIds: Option<Vec<String>>,
+    /// Order of the listed licenses.
+    #[clap(long, value_enum, default_value = "id")]
+    pub sort: ListSort,
+    /// Only list licenses matching an expression, e.g.
+    /// 'permissions has patent-use and conditions not has same-license'.
+    #[clap(long, value_name = "EXPR")]
+    pub filter: Option<String>,
+    /// Show a table of these columns, in this order, e.g. id,title,permissions-count,category.
+    #[clap(long, value_enum, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Vec<ListColumn>,
+    /// Print the listed columns as a JSON array of objects keyed by column name.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListColumn {
+    Id,
+    Title,
+    Nickname,
+    Description,
+    /// Permissiveness score, 0-100
+    Score,
+    /// Permissive, weak copyleft or strong copyleft
+    Category,
+    Permissions,
+    Conditions,
+    Limitations,
+    PermissionsCount,
+    ConditionsCount,
+    LimitationsCount,
+}
+
+impl ListColumn {
+    // As written on the command line; also the table header and JSON key
+    pub fn Name(self) -> String {
+        clap::ValueEnum::to_possible_value(&self)
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListSort {
+    /// Alphabetically by SPDX ID
+    Id,
+    /// Most permissive first (see permissiveness_weights in the config file)
+    Score,
 }
 
 #[derive(ClapArgs, Debug)]
 pub struct InfoArgs {
-    /// SPDX ID of the license.
-    pub licenseId: String,
+    /// SPDX ID of the license. Omit it in a terminal to pick one interactively.
+    pub licenseId: Option<String>,
+    /// Append the complete license text (placeholders highlighted) below the metadata.
+    #[clap(long)]
+    pub fullText: bool,
+    /// Also show word count, reading time, paragraphs, placeholders and copyleft clauses.
+    #[clap(long)]
+    pub stats: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ShowArgs {
+    /// SPDX ID of the license. Omit it in a terminal to pick one interactively.
+    pub licenseId: Option<String>,
+    /// Number each placeholder and list what it stands for below the text.
+    #[clap(long)]
+    pub annotate: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ShowPlaceholdersArgs {
+    /// SPDX ID of the license. Omit it in a terminal to pick one interactively.
+    pub licenseId: Option<String>,
+    /// Show the value `license` would use for each placeholder right now, and where it
+    /// comes from, without writing anything.
+    #[clap(long)]
+    pub resolve: bool,
 }
 
 #[derive(ClapArgs, Debug)]
 pub struct CompareArgs {
-    /// SPDX IDs of the licenses to compare. Compares all if omitted.
+    /// SPDX IDs of the licenses to compare. Compares all if omitted (and no --files given).
     pub licenseIds: Option<Vec<String>>,
+    /// Local license files to detect and include in the comparison.
+    #[clap(long, value_name = "PATH", num_args = 1..)]
+    pub files: Vec<PathBuf>,
+    /// Also show a text diff of each file against the first one.
+    #[clap(long, requires = "files")]
+    pub diff: bool,
+    /// Show every other license as rules added (+) and dropped (-) relative to this one,
+    /// per category, instead of the table. Compares against all others if no IDs are given.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub baseline: Option<String>,
+    /// Also show a table of text statistics (words, reading time, paragraphs, placeholders,
+    /// copyleft clauses) for each license.
+    #[clap(long)]
+    pub stats: bool,
+    /// Print the comparison as JSON: each license's rules with labels and descriptions, and
+    /// which licenses agree on each rule.
+    #[clap(long, conflicts_with = "diff")]
+    pub json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct NormalizeArgs {
+    /// File containing the license text, or - to read from stdin.
+    #[clap(value_name = "FILE|-", default_value = "-")]
+    pub source: String,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct IdentifyArgs {
+    /// File containing the license text, or - to read from stdin.
+    #[clap(value_name = "FILE|-")]
+    pub source: String,
+    /// Number of candidates to show.
+    #[clap(long, short = 'n', default_value_t = 5)]
+    pub limit: usize,
+    /// Print candidates as a JSON array instead of styled text.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ExportArgs {
+    /// SPDX ID of the license template to export.
+    #[clap(value_name = "SPDX_ID")]
+    pub licenseId: String,
+    /// Destination file, or - for stdout. Defaults to <SPDX_ID>.txt.
+    #[clap(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+    /// Overwrite the destination if it already exists.
+    #[clap(long)]
+    pub force: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ExportAllArgs {
+    /// Directory to write the templates to; created if missing.
+    #[clap(value_name = "DIR")]
+    pub dir: PathBuf,
+    /// Keep the YAML front matter (title, rules, description) at the top of each file.
+    #[clap(long)]
+    pub frontMatter: bool,
+    /// Overwrite files that already exist instead of skipping them.
+    #[clap(long)]
+    pub force: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnippetFormat {
+    /// A VS Code snippets JSON file (e.g. .vscode/license.code-snippets)
+    Vscode,
+    /// One <filetype>.snippets file per language, for UltiSnips
+    Ultisnips,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct SnippetsArgs {
+    /// Licenses to export snippets for. Defaults to every cached license.
+    #[clap(value_name = "SPDX_ID")]
+    pub licenseIds: Vec<String>,
+    #[clap(long, value_enum, default_value = "vscode")]
+    pub format: SnippetFormat,
+    /// File to write VS Code snippets to (default: stdout), or the directory for UltiSnips files.
+    #[clap(long, short = 'o', value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct VendorArgs {
+    /// Directory to write _licenses/ and _data/ into; created if missing.
+    #[clap(value_name = "DIR")]
+    pub dir: PathBuf,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct SyncArgs {
+    /// Keep running and re-sync every --interval, e.g. as a systemd service or launchd agent.
+    #[clap(long, conflicts_with = "resume")]
+    pub daemon: bool,
+    /// Fetch only the files an interrupted or failed sync did not get, reusing its directory listing.
+    #[clap(long)]
+    pub resume: bool,
+    /// Sync only these licenses (comma-separated IDs, e.g. mit,apache-2.0); the cache then holds
+    /// just them. Overrides `only` in the [sync] config table.
+    #[clap(long, value_name = "SPDX_ID", value_delimiter = ',', num_args = 1.., conflicts_with = "resume")]
+    pub only: Vec<String>,
+    /// Time between syncs in daemon mode: a number with s, m, h or d (e.g. 30m, 24h).
+    #[clap(long, value_name = "DURATION", default_value = "24h", value_parser = ParseInterval, requires = "daemon")]
+    pub interval: Duration,
+}
+
+// A bare number is seconds. At least a minute, so a typo cannot hammer the GitHub API.
+fn ParseInterval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("'{}' is not a duration like 30m or 24h", value))?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(format!("unknown unit '{}'; use s, m, h or d", unit)),
+    };
+
+    let interval = Duration::from_secs(number.saturating_mul(multiplier));
+
+    if interval < Duration::from_secs(60) {
+        return Err("the interval must be at least 1m".to_string());
+    }
+
+    Ok(interval)
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ExplainArgs {
+    /// Rule tag as used in rules.yml, e.g. "patent-use" or "disclose-source".
+    pub tag: String,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct UndoArgs {
+    /// Undo even if a file was edited after getlicense wrote it.
+    #[clap(long)]
+    pub force: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct HistoryArgs {
+    /// Number of syncs to show, most recent first.
+    #[clap(long, short = 'n', default_value_t = 10)]
+    pub limit: usize,
+    /// Print the syncs as a JSON array instead of styled text.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct StatsArgs {
+    /// Print the figures as a JSON object instead of styled text.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct LintDataArgs {
+    /// Print the issues as a JSON array instead of styled text.
+    #[clap(long)]
+    pub json: bool,
 }
 
 #[derive(ClapArgs, Debug)]
@@ -94,18 +547,55 @@ pub struct FindArgs {
     /// List of rule tags that MUST NOT be present.
     #[clap(long, value_name = "RULE_TAG", num_args = 1..)]
     pub disallow: Option<Vec<String>>,
+    /// Rank results by how many rules they share with this license (Jaccard over tags).
+    #[clap(long, value_name = "SPDX_ID")]
+    pub similarTo: Option<String>,
+    /// Only keep licenses with at least this permissiveness score (0-100).
+    #[clap(long, value_name = "SCORE", value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub minScore: Option<u8>,
+    /// Only keep results sharing at least this percentage of rules with --similar-to.
+    #[clap(long, value_name = "PERCENT", requires = "similarTo", value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub minSimilarity: Option<u8>,
+    /// Only show the N best matches: fewest conditions beyond --require, then most permissive,
+    /// then most widely used.
+    #[clap(long, value_name = "N")]
+    pub best: Option<usize>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ChooseArgs {
+    /// Skip the questions and recommend from the answers saved by the last run, e.g. after a sync.
+    #[clap(long)]
+    pub fromSaved: bool,
+    /// Read and save answers in this TOML file instead of config.toml, e.g. one shared by a team.
+    #[clap(long, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+    /// Number of recommendations to show.
+    #[clap(long, value_name = "N")]
+    pub best: Option<usize>,
 }
 
 #[derive(ClapArgs, Debug)]
 pub struct LicenseFillArgs {
-    /// SPDX ID of the license template to fill (case-insensitive).
-    pub licenseId: String,
+    /// SPDX IDs of the license templates to fill (case-insensitive). Several IDs write one
+    /// file each into LICENSES/. Omit them in a terminal to pick one interactively.
+    #[clap(value_name = "SPDX_ID")]
+    pub licenseIds: Vec<String>,
     /// Full name of the copyright holder.
     #[clap(short = 'f', long)]
     pub fullname: Option<String>,
     /// Copyright year. Defaults to current year (not saved in preferences).
     #[clap(short = 'y', long)]
     pub year: Option<String>,
+    /// Derive the year from the project instead of the clock: the first to the latest
+    /// commit (git), or the newest modification time (mtime). With --year, the derived
+    /// year ends a range starting at --year.
+    #[clap(long, value_enum, value_name = "SOURCE")]
+    pub yearFrom: Option<YearSource>,
+    /// File or directory --year-from looks at (the repository for git). Defaults to the
+    /// current directory.
+    #[clap(long, value_name = "PATH", requires = "yearFrom")]
+    pub yearPath: Option<PathBuf>,
     /// Project name.
     #[clap(short = 'p', long)]
     pub project: Option<String>,
@@ -115,9 +605,53 @@ pub struct LicenseFillArgs {
     /// Project URL.
     #[clap(short = 'u', long)]
     pub projecturl: Option<String>,
-    /// Output file path. Defaults to 'LICENSE'.
+    /// GitHub username or organization, for the [login] placeholder.
+    #[clap(long)]
+    pub login: Option<String>,
+    /// Output file path. Defaults to an existing COPYING/LICENSE.txt/LICENCE/... in the
+    /// current directory, otherwise 'LICENSE'.
     #[clap(short = 'o', long, value_name = "OUTPUT_PATH")]
     pub output: Option<PathBuf>,
+    /// File name to write in the current directory, ignoring any existing license file name.
+    #[clap(long, value_name = "NAME", conflicts_with = "output")]
+    pub filename: Option<String>,
+    /// With several IDs, write LICENSE-<SPDX_ID> files in the current directory instead of
+    /// LICENSES/<SPDX_ID>.txt.
+    #[clap(long, conflicts_with_all = ["output", "filename"])]
+    pub suffixed: bool,
+    /// Remove placeholders that have no value (and any empty brackets or lines they leave)
+    /// instead of keeping them in the output.
+    #[clap(long)]
+    pub stripUnfilled: bool,
+    /// Ask for each placeholder value the template uses, offering the best-known value (flag,
+    /// saved preference, git config, ...) as the default. Answers are saved as preferences.
+    #[clap(short = 'i', long)]
+    pub interactive: bool,
+    /// For GPL, AGPL and LGPL: append the "How to Apply These Terms" notice, filled in with
+    /// the project name, --description, year and holder. Missing values are asked for in a
+    /// terminal.
+    #[clap(long)]
+    pub withAppendix: bool,
+    /// One-line description of the project, for the [description] placeholder and the
+    /// --with-appendix notice.
+    #[clap(short = 'd', long, value_name = "TEXT")]
+    pub description: Option<String>,
+    /// Write the --with-appendix notice to this file instead of appending it to the license.
+    #[clap(long, value_name = "PATH", requires = "withAppendix")]
+    pub appendixOutput: Option<PathBuf>,
+    /// Record the SPDX ID, template SHA, date and values used in .getlicense-provenance.toml
+    /// next to the output, so outdated, upgrade and validate can work from exact data.
+    #[clap(long)]
+    pub provenance: bool,
+    /// Fill the current template even where getlicense.lock pins an older version, and pin
+    /// that. Creates getlicense.lock in the current directory if there is none.
+    #[clap(long)]
+    pub updateLock: bool,
+    /// Write the translation in this language (e.g. de) instead of the English text, from the
+    /// [translations] sources in config.toml or the official EUPL-1.2 translations. Fetched
+    /// once, then kept in the cache.
+    #[clap(long, value_name = "LANG", requires = "licenseIds")]
+    pub lang: Option<String>,
 }
 
 #[derive(ClapArgs, Debug)]
@@ -134,6 +668,9 @@ pub struct GetPlaceholderArgs {
     /// The placeholder key to retrieve. Shows all if omitted.
     #[clap(value_parser = clap::builder::PossibleValuesParser::new(CACHABLE_PLACEHOLDER_KEYS_ARRAY).map(|s| s.to_string()))]
     pub key: Option<String>,
+    /// Print the saved value(s) as a JSON object instead of styled text.
+    #[clap(long)]
+    pub json: bool,
 }
 
 #[derive(ClapArgs, Debug)]
@@ -142,3 +679,231 @@ pub struct ClearPlaceholdersArgs {
     #[clap(value_parser = clap::builder::PossibleValuesParser::new(CACHABLE_PLACEHOLDER_KEYS_ARRAY).map(|s| s.to_string()))]
     pub keys: Option<Vec<String>>,
 }
+
+#[derive(ClapArgs, Debug)]
+pub struct ImportPlaceholdersArgs {
+    /// Path to the Cargo manifest to read project metadata from.
+    #[clap(long, value_name = "PATH", default_value = "Cargo.toml")]
+    pub manifestPath: PathBuf,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ValidateArgs {
+    /// License file to check. Defaults to LICENSE (or LICENSE.md, LICENSE.txt, COPYING) in the current directory.
+    pub path: Option<PathBuf>,
+    /// Also fail unless the file contains this license (SPDX ID, case-insensitive).
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct OutdatedArgs {
+    /// License file to check. Defaults to LICENSE (or LICENSE.md, LICENSE.txt, COPYING) in the current directory.
+    pub path: Option<PathBuf>,
+    /// Template to compare against (SPDX ID). Defaults to the closest matching template.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct UpgradeArgs {
+    /// License file to upgrade. Defaults to LICENSE (or LICENSE.md, LICENSE.txt, COPYING) in the current directory.
+    pub path: Option<PathBuf>,
+    /// Template to upgrade to (SPDX ID). Defaults to the closest matching template.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+    /// Write without asking for confirmation after showing the diff.
+    #[clap(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct UnfillArgs {
+    /// License file to read. Defaults to LICENSE (or LICENSE.md, LICENSE.txt, COPYING) in the current directory.
+    pub path: Option<PathBuf>,
+    /// Template to align the file against (SPDX ID). Defaults to the closest matching template.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+    /// Save the recovered values (except the year) as placeholder preferences without asking.
+    #[clap(long)]
+    pub save: bool,
+    /// Record the license and recovered values in .getlicense-provenance.toml without asking.
+    #[clap(long)]
+    pub provenance: bool,
+    /// Print the recovered values as JSON instead of styled text. Nothing is asked.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct CheckArgs {
+    /// Also fail unless the license file contains this license (SPDX ID, case-insensitive).
+    /// Source headers are checked against it too.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+    /// Check source file headers, like check-headers. Same as `headers = true` under [check] in .getlicense.toml.
+    #[clap(long)]
+    pub headers: bool,
+    /// Check dependency licenses against approved_licenses and banned_licenses. Same as
+    /// `dependencies = true` under [check] in .getlicense.toml.
+    #[clap(long)]
+    pub dependencies: bool,
+    /// Print the results as a JSON array instead of styled text.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct VerifyArgs {
+    /// Project directory containing the license file and manifests.
+    #[clap(default_value = ".")]
+    pub projectDir: PathBuf,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct NoticesArgs {
+    /// Project directory whose dependencies are listed.
+    #[clap(default_value = ".")]
+    pub projectDir: PathBuf,
+    /// Where to write the notices, or - for stdout. Defaults to THIRD-PARTY-NOTICES in the project directory.
+    #[clap(long, short = 'o', value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct CheckHeadersArgs {
+    /// Files or directories to check. Directories are searched recursively for source files.
+    #[clap(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+    /// Expected SPDX ID. Defaults to the license detected in the project's license file.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+    /// Summarize header coverage per directory and per language instead of listing
+    /// failures. Always exits successfully.
+    #[clap(long)]
+    pub report: bool,
+    /// Print the coverage report as JSON, e.g. for dashboards.
+    #[clap(long, requires = "report")]
+    pub json: bool,
+    /// Number of threads that walk directories and read files. Defaults to one per CPU.
+    #[clap(long, short = 'j', value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: Option<u16>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct AddHeadersArgs {
+    /// Files or directories to update. Directories are searched recursively for source files.
+    #[clap(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+    /// SPDX expression to write. Defaults to the license detected in the project's license file.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+    /// Header style. Defaults to [headers] style/styles in .getlicense.toml, else spdx.
+    #[clap(long, value_enum)]
+    pub style: Option<HeaderStyle>,
+    /// Also add a "Copyright (c) <year> <fullname>" line.
+    #[clap(long)]
+    pub copyright: bool,
+    /// Copyright holder for --copyright. Defaults to the project or saved fullname.
+    #[clap(long)]
+    pub fullname: Option<String>,
+    /// List the files that would get a header without changing them.
+    #[clap(long)]
+    pub dryRun: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct FixHeadersArgs {
+    /// Files or directories to fix. Directories are searched recursively for source files.
+    #[clap(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+    /// Correct SPDX expression. Defaults to the license detected in the project's license file.
+    #[clap(long, value_name = "SPDX_ID")]
+    pub license: Option<String>,
+    /// Copyright holder to replace, e.g. a former company name. Can be repeated.
+    #[clap(long, value_name = "NAME")]
+    pub oldHolder: Vec<String>,
+    /// Replacement for --old-holder. Defaults to the project or saved fullname.
+    #[clap(long)]
+    pub fullname: Option<String>,
+    /// Show the changes as diffs without writing them.
+    #[clap(long)]
+    pub dryRun: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct AliasArgs {
+    #[clap(subcommand)]
+    pub command: AliasCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommands {
+    /// Define or replace an alias.
+    Add(AliasAddArgs),
+    /// Remove an alias.
+    Remove(AliasRemoveArgs),
+    /// List the defined aliases.
+    List,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct AliasAddArgs {
+    /// The shorthand, e.g. "corp".
+    pub name: String,
+    /// The license ID it stands for.
+    #[clap(value_name = "SPDX_ID")]
+    pub licenseId: String,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct AliasRemoveArgs {
+    pub name: String,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct HookArgs {
+    #[clap(subcommand)]
+    pub command: HookCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HookCommands {
+    /// Write a pre-commit hook that runs validate and check-headers.
+    Install(HookInstallArgs),
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct HookInstallArgs {
+    /// Replace an existing pre-commit hook or .pre-commit-config.yaml.
+    #[clap(long)]
+    pub force: bool,
+    /// Write a .pre-commit-config.yaml for the pre-commit framework instead of a git hook.
+    #[clap(long = "pre-commit")]
+    pub preCommitFramework: bool,
+    /// Only validate the license file; do not check source file headers.
+    #[clap(long)]
+    pub skipHeaders: bool,
+}
+
+#[cfg(feature = "self-update")]
+#[derive(ClapArgs, Debug)]
+pub struct SelfUpdateArgs {
+    /// Only report whether a newer release exists; do not download it.
+    #[clap(long)]
+    pub check: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestParseInterval() {
+        assert_eq!(ParseInterval("24h"), Ok(Duration::from_secs(86400)));
+        assert_eq!(ParseInterval("90"), Ok(Duration::from_secs(90)));
+        assert!(ParseInterval("30s").is_err());
+        assert!(ParseInterval("2w").is_err());
+        assert!(ParseInterval("h").is_err());
+    }
+}
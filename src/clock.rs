@@ -0,0 +1,95 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use once_cell::sync::OnceCell;
+use std::env;
+
+// Set by --now; wins over SOURCE_DATE_EPOCH
+static NOW_OVERRIDE: OnceCell<DateTime<Utc>> = OnceCell::new();
+
+// Where "now" comes from for anything written into generated files (copyright years,
+// notice dates, provenance timestamps).
+pub trait Clock {
+    fn Now(&self) -> DateTime<Utc>;
+
+    fn Year(&self) -> i32 {
+        self.Now().year()
+    }
+}
+
+// The system clock, unless --now or SOURCE_DATE_EPOCH pins the time for a reproducible build.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for SystemClock {
+    fn Now(&self) -> DateTime<Utc> {
+        PinnedNow().unwrap_or_else(Utc::now)
+    }
+
+    // Without a pinned time the year follows the local calendar, as users expect on New Year's Eve
+    fn Year(&self) -> i32 {
+        PinnedNow().map(|now| now.year()).unwrap_or_else(|| chrono::Local::now().year())
+    }
+}
+
+impl Clock for FixedClock {
+    fn Now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+fn PinnedNow() -> Option<DateTime<Utc>> {
+    NOW_OVERRIDE.get().copied().or_else(|| {
+        env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+    })
+}
+
+// Called once from main for --now; later calls are ignored.
+pub fn SetNow(now: DateTime<Utc>) {
+    let _ = NOW_OVERRIDE.set(now);
+}
+
+pub fn Now() -> DateTime<Utc> {
+    SystemClock.Now()
+}
+
+pub fn CurrentYear() -> i32 {
+    SystemClock.Year()
+}
+
+// Accepts an RFC 3339 timestamp, a YYYY-MM-DD date (midnight UTC) or Unix seconds.
+pub fn ParseNow(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    value
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .ok_or_else(|| format!("'{}' is not an RFC 3339 timestamp, YYYY-MM-DD date or Unix time", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestParseNow() {
+        assert_eq!(ParseNow("2021-03-04").unwrap().year(), 2021);
+        assert_eq!(ParseNow("1700000000").unwrap().year(), 2023);
+        assert_eq!(ParseNow("2019-12-31T23:30:00-02:00").unwrap().year(), 2020);
+        assert!(ParseNow("last tuesday").is_err());
+
+        assert_eq!(FixedClock(ParseNow("1999-06-01").unwrap()).Year(), 1999);
+    }
+}
@@ -0,0 +1,452 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use crate::constants::{
+    CONFIG_DIR_NAME, DEFAULT_CONFIG_FILENAME, EUPL_TRANSLATION_LANGUAGES, EUPL_TRANSLATION_URL_TEMPLATE,
+    PROJECT_CONFIG_FILENAME,
+};
+use crate::error::ConfigError;
+use crate::resolve::{DEFAULT_PLACEHOLDER_PRECEDENCE, PlaceholderSource};
+
+// User-level settings, read from config.toml. Every field is optional so an
+// empty or missing file behaves exactly like the built-in defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Config {
+    // Highest priority first. Sources omitted here are never consulted.
+    #[serde(rename = "placeholder_precedence", skip_serializing_if = "Option::is_none")]
+    pub placeholderPrecedence: Option<Vec<PlaceholderSource>>,
+    // Profile used when --profile is not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    // Profile name -> standard placeholder key -> value
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, HashMap<String, String>>,
+    pub network: NetworkConfig,
+    pub github: GithubConfig,
+    // Named caches selected with --cache-profile; names without an entry still get their own file
+    #[serde(rename = "cache_profiles", skip_serializing_if = "HashMap::is_empty")]
+    pub cacheProfiles: HashMap<String, CacheProfileConfig>,
+    // Set to false to disable the daily "newer version available" notice
+    #[serde(rename = "update_check", skip_serializing_if = "Option::is_none")]
+    pub updateCheck: Option<bool>,
+    // Remote TOML with organization-wide policy and defaults (see OrgConfig)
+    #[serde(rename = "org_config_url", skip_serializing_if = "Option::is_none")]
+    pub orgConfigUrl: Option<String>,
+    // Filled from org_config_url at startup, never read from config.toml itself
+    #[serde(skip)]
+    pub org: OrgConfig,
+    // Replaces individual entries of the built-in permissiveness weights
+    #[serde(rename = "permissiveness_weights")]
+    pub permissivenessWeights: PermissivenessWeightsConfig,
+    // Shorthand -> license ID, managed with `getlicense alias`; matched case-insensitively
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub aliases: HashMap<String, String>,
+    // Request host -> where its token comes from, e.g. [auth."github.example.com"] token_env = "GHE_TOKEN"
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub auth: HashMap<String, AuthConfig>,
+    // Last answers of the `choose` questionnaire, reused by `choose --from-saved`
+    pub choose: ChooseAnswers,
+    pub sync: SyncConfig,
+    // License ID -> language code -> URL or path of the translated text, used by `license --lang`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub translations: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SyncConfig {
+    // License IDs every sync is limited to, like `sync --only`; empty syncs them all
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<String>,
+}
+
+// Answers to the `choose` questions. None means no preference.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ChooseAnswers {
+    // May others ship the code in closed-source products?
+    #[serde(rename = "closed_source_use", skip_serializing_if = "Option::is_none")]
+    pub closedSourceUse: Option<bool>,
+    // Should the license grant patent rights explicitly?
+    #[serde(rename = "patent_grant", skip_serializing_if = "Option::is_none")]
+    pub patentGrant: Option<bool>,
+    // Should offering the software over a network count as distributing it?
+    #[serde(rename = "network_copyleft", skip_serializing_if = "Option::is_none")]
+    pub networkCopyleft: Option<bool>,
+    // Must modified files state what was changed?
+    #[serde(rename = "document_changes", skip_serializing_if = "Option::is_none")]
+    pub documentChanges: Option<bool>,
+}
+
+impl ChooseAnswers {
+    pub fn IsEmpty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// Credential sources for one host, tried in this order until one yields a token.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AuthConfig {
+    // Name of the environment variable holding the token
+    #[serde(rename = "token_env", skip_serializing_if = "Option::is_none")]
+    pub tokenEnv: Option<String>,
+    // Service of an OS keyring entry (macOS Keychain, or Secret Service via secret-tool)
+    #[serde(rename = "keyring_service", skip_serializing_if = "Option::is_none")]
+    pub keyringService: Option<String>,
+    // Account of that entry; defaults to the host
+    #[serde(rename = "keyring_account", skip_serializing_if = "Option::is_none")]
+    pub keyringAccount: Option<String>,
+    // The password of the host's machine entry in ~/.netrc (or $NETRC)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub netrc: bool,
+}
+
+// Category -> rule tag -> weight, e.g. [permissiveness_weights.conditions] disclose-source = -50
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PermissivenessWeightsConfig {
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub permissions: HashMap<String, i32>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub conditions: HashMap<String, i32>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub limitations: HashMap<String, i32>,
+}
+
+// Shared policy fetched from org_config_url so every developer gets the same defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct OrgConfig {
+    // When non-empty, licenses outside this list get a warning
+    #[serde(rename = "approved_licenses", skip_serializing_if = "Vec::is_empty")]
+    pub approvedLicenses: Vec<String>,
+    // Refused by `license` and flagged in `notices`
+    #[serde(rename = "banned_licenses", skip_serializing_if = "Vec::is_empty")]
+    pub bannedLicenses: Vec<String>,
+    // Copyright holder used when no other source provides a fullname
+    #[serde(rename = "default_holder", skip_serializing_if = "Option::is_none")]
+    pub defaultHolder: Option<String>,
+}
+
+impl OrgConfig {
+    pub fn IsBanned(&self, spdxId: &str) -> bool {
+        self.bannedLicenses.iter().any(|id| id.eq_ignore_ascii_case(spdxId))
+    }
+
+    pub fn IsApproved(&self, spdxId: &str) -> bool {
+        self.approvedLicenses.is_empty()
+            || self.approvedLicenses.iter().any(|id| id.eq_ignore_ascii_case(spdxId))
+    }
+}
+
+// Where templates are synced from. Defaults to github.com/github/choosealicense.com.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct GithubConfig {
+    // GitHub Enterprise hostname, e.g. "github.example.com"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    // Explicit overrides when the GHE layout differs from <host>/api/v3 and <host>/raw
+    #[serde(rename = "api_base_url", skip_serializing_if = "Option::is_none")]
+    pub apiBaseUrl: Option<String>,
+    #[serde(rename = "raw_base_url", skip_serializing_if = "Option::is_none")]
+    pub rawBaseUrl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+// A cache kept apart from the default one, optionally synced from a different repository.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CacheProfileConfig {
+    // Defaults to license_cache_rs.<name>.json next to the default cache
+    #[serde(rename = "cache_file", skip_serializing_if = "Option::is_none")]
+    pub cacheFile: Option<PathBuf>,
+    // Replaces the top-level [github] section for this profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github: Option<GithubConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct NetworkConfig {
+    // Seconds
+    #[serde(rename = "connect_timeout", skip_serializing_if = "Option::is_none")]
+    pub connectTimeout: Option<u64>,
+    // Seconds, bounding each request from connect to the end of the body
+    #[serde(rename = "request_timeout", skip_serializing_if = "Option::is_none")]
+    pub requestTimeout: Option<u64>,
+    // URL templates tried in order when the raw host fails, e.g.
+    // "https://cdn.jsdelivr.net/gh/{owner}/{repo}@{branch}/{path}"
+    #[serde(rename = "raw_mirrors", skip_serializing_if = "Vec::is_empty")]
+    pub rawMirrors: Vec<String>,
+    // Size limit for the on-disk cache of raw downloads; 0 disables it
+    #[serde(rename = "http_cache_max_mb", skip_serializing_if = "Option::is_none")]
+    pub httpCacheMaxMb: Option<u64>,
+}
+
+fn DefaultTranslationSource(spdxId: &str, lang: &str) -> Option<String> {
+    let lang = lang.to_lowercase();
+    (spdxId.eq_ignore_ascii_case("eupl-1.2") && EUPL_TRANSLATION_LANGUAGES.contains(&lang.as_str()))
+        .then(|| EUPL_TRANSLATION_URL_TEMPLATE.replace("{LANG}", &lang.to_uppercase()))
+}
+
+impl Config {
+    // The configured source, else a built-in one for translations with a known official home
+    pub fn TranslationSource(&self, spdxId: &str, lang: &str) -> Option<String> {
+        self.translations
+            .iter()
+            .find(|(id, _)| id.eq_ignore_ascii_case(spdxId))
+            .and_then(|(_, byLang)| byLang.iter().find(|(code, _)| code.eq_ignore_ascii_case(lang)))
+            .map(|(_, source)| source.clone())
+            .or_else(|| DefaultTranslationSource(spdxId, lang))
+    }
+
+    pub fn PlaceholderPrecedence(&self) -> Vec<PlaceholderSource> {
+        self.placeholderPrecedence
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PLACEHOLDER_PRECEDENCE.to_vec())
+    }
+
+    // An explicitly requested profile must exist; the configured default may be absent.
+    pub fn ProfilePlaceholders(
+        &self,
+        requestedProfile: Option<&str>,
+    ) -> Result<HashMap<String, String>, ConfigError> {
+        match requestedProfile.or(self.profile.as_deref()) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ConfigError::UnknownProfile(name.to_string())),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    // Cache file and template source for --cache-profile NAME
+    pub fn ResolveAlias(&self, id: &str) -> Option<&str> {
+        self.aliases
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(id))
+            .map(|(_, target)| target.as_str())
+    }
+
+    pub fn CacheProfile(&self, name: &str, defaultCachePath: &Path) -> Result<(PathBuf, &GithubConfig), ConfigError> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(ConfigError::InvalidCacheProfile(name.to_string()));
+        }
+
+        let profile = self.cacheProfiles.get(name);
+        let cachePath = profile
+            .and_then(|p| p.cacheFile.clone())
+            .unwrap_or_else(|| ProfileCachePath(defaultCachePath, name));
+        let github = profile.and_then(|p| p.github.as_ref()).unwrap_or(&self.github);
+
+        Ok((cachePath, github))
+    }
+}
+
+// license_cache_rs.json -> license_cache_rs.<name>.json, so the bodies directory is separate too
+pub fn ProfileCachePath(defaultCachePath: &Path, name: &str) -> PathBuf {
+    let stem = defaultCachePath
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    defaultCachePath.with_file_name(format!("{}.{}.json", stem, name))
+}
+
+// What `add-headers` writes at the top of a source file
+#[derive(Serialize, Deserialize, clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderStyle {
+    // Only the SPDX-License-Identifier line (plus the copyright line if requested)
+    #[default]
+    Spdx,
+    // The SPDX line followed by a short paragraph naming the license and the LICENSE file
+    Notice,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct HeadersConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<HeaderStyle>,
+    // File extension (without the dot) -> style, overriding `style`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub styles: HashMap<String, HeaderStyle>,
+    // Add a "Copyright (c) <year> <fullname>" line below the SPDX line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<bool>,
+    // Globs in .gitignore syntax, relative to the project directory, that header commands skip
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl HeadersConfig {
+    pub fn StyleFor(&self, path: &Path) -> HeaderStyle {
+        path.extension()
+            .and_then(|ext| self.styles.get(&ext.to_string_lossy().to_lowercase()))
+            .copied()
+            .or(self.style)
+            .unwrap_or_default()
+    }
+}
+
+// Project-level file (.getlicense.toml) committed alongside the code.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ProjectConfig {
+    pub placeholders: HashMap<String, String>,
+    pub headers: HeadersConfig,
+    pub check: CheckConfig,
+}
+
+// Optional steps `check` runs without being asked on the command line.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CheckConfig {
+    // Every source file carries the project's SPDX header
+    pub headers: bool,
+    // Dependency licenses follow approved_licenses/banned_licenses
+    pub dependencies: bool,
+}
+
+pub fn DefaultConfigPath() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(CONFIG_DIR_NAME)
+        .join(DEFAULT_CONFIG_FILENAME)
+}
+
+fn LoadTomlFile<T: for<'de> Deserialize<'de> + Default>(path: &Path) -> Result<T, ConfigError> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| ConfigError::Io(e, path.to_path_buf()))?;
+
+    toml::from_str(&content).map_err(|e| ConfigError::Parse(e, path.to_path_buf()))
+}
+
+pub fn LoadConfig(configPath: &Path) -> Result<Config, ConfigError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Config] Loading config from {:?}", configPath);
+    }
+
+    LoadTomlFile(configPath)
+}
+
+// Rewrites only the [aliases] table; other settings are kept, comments are not
+pub fn SaveAliases(configPath: &Path, aliases: &HashMap<String, String>) -> Result<(), ConfigError> {
+    let table: toml::Table = aliases
+        .iter()
+        .map(|(alias, target)| (alias.clone(), toml::Value::String(target.clone())))
+        .collect();
+
+    SaveTable(configPath, "aliases", table)
+}
+
+// Rewrites only the [choose] table, like SaveAliases
+pub fn SaveChooseAnswers(configPath: &Path, answers: &ChooseAnswers) -> Result<(), ConfigError> {
+    let table = toml::Table::try_from(answers).map_err(|e| ConfigError::Serialize(e, configPath.to_path_buf()))?;
+
+    SaveTable(configPath, "choose", table)
+}
+
+// A shared answers file holds just the [choose] keys, without the table header
+pub fn LoadChooseAnswers(answersPath: &Path) -> Result<ChooseAnswers, ConfigError> {
+    let content = fs::read_to_string(answersPath).map_err(|e| ConfigError::Io(e, answersPath.to_path_buf()))?;
+
+    toml::from_str(&content).map_err(|e| ConfigError::Parse(e, answersPath.to_path_buf()))
+}
+
+pub fn SaveChooseAnswersFile(answersPath: &Path, answers: &ChooseAnswers) -> Result<(), ConfigError> {
+    let content = toml::to_string(answers).map_err(|e| ConfigError::Serialize(e, answersPath.to_path_buf()))?;
+
+    fs::write(answersPath, content).map_err(|e| ConfigError::Io(e, answersPath.to_path_buf()))
+}
+
+// Replaces one top-level table of config.toml, removing it when empty
+fn SaveTable(configPath: &Path, key: &str, table: toml::Table) -> Result<(), ConfigError> {
+    let mut document: toml::Table = LoadTomlFile(configPath)?;
+
+    if table.is_empty() {
+        document.remove(key);
+    } else {
+        document.insert(key.to_string(), toml::Value::Table(table));
+    }
+
+    let content = toml::to_string(&document).map_err(|e| ConfigError::Serialize(e, configPath.to_path_buf()))?;
+
+    if let Some(parent) = configPath.parent() {
+        fs::create_dir_all(parent).map_err(|e| ConfigError::Io(e, parent.to_path_buf()))?;
+    }
+
+    fs::write(configPath, content).map_err(|e| ConfigError::Io(e, configPath.to_path_buf()))
+}
+
+pub fn LoadProjectConfig(projectDir: &Path) -> Result<ProjectConfig, ConfigError> {
+    let projectConfigPath = projectDir.join(PROJECT_CONFIG_FILENAME);
+
+    if crate::VERBOSE.load(Ordering::SeqCst) && projectConfigPath.exists() {
+        eprintln!("[Config] Using project file {:?}", projectConfigPath);
+    }
+
+    LoadTomlFile(&projectConfigPath)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestConfiguredTranslationOverridesBuiltIn() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.TranslationSource("EUPL-1.2", "DE").as_deref(),
+            Some("https://joinup.ec.europa.eu/sites/default/files/custom-page/attachment/2020-03/EUPL-1.2%20DE.txt")
+        );
+        assert_eq!(config.TranslationSource("eupl-1.2", "ja"), None);
+        assert_eq!(config.TranslationSource("mit", "de"), None);
+
+        config.translations.insert("eupl-1.2".to_string(), HashMap::from([("de".to_string(), "legal/de.txt".to_string())]));
+        assert_eq!(config.TranslationSource("eupl-1.2", "de").as_deref(), Some("legal/de.txt"));
+        assert!(config.TranslationSource("eupl-1.2", "fr").is_some());
+    }
+
+    #[test]
+    fn TestSaveAliasesKeepsOtherSettings() {
+        let dir = TempDir::New("aliases");
+        let configPath = dir.join("config.toml");
+        fs::write(&configPath, "profile = \"work\"\n\n[network]\nconnect_timeout = 5\n").unwrap();
+
+        let aliases = HashMap::from([("agpl".to_string(), "agpl-3.0".to_string())]);
+        SaveAliases(&configPath, &aliases).unwrap();
+        let config = LoadConfig(&configPath).unwrap();
+
+        assert_eq!(config.profile.as_deref(), Some("work"));
+        assert_eq!(config.network.connectTimeout, Some(5));
+        assert_eq!(config.ResolveAlias("AGPL"), Some("agpl-3.0"));
+
+        SaveAliases(&configPath, &HashMap::new()).unwrap();
+        assert!(LoadConfig(&configPath).unwrap().aliases.is_empty());
+
+        let answers = ChooseAnswers { closedSourceUse: Some(false), patentGrant: Some(true), ..Default::default() };
+        SaveChooseAnswers(&configPath, &answers).unwrap();
+        let config = LoadConfig(&configPath).unwrap();
+
+        assert_eq!(config.choose, answers);
+        assert_eq!(config.profile.as_deref(), Some("work"));
+    }
+}
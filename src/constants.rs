@@ -1,17 +1,63 @@
 // --- GitHub API ---
 pub const GITHUB_API_BASE_URL: &str = "https://api.github.com";
+pub const RAW_CONTENT_BASE_URL: &str = "https://raw.githubusercontent.com";
+pub const GITHUB_PUBLIC_HOST: &str = "github.com";
+// Covered by an [auth."github.com"] entry along with github.com itself
+pub const GITHUB_PUBLIC_SERVICE_HOSTS: [&str; 2] = ["api.github.com", "raw.githubusercontent.com"];
+// Relative to https://<host> on GitHub Enterprise Server
+pub const GHE_API_PATH: &str = "/api/v3";
+pub const GHE_RAW_PATH: &str = "/raw";
 pub const GITHUB_API_VERSION_HEADER: &str = "application/vnd.github.v3+json";
 pub const APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 pub const OWNER_CONST: &str = "github";
 pub const REPO_CONST: &str = "choosealicense.com";
 pub const BRANCH_CONST: &str = "gh-pages";
+// Public page for a license, followed by its lowercase SPDX ID and "/"
+pub const CHOOSEALICENSE_LICENSE_URL: &str = "https://choosealicense.com/licenses/";
+// Base for site-relative links ("/licenses/mit/") in descriptions from the data files
+pub const CHOOSEALICENSE_SITE_URL: &str = "https://choosealicense.com";
+
+// Seconds; overridable via --connect-timeout/--request-timeout, env, or [network] in config
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+// Where getlicense's own release binaries are published
+pub const SELF_RELEASE_OWNER: &str = "kgruiz";
+pub const SELF_RELEASE_REPO: &str = "getlicense";
+// Minimum time between automatic "newer version available" checks
+pub const UPDATE_CHECK_INTERVAL_HOURS: i64 = 24;
+pub const UPDATE_CHECK_TIMEOUT_SECS: u64 = 3;
+// Release assets are named "getlicense-<arch>-<os>[.exe]" with checksums in this file
+#[cfg(feature = "self-update")]
+pub const RELEASE_CHECKSUMS_ASSET: &str = "SHA256SUMS";
 
 pub const LICENSES_PATH_STR: &str = "_licenses";
 pub const DATA_PATH_STR: &str = "_data";
 
 // --- Cache ---
 pub const DEFAULT_CACHE_FILENAME: &str = "license_cache_rs.json";
+// Saved placeholder preferences, stored next to the cache file by default
+pub const DEFAULT_PLACEHOLDERS_FILENAME: &str = "getlicense_placeholders.json";
+
+// --- Config ---
+// Resolved relative to the platform config directory (e.g. ~/.config/getlicense/config.toml)
+pub const CONFIG_DIR_NAME: &str = "getlicense";
+pub const DEFAULT_CONFIG_FILENAME: &str = "config.toml";
+// `getlicense foo` runs a `getlicense-foo` executable from PATH when foo is not built in
+pub const PLUGIN_PREFIX: &str = "getlicense-";
+// Last copy of the organization config fetched from org_config_url, next to config.toml
+pub const ORG_CONFIG_CACHE_FILENAME: &str = "org_config.toml";
+pub const ORG_CONFIG_REFRESH_HOURS: u64 = 24;
+// Raw template downloads kept next to the cache file, evicted least recently used first
+pub const DEFAULT_HTTP_CACHE_MAX_MB: u64 = 32;
+// A sync writes the cache after this many fetched licenses, so an interrupted one resumes
+pub const SYNC_CHECKPOINT_INTERVAL: usize = 10;
+// Body files no cache entry references are only deleted once this old, so another process
+// sharing the bodies directory never loses a body it is about to read or has just written
+pub const UNREFERENCED_BODY_GRACE_HOURS: u64 = 24;
+// Per-project placeholder values, looked up in the current working directory
+pub const PROJECT_CONFIG_FILENAME: &str = ".getlicense.toml";
 
 // Specific data file keys (used to access them in the cache.data_files HashMap)
 pub const RULES_YML_KEY: &str = "data:rules.yml";
@@ -20,46 +66,53 @@ pub const FIELDS_YML_KEY: &str = "data:fields.yml";
 // --- Placeholder Management ---
 // Standardized keys used internally for the user_placeholders cache and for CLI arg mapping.
 // 'year' is intentionally excluded as it's not cached with user preferences.
-pub const CACHABLE_PLACEHOLDER_KEYS: [&str; 4] = [
+pub const CACHABLE_PLACEHOLDER_KEYS: [&str; 5] = [
     "fullname",
     "project",
     "email",
     "projecturl",
+    "login",
 ];
-pub const CACHABLE_PLACEHOLDER_KEYS_ARRAY: [&str; 4] = CACHABLE_PLACEHOLDER_KEYS;
+pub const CACHABLE_PLACEHOLDER_KEYS_ARRAY: [&str; 5] = CACHABLE_PLACEHOLDER_KEYS;
 
 
 // --- Mappings ---
 
 // Maps CLI argument names to standardized cache keys
-pub const CLI_ARG_TO_CACHE_KEY_TUPLES: [(&str, &str); 4] = [
+pub const CLI_ARG_TO_CACHE_KEY_TUPLES: [(&str, &str); 5] = [
     // clap arg dest name -> standard cache key
     ("fullname", "fullname"),
     ("project", "project"),
     ("email", "email"),
     ("projecturl", "projecturl"),
+    ("login", "login"),
 ];
 
 // Maps raw placeholder strings (found in license templates, keys are lowercased for matching)
 // to standardized internal keys.
-pub const RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES: [(&str, &str); 8] = [
+pub const RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES: [(&str, &str); 11] = [
     ("fullname", "fullname"),
     ("name of copyright owner", "fullname"),
-    ("login", "fullname"),
+    // Used inside SPDX template markup, e.g. original="Copyright (c) <year> <copyright holders>"
+    ("copyright holders", "fullname"),
+    ("copyright holder", "fullname"),
+    // A GitHub username, which is not necessarily the legal copyright holder
+    ("login", "login"),
     ("project", "project"),
     ("email", "email"),
     ("projecturl", "projecturl"),
     ("year", "year"),
     ("yyyy", "year"),
+    ("description", "description"),
 ];
 
 // Map standard placeholder keys to command-line argument suggestions
 pub const PLACEHOLDER_TO_ARG_MAP_TUPLES: [(&str, &str); 9] = [
     ("fullname", "--fullname"),
-    ("login", "--fullname (recommended for user/org name)"),
+    ("login", "--login"),
     ("email", "--email"),
     ("project", "--project"),
-    ("description", "(no direct argument for '[description]')"),
+    ("description", "--description"),
     ("year", "--year"),
     ("projecturl", "--projecturl"),
     ("yyyy", "--year"),
@@ -67,6 +120,19 @@ pub const PLACEHOLDER_TO_ARG_MAP_TUPLES: [(&str, &str); 9] = [
 ];
 
 
+// Columns used for wrapped text when the terminal width is unknown
+pub const DEFAULT_WRAP_WIDTH: usize = 80;
+
+// Recommendations `choose` shows when --best is not given
+pub const DEFAULT_CHOOSE_RESULTS: usize = 3;
+// Used for long output when $PAGER is not set
+pub const DEFAULT_PAGER: &str = "less -R";
+// Used by `edit-placeholders` when neither $VISUAL nor $EDITOR is set
+#[cfg(windows)]
+pub const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+pub const DEFAULT_EDITOR: &str = "vi";
+
 // --- Key Rules for Comparison Table ---
 // (Label, tag_key_or_special_indicator)
 pub const KEY_RULES_FOR_COMPARISON_ARRAY: [(&str, &str); 10] = [
@@ -83,3 +149,152 @@ pub const KEY_RULES_FOR_COMPARISON_ARRAY: [(&str, &str); 10] = [
     // Special key for limitations
     ("Patent use (Lim)", "patent-use_lim"),
 ];
+
+// --- License Text Statistics ---
+// Condition tags counted as copyleft clauses by `info --stats` and `compare --stats`
+pub const COPYLEFT_CONDITION_TAGS: [&str; 5] = [
+    "disclose-source", "same-license", "same-license--file", "same-license--library",
+    "network-use-disclose",
+];
+// Typical adult reading speed, used for the estimated reading time
+pub const READING_WORDS_PER_MINUTE: usize = 200;
+// In the order `stats` reports them, as returned by stats::LicenseCategory
+pub const LICENSE_CATEGORIES: [&str; 3] = ["permissive", "weak copyleft", "strong copyleft"];
+// Rows `stats` shows for the most common conditions and the newest additions
+pub const CORPUS_STATS_TOP_CONDITIONS: usize = 5;
+pub const CORPUS_STATS_NEWEST_ADDITIONS: usize = 5;
+
+// --- Permissiveness Score ---
+// (category, tag, weight). Permissions raise the score, conditions and limitations lower it;
+// tags not listed weigh 0. Overridable under [permissiveness_weights.<category>] in config.
+pub const PERMISSIVENESS_WEIGHT_TUPLES: [(&str, &str, i32); 17] = [
+    ("permissions", "commercial-use", 15),
+    ("permissions", "modifications", 15),
+    ("permissions", "distribution", 15),
+    ("permissions", "private-use", 10),
+    ("permissions", "patent-use", 5),
+    ("conditions", "include-copyright", -5),
+    ("conditions", "include-copyright--source", -5),
+    ("conditions", "document-changes", -10),
+    ("conditions", "disclose-source", -30),
+    ("conditions", "network-use-disclose", -20),
+    ("conditions", "same-license", -40),
+    ("conditions", "same-license--file", -20),
+    ("conditions", "same-license--library", -25),
+    ("limitations", "trademark-use", -5),
+    ("limitations", "patent-use", -10),
+    ("limitations", "liability", 0),
+    ("limitations", "warranty", 0),
+];
+
+// --- License File and Header Checks ---
+// Checked in order when no explicit license file path is given
+pub const LICENSE_FILE_CANDIDATES: [&str; 9] = [
+    "LICENSE", "LICENSE.md", "LICENSE.txt", "LICENCE", "LICENCE.md", "LICENCE.txt", "COPYING",
+    "COPYING.txt", "LICENSE-MIT",
+];
+pub const DEFAULT_LICENSE_FILENAME: &str = "LICENSE";
+// Where `license` writes one <SPDX_ID>.txt per license when given several IDs (REUSE layout)
+pub const LICENSES_DIR_NAME: &str = "LICENSES";
+// Written next to generated license files by `license --provenance`
+pub const PROVENANCE_FILENAME: &str = ".getlicense-provenance.toml";
+// Pins the template version of each generated file, in the directory `license` runs in
+pub const TEMPLATE_LOCK_FILENAME: &str = "getlicense.lock";
+// Lists the files `vendor` wrote, relative to its output directory, so the next run only
+// removes those
+pub const VENDOR_MANIFEST_FILENAME: &str = ".getlicense-vendor";
+// The official EUPL-1.2 translations published by the European Commission on Joinup, used by
+// `license eupl-1.2 --lang` unless [translations."eupl-1.2"] in config.toml names a source.
+// `{LANG}` is replaced with the upper-case language code.
+pub const EUPL_TRANSLATION_URL_TEMPLATE: &str =
+    "https://joinup.ec.europa.eu/sites/default/files/custom-page/attachment/2020-03/EUPL-1.2%20{LANG}.txt";
+pub const EUPL_TRANSLATION_LANGUAGES: [&str; 23] = [
+    "bg", "cs", "da", "de", "el", "en", "es", "et", "fi", "fr", "hr", "hu", "it", "lt", "lv", "mt", "nl", "pl", "pt", "ro",
+    "sk", "sl", "sv",
+];
+// Default output of `notices`
+pub const NOTICES_FILENAME: &str = "THIRD-PARTY-NOTICES";
+// Package manifests whose license field `verify` compares against the license file
+pub const MANIFEST_FILENAMES: [&str; 3] = ["Cargo.toml", "package.json", "pyproject.toml"];
+// Below this word similarity a file is not considered an old version of a template
+pub const DRIFT_MIN_SIMILARITY: f32 = 0.6;
+pub const SPDX_HEADER_TAG: &str = "SPDX-License-Identifier:";
+// Only the top of each file is searched for the SPDX tag
+pub const HEADER_SCAN_LINES: usize = 10;
+pub const HEADER_CHECK_EXTENSIONS: [&str; 22] = [
+    "rs", "py", "js", "jsx", "ts", "tsx", "go", "c", "h", "cc", "cpp", "hpp", "java", "kt",
+    "swift", "rb", "php", "cs", "sh", "scala", "lua", "zig",
+];
+// Language each checked extension is reported under by `check-headers --report`
+pub const HEADER_LANGUAGE_TUPLES: [(&str, &str); 22] = [
+    ("rs", "Rust"), ("py", "Python"), ("js", "JavaScript"), ("jsx", "JavaScript"),
+    ("ts", "TypeScript"), ("tsx", "TypeScript"), ("go", "Go"), ("c", "C"), ("h", "C"),
+    ("cc", "C++"), ("cpp", "C++"), ("hpp", "C++"), ("java", "Java"), ("kt", "Kotlin"),
+    ("swift", "Swift"), ("rb", "Ruby"), ("php", "PHP"), ("cs", "C#"), ("sh", "Shell"),
+    ("scala", "Scala"), ("lua", "Lua"), ("zig", "Zig"),
+];
+// Line comment used for headers written by `add-headers`; other extensions are skipped
+pub const HEADER_COMMENT_PREFIX_TUPLES: [(&str, &str); 22] = [
+    ("rs", "//"), ("py", "#"), ("js", "//"), ("jsx", "//"), ("ts", "//"), ("tsx", "//"),
+    ("go", "//"), ("c", "//"), ("h", "//"), ("cc", "//"), ("cpp", "//"), ("hpp", "//"),
+    ("java", "//"), ("kt", "//"), ("swift", "//"), ("rb", "#"), ("php", "//"), ("cs", "//"),
+    ("sh", "#"), ("scala", "//"), ("lua", "--"), ("zig", "//"),
+];
+// VS Code language ID and Vim filetype for each header extension, used by `snippets`
+pub const SNIPPET_SCOPE_TUPLES: [(&str, &str, &str); 22] = [
+    ("rs", "rust", "rust"), ("py", "python", "python"), ("js", "javascript", "javascript"),
+    ("jsx", "javascriptreact", "javascriptreact"), ("ts", "typescript", "typescript"),
+    ("tsx", "typescriptreact", "typescriptreact"), ("go", "go", "go"), ("c", "c", "c"),
+    ("h", "c", "c"), ("cc", "cpp", "cpp"), ("cpp", "cpp", "cpp"), ("hpp", "cpp", "cpp"),
+    ("java", "java", "java"), ("kt", "kotlin", "kotlin"), ("swift", "swift", "swift"),
+    ("rb", "ruby", "ruby"), ("php", "php", "php"), ("cs", "csharp", "cs"),
+    ("sh", "shellscript", "sh"), ("scala", "scala", "scala"), ("lua", "lua", "lua"),
+    ("zig", "zig", "zig"),
+];
+// Hidden directories are always skipped in addition to these
+pub const HEADER_CHECK_SKIPPED_DIRS: [&str; 3] = ["target", "node_modules", "vendor"];
+// Read like .gitignore in every walked directory, for files only header commands should skip
+pub const LICENSE_IGNORE_FILENAME: &str = ".licenseignore";
+// First comment line of hooks written by `hook install`, used to recognise them
+pub const HOOK_MARKER: &str = "# Installed by getlicense hook install";
+pub const PRE_COMMIT_CONFIG_FILENAME: &str = ".pre-commit-config.yaml";
+
+// --- License Text Matching ---
+// Spelling variants treated as the same word (subset of the SPDX matching guidelines'
+// equivalent words list). Applied after lowercasing; (variant, canonical).
+pub const EQUIVALENT_WORDS_TUPLES: [(&str, &str); 34] = [
+    ("acknowledgment", "acknowledgement"),
+    ("analogue", "analog"),
+    ("analyse", "analyze"),
+    ("artefact", "artifact"),
+    ("authorisation", "authorization"),
+    ("authorised", "authorized"),
+    ("calibre", "caliber"),
+    ("cancelled", "canceled"),
+    ("catalogue", "catalog"),
+    ("categorise", "categorize"),
+    ("centre", "center"),
+    ("copyright owner", "copyright holder"),
+    ("emphasised", "emphasized"),
+    ("favour", "favor"),
+    ("favourite", "favorite"),
+    ("fulfil", "fulfill"),
+    ("fulfilment", "fulfillment"),
+    ("initialise", "initialize"),
+    ("judgment", "judgement"),
+    ("labelling", "labeling"),
+    ("labour", "labor"),
+    ("licence", "license"),
+    ("maximise", "maximize"),
+    ("modelled", "modeled"),
+    ("modelling", "modeling"),
+    ("non-commercial", "noncommercial"),
+    ("offence", "offense"),
+    ("optimise", "optimize"),
+    ("organisation", "organization"),
+    ("organise", "organize"),
+    ("per cent", "percent"),
+    ("practise", "practice"),
+    ("programme", "program"),
+    ("sub-license", "sublicense"),
+];
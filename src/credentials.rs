@@ -0,0 +1,203 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+
+use crate::config::AuthConfig;
+use crate::constants::{GITHUB_PUBLIC_HOST, GITHUB_PUBLIC_SERVICE_HOSTS};
+
+// Host -> resolved token, so a keyring lookup runs at most once per host and run
+static RESOLVED_TOKENS: Lazy<Mutex<HashMap<String, Option<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// The [auth] entry for a request host. Hosts match case-insensitively, and "github.com"
+// also covers the API and raw content hosts of public GitHub.
+pub fn AuthFor<'a>(auth: &'a HashMap<String, AuthConfig>, host: &str) -> Option<&'a AuthConfig> {
+    let lookup = |wanted: &str| {
+        auth.iter()
+            .find(|(configured, _)| configured.eq_ignore_ascii_case(wanted))
+            .map(|(_, config)| config)
+    };
+
+    lookup(host).or_else(|| {
+        GITHUB_PUBLIC_SERVICE_HOSTS
+            .iter()
+            .any(|service| service.eq_ignore_ascii_case(host))
+            .then(|| lookup(GITHUB_PUBLIC_HOST))
+            .flatten()
+    })
+}
+
+// None when the host has no [auth] entry or none of its sources yields a token.
+pub fn TokenForHost(auth: &HashMap<String, AuthConfig>, host: &str) -> Option<String> {
+    let config = AuthFor(auth, host)?;
+    let mut resolved = RESOLVED_TOKENS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    resolved
+        .entry(host.to_lowercase())
+        .or_insert_with(|| {
+            let token = ResolveToken(config, host);
+
+            if token.is_none() {
+                eprintln!("Warning: the [auth] entry for {} yielded no token.", host);
+            }
+
+            token
+        })
+        .clone()
+}
+
+fn ResolveToken(config: &AuthConfig, host: &str) -> Option<String> {
+    let verbose = crate::VERBOSE.load(Ordering::SeqCst);
+
+    if let Some(name) = &config.tokenEnv {
+        match env::var(name).ok().filter(|token| !token.trim().is_empty()) {
+            Some(token) => {
+                if verbose {
+                    eprintln!("[Auth] Using ${} for {}", name, host);
+                }
+                return Some(token.trim().to_string());
+            }
+            None if verbose => eprintln!("[Auth] ${} is not set (for {})", name, host),
+            None => {}
+        }
+    }
+
+    if let Some(service) = &config.keyringService {
+        let account = config.keyringAccount.as_deref().unwrap_or(host);
+
+        if let Some(token) = KeyringPassword(service, account) {
+            if verbose {
+                eprintln!("[Auth] Using keyring entry {}/{} for {}", service, account, host);
+            }
+            return Some(token);
+        }
+        if verbose {
+            eprintln!("[Auth] No keyring entry {}/{} (for {})", service, account, host);
+        }
+    }
+
+    if config.netrc {
+        let content = NetrcPath().and_then(|path| fs::read_to_string(path).ok());
+
+        if let Some(token) = content.and_then(|content| NetrcPassword(&content, host)) {
+            if verbose {
+                eprintln!("[Auth] Using the netrc entry for {}", host);
+            }
+            return Some(token);
+        }
+        if verbose {
+            eprintln!("[Auth] No netrc entry for {}", host);
+        }
+    }
+
+    None
+}
+
+// Through the platform's own tool, so no keyring library has to be linked in
+fn KeyringPassword(service: &str, account: &str) -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", service, "-a", account, "-w"])
+            .output()
+    } else if cfg!(unix) {
+        Command::new("secret-tool")
+            .args(["lookup", "service", service, "account", account])
+            .output()
+    } else {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Auth] Keyring lookups are not supported on this platform; use token_env or netrc.");
+        }
+        return None;
+    };
+
+    output
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+fn NetrcPath() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("NETRC") {
+        return Some(PathBuf::from(path));
+    }
+
+    let fileName = if cfg!(windows) { "_netrc" } else { ".netrc" };
+    dirs::home_dir().map(|home| home.join(fileName))
+}
+
+// The password of `host`'s machine entry, falling back to a `default` entry
+pub fn NetrcPassword(content: &str, host: &str) -> Option<String> {
+    let mut machine: Option<String> = None;
+    let mut passwords: HashMap<Option<String>, String> = HashMap::new();
+    let mut inMacro = false;
+
+    for line in content.lines() {
+        // A macro body runs until the next empty line
+        if inMacro {
+            inMacro = !line.trim().is_empty();
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token {
+                "machine" => machine = tokens.next().map(|name| name.to_lowercase()),
+                "default" => machine = None,
+                "password" => {
+                    if let Some(password) = tokens.next() {
+                        passwords.entry(machine.clone()).or_insert_with(|| password.to_string());
+                    }
+                }
+                "login" | "account" => {
+                    tokens.next();
+                }
+                "macdef" => {
+                    inMacro = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    passwords
+        .remove(&Some(host.to_lowercase()))
+        .or_else(|| passwords.remove(&None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestNetrcPassword() {
+        let netrc = "machine github.example.com\n  login bot\n  password ghe-token\n\n\
+                     macdef init\ncd /tmp\n\n\
+                     machine other.example.com login x password other-token\n\
+                     default login anonymous password fallback\n";
+
+        assert_eq!(NetrcPassword(netrc, "GitHub.example.com").as_deref(), Some("ghe-token"));
+        assert_eq!(NetrcPassword(netrc, "other.example.com").as_deref(), Some("other-token"));
+        assert_eq!(NetrcPassword(netrc, "unknown.example.com").as_deref(), Some("fallback"));
+        assert_eq!(NetrcPassword("machine a login b password c\n", "d"), None);
+    }
+
+    #[test]
+    fn TestAuthForPublicGithubHosts() {
+        let auth = HashMap::from([
+            ("github.com".to_string(), AuthConfig { tokenEnv: Some("PUBLIC".to_string()), ..Default::default() }),
+            ("GHE.example.com".to_string(), AuthConfig { netrc: true, ..Default::default() }),
+        ]);
+
+        assert_eq!(AuthFor(&auth, "raw.githubusercontent.com").unwrap().tokenEnv.as_deref(), Some("PUBLIC"));
+        assert_eq!(AuthFor(&auth, "api.github.com").unwrap().tokenEnv.as_deref(), Some("PUBLIC"));
+        assert!(AuthFor(&auth, "ghe.example.com").unwrap().netrc);
+        assert_eq!(AuthFor(&auth, "cdn.jsdelivr.net"), None);
+    }
+}
@@ -0,0 +1,638 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::Ordering;
+
+use crate::error::{ActionError, AppError, ParseError};
+use crate::manifest;
+
+// A third-party package a project depends on, from whichever package manager knows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyPackage {
+    pub name: String,
+    pub version: String,
+    // SPDX expression as declared by the package; None when it declares none
+    pub license: Option<String>,
+    pub authors: Vec<String>,
+    pub repository: Option<String>,
+    // "cargo", "npm", "pypi"
+    pub ecosystem: &'static str,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+    packages: Vec<CargoMetadataPackage>,
+    #[serde(rename = "workspace_members")]
+    workspaceMembers: Vec<String>,
+    resolve: Option<CargoResolve>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataPackage {
+    id: String,
+    name: String,
+    version: String,
+    license: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    repository: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CargoResolve {
+    nodes: Vec<CargoResolveNode>,
+}
+
+#[derive(Deserialize)]
+struct CargoResolveNode {
+    id: String,
+    #[serde(default)]
+    deps: Vec<CargoNodeDep>,
+}
+
+#[derive(Deserialize)]
+struct CargoNodeDep {
+    pkg: String,
+    #[serde(rename = "dep_kinds", default)]
+    depKinds: Vec<CargoDepKind>,
+}
+
+#[derive(Deserialize)]
+struct CargoDepKind {
+    // null for normal dependencies
+    kind: Option<String>,
+}
+
+// Package IDs reachable from the workspace members through normal and build
+// dependencies. Dev-dependencies are not shipped, so they need no notice.
+fn ShippedPackageIds(metadata: &CargoMetadata) -> HashSet<String> {
+    let Some(resolve) = &metadata.resolve else {
+        return metadata.packages.iter().map(|p| p.id.clone()).collect();
+    };
+
+    let nodes: HashMap<&str, &CargoResolveNode> =
+        resolve.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let mut seen: HashSet<String> = metadata.workspaceMembers.iter().cloned().collect();
+    let mut queue: VecDeque<&str> = metadata.workspaceMembers.iter().map(String::as_str).collect();
+
+    while let Some(id) = queue.pop_front() {
+        let Some(node) = nodes.get(id) else {
+            continue;
+        };
+
+        for dep in &node.deps {
+            let shipped = dep.depKinds.is_empty()
+                || dep.depKinds.iter().any(|k| k.kind.as_deref() != Some("dev"));
+
+            if shipped && seen.insert(dep.pkg.clone()) {
+                queue.push_back(dep.pkg.as_str());
+            }
+        }
+    }
+
+    seen
+}
+
+pub fn ParseCargoMetadata(json: &str) -> Result<Vec<DependencyPackage>, AppError> {
+    let metadata: CargoMetadata = serde_json::from_str(json)
+        .map_err(|e| ParseError::JsonError("cargo metadata output".to_string(), e))?;
+    let shipped = ShippedPackageIds(&metadata);
+    let members: HashSet<&str> = metadata.workspaceMembers.iter().map(String::as_str).collect();
+
+    Ok(metadata
+        .packages
+        .iter()
+        .filter(|p| shipped.contains(&p.id) && !members.contains(p.id.as_str()))
+        .map(|p| DependencyPackage {
+            name: p.name.clone(),
+            version: p.version.clone(),
+            license: p.license.clone().filter(|l| !l.trim().is_empty()),
+            authors: p.authors.clone(),
+            repository: p.repository.clone(),
+            ecosystem: "cargo",
+        })
+        .collect())
+}
+
+pub fn ReadCargoDependencies(manifestPath: &Path) -> Result<Vec<DependencyPackage>, AppError> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--manifest-path"])
+        .arg(manifestPath)
+        .output()
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, manifestPath.to_path_buf())))?;
+
+    if !output.status.success() {
+        return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+            "cargo metadata failed for {}: {}",
+            manifestPath.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))));
+    }
+
+    ParseCargoMetadata(&String::from_utf8_lossy(&output.stdout))
+}
+
+// The installed package.json of an npm package, if node_modules is populated.
+fn ReadInstalledPackageJson(packageDir: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(packageDir.join("package.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// "author" may be "Name <email> (url)" or {"name": ...}; "contributors" is a list of either
+fn PackageJsonAuthors(manifest: &serde_json::Value) -> Vec<String> {
+    let NameOf = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Object(o) => o.get("name").and_then(|n| n.as_str()).map(|s| s.to_string()),
+        _ => None,
+    };
+
+    manifest
+        .get("author")
+        .and_then(NameOf)
+        .into_iter()
+        .chain(
+            manifest
+                .get("contributors")
+                .and_then(|c| c.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(NameOf),
+        )
+        .collect()
+}
+
+fn PackageJsonRepository(manifest: &serde_json::Value) -> Option<String> {
+    match manifest.get("repository")? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Object(o) => o.get("url").and_then(|u| u.as_str()).map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+fn NpmPackage(name: &str, version: &str, license: Option<String>, installed: Option<serde_json::Value>) -> DependencyPackage {
+    let installed = installed.unwrap_or(serde_json::Value::Null);
+
+    DependencyPackage {
+        name: name.to_string(),
+        version: version.to_string(),
+        license: license
+            .or_else(|| manifest::PackageJsonLicense(&installed))
+            .filter(|l| !l.trim().is_empty()),
+        authors: PackageJsonAuthors(&installed),
+        repository: PackageJsonRepository(&installed),
+        ecosystem: "npm",
+    }
+}
+
+// package-lock.json v2/v3: "packages" is keyed by install path, e.g.
+// "node_modules/a/node_modules/@scope/b"; "" is the project itself. npm records each
+// package's license there, so node_modules is only consulted for authors.
+pub fn ParsePackageLock(json: &str, projectDir: &Path) -> Result<Vec<DependencyPackage>, AppError> {
+    let lock: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| ParseError::JsonError("package-lock.json".to_string(), e))?;
+
+    let Some(entries) = lock.get("packages").and_then(|p| p.as_object()) else {
+        return Err(AppError::ActionErrorVariant(ActionError::InvalidInput(
+            "package-lock.json has no \"packages\" map; regenerate it with npm 7 or later".to_string(),
+        )));
+    };
+
+    let mut packages = Vec::new();
+
+    for (installPath, entry) in entries {
+        let flagged = |key: &str| entry.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        // Workspace links point at the project's own packages
+        if installPath.is_empty() || flagged("dev") || flagged("link") {
+            continue;
+        }
+
+        let Some(name) = entry
+            .get("name")
+            .and_then(|n| n.as_str())
+            .or_else(|| installPath.rsplit("node_modules/").next())
+        else {
+            continue;
+        };
+        let version = entry.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+
+        packages.push(NpmPackage(
+            name,
+            version,
+            manifest::PackageJsonLicense(entry),
+            ReadInstalledPackageJson(&projectDir.join(installPath)),
+        ));
+    }
+
+    Ok(packages)
+}
+
+// "/@scope/name@1.2.3(peer@4.0.0)" (v6), "@scope/name@1.2.3" (v9) or "/@scope/name/1.2.3" (v5)
+fn SplitPnpmPackageKey(key: &str) -> Option<(String, String)> {
+    let key = key.trim_start_matches('/');
+    let key = key.split('(').next().unwrap_or(key);
+
+    match key[1..].rfind('@') {
+        Some(at) => Some((key[..at + 1].to_string(), key[at + 2..].to_string())),
+        None => {
+            let (name, version) = key.rsplit_once('/')?;
+            Some((name.to_string(), version.to_string()))
+        }
+    }
+}
+
+// pnpm-lock.yaml does not record licenses, so they come from the installed packages:
+// either hoisted into node_modules/<name> or in the virtual store under node_modules/.pnpm.
+pub fn ParsePnpmLock(yaml: &str, projectDir: &Path) -> Result<Vec<DependencyPackage>, AppError> {
+    let lock: serde_yaml::Value = serde_yaml::from_str(yaml).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::InvalidInput(format!("pnpm-lock.yaml does not parse: {}", e)))
+    })?;
+
+    let Some(entries) = lock.get("packages").and_then(|p| p.as_mapping()) else {
+        return Ok(Vec::new());
+    };
+
+    let nodeModules = projectDir.join("node_modules");
+    let mut packages = Vec::new();
+
+    for (key, entry) in entries {
+        if entry.get("dev").and_then(|d| d.as_bool()).unwrap_or(false) {
+            continue;
+        }
+
+        let Some((name, version)) = key.as_str().filter(|k| k.len() > 1).and_then(SplitPnpmPackageKey) else {
+            continue;
+        };
+
+        let storeDir = nodeModules
+            .join(".pnpm")
+            .join(format!("{}@{}", name.replace('/', "+"), version))
+            .join("node_modules")
+            .join(&name);
+        let installed = ReadInstalledPackageJson(&storeDir)
+            .or_else(|| ReadInstalledPackageJson(&nodeModules.join(&name)));
+
+        packages.push(NpmPackage(&name, &version, None, installed));
+    }
+
+    Ok(packages)
+}
+
+fn ReadLockfile(lockPath: &Path) -> Result<String, AppError> {
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Action] Reading dependencies from {}", lockPath.display());
+    }
+
+    fs::read_to_string(lockPath)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, lockPath.to_path_buf())))
+}
+
+// package-lock.json wins when both lockfiles are present
+pub fn ReadNpmDependencies(projectDir: &Path) -> Result<Vec<DependencyPackage>, AppError> {
+    let packageLock = projectDir.join("package-lock.json");
+    let pnpmLock = projectDir.join("pnpm-lock.yaml");
+
+    if packageLock.is_file() {
+        ParsePackageLock(&ReadLockfile(&packageLock)?, projectDir)
+    } else if pnpmLock.is_file() {
+        ParsePnpmLock(&ReadLockfile(&pnpmLock)?, projectDir)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+// Trove classifiers ("License :: OSI Approved :: MIT License") that name exactly one
+// SPDX license. Ambiguous ones such as "BSD License" are left out.
+const PYTHON_LICENSE_CLASSIFIERS: &[(&str, &str)] = &[
+    ("MIT License", "MIT"),
+    ("MIT No Attribution License (MIT-0)", "MIT-0"),
+    ("Apache Software License", "Apache-2.0"),
+    ("ISC License (ISCL)", "ISC"),
+    ("Zero-Clause BSD (0BSD)", "0BSD"),
+    ("The Unlicense (Unlicense)", "Unlicense"),
+    ("Boost Software License 1.0 (BSL-1.0)", "BSL-1.0"),
+    ("Mozilla Public License 2.0 (MPL 2.0)", "MPL-2.0"),
+    ("Eclipse Public License 2.0 (EPL-2.0)", "EPL-2.0"),
+    ("Python Software Foundation License", "PSF-2.0"),
+    ("GNU General Public License v2 (GPLv2)", "GPL-2.0-only"),
+    ("GNU General Public License v2 or later (GPLv2+)", "GPL-2.0-or-later"),
+    ("GNU General Public License v3 (GPLv3)", "GPL-3.0-only"),
+    ("GNU General Public License v3 or later (GPLv3+)", "GPL-3.0-or-later"),
+    ("GNU Lesser General Public License v2 or later (LGPLv2+)", "LGPL-2.0-or-later"),
+    ("GNU Lesser General Public License v3 (LGPLv3)", "LGPL-3.0-only"),
+    ("GNU Lesser General Public License v3 or later (LGPLv3+)", "LGPL-3.0-or-later"),
+    ("GNU Affero General Public License v3", "AGPL-3.0-only"),
+    ("GNU Affero General Public License v3 or later (AGPLv3+)", "AGPL-3.0-or-later"),
+];
+
+// PEP 503: "Typing_Extensions" and "typing.extensions" are both "typing-extensions"
+fn NormalizePythonName(name: &str) -> String {
+    let mut normalized = String::new();
+
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+
+    normalized
+}
+
+// Header fields of a dist-info METADATA file (RFC 822 style, body after the first blank line)
+fn ParseDistMetadata(content: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for line in content.lines() {
+        if line.is_empty() {
+            break;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = fields.last_mut() {
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = line.split_once(':') {
+            fields.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    fields
+}
+
+// License-Expression (PEP 639) wins, then unambiguous classifiers, then a License field
+// short enough to be an identifier rather than the full license text.
+fn PythonLicenseOf(fields: &[(String, String)]) -> Option<String> {
+    let field = |key: &str| fields.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v.as_str());
+
+    if let Some(expression) = field("License-Expression").filter(|e| !e.is_empty()) {
+        return Some(expression.to_string());
+    }
+
+    let mut classified: Vec<&str> = fields
+        .iter()
+        .filter(|(k, v)| k == "Classifier" && v.starts_with("License ::"))
+        .filter_map(|(_, v)| {
+            let name = v.rsplit("::").next()?.trim();
+            PYTHON_LICENSE_CLASSIFIERS.iter().find(|(c, _)| *c == name).map(|(_, id)| *id)
+        })
+        .collect();
+    classified.dedup();
+
+    if !classified.is_empty() {
+        return Some(classified.join(" OR "));
+    }
+
+    field("License")
+        .filter(|l| !l.is_empty() && !l.contains('\n') && l.len() <= 64 && !l.eq_ignore_ascii_case("UNKNOWN"))
+        .map(str::to_string)
+}
+
+fn PythonPackageFromMetadata(name: &str, version: &str, fields: &[(String, String)]) -> DependencyPackage {
+    let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()).filter(|v| !v.is_empty() && v != "UNKNOWN");
+
+    // "Author-email: Jane Doe <jane@example.com>" when only the email field is set
+    let authors = field("Author")
+        .or_else(|| field("Author-email").map(|e| e.split('<').next().unwrap_or(&e).trim().to_string()))
+        .into_iter()
+        .filter(|a| !a.is_empty())
+        .collect();
+
+    let repository = fields
+        .iter()
+        .filter(|(k, _)| k == "Project-URL")
+        .filter_map(|(_, v)| v.split_once(','))
+        .find(|(label, _)| matches!(label.trim().to_ascii_lowercase().as_str(), "source" | "source code" | "repository" | "code"))
+        .map(|(_, url)| url.trim().to_string())
+        .or_else(|| field("Home-page"));
+
+    DependencyPackage {
+        name: name.to_string(),
+        version: version.to_string(),
+        license: PythonLicenseOf(fields),
+        authors,
+        repository,
+        ecosystem: "pypi",
+    }
+}
+
+// site-packages of the project's virtualenv (.venv, venv or env), unix or Windows layout
+fn PythonSitePackages(projectDir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    for venv in [".venv", "venv", "env"] {
+        let venvDir = projectDir.join(venv);
+
+        dirs.push(venvDir.join("Lib").join("site-packages"));
+
+        if let Ok(entries) = fs::read_dir(venvDir.join("lib")) {
+            dirs.extend(entries.flatten().map(|e| e.path().join("site-packages")));
+        }
+    }
+
+    dirs.retain(|d| d.is_dir());
+    dirs
+}
+
+// Normalized name -> METADATA header fields of every installed distribution
+fn InstalledPythonDistributions(projectDir: &Path) -> HashMap<String, Vec<(String, String)>> {
+    let mut installed = HashMap::new();
+
+    for sitePackages in PythonSitePackages(projectDir) {
+        let Ok(entries) = fs::read_dir(&sitePackages) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            if !entry.file_name().to_string_lossy().ends_with(".dist-info") {
+                continue;
+            }
+
+            let Ok(content) = fs::read_to_string(entry.path().join("METADATA")) else {
+                continue;
+            };
+            let fields = ParseDistMetadata(&content);
+
+            if let Some((_, name)) = fields.iter().find(|(k, _)| k == "Name") {
+                installed.entry(NormalizePythonName(name)).or_insert(fields);
+            }
+        }
+    }
+
+    installed
+}
+
+// (name, version) of each main-group package; old Poetry marks dev packages with category
+fn ParsePoetryLock(content: &str, lockPath: &Path) -> Result<Vec<(String, String)>, AppError> {
+    let lock: toml::Value =
+        toml::from_str(content).map_err(|e| ParseError::TomlError(lockPath.display().to_string(), e))?;
+
+    Ok(lock
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|p| p.get("category").and_then(|c| c.as_str()) != Some("dev"))
+        .filter_map(|p| {
+            let name = p.get("name")?.as_str()?;
+            let version = p.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect())
+}
+
+// (name, pinned version or "") per requirement; options, URLs and includes are skipped
+fn ParseRequirementsTxt(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(|line| line.split(" #").next().unwrap_or(line).trim())
+        .filter(|line| !line.is_empty() && !line.starts_with(['#', '-']) && !line.contains("://"))
+        .filter_map(|line| {
+            let nameEnd = line
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(line.len());
+            let name = &line[..nameEnd];
+
+            if name.is_empty() {
+                return None;
+            }
+
+            let version = line
+                .split_once("==")
+                .map(|(_, v)| v.split([';', ',', ' ']).next().unwrap_or_default().trim().to_string())
+                .unwrap_or_default();
+
+            Some((name.to_string(), version))
+        })
+        .collect()
+}
+
+// poetry.lock if present, otherwise requirements.txt. Neither records licenses, so they
+// come from the dist-info metadata installed in the project's virtualenv.
+pub fn ReadPythonDependencies(projectDir: &Path) -> Result<Vec<DependencyPackage>, AppError> {
+    let poetryLock = projectDir.join("poetry.lock");
+    let requirements = projectDir.join("requirements.txt");
+
+    let locked = if poetryLock.is_file() {
+        ParsePoetryLock(&ReadLockfile(&poetryLock)?, &poetryLock)?
+    } else if requirements.is_file() {
+        ParseRequirementsTxt(&ReadLockfile(&requirements)?)
+    } else {
+        return Ok(Vec::new());
+    };
+
+    let installed = InstalledPythonDistributions(projectDir);
+
+    if installed.is_empty() && !locked.is_empty() {
+        eprintln!(
+            "Warning: no virtualenv found in {} (.venv, venv or env); Python package licenses are unknown.",
+            projectDir.display()
+        );
+    }
+
+    Ok(locked
+        .into_iter()
+        .map(|(name, version)| match installed.get(&NormalizePythonName(&name)) {
+            Some(fields) => {
+                let installedVersion = fields.iter().find(|(k, _)| k == "Version").map(|(_, v)| v.as_str());
+                let version = if version.is_empty() { installedVersion.unwrap_or_default() } else { &version };
+                PythonPackageFromMetadata(&name, version, fields)
+            }
+            None => PythonPackageFromMetadata(&name, &version, &[]),
+        })
+        .collect())
+}
+
+// Every dependency of the project in `projectDir`, from each package manager it uses.
+pub fn ReadProjectDependencies(projectDir: &Path) -> Result<Vec<DependencyPackage>, AppError> {
+    let mut packages = Vec::new();
+
+    let cargoManifest = projectDir.join("Cargo.toml");
+    if cargoManifest.is_file() {
+        packages.extend(ReadCargoDependencies(&cargoManifest)?);
+    }
+
+    packages.extend(ReadNpmDependencies(projectDir)?);
+    packages.extend(ReadPythonDependencies(projectDir)?);
+
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestParseCargoMetadataSkipsDevDependencies() {
+        let json = r#"{
+            "packages": [
+                {"id": "app", "name": "app", "version": "0.1.0", "license": "MIT", "authors": []},
+                {"id": "serde", "name": "serde", "version": "1.0.0", "license": "MIT OR Apache-2.0", "authors": ["David Tolnay"]},
+                {"id": "cc", "name": "cc", "version": "1.0.0", "license": "MIT OR Apache-2.0", "authors": []},
+                {"id": "tempfile", "name": "tempfile", "version": "3.0.0", "license": "MIT OR Apache-2.0", "authors": []}
+            ],
+            "workspace_members": ["app"],
+            "resolve": {"nodes": [
+                {"id": "app", "deps": [
+                    {"pkg": "serde", "dep_kinds": [{"kind": null}]},
+                    {"pkg": "cc", "dep_kinds": [{"kind": "build"}]},
+                    {"pkg": "tempfile", "dep_kinds": [{"kind": "dev"}]}
+                ]},
+                {"id": "serde", "deps": []},
+                {"id": "cc", "deps": []},
+                {"id": "tempfile", "deps": []}
+            ]}
+        }"#;
+
+        let mut names: Vec<String> = ParseCargoMetadata(json).unwrap().into_iter().map(|p| p.name).collect();
+        names.sort();
+
+        assert_eq!(names, ["cc", "serde"]);
+    }
+
+    #[test]
+    fn TestParseNpmLockfiles() {
+        let packageLock = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": {"name": "app", "license": "MIT"},
+                "node_modules/left-pad": {"version": "1.3.0", "license": "WTFPL"},
+                "node_modules/a/node_modules/@scope/b": {"version": "2.0.0", "license": {"type": "ISC"}},
+                "node_modules/jest": {"version": "29.0.0", "license": "MIT", "dev": true},
+                "node_modules/ui": {"resolved": "packages/ui", "link": true}
+            }
+        }"#;
+
+        let packages = ParsePackageLock(packageLock, Path::new("/nonexistent")).unwrap();
+        let found: Vec<(&str, Option<&str>)> =
+            packages.iter().map(|p| (p.name.as_str(), p.license.as_deref())).collect();
+
+        assert_eq!(found, [("@scope/b", Some("ISC")), ("left-pad", Some("WTFPL"))]);
+
+        assert_eq!(SplitPnpmPackageKey("/@scope/b@2.0.0(react@18.0.0)"), Some(("@scope/b".to_string(), "2.0.0".to_string())));
+        assert_eq!(SplitPnpmPackageKey("left-pad@1.3.0"), Some(("left-pad".to_string(), "1.3.0".to_string())));
+        assert_eq!(SplitPnpmPackageKey("/left-pad/1.3.0"), Some(("left-pad".to_string(), "1.3.0".to_string())));
+    }
+
+    #[test]
+    fn TestPythonMetadataAndRequirements() {
+        let metadata = "Metadata-Version: 2.1\nName: Requests\nVersion: 2.31.0\nLicense: Apache 2.0\nClassifier: License :: OSI Approved :: Apache Software License\nProject-URL: Source, https://github.com/psf/requests\n\nBody";
+        let package = PythonPackageFromMetadata("requests", "2.31.0", &ParseDistMetadata(metadata));
+
+        assert_eq!(package.license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(package.repository.as_deref(), Some("https://github.com/psf/requests"));
+        assert_eq!(NormalizePythonName("Typing_Extensions"), "typing-extensions");
+
+        let requirements = "# pinned\nrequests==2.31.0 ; python_version >= '3.8'\n-r dev.txt\nclick>=8\n";
+        assert_eq!(
+            ParseRequirementsTxt(requirements),
+            [("requests".to_string(), "2.31.0".to_string()), ("click".to_string(), String::new())]
+        );
+    }
+}
@@ -1,39 +1,82 @@
-use std::path::Path;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use colored::*;
 use unicode_ellipsis::truncate_str;
 
 use crate::models::{Cache, LicenseEntry, RulesDataContent, FieldsDataContent};
-use crate::cli::Cli as FullCliArgs;
+#[cfg(feature = "net")]
+use crate::api::MirrorHealth;
+use crate::resolve::{PlaceholderSource, ResolvedValue};
+use crate::actions::check::{CheckOutcome, CheckStatus};
+use crate::actions::compare::RuleDelta;
+use crate::actions::corpus::CorpusStats;
+use crate::cli::ListColumn;
+use crate::actions::fill::WrittenLicense;
+use crate::actions::find::FindRanking;
+use crate::actions::headers::{HeaderCoverageReport, HeaderProblem};
+use crate::actions::identify::IdentifyCandidate;
+use crate::actions::lint_data::{DataLintIssue, LintSeverity};
+use crate::actions::tags::{TagDefinition, TagUsage};
+use crate::actions::unfill::UnfilledLicense;
+use crate::drift::DriftReport;
+use crate::markdown;
+use crate::history::SyncRecord;
+use crate::manifest::ManifestLicense;
+use crate::parser;
+use crate::permissiveness::PermissivenessWeights;
+use crate::provenance::ProvenanceRecord;
+use crate::stats::LicenseStats;
+use crate::terminal;
+use crate::timings::PhaseTotal;
 use textwrap::{wrap, Options as TextWrapOptions}; // Keep this for actual wrapping
 use crate::constants::{
     KEY_RULES_FOR_COMPARISON_ARRAY, PLACEHOLDER_TO_ARG_MAP_TUPLES,
-    RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES, CLI_ARG_TO_CACHE_KEY_TUPLES
+    RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES, PROJECT_CONFIG_FILENAME, DRIFT_MIN_SIMILARITY
 };
 
-fn PrintWrappedText(text: &str, indent: usize, width: usize) {
+// Every Print* below is a thin wrapper over a Render* that builds the output as a
+// String, so the same text can be returned from the library or checked in tests.
+// Writing to a String cannot fail.
+macro_rules! out {
+    ($buf:expr, $($arg:tt)*) => {{ let _ = write!($buf, $($arg)*); }};
+}
+
+macro_rules! outln {
+    ($buf:expr) => { $buf.push('\n') };
+    ($buf:expr, $($arg:tt)*) => {{ let _ = writeln!($buf, $($arg)*); }};
+}
+
+fn WriteWrappedText(out: &mut String, text: &str, indent: usize, width: usize) {
     let indentStr = " ".repeat(indent);
-    let options = TextWrapOptions::new(width - indent).subsequent_indent(&indentStr);
+    let options = TextWrapOptions::new(width.saturating_sub(indent).max(20)).subsequent_indent(&indentStr);
 
     for line in wrap(text, options) {
 
-        println!("{}{}", indentStr, line);
+        outln!(out, "{}{}", indentStr, line);
 
     }
 
 }
 
-pub fn PrintSimpleLicenseList(cache: &Cache, targetKeys: &[String]) {
-    println!("\n{}", "Available Licenses (SPDX ID: Title):".bold());
-    println!("{}", "-".repeat(50).dimmed());
+// With weights, each license is followed by its permissiveness score
+pub fn RenderSimpleLicenseList(cache: &Cache, targetKeys: &[String], weights: Option<&PermissivenessWeights>) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n{}", "Available Licenses (SPDX ID: Title):".bold());
+    outln!(out, "{}", "-".repeat(50).dimmed());
 
     for key in targetKeys {
 
         if let Some(license) = cache.licenses.get(key) {
 
-            println!("  {:<25} : {}",
-                license.spdxId.cyan(), // spdxId is correct
-                license.title
+            let score = weights
+                .map(|weights| format!(" ({}/100)", weights.Score(license)).dimmed().to_string())
+                .unwrap_or_default();
+            outln!(out, "  {} : {}{}",
+                terminal::HyperlinkPadded(&license.spdxId, &terminal::LicensePageUrl(&license.spdxId), 25).cyan(), // spdxId is correct
+                license.title,
+                score
             );
 
         }
@@ -41,32 +84,41 @@ pub fn PrintSimpleLicenseList(cache: &Cache, targetKeys: &[String]) {
 
     }
 
+    out
 }
 
-pub fn PrintDetailedLicenseList(
+pub fn PrintSimpleLicenseList(cache: &Cache, targetKeys: &[String], weights: Option<&PermissivenessWeights>) {
+    print!("{}", RenderSimpleLicenseList(cache, targetKeys, weights));
+}
+
+pub fn RenderDetailedLicenseList(
     cache: &Cache,
     targetKeys: &[String],
     _rulesDataContent: &Option<RulesDataContent>,
-) {
+    weights: &PermissivenessWeights,
+) -> String {
+    let mut out = String::new();
 
     for (i, key) in targetKeys.iter().enumerate() {
 
         if let Some(license) = cache.licenses.get(key) {
 
-            println!("\n{}", format!("SPDX ID: {}", license.spdxId).cyan().bold()); // spdxId is correct
-            println!("{}", format!("Title: {}", license.title).bold());
+            let linkedId = terminal::Hyperlink(&license.spdxId, &terminal::LicensePageUrl(&license.spdxId));
+            outln!(out, "\n{}", format!("SPDX ID: {}", linkedId).cyan().bold()); // spdxId is correct
+            outln!(out, "{}", format!("Title: {}", license.title).bold());
+            outln!(out, "{}: {}/100", "Permissiveness".bold(), weights.Score(license));
 
 
             if let Some(nick) = &license.nickname {
 
-                println!("{}", format!("Nickname: {}", nick).italic()); // nickname is correct
+                outln!(out, "{}", format!("Nickname: {}", nick).italic()); // nickname is correct
 
             }
 
             if let Some(desc) = &license.description {
 
                  let shortDesc = truncate_str(desc, 100);
-                 println!("{}: {}", "Description".bold(), shortDesc);
+                 outln!(out, "{}: {}", "Description".bold(), shortDesc);
 
             }
 
@@ -79,7 +131,7 @@ pub fn PrintDetailedLicenseList(
             ] {
 
                 let labels: Vec<&str> = rulesList.iter().map(|r| r.label.as_str()).collect();
-                println!("{} ({}): {}",
+                outln!(out, "{} ({}): {}",
                     colorFn(catName.bold()),
                     labels.len().to_string().blue(),
                     if labels.is_empty() { "None".dimmed().to_string() } else { labels.join(", ") }
@@ -90,7 +142,7 @@ pub fn PrintDetailedLicenseList(
 
             if i < targetKeys.len() - 1 {
 
-                println!("{}", "---".dimmed());
+                outln!(out, "{}", "---".dimmed());
 
             }
 
@@ -100,33 +152,105 @@ pub fn PrintDetailedLicenseList(
 
     }
 
+    out
 }
 
-pub fn PrintLicenseInfoPanel(
+pub fn PrintDetailedLicenseList(
+    cache: &Cache,
+    targetKeys: &[String],
+    rulesDataContent: &Option<RulesDataContent>,
+    weights: &PermissivenessWeights,
+) {
+    print!("{}", RenderDetailedLicenseList(cache, targetKeys, rulesDataContent, weights));
+}
+
+// Table cells for the JSON values `--columns` selects: lists are comma-separated and
+// missing values left blank
+fn ColumnCellText(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(items) => items.iter().map(ColumnCellText).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+pub fn RenderColumnTable(columns: &[ListColumn], rows: &[Vec<serde_json::Value>]) -> String {
+    let mut out = String::new();
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(columns)
+                .map(|(value, column)| {
+                    let text = ColumnCellText(value);
+                    if *column == ListColumn::Description { truncate_str(&text, 60).to_string() } else { text }
+                })
+                .collect()
+        })
+        .collect();
+
+    let headers: Vec<String> = columns.iter().map(|column| column.Name()).collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| cells.iter().map(|row| row[i].chars().count()).chain([header.len()]).max().unwrap_or(0))
+        .collect();
+
+    let line = |texts: &[String]| {
+        texts
+            .iter()
+            .zip(&widths)
+            .map(|(text, width)| format!("{:<width$}", text, width = *width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    outln!(out, "{}", line(&headers).bold());
+    for row in &cells {
+        outln!(out, "{}", line(row));
+    }
+
+    out
+}
+
+pub fn PrintColumnTable(columns: &[ListColumn], rows: &[Vec<serde_json::Value>]) {
+    print!("{}", RenderColumnTable(columns, rows));
+}
+
+pub fn RenderLicenseInfoPanel(
     licenseEntry: &LicenseEntry,
     fieldsDataContent: &Option<FieldsDataContent>,
-) {
-    println!("\n--- {} ({}) ---",
+    permissivenessScore: u8,
+) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n--- {} ({}) ---",
         licenseEntry.title.bold(),
-        licenseEntry.spdxId.bold() // spdxId is correct
+        terminal::Hyperlink(&licenseEntry.spdxId, &terminal::LicensePageUrl(&licenseEntry.spdxId)).bold() // spdxId is correct
     );
 
 
     if let Some(nick) = &licenseEntry.nickname {
 
-        println!("\n{}", format!("Nickname: {}", nick).italic()); // nickname is correct
+        outln!(out, "\n{}", format!("Nickname: {}", nick).italic()); // nickname is correct
 
     }
 
-    fn PrintTextBlockDisplay(label: &str, textOpt: Option<&String>) {
+    outln!(out, "\n{}: {}/100", "Permissiveness".bold(), permissivenessScore);
+
+    fn WriteTextBlockDisplay(out: &mut String, label: &str, textOpt: Option<&String>) {
 
         if let Some(text) = textOpt {
 
 
             if !text.trim().is_empty() {
 
-                println!("\n{}:", label.bold());
-                PrintWrappedText(text, 2, 80);
+                outln!(out, "\n{}:", label.bold());
+                WriteWrappedText(out, &markdown::RenderInlineMarkdown(text), 2, terminal::WrapWidth());
 
             }
 
@@ -135,8 +259,8 @@ pub fn PrintLicenseInfoPanel(
 
     }
 
-    PrintTextBlockDisplay("Description", licenseEntry.description.as_ref()); // description is correct
-    PrintTextBlockDisplay("How to Apply", licenseEntry.infoComponents.howToApplyText.as_ref()); // infoComponents, howToApplyText are correct
+    WriteTextBlockDisplay(&mut out, "Description", licenseEntry.description.as_ref()); // description is correct
+    WriteTextBlockDisplay(&mut out, "How to Apply", licenseEntry.infoComponents.howToApplyText.as_ref()); // infoComponents, howToApplyText are correct
 
     let parsedRules = &licenseEntry.infoComponents.parsedRules; // infoComponents, parsedRules are correct
 
@@ -149,16 +273,17 @@ pub fn PrintLicenseInfoPanel(
 
         if !rulesList.is_empty() {
 
-            println!("\n{}:", colorFn(catName.bold()));
+            outln!(out, "\n{}:", colorFn(catName.bold()));
 
             for ruleDetail in rulesList {
 
-                println!("  - {} ({})",
+                outln!(out, "  - {} ({})",
                     colorFn(ruleDetail.label.bold()),
                     ruleDetail.tag.dimmed()
                 );
-                let shortDesc = truncate_str(&ruleDetail.description, 80);
-                println!("    {}", shortDesc.italic().dimmed());
+                let plainDesc = markdown::StripInlineMarkdown(&ruleDetail.description);
+                let shortDesc = truncate_str(&plainDesc, 80);
+                outln!(out, "    {}", shortDesc.italic().dimmed());
 
             }
 
@@ -174,11 +299,15 @@ pub fn PrintLicenseInfoPanel(
 
         if !usingMap.is_empty() {
 
-            println!("\n{}", "Notable Projects Using This License:".bold());
+            outln!(out, "\n{}", "Notable Projects Using This License:".bold());
 
             for (project, url) in usingMap {
 
-                println!("  - {}: {}", project, url);
+                if url.is_empty() {
+                    outln!(out, "  - {}", project);
+                } else {
+                    outln!(out, "  - {}: {}", terminal::Hyperlink(project, url), terminal::Hyperlink(url, url));
+                }
 
             }
 
@@ -188,19 +317,18 @@ pub fn PrintLicenseInfoPanel(
 
     }
 
-    PrintTextBlockDisplay("Note", licenseEntry.infoComponents.noteText.as_ref()); // infoComponents, noteText are correct
+    WriteTextBlockDisplay(&mut out, "Note", licenseEntry.infoComponents.noteText.as_ref()); // infoComponents, noteText are correct
 
     let placeholderMapCliArgs: HashMap<_,_> = PLACEHOLDER_TO_ARG_MAP_TUPLES.iter().cloned().collect();
 
 
     if !licenseEntry.placeholdersInBody.is_empty() { // placeholdersInBody is correct
 
-        println!("\n{}", "Placeholders in Body:".bold());
+        outln!(out, "\n{}", "Placeholders in Body:".bold());
 
         for phFullStr in &licenseEntry.placeholdersInBody {
 
-            let phNoBrackets = phFullStr.trim_matches(|c| c == '[' || c == ']');
-            let phLower = phNoBrackets.to_lowercase();
+            let phLower = parser::PlaceholderName(phFullStr);
             let mut description = "No description available".to_string();
 
 
@@ -218,26 +346,205 @@ pub fn PrintLicenseInfoPanel(
 
             let argSuggestion = placeholderMapCliArgs.get(phLower.as_str()).unwrap_or(&"(no direct argument)");
             let defaultInfo = if phLower == "year" || phLower == "yyyy" { " (defaults to current year)" } else { "" };
-            println!("  - {}", phFullStr.magenta().bold());
-            println!("    {}: {}", "Description".dimmed(), description);
-            println!("    {}: {}{}", "Argument".dimmed(), argSuggestion, defaultInfo);
+            outln!(out, "  - {}", phFullStr.magenta().bold());
+            outln!(out, "    {}: {}", "Description".dimmed(), description);
+            outln!(out, "    {}: {}{}", "Argument".dimmed(), argSuggestion, defaultInfo);
 
         }
 
 
     } else {
 
-        println!("\n{}: {}", "Placeholders in Body".bold(), "(None detected)".dimmed());
+        outln!(out, "\n{}: {}", "Placeholders in Body".bold(), "(None detected)".dimmed());
 
     }
 
+    out
 }
 
-pub fn PrintPlaceholderList(
+pub fn PrintLicenseInfoPanel(
     licenseEntry: &LicenseEntry,
     fieldsDataContent: &Option<FieldsDataContent>,
+    permissivenessScore: u8,
+    stats: Option<&LicenseStats>,
 ) {
-    println!("\n--- {} ({}) ---",
+    let mut out = RenderLicenseInfoPanel(licenseEntry, fieldsDataContent, permissivenessScore);
+
+    if let Some(stats) = stats {
+        out.push_str(&RenderLicenseStats(stats));
+    }
+
+    print!("{}", out);
+}
+
+fn CopyleftSummary(stats: &LicenseStats) -> String {
+    if stats.copyleftClauses.is_empty() {
+        "none".to_string()
+    } else {
+        stats.copyleftClauses.join(", ")
+    }
+}
+
+pub fn RenderLicenseStats(stats: &LicenseStats) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n{}:", "Statistics".bold());
+    outln!(out, "  {:<18}{}", "Words", stats.wordCount);
+    outln!(out, "  {:<18}~{} min", "Reading time", stats.readingMinutes);
+    outln!(out, "  {:<18}{}", "Paragraphs", stats.paragraphCount);
+    outln!(out, "  {:<18}{}", "Placeholders", stats.placeholderCount);
+    outln!(out, "  {:<18}{}", "Copyleft clauses", CopyleftSummary(stats));
+
+    out
+}
+
+// One row per license, so length and copyleft reach can be weighed side by side
+pub fn RenderStatsTable(rows: &[(String, LicenseStats)]) -> String {
+    let mut out = String::new();
+    let labelWidth = rows.iter().map(|(label, _)| label.chars().count() + 2).max().unwrap_or(0).max(20);
+
+    outln!(out, "\n{}", "Text Statistics:".bold());
+    outln!(out, "{:<labelWidth$}{:>7} {:>9} {:>11} {:>13}  {}",
+        "SPDX ID".cyan(), "Words", "Reading", "Paragraphs", "Placeholders", "Copyleft clauses");
+
+    for (label, stats) in rows {
+        outln!(out, "{:<labelWidth$}{:>7} {:>9} {:>11} {:>13}  {}",
+            label.cyan(),
+            stats.wordCount,
+            format!("~{} min", stats.readingMinutes),
+            stats.paragraphCount,
+            stats.placeholderCount,
+            CopyleftSummary(stats)
+        );
+    }
+
+    out
+}
+
+pub fn PrintStatsTable(rows: &[(String, LicenseStats)]) {
+    print!("{}", RenderStatsTable(rows));
+}
+
+fn PlaceholderDescription(phLower: &str, fieldsDataContent: &Option<FieldsDataContent>) -> String {
+    fieldsDataContent
+        .as_ref()
+        .and_then(|fields| fields.items.iter().find(|f| f.name.to_lowercase() == phLower))
+        .map(|field| field.description.clone())
+        .unwrap_or_else(|| "No description available".to_string())
+}
+
+// Footnote marker after a placeholder: superscript digits, or ^N in ASCII mode
+fn FootnoteMarker(number: usize) -> String {
+    if terminal::AsciiOnly() {
+        return format!("^{}", number);
+    }
+
+    const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    number.to_string().chars().filter_map(|d| d.to_digit(10)).map(|d| SUPERSCRIPTS[d as usize]).collect()
+}
+
+// The template text with every placeholder highlighted. With `annotate`, each distinct
+// placeholder gets a footnote number, explained below the text.
+pub fn RenderLicenseBody(
+    licenseEntry: &LicenseEntry,
+    fieldsDataContent: &Option<FieldsDataContent>,
+    annotate: bool,
+) -> String {
+    let mut out = String::new();
+    let body = parser::LicenseTemplateBody(&licenseEntry.fileContentCached);
+
+    // Longest first, so a placeholder that contains another one wins
+    let mut placeholders: Vec<&String> = licenseEntry.placeholdersInBody.iter().collect();
+    placeholders.sort_by_key(|ph| std::cmp::Reverse(ph.len()));
+    let pattern = placeholders.iter().map(|ph| regex::escape(ph)).collect::<Vec<_>>().join("|");
+
+    let placeholderRe = regex::Regex::new(&pattern).ok().filter(|_| !placeholders.is_empty());
+
+    // Footnotes are numbered in order of first appearance in the text
+    let mut order: Vec<String> = Vec::new();
+    for found in placeholderRe.iter().flat_map(|re| re.find_iter(&body)) {
+        if !order.iter().any(|ph| ph == found.as_str()) {
+            order.push(found.as_str().to_string());
+        }
+    }
+
+    let highlighted = match &placeholderRe {
+        Some(re) => re
+            .replace_all(&body, |caps: &regex::Captures| {
+                let ph = &caps[0];
+                let marker = order
+                    .iter()
+                    .position(|candidate| candidate == ph)
+                    .filter(|_| annotate)
+                    .map(|index| FootnoteMarker(index + 1))
+                    .map(|marker| marker.dimmed().to_string())
+                    .unwrap_or_default();
+                format!("{}{}", ph.yellow().bold(), marker)
+            })
+            .to_string(),
+        None => body.clone(),
+    };
+
+    outln!(out, "{}", highlighted.trim_end());
+
+    if annotate && !order.is_empty() {
+        let placeholderMapCliArgs: HashMap<_,_> = PLACEHOLDER_TO_ARG_MAP_TUPLES.iter().cloned().collect();
+
+        outln!(out, "\n{}", "Placeholders:".bold());
+
+        for (index, phFullStr) in order.iter().enumerate() {
+            let phLower = parser::PlaceholderName(phFullStr);
+            let argument = placeholderMapCliArgs
+                .get(phLower.as_str())
+                .map(|arg| if arg.starts_with('(') { format!(" {}", arg) } else { format!(" ({})", arg) })
+                .unwrap_or_default();
+
+            outln!(out, "  {:>3}. {}: {}{}",
+                index + 1,
+                phFullStr.yellow().bold(),
+                PlaceholderDescription(&phLower, fieldsDataContent),
+                argument.dimmed()
+            );
+        }
+    }
+
+    out
+}
+
+pub fn PrintLicenseBody(
+    licenseEntry: &LicenseEntry,
+    fieldsDataContent: &Option<FieldsDataContent>,
+    annotate: bool,
+) {
+    terminal::PrintPaged(&RenderLicenseBody(licenseEntry, fieldsDataContent, annotate));
+}
+
+// The info panel followed by the whole license text, paged since it rarely fits on screen
+pub fn PrintLicenseInfoWithText(
+    licenseEntry: &LicenseEntry,
+    fieldsDataContent: &Option<FieldsDataContent>,
+    permissivenessScore: u8,
+    stats: Option<&LicenseStats>,
+) {
+    let mut out = RenderLicenseInfoPanel(licenseEntry, fieldsDataContent, permissivenessScore);
+
+    if let Some(stats) = stats {
+        out.push_str(&RenderLicenseStats(stats));
+    }
+
+    outln!(out, "\n--- {} ---\n", "License Text".bold());
+    out.push_str(&RenderLicenseBody(licenseEntry, fieldsDataContent, false));
+
+    terminal::PrintPaged(&out);
+}
+
+pub fn RenderPlaceholderList(
+    licenseEntry: &LicenseEntry,
+    fieldsDataContent: &Option<FieldsDataContent>,
+) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n--- {} ({}) ---",
         format!("Placeholders for {}", licenseEntry.title).bold(),
         licenseEntry.spdxId.bold() // spdxId is correct
     );
@@ -246,15 +553,14 @@ pub fn PrintPlaceholderList(
 
     if licenseEntry.placeholdersInBody.is_empty() { // placeholdersInBody is correct
 
-        println!("  {}", "(No standard [placeholder] patterns found)".dimmed());
+        outln!(out, "  {}", "(No standard [placeholder] patterns found)".dimmed());
 
     } else {
 
 
         for phFullStr in &licenseEntry.placeholdersInBody { // placeholdersInBody is correct
 
-            let phNoBrackets = phFullStr.trim_matches(|c| c == '[' || c == ']');
-            let phLower = phNoBrackets.to_lowercase();
+            let phLower = parser::PlaceholderName(phFullStr);
             let mut description = "No description available".to_string();
 
 
@@ -272,122 +578,588 @@ pub fn PrintPlaceholderList(
 
             let argSuggestion = placeholderMapCliArgs.get(phLower.as_str()).unwrap_or(&"(no direct argument)");
             let defaultInfo = if phLower == "year" || phLower == "yyyy" { " (defaults to current year if not provided)" } else { "" };
-            println!("  - {}", phFullStr.magenta().bold());
-            println!("    {}: {}", "Description".dimmed(), description);
-            println!("    {}: {}{}", "Argument".dimmed(), argSuggestion, defaultInfo);
+            outln!(out, "  - {}", phFullStr.magenta().bold());
+            outln!(out, "    {}: {}", "Description".dimmed(), description);
+            outln!(out, "    {}: {}{}", "Argument".dimmed(), argSuggestion, defaultInfo);
 
         }
 
 
     }
 
+    out
 }
 
-pub fn PrintComparisonTable(
-    licensesToCompare: &[&LicenseEntry],
-    _rulesDataContent: &Option<RulesDataContent>,
+pub fn RenderResolvedPlaceholders(
+    licenseEntry: &LicenseEntry,
+    resolvedPlaceholders: &HashMap<String, ResolvedValue>,
+    precedence: &[PlaceholderSource],
+) -> String {
+    let mut out = String::new();
+    let precedenceNames: Vec<&str> = precedence.iter().map(|s| s.Name()).collect();
+
+    outln!(out, "\n--- {} ({}) ---", format!("Placeholder resolution for {}", licenseEntry.title).bold(), licenseEntry.spdxId.bold());
+    outln!(out, "  {}", format!("precedence: {}", precedenceNames.join(" > ")).dimmed());
+
+    if licenseEntry.placeholdersInBody.is_empty() {
+        outln!(out, "  {}", "(No standard [placeholder] patterns found)".dimmed());
+        return out;
+    }
+
+    let placeholderMapCliArgs: HashMap<_,_> = PLACEHOLDER_TO_ARG_MAP_TUPLES.iter().cloned().collect();
+    let rawPhToStdKeyMap: HashMap<_,_> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES.iter().cloned().collect();
+
+    for phFullStr in &licenseEntry.placeholdersInBody {
+        let phLower = parser::PlaceholderName(phFullStr);
+        let standardKey = rawPhToStdKeyMap.get(phLower.as_str());
+
+        match standardKey.and_then(|key| resolvedPlaceholders.get(*key)) {
+            Some(resolved) => outln!(out, "  - {}: \"{}\" from {}",
+                phFullStr.magenta().bold(),
+                resolved.value,
+                SourceLabel(resolved.source, &phLower, &placeholderMapCliArgs)
+            ),
+            None => {
+                let hint = match placeholderMapCliArgs.get(phLower.as_str()) {
+                    Some(arg) if standardKey.is_some() => format!("unfilled; pass {}", arg),
+                    _ if standardKey.is_some() => "unfilled".to_string(),
+                    _ => "unfilled; no argument or preference maps to it".to_string(),
+                };
+                outln!(out, "  - {}: {}", phFullStr.magenta().bold(), hint.red());
+            }
+        }
+    }
+
+    out
+}
+
+pub fn PrintResolvedPlaceholders(
+    licenseEntry: &LicenseEntry,
+    resolvedPlaceholders: &HashMap<String, ResolvedValue>,
+    precedence: &[PlaceholderSource],
+) {
+    print!("{}", RenderResolvedPlaceholders(licenseEntry, resolvedPlaceholders, precedence));
+}
+
+pub fn PrintPlaceholderList(
+    licenseEntry: &LicenseEntry,
+    fieldsDataContent: &Option<FieldsDataContent>,
+) {
+    print!("{}", RenderPlaceholderList(licenseEntry, fieldsDataContent));
+}
+
+fn LicenseHasRule(license: &LicenseEntry, tagKey: &str) -> bool {
+    if let Some(baseTag) = tagKey.strip_suffix("_perm") {
+        license.permissions.iter().any(|t| t == baseTag)
+    } else if let Some(baseTag) = tagKey.strip_suffix("_lim") {
+        license.limitations.iter().any(|t| t == baseTag)
+    } else {
+        license.permissions.iter()
+            .chain(&license.conditions)
+            .chain(&license.limitations)
+            .any(|t| t == tagKey)
+    }
+}
+
+// Without color the marks alone are easy to misread in a dense table, so spell them out
+fn RuleIndicator(hasRule: bool) -> ColoredString {
+    let yesMark = if terminal::AsciiOnly() { "  +  " } else { "  ✓  " };
+
+    match (hasRule, terminal::ColorEnabled()) {
+        (true, true) => yesMark.green().bold(),
+        (false, true) => "  X  ".red().bold(),
+        (true, false) => " yes ".into(),
+        (false, false) => " no  ".into(),
+    }
+}
+
+// Rows for cached licenses link to their page; rows for local files stay plain
+fn LicenseLabelCell(rowLabel: &str, license: &LicenseEntry, width: usize) -> ColoredString {
+    if rowLabel == license.spdxId {
+        terminal::HyperlinkPadded(rowLabel, &terminal::LicensePageUrl(&license.spdxId), width).cyan()
+    } else {
+        format!("{:<width$}", rowLabel).cyan()
+    }
+}
+
+// Each license as "+adds / -drops" per category relative to the baseline. Tags are shown
+// with their rules.yml label when it is available.
+pub fn RenderBaselineComparison(
+    baselineLabel: &str,
+    deltas: &[(&str, Vec<RuleDelta>)],
+    rulesDataContent: &Option<RulesDataContent>,
+) -> String {
+    let mut out = String::new();
+
+    let labelOf = |category: &str, tag: &str| {
+        rulesDataContent
+            .as_ref()
+            .and_then(|rules| {
+                let rulesInCategory = match category {
+                    "permissions" => &rules.permissions,
+                    "conditions" => &rules.conditions,
+                    _ => &rules.limitations,
+                };
+                rulesInCategory.iter().find(|rule| rule.tag == tag)
+            })
+            .map(|rule| format!("{} {}", tag, format!("({})", rule.label).dimmed()))
+            .unwrap_or_else(|| tag.to_string())
+    };
+
+    outln!(out, "Baseline: {}", baselineLabel.cyan().bold());
+
+    for (label, categoryDeltas) in deltas {
+
+        outln!(out, "\n{} {}", label.cyan().bold(), format!("vs {}", baselineLabel).dimmed());
+
+        if categoryDeltas.iter().all(|delta| delta.added.is_empty() && delta.dropped.is_empty()) {
+            outln!(out, "  {}", "Same rules as the baseline.".dimmed());
+            continue;
+        }
+
+        for delta in categoryDeltas {
+
+            if delta.added.is_empty() && delta.dropped.is_empty() {
+                continue;
+            }
+
+            outln!(out, "  {}:", delta.category.bold());
+
+            for tag in &delta.added {
+                outln!(out, "    {} {}", "+".green().bold(), labelOf(delta.category, tag));
+            }
+            for tag in &delta.dropped {
+                outln!(out, "    {} {}", "-".red().bold(), labelOf(delta.category, tag));
+            }
+
+        }
+
+    }
+
+    out
+}
+
+pub fn PrintBaselineComparison(
+    baselineLabel: &str,
+    deltas: &[(&str, Vec<RuleDelta>)],
+    rulesDataContent: &Option<RulesDataContent>,
 ) {
-    let licenseNames: Vec<String> = licensesToCompare.iter().map(|l| l.spdxId.clone()).collect(); // spdxId is correct
-    println!("Comparing: {}", licenseNames.join(", ").cyan());
-    println!("\n{}", "Key Rule Indicators Table (Simplified):".bold());
+    print!("{}", RenderBaselineComparison(baselineLabel, deltas, rulesDataContent));
+}
+
+pub fn RenderComparisonTable(
+    licensesToCompare: &[(String, &LicenseEntry)],
+    _rulesDataContent: &Option<RulesDataContent>,
+) -> String {
+    let mut out = String::new();
+
+    // Rows are labelled by SPDX ID, or by file and detected ID for local files
+    let licenseNames: Vec<&str> = licensesToCompare.iter().map(|(label, _)| label.as_str()).collect();
+    let labelWidth = licenseNames.iter().map(|name| name.chars().count() + 2).max().unwrap_or(0).max(20);
+    outln!(out, "Comparing: {}", licenseNames.join(", ").cyan());
+    outln!(out, "\n{}", "Key Rule Indicators Table (Simplified):".bold());
+
+    let wideTableWidth = labelWidth + KEY_RULES_FOR_COMPARISON_ARRAY.len() * 13;
+
+    // Too wide for the terminal: one row per rule and one column per license instead
+    if terminal::OutputWidth().is_some_and(|width| wideTableWidth > width) {
+        let ruleWidth = KEY_RULES_FOR_COMPARISON_ARRAY.iter().map(|(label, _)| label.len()).max().unwrap_or(0) + 2;
+        let columnWidths: Vec<usize> = licenseNames.iter().map(|name| name.chars().count().max(5) + 1).collect();
+
+        out!(out, "{:<ruleWidth$}", "Rule");
 
-    print!("{:<20}", "SPDX ID".cyan());
+        for ((rowLabel, license), width) in licensesToCompare.iter().zip(&columnWidths) {
+            out!(out, " {}", LicenseLabelCell(rowLabel, license, *width));
+        }
+
+        outln!(out);
+
+        for (label, tagKey) in KEY_RULES_FOR_COMPARISON_ARRAY.iter() {
+
+            out!(out, "{:<ruleWidth$}", label);
+
+            for ((_, license), width) in licensesToCompare.iter().zip(&columnWidths) {
+                out!(out, " {:<width$}", RuleIndicator(LicenseHasRule(license, tagKey)));
+            }
+
+            outln!(out);
+
+        }
+
+        return out;
+    }
+
+    out!(out, "{:<labelWidth$}", "SPDX ID".cyan());
 
     for (label, _) in KEY_RULES_FOR_COMPARISON_ARRAY.iter() {
 
         let wrappedLabelParts: Vec<String> = textwrap::wrap(label, 10).iter().map(|s| s.to_string()).collect();
-        print!(" {:<12}", wrappedLabelParts.get(0).unwrap_or(&"".to_string()));
+        out!(out, " {:<12}", wrappedLabelParts.first().unwrap_or(&"".to_string()));
 
     }
 
-    println!();
-    print!("{:<20}", "");
+    outln!(out);
+    out!(out, "{:<labelWidth$}", "");
 
     for (label, _) in KEY_RULES_FOR_COMPARISON_ARRAY.iter() {
 
         let wrappedLabelParts: Vec<String> = textwrap::wrap(label, 10).iter().map(|s| s.to_string()).collect();
-        print!(" {:<12}", wrappedLabelParts.get(1).unwrap_or(&"".to_string()));
+        out!(out, " {:<12}", wrappedLabelParts.get(1).unwrap_or(&"".to_string()));
 
     }
 
-    println!();
+    outln!(out);
 
 
-    for license in licensesToCompare {
+    for (rowLabel, license) in licensesToCompare {
 
-        print!("{:<20}", license.spdxId.cyan()); // spdxId is correct
+        out!(out, "{}", LicenseLabelCell(rowLabel, license, labelWidth));
 
         for (_, tagKey) in KEY_RULES_FOR_COMPARISON_ARRAY.iter() {
 
-            let mut hasRule = false;
+            out!(out, " {:<12}", RuleIndicator(LicenseHasRule(license, tagKey)));
 
+        }
 
-            if tagKey.ends_with("_perm") {
+        outln!(out);
 
-                let baseTag = tagKey.trim_end_matches("_perm");
+    }
 
-                if license.permissions.contains(&baseTag.to_string()) { hasRule = true; }
+    out
+}
+
+pub fn PrintComparisonTable(
+    licensesToCompare: &[(String, &LicenseEntry)],
+    rulesDataContent: &Option<RulesDataContent>,
+) {
+    print!("{}", RenderComparisonTable(licensesToCompare, rulesDataContent));
+}
+
+// With a reference license, each match also shows how much of its rule set it shares
+pub fn RenderFindResults(
+    matches: &[(&LicenseEntry, Option<f32>, FindRanking)],
+    totalMatches: usize,
+    similarTo: Option<&str>,
+    requireTags: &[String],
+    disallowTags: &[String],
+) -> String {
+    let mut out = String::new();
+
+    outln!(out, "Require: {}", if requireTags.is_empty() { "None".dimmed().to_string() } else { requireTags.join(", ").green().to_string() });
+    outln!(out, "Disallow: {}", if disallowTags.is_empty() { "None".dimmed().to_string() } else { disallowTags.join(", ").red().to_string() });
+
+    if let Some(referenceId) = similarTo {
+        outln!(out, "Similar to: {}", referenceId.cyan());
+    }
+
+    outln!(out, "{}", "-".repeat(50).dimmed());
 
-            } else if tagKey.ends_with("_lim") {
 
-                let baseTag = tagKey.trim_end_matches("_lim");
+    if matches.is_empty() {
+
+        outln!(out, "No licenses found matching all criteria.");
 
-                if license.limitations.contains(&baseTag.to_string()) { hasRule = true; }
+    } else {
 
-            } else if license.permissions.contains(&tagKey.to_string()) ||
-                      license.conditions.contains(&tagKey.to_string()) ||
-                      license.limitations.contains(&tagKey.to_string()) {
+        if matches.len() < totalMatches {
+            outln!(out, "Found {} matching license(s), best {} shown:", totalMatches, matches.len());
+        } else {
+            outln!(out, "Found {} matching license(s), best fit first:", totalMatches);
+        }
 
-                hasRule = true;
+        for (rank, (license, similarity, ranking)) in matches.iter().enumerate() {
 
+            let mut notes = Vec::new();
+
+            if let Some(similarity) = similarity {
+                notes.push(format!("{:.0}% same rules", similarity * 100.0));
+            }
+            notes.push(format!("score {}", ranking.score));
+            notes.push(match ranking.extraConditions.len() {
+                0 => "no extra conditions".to_string(),
+                _ => format!("extra conditions: {}", ranking.extraConditions.join(", ")),
+            });
+            if ranking.popularity > 0 {
+                notes.push(format!("used by {} notable project(s)", ranking.popularity));
             }
 
-            let indicator = if hasRule { "  ✓  ".green().bold() } else { "  X  ".red().bold() };
-            print!(" {:<12}", indicator);
+            outln!(out, "  {}. {} ({})", rank + 1, license.spdxId.cyan(), license.title);
+            outln!(out, "     {}", notes.join(" · ").dimmed());
 
         }
 
-        println!();
 
     }
 
+    out
 }
 
-pub fn PrintFindResults(matches: &[&LicenseEntry], requireTags: &[String], disallowTags: &[String]) {
-    println!("Require: {}", if requireTags.is_empty() { "None".dimmed().to_string() } else { requireTags.join(", ").green().to_string() });
-    println!("Disallow: {}", if disallowTags.is_empty() { "None".dimmed().to_string() } else { disallowTags.join(", ").red().to_string() });
-    println!("{}", "-".repeat(50).dimmed());
+pub fn PrintFindResults(
+    matches: &[(&LicenseEntry, Option<f32>, FindRanking)],
+    totalMatches: usize,
+    similarTo: Option<&str>,
+    requireTags: &[String],
+    disallowTags: &[String],
+) {
+    print!("{}", RenderFindResults(matches, totalMatches, similarTo, requireTags, disallowTags));
+}
 
+pub fn RenderTagUsage(usage: &[TagUsage], licenseCount: usize) -> String {
+    let mut out = String::new();
 
-    if matches.is_empty() {
+    outln!(out, "\n{}", format!("Rule Tags ({} cached licenses):", licenseCount).bold());
+    outln!(out, "{}", "-".repeat(50).dimmed());
 
-        println!("No licenses found matching all criteria.");
+    for tagUsage in usage {
 
-    } else {
+        let mut counts: Vec<String> = Vec::new();
+
+        for (count, category, colorFn) in [
+            (tagUsage.permissions, "permissions", ColoredString::green as fn(ColoredString)->ColoredString),
+            (tagUsage.conditions, "conditions", ColoredString::yellow as fn(ColoredString)->ColoredString),
+            (tagUsage.limitations, "limitations", ColoredString::red as fn(ColoredString)->ColoredString),
+        ] {
+
+            if count > 0 {
+                counts.push(format!("{} {}", count, colorFn(category.into())));
+            }
+
+        }
+
+        let countsStr = if counts.is_empty() { "unused".dimmed().to_string() } else { counts.join(", ") };
+        outln!(out, "  {:<22} {}{}",
+            format!("{}:", tagUsage.tag).cyan(),
+            countsStr,
+            tagUsage.label.as_ref().map(|label| format!(" {}", format!("({})", label).dimmed())).unwrap_or_default()
+        );
+
+    }
+
+    out
+}
+
+pub fn PrintTagUsage(usage: &[TagUsage], licenseCount: usize) {
+    print!("{}", RenderTagUsage(usage, licenseCount));
+}
 
-        println!("Found {} matching license(s):", matches.len());
+pub fn RenderTagExplanation(tag: &str, definitions: &[TagDefinition]) -> String {
+    let mut out = String::new();
 
-        for license in matches {
+    for definition in definitions {
 
-            println!("  - {} ({})", license.spdxId.cyan(), license.title); // spdxId is correct
+        let colorFn = match definition.category {
+            "permissions" => ColoredString::green as fn(ColoredString)->ColoredString,
+            "conditions" => ColoredString::yellow,
+            _ => ColoredString::red,
+        };
 
+        outln!(out, "\n{} {}",
+            format!("{}:", tag).cyan().bold(),
+            definition.label.as_deref().unwrap_or("(not defined in rules.yml)").bold()
+        );
+        outln!(out, "  Category: {}", colorFn(definition.category.into()));
+
+        if let Some(description) = &definition.description {
+            WriteWrappedText(&mut out, &markdown::RenderInlineMarkdown(description), 2, terminal::WrapWidth());
         }
 
+        if definition.licenses.is_empty() {
+            outln!(out, "  {}", "No cached license carries this tag.".dimmed());
+        } else {
+            outln!(out, "  Licenses ({}):", definition.licenses.len());
+            WriteWrappedText(&mut out, &definition.licenses.join(", "), 4, terminal::WrapWidth());
+        }
 
     }
 
+    out
 }
 
-pub fn DisplayLicenseSummaryAfterWrite(
+pub fn PrintTagExplanation(tag: &str, definitions: &[TagDefinition]) {
+    print!("{}", RenderTagExplanation(tag, definitions));
+}
+
+pub fn RenderSyncHistory(records: &[SyncRecord]) -> String {
+    let mut out = String::new();
+
+    if records.is_empty() {
+        outln!(out, "No syncs recorded yet. Syncs that fetch or fail to fetch files are logged here.");
+        return out;
+    }
+
+    let shortSha = |sha: &str| sha.chars().take(7).collect::<String>();
+
+    for record in records {
+
+        let mut summary = format!("fetched {} file(s), {:.1} KB", record.fetched.len(), record.bytes as f64 / 1024.0);
+
+        if !record.failures.is_empty() {
+            summary.push_str(&format!(", {}", format!("{} failure(s)", record.failures.len()).red()));
+        }
+        if record.forced {
+            summary.push_str(&format!(" {}", "(--refresh)".dimmed()));
+        }
+
+        outln!(out, "{}  {}", record.syncedAt.format("%Y-%m-%d %H:%M:%S UTC").to_string().bold(), summary);
+
+        for file in &record.fetched {
+
+            let change = match &file.previousSha {
+                Some(previous) => format!("{} -> {}", shortSha(previous), shortSha(&file.sha)),
+                None => format!("{} {}", shortSha(&file.sha), "(new)".dimmed()),
+            };
+            outln!(out, "    {:<28} {}", file.name.cyan(), change);
+
+        }
+
+        for failure in &record.failures {
+            outln!(out, "    {} {}", "failed:".red(), failure);
+        }
+
+    }
+
+    out
+}
+
+pub fn PrintSyncHistory(records: &[SyncRecord]) {
+    print!("{}", RenderSyncHistory(records));
+}
+
+// `hasHistory` is false for an in-memory cache, which cannot date any additions
+pub fn RenderCorpusStats(corpusStats: &CorpusStats, hasHistory: bool) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n{}", format!("Corpus Statistics ({} cached licenses):", corpusStats.licenseCount).bold());
+    outln!(out, "{}", "-".repeat(50).dimmed());
+
+    let percent = |count: usize| {
+        if corpusStats.licenseCount == 0 { 0.0 } else { count as f64 * 100.0 / corpusStats.licenseCount as f64 }
+    };
+
+    outln!(out, "{}", "By Category:".bold());
+    for categoryCount in &corpusStats.categories {
+        outln!(out, "  {:<22} {:>4}  {}",
+            format!("{}:", categoryCount.category).cyan(),
+            categoryCount.count,
+            format!("({:.0}%)", percent(categoryCount.count)).dimmed()
+        );
+    }
+
+    outln!(out, "{}", "Most Common Conditions:".bold());
+    if corpusStats.topConditions.is_empty() {
+        outln!(out, "  {}", "(no license carries a condition)".dimmed());
+    }
+    for condition in &corpusStats.topConditions {
+        outln!(out, "  {:<22} {:>4}  {}{}",
+            format!("{}:", condition.tag).yellow(),
+            condition.count,
+            format!("({:.0}%)", percent(condition.count)).dimmed(),
+            condition.label.as_ref().map(|label| format!(" {}", label)).unwrap_or_default()
+        );
+    }
+
+    outln!(out, "{} {:.1} per license", "Average Placeholders:".bold(), corpusStats.averagePlaceholders);
+
+    outln!(out, "{}", "Newest Additions:".bold());
+    if corpusStats.newestAdditions.is_empty() {
+        let reason = if hasHistory {
+            "(none since the first sync)"
+        } else {
+            "(no sync history is kept with --cache none)"
+        };
+        outln!(out, "  {}", reason.dimmed());
+    }
+    for addition in &corpusStats.newestAdditions {
+        outln!(out, "  {:<22} {}", addition.spdxId.green(), addition.addedAt.format("%Y-%m-%d"));
+    }
+
+    out
+}
+
+pub fn PrintCorpusStats(corpusStats: &CorpusStats, hasHistory: bool) {
+    print!("{}", RenderCorpusStats(corpusStats, hasHistory));
+}
+
+pub fn RenderSyncFailures(failures: &[String]) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n{}", format!("Sync incomplete: {} failure(s)", failures.len()).red().bold());
+
+    for failure in failures {
+        outln!(out, "  {} {}", "failed:".red(), failure);
+    }
+
+    out
+}
+
+pub fn PrintSyncFailures(failures: &[String]) {
+    eprint!("{}", RenderSyncFailures(failures));
+}
+
+pub fn RenderDataLintIssues(issues: &[DataLintIssue], licenseCount: usize) -> String {
+    let mut out = String::new();
+
+    if issues.is_empty() {
+        outln!(out, "{} {} cached license files and data files parse cleanly", "ok".green().bold(), licenseCount);
+        return out;
+    }
+
+    let mut currentFile: Option<&str> = None;
+
+    for issue in issues {
+
+        if currentFile != Some(issue.file.as_str()) {
+            outln!(out, "\n{}", issue.file.bold());
+            currentFile = Some(issue.file.as_str());
+        }
+
+        let severity = match issue.severity {
+            LintSeverity::Error => "error".red().bold(),
+            LintSeverity::Warning => "warning".yellow(),
+        };
+        outln!(out, "  {}: {} {}", severity, issue.message, format!("[{}]", issue.kind).dimmed());
+
+    }
+
+    let errors = issues.iter().filter(|issue| issue.severity == LintSeverity::Error).count();
+    outln!(out, "\n{} error(s), {} warning(s) across {} cached license files", errors, issues.len() - errors, licenseCount);
+
+    out
+}
+
+pub fn PrintDataLintIssues(issues: &[DataLintIssue], licenseCount: usize) {
+    print!("{}", RenderDataLintIssues(issues, licenseCount));
+}
+
+fn SourceLabel(source: PlaceholderSource, phLower: &str, placeholderMapCliArgs: &HashMap<&str, &str>) -> String {
+    match source {
+        PlaceholderSource::Cli => {
+            let cliArgName = placeholderMapCliArgs.get(phLower).unwrap_or(&"CLI arg");
+            format!("{} ({})", source.Label(), cliArgName).cyan().to_string()
+        }
+        PlaceholderSource::Project => format!("{} ({})", source.Label(), PROJECT_CONFIG_FILENAME).green().to_string(),
+        PlaceholderSource::Profile => source.Label().green().to_string(),
+        PlaceholderSource::Cache => source.Label().yellow().to_string(),
+        PlaceholderSource::Auto => format!("{} (git config)", source.Label()).blue().to_string(),
+        PlaceholderSource::Default => if phLower == "year" || phLower == "yyyy" {
+            "Defaulted (current year)".blue().to_string()
+        } else {
+            source.Label().blue().to_string()
+        },
+    }
+}
+
+pub fn RenderLicenseSummaryAfterWrite(
     licenseEntry: &LicenseEntry,
     _cache: &Cache,
     outputPath: &Path,
-    userProvidedForFilling: &std::collections::HashMap<String, String>,
-    cachedPlaceholdersAtStart: &std::collections::HashMap<String, String>,
+    resolvedPlaceholders: &HashMap<String, ResolvedValue>,
+    precedence: &[PlaceholderSource],
     filledLicenseBody: &str,
-    cliAllArgs: &FullCliArgs,
-) {
-    println!("\n--- {} written to {} ---",
+) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n--- {} written to {} ---",
         licenseEntry.title.bold(),
         outputPath.display().to_string().green() // outputPath is correct
     );
@@ -395,19 +1167,19 @@ pub fn DisplayLicenseSummaryAfterWrite(
 
     if let Some(nick) = &licenseEntry.nickname {
 
-        println!("\n{}", format!("Nickname: {}", nick).italic()); // nickname is correct
+        outln!(out, "\n{}", format!("Nickname: {}", nick).italic()); // nickname is correct
 
     }
 
-    fn PrintTextBlockSummary(label: &str, textOpt: Option<&String>) {
+    fn WriteTextBlockSummary(out: &mut String, label: &str, textOpt: Option<&String>) {
 
         if let Some(text) = textOpt {
 
 
             if !text.trim().is_empty() {
 
-                println!("\n{}:", label.bold());
-                PrintWrappedText(text, 2, 80);
+                outln!(out, "\n{}:", label.bold());
+                WriteWrappedText(out, &markdown::RenderInlineMarkdown(text), 2, terminal::WrapWidth());
 
             }
 
@@ -416,7 +1188,7 @@ pub fn DisplayLicenseSummaryAfterWrite(
 
     }
 
-    PrintTextBlockSummary("Description", licenseEntry.description.as_ref()); // description is correct
+    WriteTextBlockSummary(&mut out, "Description", licenseEntry.description.as_ref()); // description is correct
 
     let parsedRules = &licenseEntry.infoComponents.parsedRules; // infoComponents, parsedRules are correct
 
@@ -429,11 +1201,11 @@ pub fn DisplayLicenseSummaryAfterWrite(
 
         if !rulesList.is_empty() {
 
-            println!("\n{}:", colorFn(catName.bold()));
+            outln!(out, "\n{}:", colorFn(catName.bold()));
 
             for ruleDetail in rulesList {
 
-                println!("  - {} ({})",
+                outln!(out, "  - {} ({})",
                     colorFn(ruleDetail.label.bold()),
                     ruleDetail.tag.dimmed()
                 );
@@ -446,109 +1218,587 @@ pub fn DisplayLicenseSummaryAfterWrite(
 
     }
 
-    PrintTextBlockSummary("Note", licenseEntry.infoComponents.noteText.as_ref()); // infoComponents, noteText are correct
+    WriteTextBlockSummary(&mut out, "Note", licenseEntry.infoComponents.noteText.as_ref()); // infoComponents, noteText are correct
 
     let placeholderMapCliArgs: HashMap<_,_> = PLACEHOLDER_TO_ARG_MAP_TUPLES.iter().cloned().collect();
     let rawPhToStdKeyMap: HashMap<_,_> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES.iter().cloned().collect();
-    let cliArgToCacheKeyMap: HashMap<_,_> = CLI_ARG_TO_CACHE_KEY_TUPLES.iter().cloned().collect();
 
 
     if !licenseEntry.placeholdersInBody.is_empty() {
 
-        println!("\n{}", "Placeholder Values Used:".bold());
+        let precedenceNames: Vec<&str> = precedence.iter().map(|s| s.Name()).collect();
+        outln!(out, "\n{} {}", "Placeholder Values Used".bold(), format!("(precedence: {})", precedenceNames.join(" > ")).dimmed());
 
         for phFullStr in &licenseEntry.placeholdersInBody { // placeholdersInBody is correct
 
-            let phNoBrackets = phFullStr.trim_matches(|c| c == '[' || c == ']');
-            let phLower = phNoBrackets.to_lowercase();
-            let standardKeyOpt = rawPhToStdKeyMap.get(phLower.as_str());
+            let phLower = parser::PlaceholderName(phFullStr);
+            let resolvedOpt = rawPhToStdKeyMap.get(phLower.as_str()).and_then(|standardKey| resolvedPlaceholders.get(*standardKey));
 
             let mut sourceInfo: String;
             let mut valueUsedStr = "".to_string();
 
 
-            if let Some(standardKey) = standardKeyOpt {
+            if let Some(resolved) = resolvedOpt {
+
+                valueUsedStr = format!(" (Value: \"{}\")", resolved.value);
+                sourceInfo = SourceLabel(resolved.source, &phLower, &placeholderMapCliArgs);
+
+            } else {
 
-                let valueActuallyUsed = userProvidedForFilling.get(*standardKey);
+                sourceInfo = if rawPhToStdKeyMap.contains_key(phLower.as_str()) { "Not specified".red().to_string() }
+                             else { "Unknown placeholder".magenta().to_string() };
 
-                if let Some(valUsed) = valueActuallyUsed {
+                if filledLicenseBody.contains(phFullStr) {
 
-                    valueUsedStr = format!(" (Value: \"{}\")", valUsed);
+                    sourceInfo.push_str(&format!(" ({})", "remains in file!".red().bold()));
 
                 }
 
+            }
 
-                if *standardKey == "year" {
+            outln!(out, "  - {}: {}{}", phFullStr.magenta().bold(), sourceInfo, valueUsedStr);
 
-                    let yearArgExplicitlyPassed = cliAllArgs.command.as_ref().map_or(false, |cmd| {
+        }
 
-                        if let crate::cli::Commands::License(lic_args) = cmd { lic_args.year.is_some() } else { false }
 
-                    });
-                    sourceInfo = if yearArgExplicitlyPassed { "CLI argument (--year)".cyan().to_string() }
-                                  else { "Defaulted (current year)".blue().to_string() };
+    } else {
 
-                } else if userProvidedForFilling.contains_key(*standardKey) &&
-                          cliArgToCacheKeyMap.values().any(|&vStdKey| vStdKey == *standardKey) &&
-                          cliAllArgs.command.as_ref().map_or(false, |cmd| {
+        outln!(out, "\n{}: {}", "Placeholder Values Used".bold(), "(No standard placeholders in template)".dimmed());
 
-                              if let crate::cli::Commands::License(licArgs) = cmd {
+    }
 
-                                  match *standardKey {
-                                      "fullname" => licArgs.fullname.is_some(), "project" => licArgs.project.is_some(),
-                                      "email" => licArgs.email.is_some(), "projecturl" => licArgs.projecturl.is_some(),
-                                      _ => false,
-                                  }
+    out
+}
 
-                              } else { false }
+pub fn DisplayLicenseSummaryAfterWrite(
+    licenseEntry: &LicenseEntry,
+    cache: &Cache,
+    outputPath: &Path,
+    resolvedPlaceholders: &HashMap<String, ResolvedValue>,
+    precedence: &[PlaceholderSource],
+    filledLicenseBody: &str,
+) {
+    print!(
+        "{}",
+        RenderLicenseSummaryAfterWrite(licenseEntry, cache, outputPath, resolvedPlaceholders, precedence, filledLicenseBody)
+    );
+}
 
-                          })
-                {
-                    let cliArgName = placeholderMapCliArgs.get(phLower.as_str()).unwrap_or(&"CLI arg");
-                    sourceInfo = format!("CLI argument ({})", cliArgName).cyan().to_string();
+// One summary for a multi-license `license` run: where each file went, the values shared
+// by all of them, and what stayed unfilled
+pub fn RenderMultiLicenseSummaryAfterWrite(
+    written: &[WrittenLicense],
+    resolvedPlaceholders: &HashMap<String, ResolvedValue>,
+    precedence: &[PlaceholderSource],
+) -> String {
+    let mut out = String::new();
+    let placeholderMapCliArgs: HashMap<_,_> = PLACEHOLDER_TO_ARG_MAP_TUPLES.iter().cloned().collect();
+    let rawPhToStdKeyMap: HashMap<_,_> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES.iter().cloned().collect();
 
-                } else if cachedPlaceholdersAtStart.contains_key(*standardKey) {
+    outln!(out, "\n--- {} ---", format!("{} licenses written", written.len()).bold());
 
-                    sourceInfo = "Saved preference (cache)".yellow().to_string();
+    for file in written {
+        outln!(out, "  {} -> {}", file.entry.spdxId.cyan(), file.path.display().to_string().green());
+    }
 
-                } else {
+    // Standard keys in order of first appearance across the templates
+    let mut usedKeys: Vec<&str> = Vec::new();
+    for phFullStr in written.iter().flat_map(|file| &file.entry.placeholdersInBody) {
+        let phLower = parser::PlaceholderName(phFullStr);
+        if let Some(standardKey) = rawPhToStdKeyMap.get(phLower.as_str()) {
+            if !usedKeys.contains(standardKey) {
+                usedKeys.push(standardKey);
+            }
+        }
+    }
 
-                    sourceInfo = "Not specified".red().to_string();
+    if !usedKeys.is_empty() {
+        let precedenceNames: Vec<&str> = precedence.iter().map(|s| s.Name()).collect();
+        outln!(out, "\n{} {}", "Placeholder Values Used".bold(), format!("(precedence: {})", precedenceNames.join(" > ")).dimmed());
+
+        for standardKey in &usedKeys {
+            match resolvedPlaceholders.get(*standardKey) {
+                Some(resolved) => outln!(out, "  - {}: {} (Value: \"{}\")",
+                    standardKey.magenta().bold(),
+                    SourceLabel(resolved.source, standardKey, &placeholderMapCliArgs),
+                    resolved.value
+                ),
+                None => outln!(out, "  - {}: {}", standardKey.magenta().bold(), "Not specified".red()),
+            }
+        }
+    }
 
-                    if filledLicenseBody.contains(phFullStr) {
+    for file in written.iter().filter(|file| !file.unfilled.is_empty()) {
+        outln!(out, "{} {} still contains {}",
+            "Warning:".yellow().bold(),
+            file.path.display(),
+            file.unfilled.join(", ").magenta()
+        );
+    }
 
-                        sourceInfo.push_str(&format!(" ({})", "remains in file!".red().bold()));
+    out
+}
 
-                    }
+pub fn DisplayMultiLicenseSummaryAfterWrite(
+    written: &[WrittenLicense],
+    resolvedPlaceholders: &HashMap<String, ResolvedValue>,
+    precedence: &[PlaceholderSource],
+) {
+    print!("{}", RenderMultiLicenseSummaryAfterWrite(written, resolvedPlaceholders, precedence));
+}
 
-                    valueUsedStr = "".to_string();
+pub fn RenderValidationResult(licensePath: &Path, licenseEntry: &LicenseEntry) -> String {
+    let mut out = String::new();
 
-                }
+    outln!(out, "{} {} matches {} ({})",
+        "ok".green().bold(),
+        licensePath.display(),
+        licenseEntry.title.bold(),
+        licenseEntry.spdxId.cyan()
+    );
 
+    out
+}
 
-            } else {
+pub fn PrintValidationResult(licensePath: &Path, licenseEntry: &LicenseEntry) {
+    print!("{}", RenderValidationResult(licensePath, licenseEntry));
+}
 
-                sourceInfo = "Unknown placeholder".magenta().to_string();
+pub fn RenderVerifyResults(licensePath: &Path, licenseEntry: &LicenseEntry, results: &[(ManifestLicense, bool)]) -> String {
+    let mut out = String::new();
 
-                if filledLicenseBody.contains(phFullStr) {
+    outln!(out, "{} contains {} ({})", licensePath.display(), licenseEntry.title.bold(), licenseEntry.spdxId.cyan());
 
-                     sourceInfo.push_str(&format!(" ({})", "remains in file!".red().bold()));
+    for (declared, agrees) in results {
 
-                }
+        let status = if *agrees { "ok".green().bold() } else { "mismatch".red().bold() };
+        outln!(out, "  {:<9} {} declares \"{}\"", status, declared.manifestPath.display(), declared.expression);
 
-                valueUsedStr = "".to_string();
+    }
 
-            }
+    out
+}
+
+pub fn PrintVerifyResults(licensePath: &Path, licenseEntry: &LicenseEntry, results: &[(ManifestLicense, bool)]) {
+    print!("{}", RenderVerifyResults(licensePath, licenseEntry, results));
+}
+
+pub fn RenderCheckSummary(outcomes: &[CheckOutcome]) -> String {
+    let mut out = String::new();
+
+    for outcome in outcomes {
+
+        let status = match outcome.status {
+            CheckStatus::Pass => "pass".green().bold(),
+            CheckStatus::Fail => "fail".red().bold(),
+            CheckStatus::Skip => "skip".dimmed(),
+        };
+        let detail = if outcome.status == CheckStatus::Skip { outcome.detail.dimmed().to_string() } else { outcome.detail.clone() };
+        outln!(out, "  {:<6} {:<14} {}", status, outcome.name, detail);
+
+    }
+
+    let count = |status: CheckStatus| outcomes.iter().filter(|outcome| outcome.status == status).count();
+    let (passed, failed, skipped) = (count(CheckStatus::Pass), count(CheckStatus::Fail), count(CheckStatus::Skip));
+    let summary = format!("{} passed, {} failed, {} skipped", passed, failed, skipped);
+
+    if failed == 0 {
+        outln!(out, "\n{} {}", "ok".green().bold(), summary);
+    } else {
+        outln!(out, "\n{}", summary.bold());
+    }
+
+    out
+}
+
+pub fn PrintCheckSummary(outcomes: &[CheckOutcome]) {
+    print!("{}", RenderCheckSummary(outcomes));
+}
+
+pub fn RenderIdentifyCandidates(candidates: &[IdentifyCandidate]) -> String {
+    let mut out = String::new();
+
+    for (rank, candidate) in candidates.iter().enumerate() {
+
+        let score = format!("{:>5.1}%", candidate.score * 100.0);
+        let score = if candidate.exact { score.green().bold() } else if candidate.score >= DRIFT_MIN_SIMILARITY { score.yellow() } else { score.dimmed() };
+        outln!(out, "{:>2}. {} {:<20} {}{}",
+            rank + 1,
+            score,
+            candidate.spdxId.cyan(),
+            candidate.title,
+            if candidate.exact { " (exact match)".green().to_string() } else { String::new() }
+        );
+
+    }
+
+    out
+}
+
+pub fn PrintIdentifyCandidates(candidates: &[IdentifyCandidate]) {
+    print!("{}", RenderIdentifyCandidates(candidates));
+}
+
+pub fn RenderProvenanceNote(record: &ProvenanceRecord, licenseEntry: &LicenseEntry) -> String {
+    let mut out = String::new();
+
+    let shortSha = |sha: &str| sha.chars().take(7).collect::<String>();
+
+    outln!(out, "Generated from {} template {} on {} (current: {})",
+        record.spdxId.cyan(),
+        shortSha(&record.templateSha),
+        record.filledAt.format("%Y-%m-%d"),
+        shortSha(&licenseEntry.sha)
+    );
+
+    out
+}
+
+pub fn PrintProvenanceNote(record: &ProvenanceRecord, licenseEntry: &LicenseEntry) {
+    print!("{}", RenderProvenanceNote(record, licenseEntry));
+}
+
+pub fn RenderDriftReport(licensePath: &Path, licenseEntry: &LicenseEntry, report: &DriftReport) -> String {
+    let mut out = String::new();
+
+    if report.upToDate {
+        outln!(out, "{} {} is up to date with the current {} template", "ok".green().bold(), licensePath.display(), licenseEntry.spdxId.cyan());
+        return out;
+    }
+
+    outln!(out, "{} {} differs from the current {} template ({:.0}% similar):\n",
+        "outdated".yellow().bold(),
+        licensePath.display(),
+        licenseEntry.spdxId.cyan(),
+        report.similarity * 100.0
+    );
+
+    out.push_str(&RenderUnifiedDiff(&report.diff));
+
+    out
+}
+
+pub fn PrintDriftReport(licensePath: &Path, licenseEntry: &LicenseEntry, report: &DriftReport) {
+    print!("{}", RenderDriftReport(licensePath, licenseEntry, report));
+}
+
+pub fn RenderUnifiedDiff(lines: &[String]) -> String {
+    let mut out = String::new();
 
-            println!("  - {}: {}{}", phFullStr.magenta().bold(), sourceInfo, valueUsedStr);
+    for line in lines {
 
+        if line.starts_with("+++") || line.starts_with("---") || line.starts_with("@@") {
+            outln!(out, "{}", line.dimmed());
+        } else if line.starts_with('+') {
+            outln!(out, "{}", line.green());
+        } else if line.starts_with('-') {
+            outln!(out, "{}", line.red());
+        } else {
+            outln!(out, "{}", line);
         }
 
+    }
+
+    out
+}
+
+pub fn PrintUnifiedDiff(lines: &[String]) {
+    print!("{}", RenderUnifiedDiff(lines));
+}
+
+pub fn RenderPreservedValues(preserved: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+
+    if preserved.is_empty() {
+        outln!(out, "{}", "No placeholder values could be recovered from the existing file.".yellow());
+        return out;
+    }
+
+    let mut keys: Vec<&String> = preserved.keys().collect();
+    keys.sort();
+
+    outln!(out, "{}", "Preserving values from the existing file:".bold());
+
+    for key in keys {
+        outln!(out, "  - {}: \"{}\"", key.magenta(), preserved[key]);
+    }
+
+    outln!(out);
+
+    out
+}
+
+pub fn PrintPreservedValues(preserved: &HashMap<String, String>) {
+    print!("{}", RenderPreservedValues(preserved));
+}
+
+pub fn RenderUnfilledLicense(unfilled: &UnfilledLicense) -> String {
+    let mut out = String::new();
+
+    if unfilled.placeholders.is_empty() {
+        outln!(
+            out,
+            "{} {} matches {}, but holds no placeholder values.",
+            "Note:".yellow(),
+            unfilled.path.display(),
+            unfilled.spdxId.cyan()
+        );
+        return out;
+    }
+
+    outln!(out, "{} ({}):", unfilled.path.display().to_string().bold(), unfilled.spdxId.cyan());
+
+    for (key, value) in &unfilled.placeholders {
+        outln!(out, "  - {}: \"{}\"", key.magenta(), value);
+    }
+
+    out
+}
+
+pub fn PrintUnfilledLicense(unfilled: &UnfilledLicense) {
+    print!("{}", RenderUnfilledLicense(unfilled));
+}
+
+pub fn RenderHeaderCheckResults(expectedSpdxId: &str, checkedCount: usize, problems: &[(PathBuf, HeaderProblem)]) -> String {
+    let mut out = String::new();
+
+    for (path, problem) in problems {
+
+        match problem {
+            HeaderProblem::Missing => outln!(out, "  {} {}", "missing".red(), path.display()),
+            HeaderProblem::WrongId(found) => outln!(out, "  {} {} (found {})", "wrong id".yellow(), path.display(), found),
+        }
+
+    }
+
+    let summary = format!("{} of {} file(s) carry SPDX-License-Identifier: {}",
+        checkedCount - problems.len(),
+        checkedCount,
+        expectedSpdxId
+    );
 
+    if problems.is_empty() {
+        outln!(out, "{} {}", "ok".green().bold(), summary);
     } else {
+        outln!(out, "\n{}", summary.bold());
+    }
+
+    out
+}
 
-        println!("\n{}: {}", "Placeholder Values Used".bold(), "(No standard placeholders in template)".dimmed());
+pub fn RenderHeaderCoverageReport(report: &HeaderCoverageReport) -> String {
+    let mut out = String::new();
+
+    let colorPercent = |percent: f64| {
+        let text = format!("{:>5.1}%", percent);
+        if percent >= 100.0 { text.green() } else if percent >= 80.0 { text.yellow() } else { text.red() }
+    };
+
+    outln!(out, "\n{}", format!("SPDX-License-Identifier: {} coverage", report.expected).bold());
+    outln!(out, "{}", "-".repeat(50).dimmed());
+
+    for (title, groups) in [("By directory:", &report.directories), ("By language:", &report.languages)] {
+
+        outln!(out, "{}", title.bold());
+
+        let nameWidth = groups.iter().map(|group| group.name.chars().count()).max().unwrap_or(0).max(10);
+
+        for group in groups {
+            outln!(out, "  {:<nameWidth$}  {}  {:>4} / {}", group.name, colorPercent(group.percent), group.covered, group.files);
+        }
 
+        outln!(out);
+
+    }
+
+    outln!(out, "{} {} {} of {} file(s)",
+        "Total:".bold(),
+        colorPercent(report.percent),
+        report.covered,
+        report.files
+    );
+
+    out
+}
+
+pub fn PrintHeaderCoverageReport(report: &HeaderCoverageReport) {
+    print!("{}", RenderHeaderCoverageReport(report));
+}
+
+pub fn RenderAddedHeaders(spdxExpression: &str, added: &[PathBuf], alreadyPresent: usize, unsupported: usize, dryRun: bool) -> String {
+    let mut out = String::new();
+    let verb = if dryRun { "would add" } else { "added" };
+
+    for path in added {
+        outln!(out, "  {} {}", verb.green(), path.display());
+    }
+
+    outln!(out, "\n{}", format!("{} SPDX-License-Identifier: {} to {} file(s)", if dryRun { "Would add" } else { "Added" }, spdxExpression, added.len()).bold());
+
+    if alreadyPresent > 0 {
+        outln!(out, "{}", format!("{} file(s) already have an SPDX header; use check-headers to verify them.", alreadyPresent).dimmed());
+    }
+    if unsupported > 0 {
+        outln!(out, "{}", format!("{} file(s) skipped: no known comment syntax for their extension.", unsupported).dimmed());
     }
 
+    out
+}
+
+pub fn PrintAddedHeaders(spdxExpression: &str, added: &[PathBuf], alreadyPresent: usize, unsupported: usize, dryRun: bool) {
+    print!("{}", RenderAddedHeaders(spdxExpression, added, alreadyPresent, unsupported, dryRun));
+}
+
+pub fn RenderFixedHeaders(fixed: &[PathBuf], missing: usize, dryRun: bool) -> String {
+    let mut out = String::new();
+
+    if !dryRun {
+        for path in fixed {
+            outln!(out, "  {} {}", "fixed".green(), path.display());
+        }
+    }
+
+    let summary = if fixed.is_empty() {
+        "Every SPDX header is up to date.".to_string()
+    } else if dryRun {
+        format!("Would fix the header of {} file(s); run without --dry-run to apply.", fixed.len())
+    } else {
+        format!("Fixed the header of {} file(s).", fixed.len())
+    };
+    outln!(out, "\n{}", summary.bold());
+
+    if missing > 0 {
+        outln!(out, "{}", format!("{} file(s) have no SPDX header; use add-headers to add one.", missing).dimmed());
+    }
+
+    out
+}
+
+pub fn PrintFixedHeaders(fixed: &[PathBuf], missing: usize, dryRun: bool) {
+    print!("{}", RenderFixedHeaders(fixed, missing, dryRun));
+}
+
+pub fn PrintHeaderCheckResults(expectedSpdxId: &str, checkedCount: usize, problems: &[(PathBuf, HeaderProblem)]) {
+    print!("{}", RenderHeaderCheckResults(expectedSpdxId, checkedCount, problems));
+}
+
+#[cfg(feature = "net")]
+pub fn RenderMirrorHealth(report: &[(String, MirrorHealth)]) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n{}", "Raw Content Host Health:".bold());
+
+    for (host, health) in report {
+
+        let status = if health.failures == 0 { "ok".green() }
+                     else if health.successes > 0 { "degraded".yellow() }
+                     else { "failing".red() };
+        outln!(out, "  {:<35} {:<9} {} ok, {} failed", host, status, health.successes, health.failures);
+
+        if let Some(err) = &health.lastError {
+
+            outln!(out, "    {}: {}", "Last error".dimmed(), truncate_str(err, 100));
+
+        }
+
+    }
+
+    out
+}
+
+// Written to stderr: it describes the sync, not the command's actual output.
+#[cfg(feature = "net")]
+pub fn PrintMirrorHealth(report: &[(String, MirrorHealth)]) {
+    eprint!("{}", RenderMirrorHealth(report));
+}
+
+fn FormatDuration(duration: std::time::Duration) -> String {
+    if duration.as_secs() >= 1 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else {
+        format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+    }
+}
+
+pub fn RenderTimings(totals: &[PhaseTotal], elapsed: std::time::Duration) -> String {
+    let mut out = String::new();
+
+    outln!(out, "\n{}", "Timings:".bold());
+
+    for total in totals {
+        outln!(out, "  {:<12} {:>10}  {:>4}x", total.phase, FormatDuration(total.total), total.count);
+
+        // The slowest run is the interesting one when a phase repeats (e.g. one fetch per file)
+        if let Some(slowest) = total.slowest.as_ref().filter(|_| total.count > 1) {
+            outln!(
+                out,
+                "    {} {} ({})",
+                "slowest:".dimmed(),
+                slowest.detail.as_deref().unwrap_or("-"),
+                FormatDuration(slowest.duration)
+            );
+        }
+    }
+
+    outln!(out, "  {:<12} {:>10}", "total".bold(), FormatDuration(elapsed));
+
+    out
+}
+
+// Written to stderr so the report never mixes into piped command output.
+pub fn PrintTimings(totals: &[PhaseTotal], elapsed: std::time::Duration) {
+    eprint!("{}", RenderTimings(totals, elapsed));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestRenderColumnTable() {
+        colored::control::set_override(false);
+
+        let columns = [ListColumn::Id, ListColumn::Nickname, ListColumn::Permissions];
+        let rows = vec![
+            vec!["MIT".into(), serde_json::Value::Null, serde_json::json!(["Commercial use", "Modification"])],
+            vec!["LGPL-2.1".into(), "GNU LGPLv2.1".into(), serde_json::json!([])],
+        ];
+
+        assert_eq!(
+            RenderColumnTable(&columns, &rows),
+            "id        nickname      permissions\n\
+             MIT                     Commercial use, Modification\n\
+             LGPL-2.1  GNU LGPLv2.1\n"
+        );
+    }
+
+    #[test]
+    fn TestRenderHeaderCheckResults() {
+        colored::control::set_override(false);
+
+        let problems = vec![
+            (PathBuf::from("src/a.rs"), HeaderProblem::Missing),
+            (PathBuf::from("src/b.rs"), HeaderProblem::WrongId("GPL-3.0".to_string())),
+        ];
+
+        assert_eq!(
+            RenderHeaderCheckResults("MIT", 3, &problems),
+            "  missing src/a.rs\n  wrong id src/b.rs (found GPL-3.0)\n\n1 of 3 file(s) carry SPDX-License-Identifier: MIT\n"
+        );
+    }
+    #[test]
+    fn TestRenderLicenseBodyFootnotes() {
+        colored::control::set_override(false);
+        terminal::ASCII.store(true, std::sync::atomic::Ordering::SeqCst);
+
+        let license = LicenseEntry::ForTest(
+            "MIT",
+            "---\ntitle: MIT License\n---\n\nCopyright (c) [year] [fullname]\n\n[fullname] grants...",
+        );
+
+        let rendered = RenderLicenseBody(&license, &None, true);
+
+        assert!(rendered.starts_with("Copyright (c) [year]^1 [fullname]^2\n\n[fullname]^2 grants..."));
+        assert!(rendered.contains("  1. [year]: No description available (--year)"));
+    }
 }
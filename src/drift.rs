@@ -0,0 +1,209 @@
+use difflib::sequencematcher::SequenceMatcher;
+use regex::Regex;
+use std::sync::Arc;
+
+use std::collections::HashMap;
+
+use crate::constants::RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES;
+use crate::models::{Cache, LicenseEntry};
+use crate::normalize;
+use crate::parser;
+
+// How a local license file compares to the current upstream template.
+#[derive(Debug, Clone)]
+pub struct DriftReport {
+    // 0.0 - 1.0, word-level similarity ignoring case and whitespace
+    pub similarity: f32,
+    pub upToDate: bool,
+    // Unified diff (local -> upstream) over whitespace-normalized paragraphs
+    pub diff: Vec<String>,
+}
+
+fn Paragraphs(text: &str) -> Vec<String> {
+    text.split("\n\n")
+        .map(|para| para.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|para| !para.is_empty())
+        .collect()
+}
+
+fn Words(text: &str) -> Vec<String> {
+    normalize::NormalizeLicenseText(text)
+        .split(' ')
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn WordSimilarity(a: &str, b: &str) -> f32 {
+    let (aWords, bWords) = (Words(a), Words(b));
+    let mut matcher = SequenceMatcher::new(&aWords, &bWords);
+
+    matcher.ratio()
+}
+
+// Whole-paragraph pattern where each placeholder becomes a capture group matching any
+// run of text and each space matches `spacePattern`. Also returns the placeholder
+// behind each group, in order.
+fn ParagraphPattern(templatePara: &str, placeholders: &[String], spacePattern: &str) -> Option<(Regex, Vec<String>)> {
+    let mut pattern = regex::escape(templatePara);
+    let mut positions: Vec<(usize, String)> = Vec::new();
+
+    for placeholder in placeholders {
+        let escaped = regex::escape(placeholder);
+
+        for (idx, _) in pattern.match_indices(&escaped) {
+            positions.push((idx, placeholder.clone()));
+        }
+
+        pattern = pattern.replace(&escaped, "\0");
+    }
+
+    // Offsets shift as earlier placeholders are replaced, but relative order is preserved
+    // because replacement markers never overlap.
+    positions.sort_by_key(|(idx, _)| *idx);
+
+    let pattern = pattern.replace(' ', spacePattern).replace('\0', "(.*?)");
+    let re = Regex::new(&format!("(?is)^{}$", pattern)).ok()?;
+
+    Some((re, positions.into_iter().map(|(_, ph)| ph).collect()))
+}
+
+// Spaces are optional so output of --strip-unfilled (placeholder and its space gone) still matches.
+// Paragraphs that only differ in ways the matching guidelines ignore count as the same.
+fn ParagraphMatches(templatePara: &str, placeholders: &[String], localPara: &str) -> bool {
+    ParagraphPattern(templatePara, placeholders, r"\s*").is_some_and(|(re, _)| re.is_match(localPara))
+        || normalize::NormalizeLicenseText(templatePara) == normalize::NormalizeLicenseText(localPara)
+}
+
+// Values a previously filled file used for each standard placeholder key, found by
+// matching template paragraphs that contain placeholders against the file's paragraphs.
+pub fn RecoverPlaceholderValues(templateBody: &str, placeholders: &[String], localText: &str) -> HashMap<String, String> {
+    let rawToStdMap: HashMap<&str, &str> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES
+        .iter()
+        .cloned()
+        .collect();
+    let localParagraphs = Paragraphs(localText);
+    let mut recovered = HashMap::new();
+
+    for templatePara in Paragraphs(templateBody) {
+        if !placeholders.iter().any(|ph| templatePara.contains(ph.as_str())) {
+            continue;
+        }
+
+        // Required spaces keep adjacent placeholders like "[year] [fullname]" apart
+        let Some((re, groupPlaceholders)) = ParagraphPattern(&templatePara, placeholders, r"\s+") else {
+            continue;
+        };
+
+        let Some(caps) = localParagraphs.iter().find_map(|para| re.captures(para)) else {
+            continue;
+        };
+
+        for (i, placeholder) in groupPlaceholders.iter().enumerate() {
+            let value = caps.get(i + 1).map(|m| m.as_str().trim()).unwrap_or("");
+
+            // A placeholder left unfilled in the old file is not a value
+            if value.is_empty() || value == placeholder {
+                continue;
+            }
+
+            if let Some(standardKey) = rawToStdMap.get(parser::PlaceholderName(placeholder).as_str()) {
+                recovered
+                    .entry(standardKey.to_string())
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+    }
+
+    recovered
+}
+
+pub fn LineDiff(oldText: &str, newText: &str, oldLabel: &str, newLabel: &str) -> Vec<String> {
+    let oldLines: Vec<&str> = oldText.lines().collect();
+    let newLines: Vec<&str> = newText.lines().collect();
+
+    difflib::unified_diff(&oldLines, &newLines, oldLabel, newLabel, "", "", 2)
+        .into_iter()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+// Local paragraphs that match a template paragraph are swapped for it, so filled-in
+// names and years never show up as differences.
+fn AlignToTemplate(localParagraphs: Vec<String>, templateParagraphs: &[String], placeholders: &[String]) -> Vec<String> {
+    localParagraphs
+        .into_iter()
+        .map(|localPara| {
+            templateParagraphs
+                .iter()
+                .find(|templatePara| ParagraphMatches(templatePara, placeholders, &localPara))
+                .cloned()
+                .unwrap_or(localPara)
+        })
+        .collect()
+}
+
+pub fn CompareWithTemplate(templateBody: &str, placeholders: &[String], localText: &str) -> DriftReport {
+    let templateParagraphs = Paragraphs(templateBody);
+    let localParagraphs = AlignToTemplate(Paragraphs(localText), &templateParagraphs, placeholders);
+
+    let diff = difflib::unified_diff(
+        &localParagraphs,
+        &templateParagraphs,
+        "local",
+        "upstream",
+        "",
+        "",
+        1,
+    )
+    .into_iter()
+    .map(|line| line.trim_end().to_string())
+    .collect();
+
+    DriftReport {
+        similarity: WordSimilarity(&localParagraphs.join("\n\n"), &templateParagraphs.join("\n\n")),
+        upToDate: localParagraphs == templateParagraphs,
+        diff,
+    }
+}
+
+// The template most similar to `text`, for files that no longer match any template exactly.
+pub fn ClosestTemplate<'a>(cache: &'a Cache, text: &str) -> Option<(&'a Arc<LicenseEntry>, f32)> {
+    cache
+        .licenses
+        .values()
+        .map(|entry| {
+            let templateBody = parser::LicenseTemplateBody(&entry.fileContentCached);
+            (entry, WordSimilarity(text, &templateBody))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestCompareIgnoresFilledPlaceholders() {
+        let template = "Copyright (c) [year] [fullname]\n\nPermission is granted.\n\nNo warranty.";
+        let placeholders = vec!["[year]".to_string(), "[fullname]".to_string()];
+
+        let current = CompareWithTemplate(template, &placeholders, "Copyright (c) 2020 Jane\n\nPermission is\ngranted.\n\nNo warranty.");
+        assert!(current.upToDate);
+        assert!(current.diff.is_empty());
+
+        let stale = CompareWithTemplate(template, &placeholders, "Copyright (c) 2020 Jane\n\nPermission is granted.");
+        assert!(!stale.upToDate);
+        assert!(stale.diff.iter().any(|line| line == "+No warranty."));
+    }
+
+    #[test]
+    fn TestRecoverPlaceholderValues() {
+        let template = "Copyright (c) [year] [fullname]\n\nOld wording.";
+        let placeholders = vec!["[fullname]".to_string(), "[year]".to_string()];
+
+        let recovered = RecoverPlaceholderValues(template, &placeholders, "Copyright (c) 2019 Jane Doe\n\nNew wording.");
+        assert_eq!(recovered["year"], "2019");
+        assert_eq!(recovered["fullname"], "Jane Doe");
+    }
+}
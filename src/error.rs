@@ -18,6 +18,9 @@ pub enum AppError {
     #[error("Action execution error: {0}")]
     ActionErrorVariant(#[from] ActionError), // Renamed to avoid conflict with type ActionError
 
+    #[error("Configuration error: {0}")]
+    ConfigErrorVariant(#[from] ConfigError),
+
     #[error("I/O error for path '{1}': {0}")]
     Io(#[source] std::io::Error, PathBuf), // Or IoError if Io is a type name
 
@@ -25,9 +28,11 @@ pub enum AppError {
 
 #[derive(Error, Debug)]
 pub enum ApiError {
+    #[cfg(feature = "net")]
     #[error("Reqwest HTTP client error: {0}")]
     ReqwestError(#[from] reqwest::Error),
 
+    #[cfg(feature = "net")]
     #[error("GitHub API HTTP error (Status: {status}): {body}")]
     HttpError {
         status: reqwest::StatusCode,
@@ -36,6 +41,12 @@ pub enum ApiError {
 
     #[error("Failed to deserialize API response: {0}")]
     DeserializationError(#[from] serde_json::Error),
+
+    #[error("Network access disabled by --offline (attempted to fetch {0})")]
+    OfflineError(String),
+
+    #[error("Git blob {0} is not valid base64-encoded UTF-8")]
+    MalformedBlob(String),
 }
 
 #[derive(Error, Debug)]
@@ -50,16 +61,43 @@ pub enum CacheError {
     Deserialization(#[source] serde_json::Error, PathBuf),
 }
 
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read/write config file at '{1}': {0}")]
+    Io(#[source] std::io::Error, PathBuf),
+
+    #[error("Failed to parse config file '{1}': {0}")]
+    Parse(#[source] toml::de::Error, PathBuf),
+
+    #[error("Failed to serialize config file '{1}': {0}")]
+    Serialize(#[source] toml::ser::Error, PathBuf),
+
+    #[error("Profile '{0}' is not defined in the config file.")]
+    UnknownProfile(String),
+
+    #[error("Invalid cache profile name '{0}': use letters, digits, '-' and '_'.")]
+    InvalidCacheProfile(String),
+}
+
 #[derive(Error, Debug)]
 pub enum ParseError {
     #[error("YAML parsing error in file '{0}': {1}")]
     YamlError(String, #[source] serde_yaml::Error),
 
+    #[error("TOML parsing error in file '{0}': {1}")]
+    TomlError(String, #[source] toml::de::Error),
+
+    #[error("JSON parsing error in file '{0}': {1}")]
+    JsonError(String, #[source] serde_json::Error),
+
     #[error("Missing SPDX ID in license file: {0}")]
     MissingSpdxId(String),
 
     #[error("Regex error during parsing: {0}")]
     RegexError(#[from] regex::Error),
+
+    #[error("Malformed SPDX template markup: {0}")]
+    SpdxMarkupError(String),
 }
 
 #[derive(Error, Debug)]
@@ -67,6 +105,9 @@ pub enum ActionError {
     #[error("License with SPDX ID '{0}' not found in cache.")]
     LicenseNotFound(String),
 
+    #[error("License with SPDX ID '{0}' not found in cache (--offline is set, so it was not fetched).")]
+    LicenseNotCachedOffline(String),
+
     #[error("Required data file '{0}' not found or failed to parse from cache.")]
     MissingData(String),
 
@@ -75,4 +116,11 @@ pub enum ActionError {
 
     #[error("Failed to perform file operation for '{1}': {0}")]
     FileOperation(#[source] std::io::Error, PathBuf),
+
+    #[error("Check failed: {0}")]
+    CheckFailed(String),
+
+    #[cfg(feature = "self-update")]
+    #[error("Self-update failed: {0}")]
+    SelfUpdate(String),
 }
@@ -0,0 +1,368 @@
+// The `list --filter` expression language: comparisons over rule tags and metadata fields
+// combined with and/or/not and parentheses, e.g.
+//   permissions has patent-use and conditions not has same-license
+//   (category = permissive or score >= 80) and title ~ "BSD"
+
+use crate::models::LicenseEntry;
+use crate::permissiveness::PermissivenessWeights;
+use crate::stats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Id,
+    Title,
+    Nickname,
+    Description,
+    Category,
+    Score,
+    Permissions,
+    Conditions,
+    Limitations,
+    // Any of permissions, conditions and limitations
+    Rules,
+}
+
+const FILTER_FIELD_TUPLES: [(&str, FilterField); 10] = [
+    ("id", FilterField::Id),
+    ("title", FilterField::Title),
+    ("nickname", FilterField::Nickname),
+    ("description", FilterField::Description),
+    ("category", FilterField::Category),
+    ("score", FilterField::Score),
+    ("permissions", FilterField::Permissions),
+    ("conditions", FilterField::Conditions),
+    ("limitations", FilterField::Limitations),
+    ("rules", FilterField::Rules),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    // Rule fields: the tag is present
+    Has,
+    NotHas,
+    // Text fields, case-insensitive
+    Equals,
+    NotEquals,
+    // Text fields: substring, case-insensitive
+    Contains,
+    // Score
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    Compare(FilterField, FilterOp, String),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    // Quoted, so never a keyword
+    Text(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+fn Tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => text.push(next),
+                        None => return Err(format!("unterminated {} quote", c)),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            '=' | '~' => {
+                chars.next();
+                tokens.push(Token::Op(if c == '=' { "=" } else { "~" }));
+            }
+            '!' | '<' | '>' => {
+                chars.next();
+                let withEquals = chars.next_if_eq(&'=').is_some();
+                tokens.push(Token::Op(match (c, withEquals) {
+                    ('!', true) => "!=",
+                    ('<', true) => "<=",
+                    ('<', false) => "<",
+                    ('>', true) => ">=",
+                    ('>', false) => ">",
+                    _ => return Err("'!' must be followed by '='".to_string()),
+                }));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "()\"'=~!<>".contains(next) {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn Peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn Next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn NextIsKeyword(&self, keyword: &str) -> bool {
+        matches!(self.Peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn ParseOr(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.ParseAnd()?;
+        while self.NextIsKeyword("or") {
+            self.position += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.ParseAnd()?));
+        }
+        Ok(expr)
+    }
+
+    fn ParseAnd(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.ParseNot()?;
+        while self.NextIsKeyword("and") {
+            self.position += 1;
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.ParseNot()?));
+        }
+        Ok(expr)
+    }
+
+    fn ParseNot(&mut self) -> Result<FilterExpr, String> {
+        if self.NextIsKeyword("not") {
+            self.position += 1;
+            return Ok(FilterExpr::Not(Box::new(self.ParseNot()?)));
+        }
+
+        if self.Peek() == Some(&Token::Open) {
+            self.position += 1;
+            let expr = self.ParseOr()?;
+            return match self.Next() {
+                Some(Token::Close) => Ok(expr),
+                _ => Err("missing ')'".to_string()),
+            };
+        }
+
+        self.ParseComparison()
+    }
+
+    fn ParseComparison(&mut self) -> Result<FilterExpr, String> {
+        let fieldName = match self.Next() {
+            Some(Token::Word(word)) => word,
+            Some(other) => return Err(format!("expected a field name, found {:?}", other)),
+            None => return Err("expected a field name, found the end of the filter".to_string()),
+        };
+        let field = FILTER_FIELD_TUPLES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&fieldName))
+            .map(|(_, field)| *field)
+            .ok_or_else(|| {
+                let names: Vec<&str> = FILTER_FIELD_TUPLES.iter().map(|(name, _)| *name).collect();
+                format!("unknown field '{}'; expected one of: {}", fieldName, names.join(", "))
+            })?;
+
+        let op = match self.Next() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("has") => FilterOp::Has,
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("not") && self.NextIsKeyword("has") => {
+                self.position += 1;
+                FilterOp::NotHas
+            }
+            Some(Token::Op("=")) => FilterOp::Equals,
+            Some(Token::Op("!=")) => FilterOp::NotEquals,
+            Some(Token::Op("~")) => FilterOp::Contains,
+            Some(Token::Op(">")) => FilterOp::Greater,
+            Some(Token::Op(">=")) => FilterOp::GreaterOrEqual,
+            Some(Token::Op("<")) => FilterOp::Less,
+            Some(Token::Op("<=")) => FilterOp::LessOrEqual,
+            _ => return Err(format!("expected an operator after '{}'", fieldName)),
+        };
+
+        let isRuleField = matches!(
+            field,
+            FilterField::Permissions | FilterField::Conditions | FilterField::Limitations | FilterField::Rules
+        );
+        let isOrderOp = matches!(
+            op,
+            FilterOp::Greater | FilterOp::GreaterOrEqual | FilterOp::Less | FilterOp::LessOrEqual
+        );
+        let fitsField = match op {
+            FilterOp::Has | FilterOp::NotHas => isRuleField,
+            _ if isOrderOp => field == FilterField::Score,
+            _ => !isRuleField,
+        };
+        if !fitsField {
+            let supported = if isRuleField {
+                "has, not has"
+            } else if field == FilterField::Score {
+                "=, !=, >, >=, <, <="
+            } else {
+                "=, !=, ~"
+            };
+            return Err(format!("'{}' supports only: {}", fieldName, supported));
+        }
+
+        let value = match self.Next() {
+            Some(Token::Word(word)) | Some(Token::Text(word)) => word,
+            _ => return Err(format!("expected a value after '{}'", fieldName)),
+        };
+
+        if field == FilterField::Score && value.parse::<u8>().is_err() {
+            return Err(format!("score is compared with a number from 0 to 100, not '{}'", value));
+        }
+
+        Ok(FilterExpr::Compare(field, op, value))
+    }
+}
+
+pub fn ParseFilter(input: &str) -> Result<FilterExpr, String> {
+    let mut parser = Parser { tokens: Tokenize(input)?, position: 0 };
+    let expr = parser.ParseOr()?;
+
+    match parser.Peek() {
+        None => Ok(expr),
+        Some(token) => Err(format!("unexpected {:?}", token)),
+    }
+}
+
+impl FilterExpr {
+    pub fn Matches(&self, licenseEntry: &LicenseEntry, weights: &PermissivenessWeights) -> bool {
+        match self {
+            FilterExpr::Not(inner) => !inner.Matches(licenseEntry, weights),
+            FilterExpr::And(left, right) => left.Matches(licenseEntry, weights) && right.Matches(licenseEntry, weights),
+            FilterExpr::Or(left, right) => left.Matches(licenseEntry, weights) || right.Matches(licenseEntry, weights),
+            FilterExpr::Compare(field, op, value) => CompareField(licenseEntry, weights, *field, *op, value),
+        }
+    }
+}
+
+fn CompareField(
+    licenseEntry: &LicenseEntry,
+    weights: &PermissivenessWeights,
+    field: FilterField,
+    op: FilterOp,
+    value: &str,
+) -> bool {
+    let tags: Vec<&String> = match field {
+        FilterField::Permissions => licenseEntry.permissions.iter().collect(),
+        FilterField::Conditions => licenseEntry.conditions.iter().collect(),
+        FilterField::Limitations => licenseEntry.limitations.iter().collect(),
+        FilterField::Rules => licenseEntry
+            .permissions
+            .iter()
+            .chain(&licenseEntry.conditions)
+            .chain(&licenseEntry.limitations)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if field == FilterField::Score {
+        let score = weights.Score(licenseEntry);
+        let target: u8 = value.parse().unwrap_or_default();
+        return match op {
+            FilterOp::Equals => score == target,
+            FilterOp::NotEquals => score != target,
+            FilterOp::Greater => score > target,
+            FilterOp::GreaterOrEqual => score >= target,
+            FilterOp::Less => score < target,
+            FilterOp::LessOrEqual => score <= target,
+            _ => false,
+        };
+    }
+
+    let text = match field {
+        FilterField::Id => licenseEntry.spdxId.as_str(),
+        FilterField::Title => licenseEntry.title.as_str(),
+        FilterField::Nickname => licenseEntry.nickname.as_deref().unwrap_or(""),
+        FilterField::Description => licenseEntry.description.as_deref().unwrap_or(""),
+        FilterField::Category => stats::LicenseCategory(licenseEntry),
+        _ => "",
+    };
+
+    match op {
+        FilterOp::Has => tags.iter().any(|tag| tag.eq_ignore_ascii_case(value)),
+        FilterOp::NotHas => !tags.iter().any(|tag| tag.eq_ignore_ascii_case(value)),
+        FilterOp::Equals => text.eq_ignore_ascii_case(value),
+        FilterOp::NotEquals => !text.eq_ignore_ascii_case(value),
+        FilterOp::Contains => text.to_lowercase().contains(&value.to_lowercase()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Strings;
+
+    fn License(spdxId: &str, permissions: &[&str], conditions: &[&str]) -> LicenseEntry {
+        LicenseEntry {
+            permissions: Strings(permissions),
+            conditions: Strings(conditions),
+            ..LicenseEntry::ForTest(spdxId, "")
+        }
+    }
+
+    #[test]
+    fn TestParseAndMatchFilter() {
+        let weights = PermissivenessWeights::default();
+        let apache = License("Apache-2.0", &["commercial-use", "patent-use"], &["include-copyright"]);
+        let gpl = License("GPL-3.0", &["commercial-use", "patent-use"], &["same-license"]);
+        let mit = License("MIT", &["commercial-use"], &["include-copyright"]);
+
+        let filter = ParseFilter("permissions has patent-use and conditions not has same-license").unwrap();
+        assert!(filter.Matches(&apache, &weights));
+        assert!(!filter.Matches(&gpl, &weights));
+        assert!(!filter.Matches(&mit, &weights));
+
+        let filter = ParseFilter("not (category = 'strong copyleft' or id = mit) and title ~ LICENSE").unwrap();
+        assert!(filter.Matches(&apache, &weights));
+        assert!(!filter.Matches(&gpl, &weights));
+        assert!(!filter.Matches(&mit, &weights));
+
+        assert!(ParseFilter("permissions = patent-use").is_err());
+        assert!(ParseFilter("score >= high").is_err());
+        assert!(ParseFilter("(id = mit").is_err());
+        assert!(ParseFilter("license has x").unwrap_err().contains("unknown field"));
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::CacheError;
+
+// One upstream file that a sync downloaded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncedFile {
+    // e.g. "mit.txt" or "rules.yml"
+    pub name: String,
+    pub sha: String,
+    // None when the file was new to the cache (or the sync was forced)
+    #[serde(rename = "previous_sha", default, skip_serializing_if = "Option::is_none")]
+    pub previousSha: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncRecord {
+    #[serde(rename = "synced_at")]
+    pub syncedAt: DateTime<Utc>,
+    // --refresh: every file was fetched regardless of its SHA
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub forced: bool,
+    #[serde(default)]
+    pub fetched: Vec<SyncedFile>,
+    // Total size of the downloaded file contents
+    #[serde(default)]
+    pub bytes: u64,
+    #[serde(default)]
+    pub failures: Vec<String>,
+}
+
+impl SyncRecord {
+    pub fn New(forced: bool) -> Self {
+        Self {
+            syncedAt: Utc::now(),
+            forced,
+            fetched: Vec::new(),
+            bytes: 0,
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn RecordFetch(&mut self, name: &str, sha: &str, previousSha: Option<&str>, bytes: usize) {
+        self.fetched.push(SyncedFile {
+            name: name.to_string(),
+            sha: sha.to_string(),
+            previousSha: previousSha.map(str::to_string),
+        });
+        self.bytes += bytes as u64;
+    }
+
+    // Syncs that found everything up to date are not worth a history line
+    pub fn IsEmpty(&self) -> bool {
+        self.fetched.is_empty() && self.failures.is_empty()
+    }
+}
+
+// <cache stem>.history.jsonl next to the cache, one JSON record per line
+pub fn HistoryPathFor(cachePath: &Path) -> PathBuf {
+    let stem = cachePath
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cache".to_string());
+
+    cachePath.with_file_name(format!("{}.history.jsonl", stem))
+}
+
+pub fn AppendSyncRecord(historyPath: &Path, record: &SyncRecord) -> Result<(), CacheError> {
+    let line = serde_json::to_string(record).map_err(CacheError::Serialization)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(historyPath)
+        .map_err(|e| CacheError::Io(e, historyPath.to_path_buf()))?;
+
+    writeln!(file, "{}", line).map_err(|e| CacheError::Io(e, historyPath.to_path_buf()))
+}
+
+// Oldest first. Lines that do not parse (e.g. a write cut short) are skipped.
+pub fn LoadSyncHistory(historyPath: &Path) -> Result<Vec<SyncRecord>, CacheError> {
+    if !historyPath.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(historyPath)
+        .map_err(|e| CacheError::Io(e, historyPath.to_path_buf()))?;
+
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestSyncHistoryRoundTrip() {
+        let dir = TempDir::New("history");
+        let historyPath = HistoryPathFor(&dir.join("license_cache_rs.json"));
+
+        let mut first = SyncRecord::New(false);
+        first.RecordFetch("mit.txt", "bbb", Some("aaa"), 1070);
+        let mut second = SyncRecord::New(true);
+        second.failures.push("gpl-3.0.txt: timed out".to_string());
+
+        AppendSyncRecord(&historyPath, &first).unwrap();
+        AppendSyncRecord(&historyPath, &second).unwrap();
+        fs::OpenOptions::new().append(true).open(&historyPath).unwrap().write_all(b"{\"trunc").unwrap();
+
+        assert_eq!(LoadSyncHistory(&historyPath).unwrap(), vec![first, second]);
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+
+use crate::integrity::{GitBlobSha, Sha256Hex};
+
+// Raw downloads on disk, keyed by URL and the git blob SHA the listing promised for it.
+// The SHA makes an entry immutable: a template that changed upstream gets a new key, so
+// hits never need a request and stale entries simply age out.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    pub dir: PathBuf,
+    pub maxBytes: u64,
+    // False with --no-cache-write: hits are still served but nothing is stored or touched
+    pub writable: bool,
+}
+
+// What a server sent to identify a response, echoed back as If-None-Match and
+// If-Modified-Since so an unchanged body is answered with 304 instead of re-sent.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Validators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(rename = "last_modified", default, skip_serializing_if = "Option::is_none")]
+    pub lastModified: Option<String>,
+}
+
+impl Validators {
+    pub fn IsEmpty(&self) -> bool {
+        self.etag.is_none() && self.lastModified.is_none()
+    }
+}
+
+// <sha256(url)>.latest.json: which stored response is the newest for a URL, whatever its SHA
+#[derive(Serialize, Deserialize, Debug)]
+struct LatestPointer {
+    #[serde(rename = "blob_sha")]
+    blobSha: String,
+    #[serde(flatten)]
+    validators: Validators,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct StoredResponse {
+    url: String,
+    #[serde(rename = "blob_sha")]
+    blobSha: String,
+    body: String,
+}
+
+pub fn ResponseCacheDirFor(cachePath: &Path) -> PathBuf {
+    let stem = cachePath
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cache".to_string());
+
+    cachePath.with_file_name(format!("{}.http", stem))
+}
+
+impl ResponseCache {
+    fn EntryPath(&self, url: &str, blobSha: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", Sha256Hex(format!("{}\n{}", url, blobSha).as_bytes())))
+    }
+
+    fn LatestPath(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.latest.json", Sha256Hex(url.as_bytes())))
+    }
+
+    // The newest body stored for `url` and its validators, for a conditional request when the
+    // listing reports a SHA that is not cached. None if it was evicted or has no validators.
+    pub fn Latest(&self, url: &str) -> Option<(String, Validators)> {
+        let pointer: LatestPointer = serde_json::from_slice(&fs::read(self.LatestPath(url)).ok()?).ok()?;
+
+        if pointer.validators.IsEmpty() {
+            return None;
+        }
+
+        let path = self.EntryPath(url, &pointer.blobSha);
+        let stored: StoredResponse = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+
+        (stored.url == url && GitBlobSha(stored.body.as_bytes()) == pointer.blobSha)
+            .then_some((stored.body, pointer.validators))
+    }
+
+    pub fn Get(&self, url: &str, blobSha: &str) -> Option<String> {
+        let path = self.EntryPath(url, blobSha);
+        let stored: StoredResponse = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+
+        // A truncated or hand-edited entry is treated as a miss and overwritten later
+        if stored.url != url || stored.blobSha != blobSha || GitBlobSha(stored.body.as_bytes()) != blobSha {
+            return None;
+        }
+
+        // The modification time doubles as the last-use time for eviction
+        if self.writable {
+            if let Ok(file) = fs::File::options().append(true).open(&path) {
+                let _ = file.set_modified(SystemTime::now());
+            }
+        }
+
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[HTTP Cache] Hit for {}", url);
+        }
+
+        Some(stored.body)
+    }
+
+    // Only content matching the promised SHA is stored, so a bad download is retried next time.
+    // Failures are not fatal: the response cache is an optimization.
+    pub fn Put(&self, url: &str, blobSha: &str, body: &str, validators: &Validators) {
+        if !self.writable || self.maxBytes == 0 || GitBlobSha(body.as_bytes()) != blobSha {
+            return;
+        }
+
+        let stored = StoredResponse { url: url.to_string(), blobSha: blobSha.to_string(), body: body.to_string() };
+        let pointer = LatestPointer { blobSha: blobSha.to_string(), validators: validators.clone() };
+        let written = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(self.EntryPath(url, blobSha), serde_json::to_vec(&stored)?))
+            .and_then(|_| fs::write(self.LatestPath(url), serde_json::to_vec(&pointer)?));
+
+        match written {
+            Ok(()) => self.Evict(),
+            Err(e) => {
+                if crate::VERBOSE.load(Ordering::SeqCst) {
+                    eprintln!("[HTTP Cache] Could not store response for {}: {}", url, e);
+                }
+            }
+        }
+    }
+
+    // Drops least recently used entries until the directory fits in maxBytes
+    fn Evict(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let metadata = entry.metadata().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len(), entry.path()))
+            })
+            .collect();
+        let mut totalBytes: u64 = files.iter().map(|(_, size, _)| size).sum();
+
+        files.sort();
+
+        for (_, size, path) in files {
+            if totalBytes <= self.maxBytes {
+                break;
+            }
+
+            if fs::remove_file(&path).is_ok() {
+                totalBytes -= size;
+
+                if crate::VERBOSE.load(Ordering::SeqCst) {
+                    eprintln!("[HTTP Cache] Evicted {:?}", path);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestResponseCacheEviction() {
+        let dir = TempDir::New("http-cache");
+        let body = "x".repeat(600);
+        let sha = GitBlobSha(body.as_bytes());
+        let cache = ResponseCache { dir: dir.to_path_buf(), maxBytes: 1000, writable: true };
+
+        cache.Put("https://example.com/a.txt", &sha, &body, &Validators::default());
+        assert_eq!(cache.Get("https://example.com/a.txt", &sha), Some(body.clone()));
+        assert_eq!(cache.Get("https://example.com/a.txt", "0000"), None);
+
+        // Content that does not match its SHA is never stored
+        cache.Put("https://example.com/c.txt", &sha, "tampered", &Validators::default());
+        assert_eq!(cache.Get("https://example.com/c.txt", &sha), None);
+
+        // Two entries do not fit in 1000 bytes, so the older one goes
+        let older = cache.EntryPath("https://example.com/a.txt", &sha);
+        fs::File::options()
+            .append(true)
+            .open(&older)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        cache.Put("https://example.com/b.txt", &sha, &body, &Validators::default());
+        assert_eq!(cache.Get("https://example.com/a.txt", &sha), None);
+        assert_eq!(cache.Get("https://example.com/b.txt", &sha), Some(body));
+    }
+
+    #[test]
+    fn TestResponseCacheLatestForConditionalRequests() {
+        let dir = TempDir::New("http-latest");
+        let cache = ResponseCache { dir: dir.to_path_buf(), maxBytes: 1 << 20, writable: true };
+        let url = "https://example.com/gpl-3.0.txt";
+        let validators = Validators { etag: Some("\"abc\"".to_string()), lastModified: None };
+
+        // Nothing to send a conditional request with
+        cache.Put(url, &GitBlobSha(b"v1"), "v1", &Validators::default());
+        assert_eq!(cache.Latest(url), None);
+
+        cache.Put(url, &GitBlobSha(b"v2"), "v2", &validators);
+        assert_eq!(cache.Latest(url), Some(("v2".to_string(), validators)));
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::CacheError;
+use crate::integrity::GitBlobSha;
+
+// One file written by a `license` run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JournaledFile {
+    // Absolute, so `undo` works from any directory
+    pub path: PathBuf,
+    // None for files that are not a license text, e.g. --appendix-output
+    #[serde(rename = "spdx_id", default, skip_serializing_if = "Option::is_none")]
+    pub spdxId: Option<String>,
+    // Git blob SHA of the content the run replaced, which is kept in the backups directory
+    // under that name. None when the file did not exist before.
+    #[serde(rename = "previous_sha", default, skip_serializing_if = "Option::is_none")]
+    pub previousSha: Option<String>,
+    // Git blob SHA of what the run wrote, to notice later hand edits before undoing
+    #[serde(rename = "written_sha")]
+    pub writtenSha: String,
+}
+
+// One `license` invocation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FillRecord {
+    #[serde(rename = "written_at")]
+    pub writtenAt: DateTime<Utc>,
+    pub files: Vec<JournaledFile>,
+}
+
+// <cache stem>.fills.jsonl next to the cache, plus <cache stem>.backups/ holding the
+// content each run replaced.
+#[derive(Debug, Clone)]
+pub struct FillJournal {
+    pub journalPath: PathBuf,
+    pub backupsDir: PathBuf,
+}
+
+impl FillJournal {
+    pub fn ForCache(cachePath: &Path) -> Self {
+        let stem = cachePath
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "cache".to_string());
+
+        Self {
+            journalPath: cachePath.with_file_name(format!("{}.fills.jsonl", stem)),
+            backupsDir: cachePath.with_file_name(format!("{}.backups", stem)),
+        }
+    }
+
+    pub fn BackupPath(&self, sha: &str) -> PathBuf {
+        self.backupsDir.join(sha)
+    }
+
+    // Saves the current content of `path` before it is overwritten; None if there is none
+    pub fn BackUp(&self, path: &Path) -> Result<Option<String>, CacheError> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = fs::read(path).map_err(|e| CacheError::Io(e, path.to_path_buf()))?;
+        let sha = GitBlobSha(&content);
+        let backupPath = self.BackupPath(&sha);
+
+        if !backupPath.exists() {
+            fs::create_dir_all(&self.backupsDir).map_err(|e| CacheError::Io(e, self.backupsDir.clone()))?;
+            fs::write(&backupPath, &content).map_err(|e| CacheError::Io(e, backupPath.clone()))?;
+        }
+
+        Ok(Some(sha))
+    }
+
+    pub fn Append(&self, record: &FillRecord) -> Result<(), CacheError> {
+        let line = serde_json::to_string(record).map_err(CacheError::Serialization)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.journalPath)
+            .map_err(|e| CacheError::Io(e, self.journalPath.clone()))?;
+
+        writeln!(file, "{}", line).map_err(|e| CacheError::Io(e, self.journalPath.clone()))
+    }
+
+    // Oldest first. Lines that do not parse (e.g. a write cut short) are skipped.
+    pub fn Load(&self) -> Result<Vec<FillRecord>, CacheError> {
+        if !self.journalPath.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.journalPath)
+            .map_err(|e| CacheError::Io(e, self.journalPath.clone()))?;
+
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    // Drops the newest record, and the backups no remaining record refers to
+    pub fn RemoveLast(&self) -> Result<Option<FillRecord>, CacheError> {
+        let mut records = self.Load()?;
+        let Some(last) = records.pop() else {
+            return Ok(None);
+        };
+
+        let mut content = String::new();
+        for record in &records {
+            content.push_str(&serde_json::to_string(record).map_err(CacheError::Serialization)?);
+            content.push('\n');
+        }
+        fs::write(&self.journalPath, content).map_err(|e| CacheError::Io(e, self.journalPath.clone()))?;
+
+        for sha in last.files.iter().filter_map(|file| file.previousSha.as_ref()) {
+            let stillUsed = records
+                .iter()
+                .flat_map(|record| &record.files)
+                .any(|file| file.previousSha.as_ref() == Some(sha));
+
+            if !stillUsed {
+                let _ = fs::remove_file(self.BackupPath(sha));
+            }
+        }
+
+        Ok(Some(last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestFillJournalBackUpAndRemoveLast() {
+        let dir = TempDir::New("journal");
+        let journal = FillJournal::ForCache(&dir.join("license_cache_rs.json"));
+        let licensePath = dir.join("LICENSE");
+
+        assert_eq!(journal.BackUp(&licensePath).unwrap(), None);
+
+        fs::write(&licensePath, "old text\n").unwrap();
+        let previousSha = journal.BackUp(&licensePath).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(journal.BackupPath(&previousSha)).unwrap(), "old text\n");
+
+        let record = FillRecord {
+            writtenAt: Utc::now(),
+            files: vec![JournaledFile {
+                path: licensePath.clone(),
+                spdxId: Some("MIT".to_string()),
+                previousSha: Some(previousSha.clone()),
+                writtenSha: GitBlobSha(b"new text\n"),
+            }],
+        };
+        journal.Append(&record).unwrap();
+
+        assert_eq!(journal.RemoveLast().unwrap(), Some(record));
+        assert!(journal.Load().unwrap().is_empty());
+        assert!(!journal.BackupPath(&previousSha).exists());
+    }
+}
@@ -0,0 +1,112 @@
+//! Fetch, inspect, and fill open source license templates from choosealicense.com.
+//!
+//! The [`store::LicenseStore`] type is the entry point for embedding getlicense in other
+//! tools: it syncs and loads the local cache and returns typed results instead of printing.
+//!
+//! ```no_run
+//! # async fn demo() -> Result<(), getlicense::error::AppError> {
+//! use getlicense::store::LicenseStore;
+//! use std::collections::HashMap;
+//!
+//! let mut store = LicenseStore::open(LicenseStore::default_cache_path()).await?;
+//! store.sync(false).await?;
+//!
+//! let permissive = store.find(&["commercial-use"], &["disclose-source"])?;
+//! let vars = HashMap::from([("fullname".to_string(), "Jane Doe".to_string())]);
+//! let text = store.fill("mit", &vars)?;
+//! # Ok(())
+//! # }
+//! ```
+#![allow(non_snake_case)]
+#![allow(clippy::collapsible_if, clippy::needless_return, clippy::enum_variant_names)]
+
+use once_cell::sync::Lazy;
+use std::sync::atomic::AtomicBool;
+
+pub mod error;
+pub mod models;
+pub mod store;
+
+// Building blocks of the getlicense binary. Public so the CLI can use them, but not
+// covered by any stability promise.
+#[doc(hidden)]
+pub mod actions;
+#[doc(hidden)]
+pub mod appendix;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod api;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod clock;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod constants;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod credentials;
+#[doc(hidden)]
+pub mod dependencies;
+#[doc(hidden)]
+pub mod display;
+#[doc(hidden)]
+pub mod drift;
+#[doc(hidden)]
+pub mod filter;
+#[doc(hidden)]
+pub mod history;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod http_cache;
+#[doc(hidden)]
+pub mod integrity;
+#[doc(hidden)]
+pub mod journal;
+#[doc(hidden)]
+pub mod lockfile;
+#[doc(hidden)]
+pub mod manifest;
+#[doc(hidden)]
+pub mod markdown;
+#[doc(hidden)]
+pub mod normalize;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod org;
+#[doc(hidden)]
+pub mod parser;
+#[doc(hidden)]
+pub mod permissiveness;
+#[cfg(any(unix, windows))]
+#[doc(hidden)]
+pub mod picker;
+#[doc(hidden)]
+pub mod provenance;
+#[doc(hidden)]
+pub mod resolve;
+#[doc(hidden)]
+pub mod spdx_markup;
+#[doc(hidden)]
+pub mod stats;
+#[doc(hidden)]
+pub mod terminal;
+#[cfg(test)]
+mod test_support;
+#[doc(hidden)]
+pub mod timings;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod translations;
+#[cfg(feature = "net")]
+#[doc(hidden)]
+pub mod update;
+
+/// Print detailed status messages to stderr.
+pub static VERBOSE: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+/// Set by --offline. The API layer refuses to issue any request while this is true.
+pub static OFFLINE: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::constants::TEMPLATE_LOCK_FILENAME;
+use crate::error::{ActionError, AppError, ParseError};
+
+// The template version one generated file was filled from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LockedTemplate {
+    #[serde(rename = "spdx_id")]
+    pub spdxId: String,
+    // Git blob SHA of the template in the upstream repository
+    #[serde(rename = "template_sha")]
+    pub templateSha: String,
+}
+
+// getlicense.lock: generated file -> pinned template, committed with the project so every
+// machine fills the same template version until the lock is updated.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TemplateLock {
+    #[serde(default)]
+    pub files: BTreeMap<String, LockedTemplate>,
+}
+
+// Relative paths as written, with forward slashes, so the lock reads the same on every OS
+fn LockKey(outputPath: &Path) -> String {
+    let key = outputPath.to_string_lossy().replace('\\', "/");
+    key.strip_prefix("./").map(str::to_string).unwrap_or(key)
+}
+
+impl TemplateLock {
+    // The pin for `outputPath`, unless it pins another license than the one being written
+    pub fn PinFor(&self, outputPath: &Path, spdxId: &str) -> Option<&LockedTemplate> {
+        self.files
+            .get(&LockKey(outputPath))
+            .filter(|pinned| pinned.spdxId.eq_ignore_ascii_case(spdxId))
+    }
+
+    // Returns whether the lock changed
+    pub fn Pin(&mut self, outputPath: &Path, spdxId: &str, templateSha: &str) -> bool {
+        let pinned = LockedTemplate {
+            spdxId: spdxId.to_string(),
+            templateSha: templateSha.to_string(),
+        };
+
+        self.files.insert(LockKey(outputPath), pinned.clone()) != Some(pinned)
+    }
+}
+
+// Like .getlicense.toml, the lock is only looked for in the directory getlicense runs in
+pub fn TemplateLockPath(projectDir: &Path) -> PathBuf {
+    projectDir.join(TEMPLATE_LOCK_FILENAME)
+}
+
+// None when the project has no lockfile
+pub fn LoadTemplateLock(projectDir: &Path) -> Result<Option<TemplateLock>, AppError> {
+    let lockPath = TemplateLockPath(projectDir);
+
+    if !lockPath.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&lockPath)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, lockPath.clone())))?;
+
+    Ok(Some(
+        toml::from_str(&content).map_err(|e| ParseError::TomlError(lockPath.display().to_string(), e))?,
+    ))
+}
+
+pub fn SaveTemplateLock(projectDir: &Path, lock: &TemplateLock) -> Result<(), AppError> {
+    let lockPath = TemplateLockPath(projectDir);
+
+    let content = toml::to_string_pretty(lock).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(io::Error::other(e), lockPath.clone()))
+    })?;
+
+    fs::write(&lockPath, format!("# Written by getlicense; `license --update-lock` re-pins.\n{}", content))
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, lockPath)))
+}
+
+// Loads every pinned template version the current templates do not match, from the
+// bodies directory (None for an in-memory cache) or else by downloading it by its blob
+// SHA. Returns whether the cache changed and needs saving.
+#[cfg(feature = "net")]
+pub async fn EnsurePinnedTemplates(
+    cache: &mut crate::models::Cache,
+    lock: &TemplateLock,
+    bodiesDir: Option<&Path>,
+) -> Result<bool, AppError> {
+    use std::sync::atomic::Ordering;
+
+    let mut changed = false;
+
+    for pinned in lock.files.values() {
+        let sha = &pinned.templateSha;
+        let current = cache.licenses.get(&pinned.spdxId.to_lowercase());
+        if current.is_some_and(|entry| entry.sha == *sha) || cache.pinnedTemplateBodies.contains_key(sha) {
+            continue;
+        }
+
+        let stored = match bodiesDir {
+            Some(dir) => crate::cache::ReadBodyFile(dir, sha).await,
+            None => None,
+        };
+
+        let content = match stored {
+            Some(content) => content,
+            None => {
+                if crate::VERBOSE.load(Ordering::SeqCst) {
+                    eprintln!("[Lock] Fetching pinned template {} of {}", sha, pinned.spdxId);
+                }
+
+                let content = crate::api::FetchGitBlob(sha).await?;
+
+                if crate::integrity::GitBlobSha(content.as_bytes()) != *sha {
+                    return Err(AppError::ActionErrorVariant(ActionError::CheckFailed(format!(
+                        "the download of pinned template {} of {} does not match its SHA",
+                        sha, pinned.spdxId
+                    ))));
+                }
+
+                // Written to the bodies directory when the cache is saved
+                changed = true;
+                content
+            }
+        };
+
+        changed |= cache.pinnedTemplates.insert(sha.clone());
+        cache.pinnedTemplateBodies.insert(sha.clone(), content);
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestTemplateLockRoundTrip() {
+        let dir = TempDir::New("lock");
+
+        assert_eq!(LoadTemplateLock(&dir).unwrap(), None);
+
+        let mut lock = TemplateLock::default();
+        assert!(lock.Pin(Path::new("./LICENSE"), "MIT", "aaa"));
+        assert!(!lock.Pin(Path::new("LICENSE"), "MIT", "aaa"));
+        assert!(lock.Pin(Path::new("LICENSES/Apache-2.0.txt"), "Apache-2.0", "bbb"));
+        SaveTemplateLock(&dir, &lock).unwrap();
+
+        let loaded = LoadTemplateLock(&dir).unwrap().unwrap();
+        assert_eq!(loaded, lock);
+        assert_eq!(loaded.PinFor(Path::new("LICENSE"), "mit").map(|p| p.templateSha.as_str()), Some("aaa"));
+        assert_eq!(loaded.PinFor(Path::new("LICENSE"), "ISC"), None);
+    }
+
+    #[cfg(feature = "net")]
+    #[tokio::test]
+    async fn TestEnsurePinnedTemplatesReadsBodiesDirectory() {
+        let dir = TempDir::New("lock-bodies");
+        let content = "---\ntitle: MIT\n---\n\nOld text (c) [year] [fullname]\n";
+        let sha = crate::integrity::GitBlobSha(content.as_bytes());
+        fs::write(dir.join(&sha), content).unwrap();
+
+        let mut lock = TemplateLock::default();
+        lock.Pin(Path::new("LICENSE"), "MIT", &sha);
+        let mut cache = crate::models::Cache::default();
+
+        assert!(EnsurePinnedTemplates(&mut cache, &lock, Some(&dir)).await.unwrap());
+        assert_eq!(cache.pinnedTemplateBodies[&sha], content);
+        assert!(cache.pinnedTemplates.contains(&sha));
+        // Already loaded: nothing to do, nothing to save
+        assert!(!EnsurePinnedTemplates(&mut cache, &lock, Some(&dir)).await.unwrap());
+    }
+}
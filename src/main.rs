@@ -1,38 +1,109 @@
 #![allow(non_snake_case)]
+#![allow(clippy::collapsible_if, clippy::needless_return, clippy::enum_variant_names, clippy::result_large_err)]
 
 use clap::Parser;
 use once_cell::sync::Lazy;
+use std::env;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tokio;
-
-mod cli;
-// For Cache, etc. if used directly in main
-mod actions;
-mod cache;
-mod constants;
-mod error;
-mod models;
-// For potential direct calls or if actions re-export display functions
-mod api;
-mod display;
-mod parser;
-
-use cli::{Cli, Commands};
-use constants::DEFAULT_CACHE_FILENAME;
+use std::time::{Duration, Instant};
+
+use getlicense::{OFFLINE, VERBOSE};
+use getlicense::{actions, api, cache, cli, clock, config, constants, display, error, history, http_cache, journal, lockfile, org, terminal, timings, translations, update};
+
+use cli::{AliasCommands, CacheMode, Cli, Commands, HookCommands};
+use constants::{DEFAULT_CACHE_FILENAME, DEFAULT_HTTP_CACHE_MAX_MB, DEFAULT_PLACEHOLDERS_FILENAME};
 use error::AppError;
 
 // Global flag to indicate if cache was modified by an action (e.g. placeholder management)
 // This helps decide if SaveCache needs to be called.
-pub static VERBOSE: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
 static CACHE_MODIFIED_BY_ACTION: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+// Placeholder preferences live in their own file, so changing them never rewrites the license cache.
+static PLACEHOLDERS_MODIFIED_BY_ACTION: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+
+// CLI flags (and their env vars) win over the config file, which wins over built-in defaults.
+fn BuildNetworkSettings(
+    cli_args: &Cli,
+    app_config: &config::Config,
+    github_config: &config::GithubConfig,
+    response_cache: Option<http_cache::ResponseCache>,
+) -> api::NetworkSettings {
+    let default_network = api::NetworkSettings::default();
+    let mut source = cli_args
+        .githubHost
+        .as_deref()
+        .or(github_config.host.as_deref())
+        .map(api::SourceRepo::ForHost)
+        .unwrap_or_default();
+
+    if let Some(url) = &github_config.apiBaseUrl {
+        source.apiBaseUrl = url.trim_end_matches('/').to_string();
+    }
+    if let Some(url) = &github_config.rawBaseUrl {
+        source.rawBaseUrl = url.trim_end_matches('/').to_string();
+    }
+    if let Some(owner) = &github_config.owner {
+        source.owner = owner.clone();
+    }
+    if let Some(repo) = &github_config.repo {
+        source.repo = repo.clone();
+    }
+    if let Some(branch) = &github_config.branch {
+        source.branch = branch.clone();
+    }
+
+    api::NetworkSettings {
+        connectTimeout: cli_args
+            .connectTimeout
+            .or(app_config.network.connectTimeout)
+            .map(Duration::from_secs)
+            .unwrap_or(default_network.connectTimeout),
+        requestTimeout: cli_args
+            .requestTimeout
+            .or(app_config.network.requestTimeout)
+            .map(Duration::from_secs)
+            .unwrap_or(default_network.requestTimeout),
+        rawMirrors: cli_args
+            .mirrors
+            .iter()
+            .chain(app_config.network.rawMirrors.iter())
+            .cloned()
+            .collect(),
+        source,
+        responseCache: response_cache,
+        auth: app_config.auth.clone(),
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
-    let cli_args = Cli::parse();
+    let mut cli_args = Cli::parse();
 
     VERBOSE.store(cli_args.verbose, Ordering::SeqCst);
+    OFFLINE.store(cli_args.offline, Ordering::SeqCst);
+    if cli_args.timings {
+        timings::Enable();
+    }
+    if let Some(now) = cli_args.now {
+        clock::SetNow(now);
+    }
+    // Held until main returns, so a Windows console gets its code page back
+    let console_support = terminal::PrepareConsole();
+    if terminal::PlainOutputRequested() || !console_support.escapes {
+        colored::control::set_override(false);
+        terminal::ANIMATIONS.store(false, Ordering::SeqCst);
+    }
+    terminal::ASCII.store(
+        terminal::DetectAsciiOnly(cli_args.ascii) || !console_support.unicode,
+        Ordering::SeqCst,
+    );
+    terminal::HYPERLINKS.store(terminal::DetectHyperlinkSupport(), Ordering::SeqCst);
+    terminal::WIDTH.store(
+        terminal::DetectWidth(cli_args.width.map(usize::from)).unwrap_or(0),
+        Ordering::SeqCst,
+    );
 
     if VERBOSE.load(Ordering::SeqCst) {
         eprintln!("Verbose mode enabled.");
@@ -46,49 +117,327 @@ async fn main() -> Result<(), AppError> {
         return Ok(());
     }
 
-    let cache_file_path = cli_args.cacheFile.clone().unwrap_or_else(|| {
-        // Attempt to construct a default path, e.g., in user's config directory
-        let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
-        home_dir.join(DEFAULT_CACHE_FILENAME)
-    });
+    // Works on the given text alone, so it needs neither config nor cache
+    if let Some(Commands::Normalize(args)) = &cli_args.command {
+        return actions::normalize::NormalizeInput(&args.source);
+    }
+
+    let config_file_path = cli_args
+        .config
+        .clone()
+        .unwrap_or_else(config::DefaultConfigPath);
+    let mut app_config = config::LoadConfig(&config_file_path)?;
+
+    // Aliases stand in for license IDs in every command that takes one
+    if let Some(command) = cli_args.command.as_mut() {
+        for license_id in command.LicenseIdsMut() {
+            if let Some(target) = app_config.ResolveAlias(license_id) {
+                if VERBOSE.load(Ordering::SeqCst) {
+                    eprintln!("[Config] Alias '{}' resolves to {}", license_id, target);
+                }
+                *license_id = target.to_string();
+            }
+        }
+    }
+
+    // Attempt to construct a default path, e.g., in user's config directory
+    let default_cache_path = dirs::home_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join(DEFAULT_CACHE_FILENAME);
+
+    let (cache_file_path, github_config) = match &cli_args.cacheProfile {
+        Some(name) => app_config.CacheProfile(name, &default_cache_path)?,
+        None => (
+            cli_args.cacheFile.clone().unwrap_or(default_cache_path.clone()),
+            &app_config.github,
+        ),
+    };
+
+    let in_memory_cache = cli_args.cacheMode == CacheMode::None;
+    // An in-memory cache implies nothing on disk is touched, including preferences
+    let no_disk_writes = cli_args.noCacheWrite || in_memory_cache;
+    // Templates from --source-dir must never end up in (or replace) the shared cache file
+    let no_cache_writes = no_disk_writes || cli_args.sourceDir.is_some();
+
+    let response_cache = (!in_memory_cache).then(|| http_cache::ResponseCache {
+        dir: http_cache::ResponseCacheDirFor(&cache_file_path),
+        maxBytes: app_config.network.httpCacheMaxMb.unwrap_or(DEFAULT_HTTP_CACHE_MAX_MB) * 1024 * 1024,
+        writable: !no_disk_writes,
+    })
+    .filter(|c| c.maxBytes > 0);
+    api::ConfigureNetwork(BuildNetworkSettings(&cli_args, &app_config, github_config, response_cache));
+
+    if let Some(url) = app_config.orgConfigUrl.clone() {
+        app_config.org = org::LoadOrgConfig(&url, &org::OrgConfigCachePath(&config_file_path), !no_disk_writes).await;
+    }
 
     if VERBOSE.load(Ordering::SeqCst) {
-        eprintln!("Using cache file: {:?}", cache_file_path);
+        if cli_args.sourceDir.is_some() {
+            eprintln!("Using an in-memory cache built from --source-dir.");
+        } else if in_memory_cache {
+            eprintln!("Using an in-memory cache (--cache none).");
+        } else {
+            eprintln!("Using cache file: {:?}", cache_file_path);
+        }
     }
 
-    let (mut cache_data, cache_updated_by_fetch) =
-        cache::UpdateAndLoadLicenseCache(&cache_file_path, cli_args.refresh).await?;
+    // Preferences are personal, so every cache profile shares the default location
+    let placeholders_file_path = cli_args.placeholdersFile.clone().unwrap_or_else(|| {
+        match cli_args.cacheProfile {
+            Some(_) => default_cache_path.with_file_name(DEFAULT_PLACEHOLDERS_FILENAME),
+            None => cache_file_path.with_file_name(DEFAULT_PLACEHOLDERS_FILENAME),
+        }
+    });
+
+    // Plugins manage the cache themselves, so run them before any sync
+    if let Some(Commands::External(args)) = &cli_args.command {
+        let mut plugin_env = vec![
+            ("GETLICENSE_BIN", env::current_exe().unwrap_or_default().display().to_string()),
+            ("GETLICENSE_CONFIG", config_file_path.display().to_string()),
+            ("GETLICENSE_PLACEHOLDERS_FILE", placeholders_file_path.display().to_string()),
+        ];
+        if !in_memory_cache {
+            plugin_env.push(("GETLICENSE_CACHE_FILE", cache_file_path.display().to_string()));
+        }
+        if OFFLINE.load(Ordering::SeqCst) {
+            plugin_env.push(("GETLICENSE_OFFLINE", "true".to_string()));
+        }
+
+        process::exit(actions::plugin::RunPlugin(args, &plugin_env)?);
+    }
+
+    // Only `sync` and --refresh reach upstream; everything else reads the cache as it is.
+    // An in-memory cache has nothing to read, so it always syncs.
+    let sync_requested =
+        cli_args.refresh || in_memory_cache || matches!(cli_args.command, Some(Commands::Sync(_)));
+
+    // `sync --only` replaces the [sync] allowlist for this run
+    let sync_only = match &cli_args.command {
+        Some(Commands::Sync(args)) if !args.only.is_empty() => args.only.clone(),
+        _ => app_config.sync.only.clone(),
+    };
+    let github_fetcher = api::AllowlistFetcher::New(&api::GithubFetcher, &sync_only);
+
+    let (mut cache_data, mut cache_updated_by_fetch) = match &cli_args.sourceDir {
+        Some(source_dir) => {
+            cache::UpdateAndLoadLicenseCache(&api::MemoryFetcher::FromDirectory(source_dir)?, None, false, false).await?
+        }
+        None if !sync_requested => cache::LoadLicenseCache(&github_fetcher, &cache_file_path, !no_cache_writes).await?,
+        None if matches!(&cli_args.command, Some(Commands::Sync(args)) if args.resume) => {
+            if in_memory_cache {
+                return Err(AppError::ActionErrorVariant(error::ActionError::InvalidInput(
+                    "sync --resume continues a sync saved in the cache file; it cannot be used with --cache none".to_string(),
+                )));
+            }
+            cache::ResumeSync(&github_fetcher, &cache_file_path, !no_cache_writes)
+                .await?
+                .ok_or_else(|| {
+                    AppError::ActionErrorVariant(error::ActionError::InvalidInput(
+                        "nothing to resume: the cache holds no listing from an earlier sync; run `getlicense sync` first".to_string(),
+                    ))
+                })?
+        }
+        None => {
+            cache::UpdateAndLoadLicenseCache(
+                &github_fetcher,
+                (!in_memory_cache).then_some(cache_file_path.as_path()),
+                cli_args.refresh,
+                !no_cache_writes,
+            )
+            .await?
+        }
+    };
+
+    // Reported after the command so --strict-sync still lets it run on the partial cache
+    let sync_failures = cache_data
+        .lastSync
+        .as_ref()
+        .map(|record| record.failures.clone())
+        .unwrap_or_default();
+
+    if !in_memory_cache {
+        // Preferences may still be embedded in a cache written by an older version
+        let legacy_placeholders = std::mem::take(&mut cache_data.userPlaceholders);
+        let placeholders_load =
+            cache::LoadUserPlaceholders(&placeholders_file_path, legacy_placeholders)?;
+        cache_data.userPlaceholders = placeholders_load.placeholders;
+
+        if placeholders_load.migrated {
+            PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+        }
+        if placeholders_load.legacyCopyFound {
+            // Rewrite the cache once so the stale embedded copy disappears
+            cache_updated_by_fetch = true;
+        }
+    }
+
+    if !no_cache_writes {
+        if let Err(e) = cache::SaveSyncHistory(&cache_file_path, &cache_data) {
+            eprintln!("Warning: could not record this sync in the history log: {}", e);
+        }
+    }
+
+    // Persist freshly synced data in the background while the command runs and prints
+    let pending_cache_save = if cache_updated_by_fetch && !no_cache_writes {
+        if VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("Saving cache changes to {:?}...", cache_file_path);
+        }
+
+        let snapshot = cache_data.clone();
+        let save_path = cache_file_path.clone();
+        Some(tokio::spawn(async move {
+            cache::SaveCache(&save_path, &snapshot).await
+        }))
+    } else {
+        None
+    };
 
     let mut action_was_handled = true;
+    let command_started = Instant::now();
 
     match cli_args.command {
         Some(Commands::List(args)) => {
-            actions::list::ListLicenses(&cache_data, args.licenseIds).await?;
+            actions::list::ListLicenses(&cache_data, &args, &app_config).await?;
         }
         Some(Commands::DetailedList(args)) => {
-            actions::list::DetailedListLicenses(&cache_data, args.licenseIds).await?;
+            actions::list::DetailedListLicenses(&cache_data, &args, &app_config).await?;
         }
         Some(Commands::Info(args)) => {
-            actions::info::DisplayLicenseInfo(&cache_data, &args.licenseId).await?;
+            let licenseId = actions::LicenseIdOrPick(&cache_data, args.licenseId.as_deref())?;
+            actions::info::DisplayLicenseInfo(&cache_data, &licenseId, args.fullText, args.stats, &app_config)
+                .await?;
+        }
+        Some(Commands::Show(args)) => {
+            let licenseId = actions::LicenseIdOrPick(&cache_data, args.licenseId.as_deref())?;
+            actions::info::ShowLicenseText(&cache_data, &licenseId, args.annotate).await?;
         }
         Some(Commands::ShowPlaceholders(args)) => {
-            actions::info::ShowPlaceholdersForLicense(&cache_data, &args.licenseId).await?;
+            let licenseId = actions::LicenseIdOrPick(&cache_data, args.licenseId.as_deref())?;
+            if args.resolve {
+                actions::info::ShowResolvedPlaceholders(&cache_data, &licenseId, &app_config, cli_args.profile.as_deref())
+                    .await?;
+            } else {
+                actions::info::ShowPlaceholdersForLicense(&cache_data, &licenseId).await?;
+            }
         }
         Some(Commands::Compare(args)) => {
-            actions::compare::CompareLicenses(&cache_data, args.licenseIds).await?;
+            actions::compare::CompareLicenses(
+                &cache_data,
+                args.licenseIds,
+                &args.files,
+                args.diff,
+                args.baseline,
+                args.stats,
+                args.json,
+            )
+            .await?;
         }
         Some(Commands::Find(args)) => {
-            actions::find::FindMatchingLicenses(&cache_data, args.require, args.disallow).await?;
+            actions::find::FindMatchingLicenses(
+                &cache_data,
+                args.require,
+                args.disallow,
+                args.similarTo,
+                args.minSimilarity,
+                args.minScore,
+                args.best,
+                &app_config,
+            )
+            .await?;
+        }
+        Some(Commands::Choose(args)) => {
+            actions::choose::ChooseLicense(
+                &cache_data,
+                &app_config,
+                &config_file_path,
+                args.answers.as_deref(),
+                args.fromSaved,
+                args.best,
+            )
+            .await?;
+        }
+        Some(Commands::Tags) => {
+            actions::tags::ListTags(&cache_data).await?;
+        }
+        Some(Commands::Explain(args)) => {
+            actions::tags::ExplainTag(&cache_data, &args.tag).await?;
+        }
+        Some(Commands::Stats(args)) => {
+            let history_path = (!in_memory_cache).then(|| history::HistoryPathFor(&cache_file_path));
+            actions::corpus::ShowCorpusStats(&cache_data, history_path.as_deref(), args.json).await?;
+        }
+        Some(Commands::LintData(args)) => {
+            actions::lint_data::LintData(&cache_data, args.json).await?;
+        }
+        Some(Commands::Export(args)) => {
+            actions::export::ExportTemplate(&cache_data, &args.licenseId, args.path, args.force).await?;
+        }
+        Some(Commands::Vendor(args)) => {
+            actions::vendor::VendorTemplates(&cache_data, &args.dir).await?;
+        }
+        Some(Commands::Snippets(args)) => {
+            actions::snippets::ExportSnippets(&cache_data, &args.licenseIds, args.format, args.output).await?;
+        }
+        Some(Commands::ExportAll(args)) => {
+            actions::export::ExportAllTemplates(&cache_data, &args.dir, args.frontMatter, args.force)
+                .await?;
+        }
+        Some(Commands::Sync(args)) => {
+            if args.daemon {
+                if in_memory_cache || cli_args.sourceDir.is_some() {
+                    return Err(AppError::ActionErrorVariant(error::ActionError::InvalidInput(
+                        "sync --daemon keeps a cache on disk fresh; it cannot be used with --cache none or --source-dir".to_string(),
+                    )));
+                }
+                actions::sync::RunSyncDaemon(&mut cache_data, &cache_file_path, &sync_only, args.interval, !no_disk_writes)
+                    .await?;
+            } else {
+                actions::sync::ReportSync(&cache_data).await?;
+            }
+        }
+        Some(Commands::History(args)) => {
+            let history_path = (!in_memory_cache).then(|| history::HistoryPathFor(&cache_file_path));
+            actions::history::ShowSyncHistory(history_path.as_deref(), args.limit, args.json).await?;
         }
         Some(Commands::License(ref args)) => {
+            if let Some(lang) = &args.lang {
+                if translations::EnsureTranslations(&mut cache_data, &args.licenseIds, lang, &app_config, cli_args.refresh)
+                    .await?
+                {
+                    CACHE_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+                }
+            }
+
+            // Pinned template versions the cache does not hold yet are fetched up front
+            let template_lock = lockfile::LoadTemplateLock(Path::new("."))?;
+            if let (Some(lock), false) = (&template_lock, args.updateLock) {
+                let bodies_dir = (!in_memory_cache).then(|| cache::BodiesDirFor(&cache_file_path));
+                if lockfile::EnsurePinnedTemplates(&mut cache_data, lock, bodies_dir.as_deref()).await? {
+                    CACHE_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+                }
+            }
+
             // The fill action might modify the cache (user_placeholders)
+            let fill_journal = (!no_disk_writes).then(|| journal::FillJournal::ForCache(&cache_file_path));
             let modified_placeholder_cache =
-                actions::fill::FillLicenseTemplateAction(&mut cache_data, args, &cli_args).await?;
+                actions::fill::FillLicenseTemplateAction(
+                    &mut cache_data,
+                    args,
+                    &cli_args,
+                    &app_config,
+                    fill_journal.as_ref(),
+                    template_lock,
+                )
+                .await?;
 
             if modified_placeholder_cache {
-                CACHE_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+                PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
             }
         }
+        Some(Commands::Undo(args)) => {
+            let fill_journal = (!no_disk_writes).then(|| journal::FillJournal::ForCache(&cache_file_path));
+            actions::undo::UndoLastFill(fill_journal.as_ref(), args.force).await?;
+        }
         Some(Commands::SetPlaceholder(args)) => {
             actions::placeholder_management::SetPlaceholder(
                 &mut cache_data,
@@ -96,21 +445,148 @@ async fn main() -> Result<(), AppError> {
                 &args.value,
             )
             .await?;
-            CACHE_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+            PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
         }
         Some(Commands::GetPlaceholder(args)) => {
-            actions::placeholder_management::GetPlaceholder(&cache_data, args.key.as_deref())
-                .await?;
+            actions::placeholder_management::GetPlaceholder(
+                &cache_data,
+                args.key.as_deref(),
+                args.json,
+            )
+            .await?;
         }
         Some(Commands::ClearPlaceholders(args)) => {
             actions::placeholder_management::ClearPlaceholders(&mut cache_data, args.keys).await?;
-            CACHE_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+            PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+        }
+        Some(Commands::ImportPlaceholders(args)) => {
+            if actions::placeholder_management::ImportPlaceholders(
+                &mut cache_data,
+                &args.manifestPath,
+            )
+            .await?
+            {
+                PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+            }
+        }
+        Some(Commands::EditPlaceholders) => {
+            if actions::placeholder_management::EditPlaceholders(&mut cache_data).await? {
+                PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+            }
+        }
+        Some(Commands::Validate(args)) => {
+            actions::validate::ValidateLicenseFile(&cache_data, args.path, args.license.as_deref())
+                .await?;
+        }
+        Some(Commands::Identify(args)) => {
+            actions::identify::IdentifyLicense(&cache_data, &args.source, args.limit, args.json)
+                .await?;
+        }
+        // Handled before the cache is loaded
+        Some(Commands::Normalize(_)) | Some(Commands::External(_)) => {}
+        Some(Commands::Outdated(args)) => {
+            actions::outdated::CheckOutdated(&cache_data, args.path, args.license.as_deref())
+                .await?;
+        }
+        Some(Commands::Upgrade(args)) => {
+            actions::upgrade::UpgradeLicense(
+                &cache_data,
+                args.path,
+                args.license.as_deref(),
+                args.yes,
+            )
+            .await?;
+        }
+        Some(Commands::Unfill(args)) => {
+            if actions::unfill::UnfillLicense(
+                &mut cache_data,
+                args.path,
+                args.license.as_deref(),
+                args.save,
+                args.provenance,
+                args.json,
+            )
+            .await?
+            {
+                PLACEHOLDERS_MODIFIED_BY_ACTION.store(true, Ordering::SeqCst);
+            }
+        }
+        Some(Commands::Verify(args)) => {
+            actions::verify::VerifyManifestLicense(&cache_data, &args.projectDir).await?;
+        }
+        Some(Commands::Check(args)) => {
+            let selection = actions::check::CheckSelection { headers: args.headers, dependencies: args.dependencies };
+            actions::check::CheckProject(&cache_data, args.license.as_deref(), selection, &app_config.org, args.json)
+                .await?;
+        }
+        Some(Commands::Notices(args)) => {
+            actions::notices::GenerateNotices(&cache_data, &args.projectDir, args.output, &app_config.org).await?;
+        }
+        Some(Commands::CheckHeaders(args)) => {
+            actions::headers::CheckHeaders(
+                &cache_data,
+                &args.paths,
+                args.license.as_deref(),
+                args.report,
+                args.json,
+                args.jobs.map(usize::from),
+            )
+            .await?;
+        }
+        Some(Commands::AddHeaders(args)) => {
+            actions::headers::AddHeaders(
+                &cache_data,
+                &args.paths,
+                args.license.as_deref(),
+                args.style,
+                args.copyright,
+                args.fullname.as_deref(),
+                args.dryRun,
+            )
+            .await?;
+        }
+        Some(Commands::FixHeaders(args)) => {
+            actions::headers::FixHeaders(
+                &cache_data,
+                &args.paths,
+                args.license.as_deref(),
+                &args.oldHolder,
+                args.fullname.as_deref(),
+                args.dryRun,
+            )
+            .await?;
+        }
+        Some(Commands::Alias(args)) => match args.command {
+            AliasCommands::Add(add_args) => {
+                actions::alias::AddAlias(&cache_data, &app_config, &config_file_path, &add_args.name, &add_args.licenseId)
+                    .await?;
+            }
+            AliasCommands::Remove(remove_args) => {
+                actions::alias::RemoveAlias(&app_config, &config_file_path, &remove_args.name).await?;
+            }
+            AliasCommands::List => actions::alias::ListAliases(&app_config).await?,
+        },
+        Some(Commands::Hook(args)) => match args.command {
+            HookCommands::Install(install_args) => {
+                actions::hook::InstallHook(
+                    install_args.force,
+                    install_args.preCommitFramework,
+                    install_args.skipHeaders,
+                )
+                .await?;
+            }
+        },
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate(args)) => {
+            actions::self_update::SelfUpdate(args.check).await?;
         }
         None => {
             action_was_handled = false;
         }
     }
 
+    timings::Record("command", None, command_started);
+
     if !action_was_handled && cli_args.generateCompletion.is_none() {
         <Cli as clap::CommandFactory>::command()
             .print_help()
@@ -118,20 +594,58 @@ async fn main() -> Result<(), AppError> {
         // Instead of exiting with an error code, print a newline after the help
         // text and exit successfully.
         println!();
-        return Ok(());
     }
 
-    if cache_updated_by_fetch || CACHE_MODIFIED_BY_ACTION.load(Ordering::SeqCst) {
+    let update_check_path = (!no_disk_writes).then(|| update::UpdateCheckPathFor(&cache_file_path));
+    update::NotifyIfUpdateAvailable(update_check_path.as_deref(), &app_config).await;
+
+    if let Some(handle) = pending_cache_save {
+        handle
+            .await
+            .map_err(|e| AppError::Io(io::Error::other(e), cache_file_path.clone()))??;
+    }
+
+    // Changes made by the action itself (e.g. fetched translations) need a second write
+    if no_cache_writes {
+        if VERBOSE.load(Ordering::SeqCst)
+            && (cache_updated_by_fetch || CACHE_MODIFIED_BY_ACTION.load(Ordering::SeqCst))
+        {
+            eprintln!("Not saving cache changes (cache writes are disabled).");
+        }
+    } else if CACHE_MODIFIED_BY_ACTION.load(Ordering::SeqCst) {
         if VERBOSE.load(Ordering::SeqCst) {
             eprintln!("Saving cache changes to {:?}...", cache_file_path);
         }
 
-        cache::SaveCache(&cache_file_path, &cache_data)?;
-    } else {
+        cache::SaveCache(&cache_file_path, &cache_data).await?;
+    } else if !cache_updated_by_fetch {
         if VERBOSE.load(Ordering::SeqCst) {
             eprintln!("No changes to save to cache file.");
         }
     }
 
+    if PLACEHOLDERS_MODIFIED_BY_ACTION.load(Ordering::SeqCst) && no_disk_writes {
+        // Unlike sync results, these changes were asked for, so say they were dropped
+        eprintln!(
+            "Warning: placeholder preferences were not saved to {:?} ({}).",
+            placeholders_file_path,
+            if in_memory_cache { "--cache none" } else { "--no-cache-write" }
+        );
+    } else if PLACEHOLDERS_MODIFIED_BY_ACTION.load(Ordering::SeqCst) {
+        cache::SaveUserPlaceholders(&placeholders_file_path, &cache_data.userPlaceholders)?;
+    }
+
+    if timings::IsEnabled() {
+        display::PrintTimings(&timings::SummarizePhases(&timings::Recorded()), timings::SinceStart());
+    }
+
+    if cli_args.strictSync && !sync_failures.is_empty() {
+        display::PrintSyncFailures(&sync_failures);
+        return Err(AppError::ActionErrorVariant(error::ActionError::CheckFailed(format!(
+            "sync finished with {} failure(s) (--strict-sync)",
+            sync_failures.len()
+        ))));
+    }
+
     return Ok(());
 }
@@ -0,0 +1,236 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::constants::MANIFEST_FILENAMES;
+use crate::error::{ActionError, AppError, ParseError};
+
+// Fields of a Cargo.toml [package] table that getlicense cares about.
+// Values inherited from a workspace (`name.workspace = true`) are not plain strings and are skipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CargoPackage {
+    pub name: Option<String>,
+    pub repository: Option<String>,
+    pub homepage: Option<String>,
+    // As written, e.g. "Jane Doe <jane@example.com>"
+    pub authors: Vec<String>,
+}
+
+// "Jane Doe <jane@example.com>" -> (Some("Jane Doe"), Some("jane@example.com")). Either part
+// may be missing: "Jane Doe" has no email, "<jane@example.com>" no name.
+pub fn SplitAuthor(author: &str) -> (Option<String>, Option<String>) {
+    let nonEmpty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+
+    match author.split_once('<') {
+        Some((name, rest)) => (nonEmpty(name), rest.split_once('>').and_then(|(email, _)| nonEmpty(email))),
+        None => (nonEmpty(author), None),
+    }
+}
+
+// The SPDX expression declared in a package manifest, e.g. "MIT OR Apache-2.0".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestLicense {
+    pub manifestPath: PathBuf,
+    pub expression: String,
+}
+
+fn ReadManifestFile(manifestPath: &Path) -> Result<String, AppError> {
+    fs::read_to_string(manifestPath).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, manifestPath.to_path_buf()))
+    })
+}
+
+fn ReadTomlManifest(manifestPath: &Path) -> Result<toml::Value, AppError> {
+    let content = ReadManifestFile(manifestPath)?;
+
+    Ok(toml::from_str(&content)
+        .map_err(|e| ParseError::TomlError(manifestPath.display().to_string(), e))?)
+}
+
+pub fn ReadCargoPackage(manifestPath: &Path) -> Result<Option<CargoPackage>, AppError> {
+    if !manifestPath.exists() {
+        return Ok(None);
+    }
+
+    let manifest = ReadTomlManifest(manifestPath)?;
+
+    let Some(package) = manifest.get("package") else {
+        return Ok(None);
+    };
+    let field = |name: &str| {
+        package
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
+    let authors = package
+        .get("authors")
+        .and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    Ok(Some(CargoPackage {
+        name: field("name"),
+        repository: field("repository"),
+        homepage: field("homepage"),
+        authors,
+    }))
+}
+
+// package.json "license" as a string or legacy {"type": ...}, or the even older
+// "licenses": [{"type": ...}] array, which lists alternatives.
+pub fn PackageJsonLicense(manifest: &serde_json::Value) -> Option<String> {
+    let TypeOf = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Object(o) => o.get("type").and_then(|t| t.as_str()).map(|s| s.to_string()),
+        _ => None,
+    };
+
+    if let Some(license) = manifest.get("license").and_then(TypeOf) {
+        return Some(license);
+    }
+
+    let alternatives: Vec<String> = manifest
+        .get("licenses")
+        .and_then(|l| l.as_array())
+        .map(|list| list.iter().filter_map(TypeOf).collect())
+        .unwrap_or_default();
+
+    (!alternatives.is_empty()).then(|| alternatives.join(" OR "))
+}
+
+// Cargo.toml package.license, package.json license (string or legacy {"type": ...}),
+// and pyproject.toml project.license or tool.poetry.license.
+fn LicenseFieldOf(fileName: &str, manifestPath: &Path) -> Result<Option<String>, AppError> {
+    let expression = match fileName {
+        "Cargo.toml" => ReadTomlManifest(manifestPath)?
+            .get("package")
+            .and_then(|p| p.get("license"))
+            .and_then(|l| l.as_str())
+            .map(|s| s.to_string()),
+        "package.json" => {
+            let content = ReadManifestFile(manifestPath)?;
+            let manifest: serde_json::Value = serde_json::from_str(&content)
+                .map_err(|e| ParseError::JsonError(manifestPath.display().to_string(), e))?;
+
+            PackageJsonLicense(&manifest)
+        }
+        "pyproject.toml" => {
+            let manifest = ReadTomlManifest(manifestPath)?;
+
+            // A {file = ...} or {text = ...} table holds the license text, not an identifier
+            manifest
+                .get("project")
+                .and_then(|p| p.get("license"))
+                .and_then(|l| l.as_str())
+                .or_else(|| {
+                    manifest
+                        .get("tool")
+                        .and_then(|t| t.get("poetry"))
+                        .and_then(|p| p.get("license"))
+                        .and_then(|l| l.as_str())
+                })
+                .map(|s| s.to_string())
+        }
+        _ => None,
+    };
+
+    Ok(expression.filter(|e| !e.trim().is_empty()))
+}
+
+pub fn ReadManifestLicenses(projectDir: &Path) -> Result<Vec<ManifestLicense>, AppError> {
+    let mut licenses = Vec::new();
+
+    for fileName in MANIFEST_FILENAMES {
+        let manifestPath = projectDir.join(fileName);
+
+        if !manifestPath.is_file() {
+            continue;
+        }
+
+        if let Some(expression) = LicenseFieldOf(fileName, &manifestPath)? {
+            licenses.push(ManifestLicense {
+                manifestPath,
+                expression,
+            });
+        }
+    }
+
+    Ok(licenses)
+}
+
+// License IDs referenced by an SPDX expression, without operators or parentheses.
+// "(MIT OR Apache-2.0) AND BSD-3-Clause" -> ["MIT", "Apache-2.0", "BSD-3-Clause"]
+// Older crates write "MIT/Apache-2.0", which Cargo reads as OR.
+pub fn SpdxExpressionIds(expression: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut tokens = expression
+        .split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '/'))
+        .filter(|t| !t.is_empty());
+
+    while let Some(token) = tokens.next() {
+        match token.to_uppercase().as_str() {
+            "OR" | "AND" => {}
+            // Exception names (e.g. "LLVM-exception") are not licenses
+            "WITH" => {
+                tokens.next();
+            }
+            _ => ids.push(token.trim_end_matches('+').to_string()),
+        }
+    }
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestSpdxExpressionIds() {
+        assert_eq!(SpdxExpressionIds("MIT"), vec!["MIT"]);
+        assert_eq!(
+            SpdxExpressionIds("(MIT OR Apache-2.0) AND GPL-2.0+ WITH Classpath-exception-2.0"),
+            vec!["MIT", "Apache-2.0", "GPL-2.0"]
+        );
+        assert_eq!(SpdxExpressionIds("MIT/Apache-2.0"), vec!["MIT", "Apache-2.0"]);
+    }
+
+    #[test]
+    fn TestReadCargoPackage() {
+        let dir = TempDir::New("cargo-package");
+        let manifestPath = dir.join("Cargo.toml");
+
+        assert_eq!(ReadCargoPackage(&manifestPath).unwrap(), None);
+
+        fs::write(
+            &manifestPath,
+            "[package]\nname = \"demo\"\nhomepage = \"https://demo.example.com\"\n\
+             repository.workspace = true\nauthors = [\"Jane Doe <jane@example.com>\", \"Bot\"]\n",
+        )
+        .unwrap();
+        let package = ReadCargoPackage(&manifestPath).unwrap().unwrap();
+        assert_eq!(package.name.as_deref(), Some("demo"));
+        assert_eq!(package.repository, None);
+        assert_eq!(package.homepage.as_deref(), Some("https://demo.example.com"));
+        assert_eq!(package.authors, ["Jane Doe <jane@example.com>", "Bot"]);
+
+        fs::write(&manifestPath, "[workspace]\nmembers = [\"a\"]\n").unwrap();
+        assert_eq!(ReadCargoPackage(&manifestPath).unwrap(), None);
+
+        fs::write(&manifestPath, "[package\n").unwrap();
+        assert!(ReadCargoPackage(&manifestPath).is_err());
+    }
+
+    #[test]
+    fn TestSplitAuthor() {
+        let owned = |name: Option<&str>, email: Option<&str>| (name.map(str::to_string), email.map(str::to_string));
+
+        assert_eq!(SplitAuthor("Jane Doe <jane@example.com>"), owned(Some("Jane Doe"), Some("jane@example.com")));
+        assert_eq!(SplitAuthor("  Jane Doe  "), owned(Some("Jane Doe"), None));
+        assert_eq!(SplitAuthor("<jane@example.com>"), owned(None, Some("jane@example.com")));
+        assert_eq!(SplitAuthor("Jane Doe <>"), owned(Some("Jane Doe"), None));
+        assert_eq!(SplitAuthor("Jane Doe <jane@example.com"), owned(Some("Jane Doe"), None));
+    }
+}
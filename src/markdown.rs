@@ -0,0 +1,82 @@
+use colored::*;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::constants::CHOOSEALICENSE_SITE_URL;
+use crate::terminal;
+
+// The inline markdown found in rules.yml descriptions and license front matter:
+// [text](url) links, `code`, **strong** and *emphasis*. Block-level syntax does not occur.
+static INLINE_MARKDOWN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"\[(?P<linkText>[^\]]+)\]\((?P<url>[^)\s]+)\)",
+        r"|`(?P<code>[^`]+)`",
+        r"|\*\*(?P<strong>[^*]+)\*\*",
+        r"|\*(?P<emphasis>[^*\s](?:[^*]*[^*\s])?)\*",
+    ))
+    .expect("valid inline markdown regex")
+});
+
+fn AbsoluteUrl(url: &str) -> String {
+    if url.starts_with('/') { format!("{}{}", CHOOSEALICENSE_SITE_URL, url) } else { url.to_string() }
+}
+
+// Styled for the terminal. Links become OSC 8 hyperlinks where supported, otherwise
+// "text (url)", so the target is never lost.
+pub fn RenderInlineMarkdown(text: &str) -> String {
+    INLINE_MARKDOWN_RE
+        .replace_all(text, |caps: &Captures| {
+            if let (Some(linkText), Some(url)) = (caps.name("linkText"), caps.name("url")) {
+                let url = AbsoluteUrl(url.as_str());
+
+                if terminal::HYPERLINKS.load(std::sync::atomic::Ordering::SeqCst) || linkText.as_str() == url {
+                    terminal::Hyperlink(linkText.as_str(), &url).underline().to_string()
+                } else {
+                    format!("{} ({})", linkText.as_str().underline(), url)
+                }
+            } else if let Some(code) = caps.name("code") {
+                code.as_str().cyan().to_string()
+            } else if let Some(strong) = caps.name("strong") {
+                strong.as_str().bold().to_string()
+            } else if let Some(emphasis) = caps.name("emphasis") {
+                emphasis.as_str().italic().to_string()
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .to_string()
+}
+
+// Markup removed, for places that truncate or measure the text
+pub fn StripInlineMarkdown(text: &str) -> String {
+    INLINE_MARKDOWN_RE
+        .replace_all(text, |caps: &Captures| {
+            ["linkText", "code", "strong", "emphasis"]
+                .iter()
+                .find_map(|name| caps.name(name))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default()
+        })
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestInlineMarkdown() {
+        colored::control::set_override(false);
+
+        let text = "See [the FSF](https://www.gnu.org/licenses/) or [MIT](/licenses/mit/); use **any** `LICENSE` *file*, 2 * 3 * 4.";
+
+        assert_eq!(
+            StripInlineMarkdown(text),
+            "See the FSF or MIT; use any LICENSE file, 2 * 3 * 4."
+        );
+        assert_eq!(
+            RenderInlineMarkdown(text),
+            "See the FSF (https://www.gnu.org/licenses/) or MIT (https://choosealicense.com/licenses/mit/); use any LICENSE file, 2 * 3 * 4."
+        );
+    }
+}
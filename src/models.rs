@@ -1,19 +1,70 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Cache {
     // Ensures licenses field exists even if missing in JSON
     #[serde(default)]
-    // Key: lowercase SPDX ID
-    pub licenses: HashMap<String, LicenseEntry>,
+    // Key: lowercase SPDX ID. Entries are shared, so carrying them between cache
+    // generations or into action results never copies the license bodies.
+    pub licenses: HashMap<String, Arc<LicenseEntry>>,
     #[serde(default)]
     // Key: e.g., "data:rules.yml"
-    pub dataFiles: HashMap<String, DataFileEntry>,
-    // Allow alias for backward compatibility
-    #[serde(default, alias = "user_placeholders_cache")]
+    pub dataFiles: HashMap<String, Arc<DataFileEntry>>,
+    // Persisted in a separate placeholders file. Still read here (never written) so
+    // preferences embedded by older versions can be migrated out of the cache.
+    #[serde(default, alias = "user_placeholders_cache", skip_serializing)]
     // Key: standardized placeholder key (e.g., "fullname")
     pub userPlaceholders: HashMap<String, String>,
+    // Directory listings of the last sync, keyed by repository path ("_data", "_licenses"),
+    // so `sync --resume` can fetch what is still missing without listing again
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub listings: HashMap<String, Vec<GitHubFile>>,
+    // Lowercase SPDX ID -> language code -> translated text, fetched by `license --lang`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub translations: HashMap<String, HashMap<String, Arc<TranslationEntry>>>,
+    // Git blob SHAs of template versions pinned in a getlicense.lock that the synced
+    // templates have moved past. Their files live in the bodies directory like any other
+    // body and are exempt from its clean-up.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub pinnedTemplates: BTreeSet<String>,
+    // Git blob SHA -> file content of the pinned versions loaded for this run
+    #[serde(skip)]
+    pub pinnedTemplateBodies: HashMap<String, String>,
+    // Upstream file name -> why the last sync could not parse it. Such files never make it
+    // into the cache, so lint-data reports them from here.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parseFailures: BTreeMap<String, String>,
+    // What the sync that produced this cache fetched; appended to the history log by
+    // whoever persists the cache
+    #[serde(skip)]
+    pub lastSync: Option<crate::history::SyncRecord>,
+}
+
+impl Cache {
+    fn ParsedDataFile<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.dataFiles
+            .get(key)
+            .and_then(|entry| T::deserialize(&entry.content).ok())
+    }
+
+    pub fn RulesData(&self) -> Option<RulesDataContent> {
+        self.ParsedDataFile(crate::constants::RULES_YML_KEY)
+    }
+
+    pub fn FieldsData(&self) -> Option<FieldsDataContent> {
+        self.ParsedDataFile(crate::constants::FIELDS_YML_KEY)
+    }
+}
+
+// Rate-limits the automatic new-version check; stored in its own file next to the cache
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateCheckState {
+    pub checkedAt: chrono::DateTime<chrono::Utc>,
+    // Latest release tag seen at checkedAt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latestVersion: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -26,6 +77,9 @@ pub struct LicenseEntry {
     pub description: Option<String>,
     pub filename: String,
     pub sha: String,
+    // SHA-256 of fileContentCached, checked whenever the body is loaded from disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
     // Raw tags
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub permissions: Vec<String>,
@@ -35,7 +89,9 @@ pub struct LicenseEntry {
     // Raw tags
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub limitations: Vec<String>,
-    // Full raw license body
+    // Full raw license body. Stored in the content-addressed bodies directory keyed by
+    // `sha`; only read from JSON for caches written before that existed.
+    #[serde(default, skip_serializing)]
     pub fileContentCached: String,
     // e.g., ["[fullname]", "[year]"]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -43,6 +99,31 @@ pub struct LicenseEntry {
     pub infoComponents: InfoComponents,
 }
 
+// Test fixture: a license with the given body and no rule tags. Override the rest with
+// struct update syntax.
+#[cfg(test)]
+impl LicenseEntry {
+    pub fn ForTest(spdxId: &str, fileContent: &str) -> Self {
+        LicenseEntry {
+            spdxId: spdxId.to_string(),
+            title: format!("{} License", spdxId),
+            nickname: None,
+            description: None,
+            filename: format!("{}.txt", spdxId.to_lowercase()),
+            sha: String::new(),
+            sha256: None,
+            permissions: Vec::new(),
+            conditions: Vec::new(),
+            limitations: Vec::new(),
+            fileContentCached: fileContent.to_string(),
+            placeholdersInBody: crate::parser::FindPlaceholdersInBody(&crate::parser::LicenseTemplateBody(
+                fileContent,
+            )),
+            infoComponents: Default::default(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct InfoComponents {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -73,6 +154,14 @@ pub struct RuleDetail {
     pub description: String,
 }
 
+// A license text in another language, from a [translations] source in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TranslationEntry {
+    // URL or path it was read from; the translation is fetched again when this changes
+    pub source: String,
+    pub content: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DataFileEntry {
     pub sha: String,
@@ -80,7 +169,7 @@ pub struct DataFileEntry {
     pub content: serde_yaml::Value,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GitHubFile {
     pub name: String,
     // "file" or "dir"
@@ -88,14 +177,42 @@ pub struct GitHubFile {
     pub fileType: String,
     pub sha: String,
     // Present for files
+    #[serde(rename = "download_url")]
     pub downloadUrl: Option<String>,
 }
 
+// Response of the git blobs API, used to fetch a template version by its SHA
+#[derive(Deserialize, Debug, Clone)]
+pub struct GitHubBlob {
+    pub content: String,
+    // "base64" or "utf-8"
+    pub encoding: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GitHubRelease {
+    #[serde(rename = "tag_name")]
+    pub tagName: String,
+    #[cfg(feature = "self-update")]
+    #[serde(default)]
+    pub assets: Vec<GitHubReleaseAsset>,
+}
+
+#[cfg(feature = "self-update")]
+#[derive(Deserialize, Debug, Clone)]
+pub struct GitHubReleaseAsset {
+    pub name: String,
+    #[serde(rename = "browser_download_url")]
+    pub browserDownloadUrl: String,
+}
+
 #[derive(Deserialize, Debug, Clone, Default)]
 // To match YAML keys like "spdx-id"
 #[serde(rename_all = "kebab-case")]
 pub struct FrontMatter {
-    // Optional because we might guess it
+    // Optional because we might guess it. rename_all leaves camelCase names alone, so
+    // the upstream key needs spelling out.
+    #[serde(rename = "spdx-id")]
     pub spdxId: Option<String>,
     pub title: Option<String>,
     pub nickname: Option<String>,
@@ -109,10 +226,51 @@ pub struct FrontMatter {
     pub conditions: Vec<String>,
     #[serde(default)]
     pub limitations: Vec<String>,
-    // Project name -> URL
+    // Project name -> URL, empty when upstream lists a project without one
+    #[serde(default, deserialize_with = "DeserializeUsing")]
     pub using: Option<HashMap<String, String>>,
 }
 
+// choosealicense has written `using` both as a map and as a list of single-entry maps
+// (and sometimes bare project names), with or without URLs.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UsingField {
+    Map(HashMap<String, Option<String>>),
+    List(Vec<UsingListItem>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UsingListItem {
+    Project(HashMap<String, Option<String>>),
+    Name(String),
+}
+
+fn DeserializeUsing<'de, D>(deserializer: D) -> Result<Option<HashMap<String, String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let projects: Vec<(String, Option<String>)> = match Option::<UsingField>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(UsingField::Map(map)) => map.into_iter().collect(),
+        Some(UsingField::List(items)) => items
+            .into_iter()
+            .flat_map(|item| match item {
+                UsingListItem::Project(map) => map.into_iter().collect::<Vec<_>>(),
+                UsingListItem::Name(name) => vec![(name, None)],
+            })
+            .collect(),
+    };
+
+    Ok(Some(
+        projects
+            .into_iter()
+            .map(|(project, url)| (project, url.unwrap_or_default().trim().to_string()))
+            .collect(),
+    ))
+}
+
 // Example for rules.yml content
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct RulesDataContent {
@@ -133,12 +291,31 @@ pub struct RuleSource {
 
 // Example for fields.yml content
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(from = "FieldsDataShape")]
 pub struct FieldsDataContent {
-    // Assuming the root is a list or a dict with a "fields" key
+    // Upstream's root is a list; a dict with a "fields" key is accepted too
     #[serde(default, alias="fields")]
     pub items: Vec<FieldSource>,
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldsDataShape {
+    List(Vec<FieldSource>),
+    Map {
+        #[serde(default, alias = "fields")]
+        items: Vec<FieldSource>,
+    },
+}
+
+impl From<FieldsDataShape> for FieldsDataContent {
+    fn from(shape: FieldsDataShape) -> Self {
+        match shape {
+            FieldsDataShape::List(items) | FieldsDataShape::Map { items } => Self { items },
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FieldSource {
     // This is the placeholder name e.g., "fullname"
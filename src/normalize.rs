@@ -0,0 +1,73 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::constants::EQUIVALENT_WORDS_TUPLES;
+
+// Copyright notices are ignored when matching (SPDX matching guidelines, section 11).
+// A bare "Copyright" at the end of a line is the lead-in of a notice whose holder is a
+// placeholder, so template fragments and filled text drop the same lines.
+static COPYRIGHT_LINE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?im)^[ \t]*(?:copyright\b[ \t]*(?:\(c\)|©|\d|\[|<|$)|©|\(c\)[ \t]+\d|all rights reserved\.?[ \t]*$).*$")
+        .unwrap()
+});
+
+// Bullets and list numbering at the start of a line ("*", "-", "1.", "(a)")
+static LIST_MARKER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*(?:[*•·-]|\d{1,2}[.)]|\([a-z0-9]{1,3}\))[ \t]+").unwrap());
+
+static EQUIVALENT_WORDS: Lazy<(Regex, HashMap<&'static str, &'static str>)> = Lazy::new(|| {
+    let alternatives: Vec<String> = EQUIVALENT_WORDS_TUPLES
+        .iter()
+        .map(|(variant, _)| regex::escape(variant))
+        .collect();
+    let re = Regex::new(&format!(r"\b(?:{})\b", alternatives.join("|"))).unwrap();
+
+    (re, EQUIVALENT_WORDS_TUPLES.iter().cloned().collect())
+});
+
+fn NormalizePunctuation(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{201C}' | '\u{201D}'
+            | '\u{201E}' | '\u{201F}' | '"' | '`' => '\'',
+            _ => c,
+        })
+        .collect::<String>()
+        .replace('©', "(c)")
+        .replace("https://", "http://")
+}
+
+/// Canonical form of license text for comparison, following the SPDX license matching
+/// guidelines: copyright lines dropped, case folded, dashes and quotes unified, list
+/// markers removed, equivalent spellings substituted and whitespace collapsed.
+pub fn NormalizeLicenseText(text: &str) -> String {
+    let withoutCopyright = COPYRIGHT_LINE_RE.replace_all(text, "");
+    let withoutMarkers = LIST_MARKER_RE.replace_all(&withoutCopyright, "");
+    let folded = NormalizePunctuation(&withoutMarkers.to_lowercase());
+    let collapsed = folded.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let (re, canonical) = &*EQUIVALENT_WORDS;
+    re.replace_all(&collapsed, |caps: &regex::Captures| canonical[&caps[0]].to_string())
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestNormalizeLicenseText() {
+        let template = "Copyright (c) [year] [fullname]\n\n1. The Licence is granted \u{201C}as is\u{201D} \u{2014} see https://example.com.";
+        let filled = "COPYRIGHT 2024 Jane Doe\nAll rights reserved.\n\n  1.  The license is granted\n\"as is\" - see http://example.com.";
+
+        assert_eq!(NormalizeLicenseText(template), NormalizeLicenseText(filled));
+        assert_eq!(
+            NormalizeLicenseText(filled),
+            "the license is granted 'as is' - see http://example.com."
+        );
+        // A sentence that merely starts with the word is not a notice
+        assert_eq!(NormalizeLicenseText("Copyright notice must be kept."), "copyright notice must be kept.");
+    }
+}
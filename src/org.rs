@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, SystemTime};
+
+use colored::*;
+
+use crate::api;
+use crate::config::OrgConfig;
+use crate::constants::{ORG_CONFIG_CACHE_FILENAME, ORG_CONFIG_REFRESH_HOURS};
+
+pub fn OrgConfigCachePath(configPath: &Path) -> PathBuf {
+    configPath.with_file_name(ORG_CONFIG_CACHE_FILENAME)
+}
+
+fn IsFresh(cachePath: &Path) -> bool {
+    fs::metadata(cachePath)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < Duration::from_secs(ORG_CONFIG_REFRESH_HOURS * 3600))
+}
+
+// First line of the saved copy, so a copy fetched from another org_config_url is never
+// applied
+fn SourceHeader(url: &str) -> String {
+    format!("# getlicense org config from {}\n", url)
+}
+
+fn ReadCachedOrgConfig(cachePath: &Path, url: &str) -> Option<OrgConfig> {
+    let content = fs::read_to_string(cachePath).ok()?;
+
+    if !content.starts_with(&SourceHeader(url)) {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Config] Ignoring {}: it was fetched from another org_config_url", cachePath.display());
+        }
+        return None;
+    }
+
+    match toml::from_str(&content) {
+        Ok(orgConfig) => Some(orgConfig),
+        Err(e) => {
+            eprintln!("{} ignoring unreadable {}: {}", "Warning:".yellow(), cachePath.display(), e);
+            None
+        }
+    }
+}
+
+// The org config from the local copy of `url` while it is less than a day old (or when
+// offline), otherwise freshly fetched. A failed fetch falls back to the stale copy, then to
+// no policy, so an unreachable server never blocks a command.
+pub async fn LoadOrgConfig(url: &str, cachePath: &Path, persist: bool) -> OrgConfig {
+    let offline = crate::OFFLINE.load(Ordering::SeqCst);
+
+    if IsFresh(cachePath) || offline {
+        if let Some(orgConfig) = ReadCachedOrgConfig(cachePath, url) {
+            return orgConfig;
+        }
+    }
+
+    if offline {
+        eprintln!(
+            "{} no saved copy of the org config from {} and --offline prevents fetching it; continuing without it",
+            "Warning:".yellow(),
+            url
+        );
+        return OrgConfig::default();
+    }
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Config] Fetching organization config from {}", url);
+    }
+
+    let fetched = api::FetchFileContent(url).await.map_err(|e| e.to_string()).and_then(|content| {
+        toml::from_str::<OrgConfig>(&content)
+            .map(|orgConfig| (content, orgConfig))
+            .map_err(|e| format!("not a valid org config: {}", e))
+    });
+
+    match fetched {
+        Ok((content, orgConfig)) => {
+            if persist {
+                let written = cachePath
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(cachePath, SourceHeader(url) + &content));
+
+                if let Err(e) = written {
+                    eprintln!("{} could not save the org config to {}: {}", "Warning:".yellow(), cachePath.display(), e);
+                }
+            }
+
+            orgConfig
+        }
+        Err(e) => {
+            let cached = ReadCachedOrgConfig(cachePath, url);
+
+            eprintln!(
+                "{} could not fetch the org config from {} ({}); {}",
+                "Warning:".yellow(),
+                url,
+                e,
+                if cached.is_some() { "using the last saved copy" } else { "continuing without it" }
+            );
+
+            cached.unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestSavedCopyOnlyServesItsOwnUrl() {
+        let dir = TempDir::New("org-config");
+        let cachePath = OrgConfigCachePath(&dir.join("config.toml"));
+        let url = "https://intranet.example.com/getlicense/org.toml";
+        fs::write(&cachePath, SourceHeader(url) + "banned_licenses = [\"AGPL-3.0\"]\n").unwrap();
+
+        assert!(ReadCachedOrgConfig(&cachePath, url).unwrap().IsBanned("agpl-3.0"));
+        assert_eq!(ReadCachedOrgConfig(&cachePath, "https://other.example.com/org.toml"), None);
+    }
+}
@@ -5,11 +5,13 @@ use std::sync::atomic::Ordering;
 
 use crate::constants::RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES;
 use crate::error::ParseError;
+use crate::normalize;
+use crate::spdx_markup;
 use crate::models::{
     FrontMatter, InfoComponents, ParsedRules, RuleDetail, RuleSource, RulesDataContent,
 };
 
-fn SplitFrontMatterAndBody(fileContent: &str) -> (Option<String>, String) {
+pub fn SplitFrontMatterAndBody(fileContent: &str) -> (Option<String>, String) {
     if fileContent.starts_with("---") {
         if let Some(endFmIdx) = fileContent.get(3..).and_then(|s| s.find("---")) {
             let fmStr = fileContent[3..(3 + endFmIdx)].trim().to_string();
@@ -22,6 +24,11 @@ fn SplitFrontMatterAndBody(fileContent: &str) -> (Option<String>, String) {
     (None, fileContent.trim().to_string())
 }
 
+// The license text as it should appear in a LICENSE file, without the YAML front matter.
+pub fn LicenseTemplateBody(fileContent: &str) -> String {
+    SplitFrontMatterAndBody(fileContent).1
+}
+
 pub fn ParseLicenseFile(
     filename: &str,
     fileContent: &str,
@@ -97,12 +104,43 @@ pub fn _ParseDataFileToStruct<T: DeserializeOwned>(
     serde_yaml::from_str(fileContent).map_err(|e| ParseError::YamlError(filename.to_string(), e))
 }
 
+// Malformed markup is reported once in verbose mode and the text is then treated as plain.
+fn ParsedSpdxTemplate(body: &str) -> Option<Vec<spdx_markup::TemplateSegment>> {
+    if !spdx_markup::IsSpdxTemplate(body) {
+        return None;
+    }
+
+    match spdx_markup::ParseSpdxTemplate(body) {
+        Ok(segments) => Some(segments),
+        Err(e) => {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[Parse] {}; treating template as plain text", e);
+            }
+            None
+        }
+    }
+}
+
+// Finds "[name]" and mustache-style "{{ name }}" tokens, returned exactly as written.
+// For SPDX-markup templates the <<var;...>> tokens are the placeholders instead.
 pub fn FindPlaceholdersInBody(body: &str) -> Vec<String> {
-    let re = Regex::new(r"\[([^\]]+)\]").unwrap();
+    if let Some(segments) = ParsedSpdxTemplate(body) {
+        let mut tokens = Vec::new();
+        spdx_markup::CollectVarTokens(&segments, &mut tokens);
+        tokens.sort();
+        tokens.dedup();
+
+        return tokens;
+    }
+
+    let bracketRe = Regex::new(r"\[([^\]]+)\]").unwrap();
+    let mustacheRe = Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}").unwrap();
     let mut placeholders = HashSet::new();
 
-    for cap in re.captures_iter(body) {
-        placeholders.insert(cap[0].to_string());
+    for re in [&bracketRe, &mustacheRe] {
+        for cap in re.captures_iter(body) {
+            placeholders.insert(cap[0].to_string());
+        }
     }
 
     let mut sortedPlaceholders: Vec<String> = placeholders.into_iter().collect();
@@ -111,6 +149,44 @@ pub fn FindPlaceholdersInBody(body: &str) -> Vec<String> {
     sortedPlaceholders
 }
 
+// True if every literal part of the template occurs in `text`, in order. Placeholders
+// match anything, and text is compared in its normalized form (see normalize).
+pub fn TemplateMatchesText(templateBody: &str, placeholdersInBody: &[String], text: &str) -> bool {
+    let mut normalizedTemplate = normalize::NormalizeLicenseText(templateBody);
+
+    match ParsedSpdxTemplate(templateBody) {
+        Some(segments) => {
+            normalizedTemplate = spdx_markup::LiteralSegments(&segments)
+                .iter()
+                .map(|literal| normalize::NormalizeLicenseText(literal))
+                .collect::<Vec<_>>()
+                .join("\0");
+        }
+        None => {
+            for placeholder in placeholdersInBody {
+                normalizedTemplate =
+                    normalizedTemplate.replace(&normalize::NormalizeLicenseText(placeholder), "\0");
+            }
+        }
+    }
+
+    let normalizedText = normalize::NormalizeLicenseText(text);
+    let mut searchFrom = 0;
+    let mut matchedAnything = false;
+
+    for segment in normalizedTemplate.split('\0').map(str::trim).filter(|s| !s.is_empty()) {
+        match normalizedText[searchFrom..].find(segment) {
+            Some(idx) => {
+                searchFrom += idx + segment.len();
+                matchedAnything = true;
+            }
+            None => return false,
+        }
+    }
+
+    matchedAnything
+}
+
 fn BuildParsedRulesCategory(
     fmRuleTags: &[String],
     categoryNameInRulesData: &str,
@@ -175,6 +251,23 @@ pub fn BuildInfoComponents(
     }
 }
 
+// "[Name Of Copyright Owner]" and "{{ name of copyright owner }}" both become "name of copyright owner".
+pub fn PlaceholderName(placeholderAsFound: &str) -> String {
+    if let Some(name) = spdx_markup::VarTokenName(placeholderAsFound) {
+        return name;
+    }
+
+    let inner = match placeholderAsFound
+        .strip_prefix("{{")
+        .and_then(|s| s.strip_suffix("}}"))
+    {
+        Some(mustacheInner) => mustacheInner,
+        None => placeholderAsFound.trim_matches(|c| c == '[' || c == ']'),
+    };
+
+    inner.trim().to_lowercase()
+}
+
 // replacements: Standard keys: "fullname", "year", etc.
 // placeholdersAsFoundInBody: e.g. "[year]", "[fullname]", "[name of copyright owner]"
 pub fn FillLicenseTemplateBody(
@@ -182,6 +275,10 @@ pub fn FillLicenseTemplateBody(
     replacements: &HashMap<String, String>,
     placeholdersAsFoundInBody: &[String],
 ) -> String {
+    if let Some(segments) = ParsedSpdxTemplate(templateBody) {
+        return spdx_markup::RenderSpdxTemplate(&segments, replacements);
+    }
+
     let mut filledBody = templateBody.to_string();
     let rawToStdMap: HashMap<&str, &str> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES
         .iter()
@@ -189,9 +286,8 @@ pub fn FillLicenseTemplateBody(
         .collect();
 
     for phInBodyWithBrackets in placeholdersAsFoundInBody {
-        // ph_in_body_with_brackets is like "[year]" or "[name of copyright owner]"
-        let phTextNoBrackets = phInBodyWithBrackets.trim_matches(|c| c == '[' || c == ']');
-        let phTextNoBracketsLower = phTextNoBrackets.to_lowercase();
+        // ph_in_body_with_brackets is like "[year]", "[name of copyright owner]" or "{{ year }}"
+        let phTextNoBracketsLower = PlaceholderName(phInBodyWithBrackets);
 
         // Find the standard key for this placeholder
 
@@ -207,6 +303,42 @@ pub fn FillLicenseTemplateBody(
     filledBody
 }
 
+// Removes the given placeholders and tidies what they leave behind: empty "()"/"<>",
+// doubled spaces, space before punctuation, and lines left with nothing but punctuation.
+// Lines without any of the placeholders are kept verbatim.
+pub fn StripUnfilledPlaceholders(body: &str, unfilledPlaceholders: &[String]) -> String {
+    let emptyGroupRe = Regex::new(r"\(\s*\)|<\s*>|\[\s*\]").unwrap();
+    let spaceBeforePunctRe = Regex::new(r"\s+([,.;:])").unwrap();
+    let mut keptLines: Vec<String> = Vec::new();
+
+    for line in body.lines() {
+        if !unfilledPlaceholders.iter().any(|ph| line.contains(ph.as_str())) {
+            keptLines.push(line.to_string());
+            continue;
+        }
+
+        let mut stripped = line.to_string();
+
+        for placeholder in unfilledPlaceholders {
+            stripped = stripped.replace(placeholder.as_str(), "");
+        }
+
+        stripped = emptyGroupRe.replace_all(&stripped, "").to_string();
+
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        let words = stripped.split_whitespace().collect::<Vec<_>>().join(" ");
+        let tidied = spaceBeforePunctRe.replace_all(&words, "$1").to_string();
+
+        if tidied.chars().all(|c| c.is_ascii_punctuation() || c.is_whitespace()) {
+            continue;
+        }
+
+        keptLines.push(format!("{}{}", indent, tidied));
+    }
+
+    keptLines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +360,40 @@ mod tests {
         assert_eq!(body, "Body text only");
     }
 
+    #[test]
+    fn TestParseUsingListAndMapForms() {
+        let listForm = "---\nusing:\n  - Babel: https://github.com/babel/babel\n  - Unlinked:\n---\nBody";
+        let (_, fm, _) = ParseLicenseFile("mit.txt", listForm).unwrap();
+        let using = fm.using.unwrap();
+        assert_eq!(using["Babel"], "https://github.com/babel/babel");
+        assert_eq!(using["Unlinked"], "");
+
+        let mapForm = "---\nusing:\n  Babel: https://github.com/babel/babel\n---\nBody";
+        let (_, fm, _) = ParseLicenseFile("mit.txt", mapForm).unwrap();
+        assert_eq!(fm.using.unwrap().len(), 1);
+
+        let (spdxId, fm, _) = ParseLicenseFile("mit.txt", "---\ntitle: MIT\nspdx-id: MIT\n---\nBody").unwrap();
+        assert_eq!(spdxId, "MIT");
+        assert!(fm.using.is_none());
+    }
+
+    #[test]
+    fn TestTemplateMatchesFilledText() {
+        let template = "Copyright (c) [year] [fullname]\n\nPermission is hereby granted,\nfree of charge.";
+        let placeholders = vec!["[year]".to_string(), "[fullname]".to_string()];
+
+        assert!(TemplateMatchesText(template, &placeholders, "Copyright (c) 2024 Jane Doe\n\npermission is hereby  granted, free of charge.\n"));
+        assert!(!TemplateMatchesText(template, &placeholders, "Copyright (c) 2024 Jane Doe\n\nPermission is granted."));
+    }
+
+    #[test]
+    fn TestStripUnfilledPlaceholders() {
+        let body = "Copyright (c) 2025 Jane Doe <[email]>\n\n[project]\n  Permission is granted.";
+        let stripped = StripUnfilledPlaceholders(body, &["[email]".to_string(), "[project]".to_string()]);
+
+        assert_eq!(stripped, "Copyright (c) 2025 Jane Doe\n\n  Permission is granted.");
+    }
+
     #[test]
     fn TestFindPlaceholders() {
         let body = "Copyright [year] by [fullname]. Project: [project].";
@@ -273,6 +439,24 @@ mod tests {
 
         let filled = FillLicenseTemplateBody(template, &replacements, &placeholdersInTemplate);
         assert_eq!(filled, "Copyright 2023 by Acme Corp.");
+
+        let replacements = HashMap::from([("description".to_string(), "Frobnicates widgets".to_string())]);
+        let filled = FillLicenseTemplateBody("[project]: [description]", &replacements, &FindPlaceholdersInBody("[project]: [description]"));
+        assert_eq!(filled, "[project]: Frobnicates widgets");
+    }
+
+    #[test]
+    fn TestMustachePlaceholders() {
+        let template = "Copyright {{ year }} {{fullname}}, see [project].";
+        let placeholders = FindPlaceholdersInBody(template);
+        assert_eq!(placeholders, vec!["[project]", "{{ year }}", "{{fullname}}"]);
+
+        let replacements = HashMap::from([
+            ("year".to_string(), "2025".to_string()),
+            ("fullname".to_string(), "Jane Doe".to_string()),
+        ]);
+        let filled = FillLicenseTemplateBody(template, &replacements, &placeholders);
+        assert_eq!(filled, "Copyright 2025 Jane Doe, see [project].");
     }
 
     #[test]
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use crate::config::PermissivenessWeightsConfig;
+use crate::constants::PERMISSIVENESS_WEIGHT_TUPLES;
+use crate::models::LicenseEntry;
+
+// Weight of each (category, tag); the built-in table with config overrides applied.
+#[derive(Debug, Clone)]
+pub struct PermissivenessWeights {
+    weights: HashMap<(String, String), i32>,
+}
+
+impl Default for PermissivenessWeights {
+    fn default() -> Self {
+        Self {
+            weights: PERMISSIVENESS_WEIGHT_TUPLES
+                .iter()
+                .map(|(category, tag, weight)| ((category.to_string(), tag.to_string()), *weight))
+                .collect(),
+        }
+    }
+}
+
+impl PermissivenessWeights {
+    pub fn WithOverrides(overrides: &PermissivenessWeightsConfig) -> Self {
+        let mut weights = Self::default();
+
+        for (category, table) in [
+            ("permissions", &overrides.permissions),
+            ("conditions", &overrides.conditions),
+            ("limitations", &overrides.limitations),
+        ] {
+            for (tag, weight) in table {
+                weights.weights.insert((category.to_string(), tag.clone()), *weight);
+            }
+        }
+
+        weights
+    }
+
+    fn Weight(&self, category: &str, tag: &str) -> i32 {
+        self.weights
+            .get(&(category.to_string(), tag.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    // 0 for a license carrying every penalized rule and no rewarded one, 100 for the reverse
+    pub fn Score(&self, licenseEntry: &LicenseEntry) -> u8 {
+        let lowest: i32 = self.weights.values().filter(|w| **w < 0).sum();
+        let highest: i32 = self.weights.values().filter(|w| **w > 0).sum();
+
+        if highest == lowest {
+            return 100;
+        }
+
+        let raw: i32 = [
+            ("permissions", &licenseEntry.permissions),
+            ("conditions", &licenseEntry.conditions),
+            ("limitations", &licenseEntry.limitations),
+        ]
+        .into_iter()
+        .flat_map(|(category, tags)| tags.iter().map(move |tag| self.Weight(category, tag)))
+        .sum();
+
+        let score = f64::from(raw - lowest) / f64::from(highest - lowest) * 100.0;
+
+        score.round().clamp(0.0, 100.0) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Strings;
+
+    fn License(permissions: &[&str], conditions: &[&str], limitations: &[&str]) -> LicenseEntry {
+        LicenseEntry {
+            permissions: Strings(permissions),
+            conditions: Strings(conditions),
+            limitations: Strings(limitations),
+            ..LicenseEntry::ForTest("", "")
+        }
+    }
+
+    #[test]
+    fn TestPermissivenessScore() {
+        let permissions = ["commercial-use", "modifications", "distribution", "private-use"];
+        let mit = License(&permissions, &["include-copyright"], &["liability", "warranty"]);
+        let gpl = License(
+            &permissions,
+            &["include-copyright", "document-changes", "disclose-source", "same-license"],
+            &["liability", "warranty"],
+        );
+
+        let weights = PermissivenessWeights::default();
+        assert!(weights.Score(&mit) > weights.Score(&gpl));
+        assert_eq!(weights.Score(&License(&[], &[], &[])), 74);
+
+        // Overrides replace single entries and keep the rest
+        let overrides = PermissivenessWeightsConfig {
+            conditions: HashMap::from([("same-license".to_string(), 0)]),
+            ..Default::default()
+        };
+        let lenient = PermissivenessWeights::WithOverrides(&overrides);
+        assert!(lenient.Score(&gpl) > weights.Score(&gpl));
+    }
+}
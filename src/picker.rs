@@ -0,0 +1,164 @@
+use console::{Key, Term};
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use unicode_ellipsis::truncate_str;
+
+use colored::*;
+
+use crate::error::AppError;
+use crate::models::{Cache, LicenseEntry};
+use crate::terminal;
+
+const VISIBLE_ROWS: usize = 10;
+
+// Both ends must be a terminal: keys are read from stdin and the list is drawn on stderr,
+// so stdout can still be piped.
+pub fn CanPick() -> bool {
+    io::stdin().is_terminal() && Term::stderr().is_term()
+}
+
+// Case-insensitive subsequence match. Consecutive characters and characters starting a
+// word score higher, so "gpl3" ranks GPL-3.0 above LGPL-3.0. None if `query` is not a
+// subsequence of `candidate`.
+pub fn FuzzyScore(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previousMatch: Option<usize> = None;
+
+    for queryChar in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = candidate[position..].iter().position(|c| *c == queryChar)? + position;
+
+        score += 1;
+        if previousMatch.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+
+        previousMatch = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+fn RuleSummary(license: &LicenseEntry) -> String {
+    format!(
+        "{} permissions, {} conditions, {} limitations",
+        license.permissions.len(),
+        license.conditions.len(),
+        license.limitations.len()
+    )
+}
+
+// Best match first; ties and the empty query are ordered by SPDX ID
+fn MatchingLicenses<'a>(cache: &'a Cache, query: &str) -> Vec<&'a LicenseEntry> {
+    let mut scored: Vec<(i64, &LicenseEntry)> = cache
+        .licenses
+        .values()
+        .filter_map(|license| {
+            FuzzyScore(query, &format!("{} {}", license.spdxId, license.title)).map(|score| (score, license.as_ref()))
+        })
+        .collect();
+
+    scored.sort_by(|(scoreA, a), (scoreB, b)| {
+        scoreB.cmp(scoreA).then_with(|| a.spdxId.to_lowercase().cmp(&b.spdxId.to_lowercase()))
+    });
+
+    scored.into_iter().map(|(_, license)| license).collect()
+}
+
+fn DrawPicker(term: &Term, query: &str, matches: &[&LicenseEntry], selected: usize) -> io::Result<usize> {
+    let width = (term.size().1 as usize).saturating_sub(1).max(20);
+    let (up, down) = if terminal::AsciiOnly() { ("Up", "Down") } else { ("↑", "↓") };
+    let mut lines = vec![
+        format!("{}", format!("Type to filter, {}/{} to move, Enter to select, Esc to cancel", up, down).dimmed()),
+        format!("{} {}", ">".cyan().bold(), query),
+    ];
+
+    // Keep the selection in view when it moves past the visible rows
+    let first = selected.saturating_sub(VISIBLE_ROWS - 1);
+
+    for (index, license) in matches.iter().enumerate().skip(first).take(VISIBLE_ROWS) {
+        let row = format!("{:<16} {}", license.spdxId, license.title);
+        let summary = RuleSummary(license);
+        let room = width.saturating_sub(2);
+        let row = truncate_str(&row, room).to_string();
+        let summary = truncate_str(&summary, room.saturating_sub(row.chars().count() + 2)).to_string();
+
+        lines.push(if index == selected {
+            format!("{} {}  {}", ">".cyan().bold(), row.cyan().bold(), summary.dimmed())
+        } else {
+            format!("  {}  {}", row, summary.dimmed())
+        });
+    }
+
+    if matches.is_empty() {
+        lines.push(format!("  {}", "No cached license matches.".dimmed()));
+    }
+
+    for line in &lines {
+        term.write_line(line)?;
+    }
+
+    Ok(lines.len())
+}
+
+fn RunPicker(term: &Term, cache: &Cache) -> io::Result<Option<String>> {
+    let mut query = String::new();
+    let mut selected = 0;
+
+    loop {
+        let matches = MatchingLicenses(cache, &query);
+        selected = selected.min(matches.len().saturating_sub(1));
+
+        let drawn = DrawPicker(term, &query, &matches, selected)?;
+        let key = term.read_key_raw()?;
+        term.clear_last_lines(drawn)?;
+
+        match key {
+            Key::Enter => return Ok(matches.get(selected).map(|license| license.spdxId.to_lowercase())),
+            Key::Escape | Key::CtrlC => return Ok(None),
+            Key::ArrowUp => selected = selected.saturating_sub(1),
+            Key::ArrowDown | Key::Tab => selected += 1,
+            Key::Backspace => {
+                query.pop();
+                selected = 0;
+            }
+            Key::Char(c) if !c.is_control() => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+// Lowercased SPDX ID of the chosen license, or None if the user cancelled
+pub fn PickLicense(cache: &Cache) -> Result<Option<String>, AppError> {
+    let term = Term::stderr();
+    let ioError = |e| AppError::Io(e, PathBuf::from("terminal"));
+
+    term.hide_cursor().map_err(ioError)?;
+    let picked = RunPicker(&term, cache);
+    term.show_cursor().map_err(ioError)?;
+
+    picked.map_err(ioError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestFuzzyScore() {
+        assert_eq!(FuzzyScore("xyz", "MIT MIT License"), None);
+        assert_eq!(FuzzyScore("", "MIT MIT License"), Some(0));
+
+        let gpl = FuzzyScore("gpl3", "GPL-3.0 GNU General Public License v3.0").unwrap();
+        let lgpl = FuzzyScore("gpl3", "LGPL-3.0 GNU Lesser General Public License v3.0").unwrap();
+        assert!(gpl > lgpl);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::constants::{PROVENANCE_FILENAME, RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES};
+use crate::error::{ActionError, AppError, ParseError};
+use crate::models::LicenseEntry;
+use crate::parser;
+
+// How a license file was generated, so later commands can work from facts instead of
+// guessing the template and values back out of the text.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProvenanceRecord {
+    #[serde(rename = "spdx_id")]
+    pub spdxId: String,
+    // Git blob SHA of the template in the upstream repository
+    #[serde(rename = "template_sha")]
+    pub templateSha: String,
+    #[serde(rename = "filled_at")]
+    pub filledAt: DateTime<Utc>,
+    #[serde(rename = "strip_unfilled", default, skip_serializing_if = "std::ops::Not::not")]
+    pub stripUnfilled: bool,
+    // Standard key -> value, only for placeholders that occur in the template
+    #[serde(default)]
+    pub placeholders: BTreeMap<String, String>,
+}
+
+// One sidecar per directory, keyed by license file name, so LICENSE-MIT and
+// LICENSE-APACHE can live side by side.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct ProvenanceFile {
+    #[serde(default)]
+    files: BTreeMap<String, ProvenanceRecord>,
+}
+
+impl ProvenanceRecord {
+    pub fn New(
+        licenseEntry: &LicenseEntry,
+        values: &HashMap<String, String>,
+        stripUnfilled: bool,
+    ) -> Self {
+        let rawToStdMap: HashMap<&str, &str> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES
+            .iter()
+            .cloned()
+            .collect();
+
+        let placeholders = licenseEntry
+            .placeholdersInBody
+            .iter()
+            .filter_map(|ph| rawToStdMap.get(parser::PlaceholderName(ph).as_str()))
+            .filter_map(|key| values.get(*key).map(|v| (key.to_string(), v.clone())))
+            .collect();
+
+        Self {
+            spdxId: licenseEntry.spdxId.clone(),
+            templateSha: licenseEntry.sha.clone(),
+            filledAt: crate::clock::Now(),
+            stripUnfilled,
+            placeholders,
+        }
+    }
+}
+
+pub fn ProvenancePathFor(licensePath: &Path) -> PathBuf {
+    licensePath.with_file_name(PROVENANCE_FILENAME)
+}
+
+fn LicenseFileKey(licensePath: &Path) -> String {
+    licensePath
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn LoadProvenanceFile(sidecarPath: &Path) -> Result<ProvenanceFile, AppError> {
+    if !sidecarPath.exists() {
+        return Ok(ProvenanceFile::default());
+    }
+
+    let content = fs::read_to_string(sidecarPath).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(e, sidecarPath.to_path_buf()))
+    })?;
+
+    Ok(toml::from_str(&content)
+        .map_err(|e| ParseError::TomlError(sidecarPath.display().to_string(), e))?)
+}
+
+pub fn LoadProvenance(licensePath: &Path) -> Result<Option<ProvenanceRecord>, AppError> {
+    let sidecar = LoadProvenanceFile(&ProvenancePathFor(licensePath))?;
+
+    Ok(sidecar.files.get(&LicenseFileKey(licensePath)).cloned())
+}
+
+pub fn SaveProvenance(licensePath: &Path, record: &ProvenanceRecord) -> Result<(), AppError> {
+    let sidecarPath = ProvenancePathFor(licensePath);
+    let mut sidecar = LoadProvenanceFile(&sidecarPath)?;
+    sidecar.files.insert(LicenseFileKey(licensePath), record.clone());
+
+    let content = toml::to_string_pretty(&sidecar).map_err(|e| {
+        AppError::ActionErrorVariant(ActionError::FileOperation(
+            io::Error::other(e),
+            sidecarPath.clone(),
+        ))
+    })?;
+
+    fs::write(&sidecarPath, content)
+        .map_err(|e| AppError::ActionErrorVariant(ActionError::FileOperation(e, sidecarPath)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestProvenanceRoundTrip() {
+        let entry = LicenseEntry {
+            sha: "sha-mit".to_string(),
+            ..LicenseEntry::ForTest("MIT", "Copyright (c) [year] [fullname]")
+        };
+        let values = HashMap::from([
+            ("year".to_string(), "2024".to_string()),
+            ("fullname".to_string(), "Jane Doe".to_string()),
+            ("email".to_string(), "jane@example.com".to_string()),
+        ]);
+
+        let record = ProvenanceRecord::New(&entry, &values, false);
+        assert!(!record.placeholders.contains_key("email"));
+
+        let dir = TempDir::New("provenance");
+        let licensePath = dir.join("LICENSE");
+
+        SaveProvenance(&licensePath, &record).unwrap();
+        assert_eq!(LoadProvenance(&licensePath).unwrap(), Some(record));
+        assert_eq!(LoadProvenance(&dir.join("COPYING")).unwrap(), None);
+    }
+}
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use chrono::Datelike;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::Ordering;
+
+use crate::constants::HEADER_CHECK_SKIPPED_DIRS;
+
+// Where a placeholder value can come from. Order of the variants has no meaning;
+// the effective order is the configured precedence list.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceholderSource {
+    // Explicit command-line flag (e.g. --fullname)
+    Cli,
+    // [placeholders] table of .getlicense.toml in the working directory
+    Project,
+    // [profiles.<name>] table of the user config
+    Profile,
+    // Saved preferences in the license cache (set-placeholder)
+    Cache,
+    // Values detected from the environment (git config)
+    Auto,
+    // Built-in fallbacks (current year)
+    Default,
+}
+
+pub const DEFAULT_PLACEHOLDER_PRECEDENCE: [PlaceholderSource; 6] = [
+    PlaceholderSource::Cli,
+    PlaceholderSource::Project,
+    PlaceholderSource::Profile,
+    PlaceholderSource::Cache,
+    PlaceholderSource::Auto,
+    PlaceholderSource::Default,
+];
+
+impl PlaceholderSource {
+    pub fn Label(&self) -> &'static str {
+        match self {
+            PlaceholderSource::Cli => "CLI argument",
+            PlaceholderSource::Project => "Project file",
+            PlaceholderSource::Profile => "Profile",
+            PlaceholderSource::Cache => "Saved preference (cache)",
+            PlaceholderSource::Auto => "Auto-detected",
+            PlaceholderSource::Default => "Default",
+        }
+    }
+
+    pub fn Name(&self) -> &'static str {
+        match self {
+            PlaceholderSource::Cli => "cli",
+            PlaceholderSource::Project => "project",
+            PlaceholderSource::Profile => "profile",
+            PlaceholderSource::Cache => "cache",
+            PlaceholderSource::Auto => "auto",
+            PlaceholderSource::Default => "default",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedValue {
+    pub value: String,
+    pub source: PlaceholderSource,
+}
+
+// Candidate values per source, keyed by standard placeholder key (e.g. "fullname").
+#[derive(Debug, Clone, Default)]
+pub struct PlaceholderLayers {
+    layers: HashMap<PlaceholderSource, HashMap<String, String>>,
+}
+
+impl PlaceholderLayers {
+    pub fn Insert(&mut self, source: PlaceholderSource, values: HashMap<String, String>) {
+        self.layers.entry(source).or_default().extend(values);
+    }
+
+    pub fn Get(&self, source: PlaceholderSource) -> Option<&HashMap<String, String>> {
+        self.layers.get(&source)
+    }
+}
+
+// Walks the precedence list from highest to lowest priority; the first source
+// providing a non-empty value for a key wins. Sources missing from the list are ignored.
+pub fn ResolvePlaceholders(
+    layers: &PlaceholderLayers,
+    precedence: &[PlaceholderSource],
+) -> HashMap<String, ResolvedValue> {
+    let mut resolved: HashMap<String, ResolvedValue> = HashMap::new();
+
+    for source in precedence {
+        if let Some(values) = layers.Get(*source) {
+            for (key, value) in values {
+                if value.trim().is_empty() {
+                    continue;
+                }
+
+                resolved.entry(key.clone()).or_insert_with(|| ResolvedValue {
+                    value: value.clone(),
+                    source: *source,
+                });
+            }
+        }
+    }
+
+    resolved
+}
+
+fn ReadGitConfigValue(key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", key])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if value.is_empty() { None } else { Some(value) }
+}
+
+pub fn DetectGitPlaceholders() -> HashMap<String, String> {
+    let mut detected = HashMap::new();
+
+    if let Some(name) = ReadGitConfigValue("user.name") {
+        detected.insert("fullname".to_string(), name);
+    }
+
+    if let Some(email) = ReadGitConfigValue("user.email") {
+        detected.insert("email".to_string(), email);
+    }
+
+    // GitHub username, by the github.user convention that hub introduced
+    if let Some(login) = ReadGitConfigValue("github.user") {
+        detected.insert("login".to_string(), login);
+    }
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Resolve] Auto-detected from git config: {:?}", detected);
+    }
+
+    detected
+}
+
+// "2019" for a single year, "2019-2024" otherwise
+pub fn FormatYearRange(start: i32, end: i32) -> String {
+    if start >= end { end.to_string() } else { format!("{}-{}", start, end) }
+}
+
+// Years of the first and the latest commit in the repository containing `dir`
+pub fn CommitYearRange(dir: &Path) -> Option<(i32, i32)> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", "--format=%ad", "--date=format:%Y"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let years: Vec<i32> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect();
+
+    Some((*years.iter().min()?, *years.iter().max()?))
+}
+
+// Year of the newest modification time at `path`: the file itself, or anything below a
+// directory except hidden entries and build/vendor directories.
+pub fn NewestModifiedYear(path: &Path) -> Option<i32> {
+    fn NewestModified(path: &Path) -> Option<std::time::SystemTime> {
+        let metadata = fs::metadata(path).ok()?;
+
+        if !metadata.is_dir() {
+            return metadata.modified().ok();
+        }
+
+        fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                !name.starts_with('.') && !HEADER_CHECK_SKIPPED_DIRS.contains(&name.as_str())
+            })
+            .filter_map(|entry| NewestModified(&entry.path()))
+            .max()
+    }
+
+    let newest = NewestModified(path)?;
+
+    if crate::VERBOSE.load(Ordering::SeqCst) {
+        eprintln!("[Resolve] Newest modification time under {:?}: {:?}", path, newest);
+    }
+
+    Some(chrono::DateTime::<chrono::Local>::from(newest).year())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn Layer(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn TestResolveFollowsPrecedence() {
+        let mut layers = PlaceholderLayers::default();
+        layers.Insert(PlaceholderSource::Cache, Layer(&[("fullname", "Cached"), ("email", "c@x")]));
+        layers.Insert(PlaceholderSource::Cli, Layer(&[("fullname", "Flag")]));
+        layers.Insert(PlaceholderSource::Default, Layer(&[("year", "2024")]));
+
+        let resolved = ResolvePlaceholders(&layers, &DEFAULT_PLACEHOLDER_PRECEDENCE);
+
+        assert_eq!(resolved["fullname"].value, "Flag");
+        assert_eq!(resolved["fullname"].source, PlaceholderSource::Cli);
+        assert_eq!(resolved["email"].source, PlaceholderSource::Cache);
+        assert_eq!(resolved["year"].source, PlaceholderSource::Default);
+    }
+
+    #[test]
+    fn TestResolveCustomOrderSkipsOmittedSources() {
+        let mut layers = PlaceholderLayers::default();
+        layers.Insert(PlaceholderSource::Cache, Layer(&[("fullname", "Cached")]));
+        layers.Insert(PlaceholderSource::Project, Layer(&[("fullname", "Project")]));
+        layers.Insert(PlaceholderSource::Auto, Layer(&[("email", "git@x")]));
+
+        let precedence = [PlaceholderSource::Cache, PlaceholderSource::Project];
+        let resolved = ResolvePlaceholders(&layers, &precedence);
+
+        assert_eq!(resolved["fullname"].value, "Cached");
+        assert!(!resolved.contains_key("email"));
+    }
+
+    #[test]
+    fn TestNewestModifiedYear() {
+        let dir = TempDir::New("mtime");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+
+        let touch = |path: &Path, year: i32| {
+            fs::write(path, "x").unwrap();
+            let time = chrono::NaiveDate::from_ymd_opt(year, 6, 1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+            fs::File::options().append(true).open(path).unwrap().set_modified(time.into()).unwrap();
+        };
+        touch(&dir.join("src/lib.rs"), 2018);
+        touch(&dir.join("README.md"), 2016);
+        // Build output is ignored
+        touch(&dir.join("target/out"), 2030);
+
+        assert_eq!(NewestModifiedYear(&dir), Some(2018));
+        assert_eq!(NewestModifiedYear(&dir.join("README.md")), Some(2016));
+        assert_eq!(FormatYearRange(2012, 2018), "2012-2018");
+        assert_eq!(FormatYearRange(2018, 2018), "2018");
+    }
+}
@@ -0,0 +1,256 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::constants::RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES;
+use crate::error::ParseError;
+
+// Official SPDX license templates (spdx/license-list-XML "template" output) mark replaceable
+// text as <<var;name="...";original="...";match="...">> and optional text as
+// <<beginOptional>> ... <<endOptional>>.
+const VAR_OPEN: &str = "<<var;";
+const OPTIONAL_OPEN: &str = "<<beginOptional";
+const OPTIONAL_CLOSE: &str = "<<endOptional>>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSegment {
+    Text(String),
+    Var {
+        // The markup exactly as written, used as the placeholder string
+        token: String,
+        name: String,
+        original: String,
+        matchPattern: String,
+    },
+    Optional(Vec<TemplateSegment>),
+}
+
+pub fn IsSpdxTemplate(text: &str) -> bool {
+    text.contains(VAR_OPEN) || text.contains(OPTIONAL_OPEN)
+}
+
+fn MarkupError(message: &str, offset: usize) -> ParseError {
+    ParseError::SpdxMarkupError(format!("{} at byte {}", message, offset))
+}
+
+// Reads key="value" pairs up to the closing ">>". Values may contain ';' and '>' and
+// escape quotes as \".
+fn ParseAttributes(text: &str, start: usize) -> Result<(HashMap<String, String>, usize), ParseError> {
+    let bytes = text.as_bytes();
+    let mut attributes = HashMap::new();
+    let mut pos = start;
+
+    loop {
+        while pos < bytes.len() && (bytes[pos] == b';' || bytes[pos].is_ascii_whitespace()) {
+            pos += 1;
+        }
+
+        if text[pos..].starts_with(">>") {
+            return Ok((attributes, pos + 2));
+        }
+
+        let eq = text[pos..]
+            .find('=')
+            .map(|i| pos + i)
+            .ok_or_else(|| MarkupError("expected key=\"value\"", pos))?;
+        let key = text[pos..eq].trim().to_string();
+        pos = eq + 1;
+
+        if bytes.get(pos) != Some(&b'"') {
+            return Err(MarkupError("expected opening quote", pos));
+        }
+        pos += 1;
+
+        let mut value = String::new();
+        let mut chars = text[pos..].char_indices();
+
+        loop {
+            match chars.next() {
+                Some((i, '\\')) => {
+                    if let Some((_, escaped)) = chars.next() {
+                        value.push(escaped);
+                    } else {
+                        return Err(MarkupError("dangling escape", pos + i));
+                    }
+                }
+                Some((i, '"')) => {
+                    pos += i + 1;
+                    break;
+                }
+                Some((_, c)) => value.push(c),
+                None => return Err(MarkupError("unterminated quoted value", pos)),
+            }
+        }
+
+        attributes.insert(key, value);
+    }
+}
+
+fn ParseSegments(
+    text: &str,
+    mut pos: usize,
+    insideOptional: bool,
+) -> Result<(Vec<TemplateSegment>, usize), ParseError> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+
+    while pos < text.len() {
+        let rest = &text[pos..];
+
+        if rest.starts_with(VAR_OPEN) {
+            let (attributes, end) = ParseAttributes(text, pos + VAR_OPEN.len())?;
+
+            if !literal.is_empty() {
+                segments.push(TemplateSegment::Text(std::mem::take(&mut literal)));
+            }
+
+            segments.push(TemplateSegment::Var {
+                token: text[pos..end].to_string(),
+                name: attributes.get("name").cloned().unwrap_or_default(),
+                original: attributes.get("original").cloned().unwrap_or_default(),
+                matchPattern: attributes.get("match").cloned().unwrap_or_default(),
+            });
+            pos = end;
+        } else if rest.starts_with(OPTIONAL_OPEN) {
+            let (_, bodyStart) = ParseAttributes(text, pos + OPTIONAL_OPEN.len())?;
+            let (children, end) = ParseSegments(text, bodyStart, true)?;
+
+            if !literal.is_empty() {
+                segments.push(TemplateSegment::Text(std::mem::take(&mut literal)));
+            }
+
+            segments.push(TemplateSegment::Optional(children));
+            pos = end;
+        } else if rest.starts_with(OPTIONAL_CLOSE) {
+            if !insideOptional {
+                return Err(MarkupError("<<endOptional>> without <<beginOptional>>", pos));
+            }
+
+            if !literal.is_empty() {
+                segments.push(TemplateSegment::Text(literal));
+            }
+
+            return Ok((segments, pos + OPTIONAL_CLOSE.len()));
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            literal.push(c);
+            pos += c.len_utf8();
+        }
+    }
+
+    if insideOptional {
+        return Err(MarkupError("<<beginOptional>> without <<endOptional>>", pos));
+    }
+
+    if !literal.is_empty() {
+        segments.push(TemplateSegment::Text(literal));
+    }
+
+    Ok((segments, pos))
+}
+
+pub fn ParseSpdxTemplate(text: &str) -> Result<Vec<TemplateSegment>, ParseError> {
+    ParseSegments(text, 0, false).map(|(segments, _)| segments)
+}
+
+pub fn CollectVarTokens(segments: &[TemplateSegment], tokens: &mut Vec<String>) {
+    for segment in segments {
+        match segment {
+            TemplateSegment::Var { token, .. } => tokens.push(token.clone()),
+            TemplateSegment::Optional(children) => CollectVarTokens(children, tokens),
+            TemplateSegment::Text(_) => {}
+        }
+    }
+}
+
+// "copyright" from a <<var;name="copyright";...>> token
+pub fn VarTokenName(token: &str) -> Option<String> {
+    if !token.starts_with(VAR_OPEN) {
+        return None;
+    }
+
+    match ParseSpdxTemplate(token).ok()?.as_slice() {
+        [TemplateSegment::Var { name, .. }] => Some(name.to_lowercase()),
+        _ => None,
+    }
+}
+
+// A var whose name maps to a standard key is replaced outright. Otherwise the
+// <year>/<copyright holders>-style tokens inside its original text are filled and
+// the rest of the original is kept. Optional regions are always rendered.
+pub fn RenderSpdxTemplate(segments: &[TemplateSegment], replacements: &HashMap<String, String>) -> String {
+    let rawToStdMap: HashMap<&str, &str> = RAW_PLACEHOLDER_TO_STANDARD_KEY_TUPLES
+        .iter()
+        .cloned()
+        .collect();
+    let angleRe = Regex::new(r"<([^<>]+)>").unwrap();
+    let mut rendered = String::new();
+
+    for segment in segments {
+        match segment {
+            TemplateSegment::Text(text) => rendered.push_str(text),
+            TemplateSegment::Optional(children) => {
+                rendered.push_str(&RenderSpdxTemplate(children, replacements));
+            }
+            TemplateSegment::Var { name, original, .. } => {
+                let direct = rawToStdMap
+                    .get(name.to_lowercase().as_str())
+                    .and_then(|key| replacements.get(*key));
+
+                match direct {
+                    Some(value) => rendered.push_str(value),
+                    None => {
+                        let filled = angleRe.replace_all(original, |caps: &regex::Captures| {
+                            rawToStdMap
+                                .get(caps[1].trim().to_lowercase().as_str())
+                                .and_then(|key| replacements.get(*key))
+                                .cloned()
+                                .unwrap_or_else(|| caps[0].to_string())
+                        });
+                        rendered.push_str(&filled);
+                    }
+                }
+            }
+        }
+    }
+
+    rendered
+}
+
+// Fixed text that must appear, in order, in any rendering. Vars and optional regions
+// split the text since their content may vary or be absent.
+pub fn LiteralSegments(segments: &[TemplateSegment]) -> Vec<String> {
+    let mut literals = Vec::new();
+
+    for segment in segments {
+        if let TemplateSegment::Text(text) = segment {
+            literals.push(text.clone());
+        }
+    }
+
+    literals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestParseAndRenderSpdxTemplate() {
+        let template = "<<beginOptional>>MIT License<<endOptional>>\n\n<<var;name=\"copyright\";original=\"Copyright (c) <year> <copyright holders>\";match=\".{0,5000}\">>\n\nPermission is hereby granted";
+        let segments = ParseSpdxTemplate(template).unwrap();
+
+        assert_eq!(segments.len(), 4);
+        assert!(matches!(&segments[2], TemplateSegment::Var { name, .. } if name == "copyright"));
+
+        let replacements = HashMap::from([
+            ("year".to_string(), "2025".to_string()),
+            ("fullname".to_string(), "Jane Doe".to_string()),
+        ]);
+        assert_eq!(
+            RenderSpdxTemplate(&segments, &replacements),
+            "MIT License\n\nCopyright (c) 2025 Jane Doe\n\nPermission is hereby granted"
+        );
+
+        assert!(ParseSpdxTemplate("<<beginOptional>>unterminated").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use serde::Serialize;
+
+use crate::constants::{COPYLEFT_CONDITION_TAGS, READING_WORDS_PER_MINUTE};
+use crate::models::LicenseEntry;
+use crate::parser;
+
+// Size and shape of a license template, for weighing "short and simple" against the rest.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LicenseStats {
+    #[serde(rename = "word_count")]
+    pub wordCount: usize,
+    // Rounded up, so any text takes at least a minute
+    #[serde(rename = "reading_minutes")]
+    pub readingMinutes: usize,
+    #[serde(rename = "paragraph_count")]
+    pub paragraphCount: usize,
+    // Distinct placeholders, as listed by show-placeholders
+    #[serde(rename = "placeholder_count")]
+    pub placeholderCount: usize,
+    // Condition tags that make the license copyleft, in the order the license lists them
+    #[serde(rename = "copyleft_clauses")]
+    pub copyleftClauses: Vec<String>,
+}
+
+// "strong copyleft" for licenses whose copyleft reaches the whole work (or network use),
+// "weak copyleft" for file- or library-scoped copyleft, "permissive" otherwise
+pub fn LicenseCategory(licenseEntry: &LicenseEntry) -> &'static str {
+    let has = |tag: &str| licenseEntry.conditions.iter().any(|condition| condition == tag);
+
+    if has("same-license") || has("network-use-disclose") {
+        "strong copyleft"
+    } else if has("same-license--file") || has("same-license--library") {
+        "weak copyleft"
+    } else {
+        "permissive"
+    }
+}
+
+pub fn LicenseTextStats(licenseEntry: &LicenseEntry) -> LicenseStats {
+    let body = parser::LicenseTemplateBody(&licenseEntry.fileContentCached);
+    let wordCount = body.split_whitespace().count();
+
+    // Paragraphs are runs of non-blank lines
+    let mut paragraphCount = 0;
+    let mut inParagraph = false;
+    for line in body.lines() {
+        let blank = line.trim().is_empty();
+        if !blank && !inParagraph {
+            paragraphCount += 1;
+        }
+        inParagraph = !blank;
+    }
+
+    LicenseStats {
+        wordCount,
+        readingMinutes: wordCount.div_ceil(READING_WORDS_PER_MINUTE),
+        paragraphCount,
+        placeholderCount: licenseEntry.placeholdersInBody.len(),
+        copyleftClauses: licenseEntry
+            .conditions
+            .iter()
+            .filter(|tag| COPYLEFT_CONDITION_TAGS.contains(&tag.as_str()))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Strings;
+
+    #[test]
+    fn TestLicenseTextStats() {
+        let license = LicenseEntry {
+            conditions: Strings(&["include-copyright", "disclose-source", "same-license--library"]),
+            ..LicenseEntry::ForTest(
+                "LGPL-2.1",
+                "---\ntitle: Example\n---\n\nCopyright [year] [fullname]\n\n\nSome words\nhere.\n",
+            )
+        };
+
+        let stats = LicenseTextStats(&license);
+
+        assert_eq!(stats.wordCount, 6);
+        assert_eq!(stats.readingMinutes, 1);
+        assert_eq!(stats.paragraphCount, 2);
+        assert_eq!(stats.placeholderCount, 2);
+        assert_eq!(stats.copyleftClauses, ["disclose-source", "same-license--library"]);
+        assert_eq!(LicenseCategory(&license), "weak copyleft");
+    }
+}
@@ -0,0 +1,405 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use crate::actions;
+use crate::clock::{Clock, FixedClock, SystemClock};
+#[cfg(feature = "net")]
+use crate::cache;
+use crate::constants::DEFAULT_CACHE_FILENAME;
+use crate::error::{ActionError, AppError};
+use crate::models::{Cache, LicenseEntry, RulesDataContent};
+use crate::parser;
+use crate::resolve::{
+    self, DEFAULT_PLACEHOLDER_PRECEDENCE, PlaceholderLayers, PlaceholderSource, ResolvedValue,
+};
+
+/// A local copy of the choosealicense.com templates backed by a cache file.
+///
+/// Every method returns data instead of printing, so the store can be embedded in
+/// other tools. Only [`LicenseStore::sync`] touches the network.
+///
+/// Without the `net` feature (e.g. on wasm32) the store is built from an already
+/// deserialized [`Cache`] with [`LicenseStore::from_cache`].
+#[derive(Debug, Clone)]
+pub struct LicenseStore {
+    cachePath: Option<PathBuf>,
+    cache: Cache,
+}
+
+impl LicenseStore {
+    /// Loads the cache at `cachePath`. A missing file yields an empty store, and a corrupt
+    /// one is moved aside to `<name>.corrupt-<timestamp>` before starting empty.
+    #[cfg(feature = "net")]
+    pub async fn open(cachePath: impl Into<PathBuf>) -> Result<Self, AppError> {
+        let cachePath = cachePath.into();
+        let cache = cache::LoadCache(&cachePath, true).await?;
+
+        Ok(Self {
+            cachePath: Some(cachePath),
+            cache,
+        })
+    }
+
+    /// Wraps cache data obtained elsewhere, such as a JSON snapshot bundled with a web page.
+    /// The store has no backing file, so [`LicenseStore::sync`] is unavailable.
+    pub fn from_cache(cache: Cache) -> Self {
+        Self {
+            cachePath: None,
+            cache,
+        }
+    }
+
+    /// The cache location used by the getlicense CLI when --cache-file is not given.
+    pub fn default_cache_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(DEFAULT_CACHE_FILENAME)
+    }
+
+    pub fn cache_path(&self) -> Option<&Path> {
+        self.cachePath.as_deref()
+    }
+
+    /// Brings the cache up to date with the remote repository and writes it back if
+    /// anything changed. A store without a cache file syncs in memory only. Returns
+    /// whether the cache was modified.
+    #[cfg(feature = "net")]
+    pub async fn sync(&mut self, forceRefresh: bool) -> Result<bool, AppError> {
+        let (mut synced, updated) =
+            cache::UpdateAndLoadLicenseCache(&crate::api::GithubFetcher, self.cachePath.as_deref(), forceRefresh, true).await?;
+
+        synced.userPlaceholders = std::mem::take(&mut self.cache.userPlaceholders);
+
+        if let Some(cachePath) = &self.cachePath {
+            if updated {
+                cache::SaveCache(cachePath, &synced).await?;
+            }
+            cache::SaveSyncHistory(cachePath, &synced)?;
+        }
+
+        self.cache = synced;
+
+        Ok(updated)
+    }
+
+    /// Looks up a license by SPDX ID, case-insensitively.
+    pub fn get(&self, spdxId: &str) -> Option<Arc<LicenseEntry>> {
+        self.cache.licenses.get(&spdxId.to_lowercase()).cloned()
+    }
+
+    /// All cached licenses, sorted by SPDX ID.
+    pub fn list(&self) -> Vec<Arc<LicenseEntry>> {
+        let mut entries: Vec<Arc<LicenseEntry>> = self.cache.licenses.values().cloned().collect();
+        entries.sort_by(|a, b| a.spdxId.cmp(&b.spdxId));
+        entries
+    }
+
+    /// Licenses carrying every `require` tag and none of the `disallow` tags
+    /// (e.g. "commercial-use", "disclose-source"). Unknown tags are an error.
+    pub fn find(&self, require: &[&str], disallow: &[&str]) -> Result<Vec<Arc<LicenseEntry>>, AppError> {
+        let matches = actions::find::FilterLicensesByRules(&self.cache, require, disallow)?;
+
+        Ok(matches.into_iter().cloned().collect())
+    }
+
+    /// The permission, condition, and limitation definitions from rules.yml.
+    pub fn rules(&self) -> Option<RulesDataContent> {
+        self.cache.RulesData()
+    }
+
+    /// Returns the license text with placeholders replaced. `vars` uses the standard
+    /// keys ("fullname", "year", "project", "email", "projecturl", "login"); "year" defaults
+    /// to the current year. Placeholders without a value are left as-is.
+    pub fn fill(&self, spdxId: &str, vars: &HashMap<String, String>) -> Result<String, AppError> {
+        let mut request = FillRequest::new(self, spdxId);
+
+        for (key, value) in vars {
+            request = request.value(key, value);
+        }
+
+        Ok(request.render()?.body)
+    }
+}
+
+/// The outcome of [`FillRequest::render`].
+#[derive(Debug, Clone)]
+pub struct FilledLicense {
+    pub spdxId: String,
+    /// License text without front matter, placeholders replaced where a value was found.
+    pub body: String,
+    /// Standard placeholder key -> chosen value and the source it came from.
+    pub resolved: HashMap<String, ResolvedValue>,
+    /// Placeholders as written in the template (e.g. "[fullname]") that are still in `body`.
+    pub unfilled: Vec<String>,
+    /// Placeholders without a value that were removed because of [`FillRequest::strip_unfilled`].
+    pub stripped: Vec<String>,
+}
+
+/// Builds the placeholder values for one license and renders it to a string.
+///
+/// ```no_run
+/// # fn demo(store: &getlicense::store::LicenseStore) -> Result<(), getlicense::error::AppError> {
+/// use getlicense::store::FillRequest;
+///
+/// let filled = FillRequest::new(store, "mit").fullname("Jane Doe").year(2025).render()?;
+/// println!("{}", filled.body);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Values set through the builder methods are reported as [`PlaceholderSource::Cli`].
+/// "year" falls back to the current year ([`PlaceholderSource::Default`]), which honors
+/// `SOURCE_DATE_EPOCH` and can be pinned with [`FillRequest::now`].
+#[derive(Debug, Clone)]
+pub struct FillRequest<'a> {
+    cache: &'a Cache,
+    spdxId: String,
+    layers: PlaceholderLayers,
+    precedence: Vec<PlaceholderSource>,
+    stripUnfilled: bool,
+    now: Option<DateTime<Utc>>,
+    lang: Option<String>,
+    templateSha: Option<String>,
+}
+
+impl<'a> FillRequest<'a> {
+    pub fn new(store: &'a LicenseStore, spdxId: &str) -> Self {
+        Self::from_cache(&store.cache, spdxId)
+    }
+
+    #[doc(hidden)]
+    pub fn from_cache(cache: &'a Cache, spdxId: &str) -> Self {
+        Self {
+            cache,
+            spdxId: spdxId.to_lowercase(),
+            layers: PlaceholderLayers::default(),
+            precedence: DEFAULT_PLACEHOLDER_PRECEDENCE.to_vec(),
+            stripUnfilled: false,
+            now: None,
+            lang: None,
+            templateSha: None,
+        }
+    }
+
+    /// Sets a value by standard key ("fullname", "year", "project", "email", "projecturl", "login").
+    pub fn value(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.layers.Insert(
+            PlaceholderSource::Cli,
+            HashMap::from([(key.into(), value.into())]),
+        );
+        self
+    }
+
+    pub fn fullname(self, fullname: impl Into<String>) -> Self {
+        self.value("fullname", fullname)
+    }
+
+    pub fn year(self, year: impl ToString) -> Self {
+        self.value("year", year.to_string())
+    }
+
+    pub fn project(self, project: impl Into<String>) -> Self {
+        self.value("project", project)
+    }
+
+    pub fn email(self, email: impl Into<String>) -> Self {
+        self.value("email", email)
+    }
+
+    pub fn projecturl(self, projecturl: impl Into<String>) -> Self {
+        self.value("projecturl", projecturl)
+    }
+
+    pub fn login(self, login: impl Into<String>) -> Self {
+        self.value("login", login)
+    }
+
+    /// Adds candidate values from another source (project file, profile, saved preferences, ...).
+    pub fn layer(mut self, source: PlaceholderSource, values: HashMap<String, String>) -> Self {
+        self.layers.Insert(source, values);
+        self
+    }
+
+    /// Highest priority first. Sources left out are never consulted.
+    pub fn precedence(mut self, precedence: Vec<PlaceholderSource>) -> Self {
+        self.precedence = precedence;
+        self
+    }
+
+    /// The same values and settings, applied to another license.
+    pub fn for_license(&self, spdxId: &str) -> Self {
+        Self {
+            spdxId: spdxId.to_lowercase(),
+            ..self.clone()
+        }
+    }
+
+    /// Remove placeholders that received no value instead of leaving them in the text.
+    /// Off by default so missing information stays visible.
+    pub fn strip_unfilled(mut self, strip: bool) -> Self {
+        self.stripUnfilled = strip;
+        self
+    }
+
+    /// Fill the cached translation in this language (e.g. "de") instead of the English text.
+    pub fn lang(mut self, lang: Option<&str>) -> Self {
+        self.lang = lang.map(str::to_lowercase);
+        self
+    }
+
+    /// Fill the template version with this git blob SHA (e.g. pinned in a getlicense.lock)
+    /// instead of the current one. Versions other than the cached template must be in
+    /// the cache's pinned templates.
+    pub fn template_sha(mut self, templateSha: Option<&str>) -> Self {
+        self.templateSha = templateSha.map(str::to_string);
+        self
+    }
+
+    fn DefaultYear(&self) -> i32 {
+        match self.now {
+            Some(now) => FixedClock(now).Year(),
+            None => SystemClock.Year(),
+        }
+    }
+
+    /// Fixes the time the "year" default is derived from, for deterministic output.
+    pub fn now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    pub fn render(&self) -> Result<FilledLicense, AppError> {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Store] Filling license template for: {}", self.spdxId);
+        }
+
+        let licenseEntry = self
+            .cache
+            .licenses
+            .get(&self.spdxId)
+            .ok_or_else(|| actions::LicenseNotFoundError(self.spdxId.clone()))?;
+
+        let mut layers = self.layers.clone();
+        layers.Insert(
+            PlaceholderSource::Default,
+            HashMap::from([("year".to_string(), self.DefaultYear().to_string())]),
+        );
+
+        let resolved = resolve::ResolvePlaceholders(&layers, &self.precedence);
+        let replacements: HashMap<String, String> = resolved
+            .iter()
+            .map(|(k, resolvedValue)| (k.clone(), resolvedValue.value.clone()))
+            .collect();
+
+        let (template, placeholders) = match &self.lang {
+            Some(lang) => {
+                let translation = self
+                    .cache
+                    .translations
+                    .get(&self.spdxId)
+                    .and_then(|byLang| byLang.get(lang))
+                    .ok_or_else(|| {
+                        AppError::ActionErrorVariant(ActionError::MissingData(format!(
+                            "'{}' translation of {}",
+                            lang, licenseEntry.spdxId
+                        )))
+                    })?;
+                let template = parser::LicenseTemplateBody(&translation.content);
+                let placeholders = parser::FindPlaceholdersInBody(&template);
+                (template, placeholders)
+            }
+            None => match self.templateSha.as_deref().filter(|sha| *sha != licenseEntry.sha) {
+                Some(sha) => {
+                    let content = self.cache.pinnedTemplateBodies.get(sha).ok_or_else(|| {
+                        AppError::ActionErrorVariant(ActionError::MissingData(format!(
+                            "pinned template {} of {}",
+                            sha, licenseEntry.spdxId
+                        )))
+                    })?;
+                    let template = parser::LicenseTemplateBody(content);
+                    let placeholders = parser::FindPlaceholdersInBody(&template);
+                    (template, placeholders)
+                }
+                None => (
+                    parser::LicenseTemplateBody(&licenseEntry.fileContentCached),
+                    licenseEntry.placeholdersInBody.clone(),
+                ),
+            },
+        };
+
+        let mut body = parser::FillLicenseTemplateBody(&template, &replacements, &placeholders);
+
+        let mut unfilled: Vec<String> = placeholders
+            .iter()
+            .filter(|ph| body.contains(ph.as_str()))
+            .cloned()
+            .collect();
+        let mut stripped = Vec::new();
+
+        if self.stripUnfilled && !unfilled.is_empty() {
+            body = parser::StripUnfilledPlaceholders(&body, &unfilled);
+            stripped = std::mem::take(&mut unfilled);
+        }
+
+        Ok(FilledLicense {
+            spdxId: licenseEntry.spdxId.clone(),
+            body,
+            resolved,
+            unfilled,
+            stripped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestFillRequestReportsSources() {
+        let mut cache = Cache::default();
+        cache.licenses.insert(
+            "mit".to_string(),
+            Arc::new(LicenseEntry {
+                sha: "sha-mit".to_string(),
+                ..LicenseEntry::ForTest("MIT", "---\ntitle: MIT License\n---\n\nCopyright (c) [year] [fullname] <[email]>")
+            }),
+        );
+
+        let filled = FillRequest::from_cache(&cache, "MIT")
+            .fullname("Jane Doe")
+            .layer(PlaceholderSource::Profile, HashMap::from([("fullname".into(), "Other".into())]))
+            .render()
+            .unwrap();
+
+        assert!(filled.body.starts_with("Copyright (c) "));
+        assert!(filled.body.contains("Jane Doe <[email]>"));
+        assert_eq!(filled.resolved["fullname"].source, PlaceholderSource::Cli);
+        assert_eq!(filled.resolved["year"].source, PlaceholderSource::Default);
+        assert_eq!(filled.unfilled, vec!["[email]".to_string()]);
+
+        cache.translations.entry("mit".to_string()).or_default().insert(
+            "de".to_string(),
+            Arc::new(crate::models::TranslationEntry {
+                source: "mit-de.txt".to_string(),
+                content: "Copyright (c) [year] [fullname]\n\nHiermit wird ...\n".to_string(),
+            }),
+        );
+        let request = FillRequest::from_cache(&cache, "MIT").fullname("Jane Doe").year(2025);
+
+        let translated = request.clone().lang(Some("DE")).render().unwrap();
+        assert_eq!(translated.body, "Copyright (c) 2025 Jane Doe\n\nHiermit wird ...");
+        assert!(translated.unfilled.is_empty());
+        assert!(request.clone().lang(Some("fr")).render().is_err());
+
+        cache.pinnedTemplateBodies.insert("old".to_string(), "---\ntitle: MIT\n---\n\nOld text (c) [year] [fullname]\n".to_string());
+        let request = FillRequest::from_cache(&cache, "MIT").fullname("Jane Doe").year(2025);
+
+        let pinned = request.clone().template_sha(Some("old")).render().unwrap();
+        assert_eq!(pinned.body, "Old text (c) 2025 Jane Doe");
+        assert!(request.template_sha(Some("missing")).render().is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use once_cell::sync::Lazy;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::constants::{CHOOSEALICENSE_LICENSE_URL, DEFAULT_PAGER, DEFAULT_WRAP_WIDTH};
+
+// Off by default so rendered output stays plain for the library and tests; the CLI
+// turns it on after probing the terminal.
+pub static HYPERLINKS: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+
+// Columns available for output; 0 means unknown (not a terminal and no --width), in
+// which case fixed legacy widths are used so piped output stays stable.
+pub static WIDTH: Lazy<AtomicUsize> = Lazy::new(|| AtomicUsize::new(0));
+
+#[cfg(any(unix, windows))]
+fn StdoutTerminalWidth() -> Option<usize> {
+    terminal_size::terminal_size_of(io::stdout())
+        .map(|(terminal_size::Width(columns), _)| columns as usize)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn StdoutTerminalWidth() -> Option<usize> {
+    None
+}
+
+#[cfg(any(unix, windows))]
+fn StdoutTerminalHeight() -> Option<usize> {
+    terminal_size::terminal_size_of(io::stdout()).map(|(_, terminal_size::Height(rows))| rows as usize)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn StdoutTerminalHeight() -> Option<usize> {
+    None
+}
+
+// Prints `text` to stdout, through $PAGER (default "less -R") when stdout is a terminal
+// the text does not fit on. Falls back to printing if the pager cannot be started.
+pub fn PrintPaged(text: &str) {
+    let fits = StdoutTerminalHeight().is_none_or(|rows| text.lines().count() < rows);
+
+    if !fits && io::stdout().is_terminal() && RunPager(text).is_ok() {
+        return;
+    }
+
+    print!("{}", text);
+}
+
+fn RunPager(text: &str) -> io::Result<()> {
+    use std::io::Write;
+
+    let pager = env::var("PAGER").ok().filter(|p| !p.trim().is_empty()).unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_PAGER);
+
+    let mut child = std::process::Command::new(program)
+        .args(parts)
+        .stdin(std::process::Stdio::piped())
+        .spawn()?;
+
+    // The user may quit the pager before reading everything; that is not an error
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    child.wait().map(|_| ())
+}
+
+// --width wins, then the size of the terminal on stdout, then $COLUMNS.
+pub fn DetectWidth(requested: Option<usize>) -> Option<usize> {
+    requested
+        .or_else(StdoutTerminalWidth)
+        .or_else(|| env::var("COLUMNS").ok().and_then(|c| c.parse().ok()))
+        .filter(|columns| *columns > 0)
+}
+
+pub fn OutputWidth() -> Option<usize> {
+    match WIDTH.load(Ordering::SeqCst) {
+        0 => None,
+        columns => Some(columns),
+    }
+}
+
+// Width for wrapped prose, which stays readable even on very wide terminals
+pub fn WrapWidth() -> usize {
+    OutputWidth().map_or(DEFAULT_WRAP_WIDTH, |columns| columns.min(DEFAULT_WRAP_WIDTH * 3 / 2))
+}
+
+// Progress bars and spinners; off for dumb terminals and NO_COLOR
+pub static ANIMATIONS: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(true));
+
+// NO_COLOR (https://no-color.org) with any non-empty value, or a terminal that cannot
+// handle escape sequences at all.
+pub fn PlainOutputRequested() -> bool {
+    env::var("NO_COLOR").is_ok_and(|value| !value.is_empty())
+        || env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+pub fn ColorEnabled() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+// Plain ASCII for glyphs and progress bars, for consoles and log collectors that
+// mangle anything else
+pub static ASCII: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+
+// The effective locale is the first of LC_ALL, LC_CTYPE and LANG that is set. An unset
+// locale says nothing about the terminal, so only an explicit non-UTF-8 one (including
+// "C" and "POSIX") counts.
+fn LocaleIsUtf8() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+        .is_none_or(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+pub fn DetectAsciiOnly(requested: bool) -> bool {
+    requested || !LocaleIsUtf8()
+}
+
+pub fn AsciiOnly() -> bool {
+    ASCII.load(Ordering::SeqCst)
+}
+
+// Braille spinner frames need a font that has them: fine on Unix terminals and in
+// Windows Terminal, missing in the classic Windows console host
+pub fn SpinnerGlyphsSupported() -> bool {
+    !AsciiOnly() && (cfg!(not(windows)) || env::var_os("WT_SESSION").is_some())
+}
+
+// What the console on stdout/stderr can show once PrepareConsole has set it up.
+pub struct ConsoleSupport {
+    // Escape sequences for colors and progress bar redraws
+    pub escapes: bool,
+    // UTF-8 text, e.g. the box-drawing and check mark glyphs
+    pub unicode: bool,
+    #[cfg(windows)]
+    restoreCodePage: Option<u32>,
+}
+
+#[cfg(windows)]
+const CP_UTF8: u32 = 65001;
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetConsoleOutputCP() -> u32;
+    fn SetConsoleOutputCP(codePageId: u32) -> i32;
+}
+
+// Windows consoles start without escape-sequence processing and with an OEM code page
+// (e.g. 437 or 850 in cmd.exe), which garbles colors and every non-ASCII byte we print.
+// Switches both on; a legacy console that refuses gets plain ASCII output instead. The
+// previous code page is restored when the returned value is dropped, since cmd.exe keeps
+// it for later programs.
+#[cfg(windows)]
+pub fn PrepareConsole() -> ConsoleSupport {
+    // Checking color support enables virtual terminal processing where the console allows it
+    let escapes = [console::Term::stdout(), console::Term::stderr()]
+        .iter()
+        .all(|term| !term.is_term() || term.features().colors_supported());
+
+    // SAFETY: plain kernel32 calls without pointers; both fail harmlessly without a console
+    let previous = unsafe { GetConsoleOutputCP() };
+    let unicode = previous == CP_UTF8 || unsafe { SetConsoleOutputCP(CP_UTF8) } != 0;
+
+    ConsoleSupport {
+        escapes,
+        unicode,
+        restoreCodePage: (previous != 0 && previous != CP_UTF8 && unicode).then_some(previous),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn PrepareConsole() -> ConsoleSupport {
+    ConsoleSupport { escapes: true, unicode: true }
+}
+
+#[cfg(windows)]
+impl Drop for ConsoleSupport {
+    fn drop(&mut self) {
+        if let Some(codePage) = self.restoreCodePage {
+            // SAFETY: see PrepareConsole
+            unsafe { SetConsoleOutputCP(codePage) };
+        }
+    }
+}
+
+fn EnvFlag(name: &str) -> Option<bool> {
+    env::var(name).ok().map(|value| !matches!(value.as_str(), "" | "0" | "false"))
+}
+
+// Terminals known to render OSC 8 links. Unknown terminals get plain text, since
+// unsupported ones may print the escape sequence literally.
+pub fn DetectHyperlinkSupport() -> bool {
+    if let Some(forced) = EnvFlag("FORCE_HYPERLINK") {
+        return forced;
+    }
+
+    if !io::stdout().is_terminal() || env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return false;
+    }
+
+    if env::var("WT_SESSION").is_ok()
+        || env::var("KONSOLE_VERSION").is_ok()
+        || env::var("DOMTERM").is_ok()
+    {
+        return true;
+    }
+
+    if env::var("VTE_VERSION")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v >= 5000)
+    {
+        return true;
+    }
+
+    if env::var("TERM_PROGRAM").is_ok_and(|program| {
+        matches!(program.as_str(), "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper")
+    }) {
+        return true;
+    }
+
+    env::var("TERM").is_ok_and(|term| {
+        ["kitty", "alacritty", "foot", "wezterm", "ghostty"]
+            .iter()
+            .any(|known| term.contains(known))
+    })
+}
+
+// `text` as a clickable link to `url` when enabled, otherwise unchanged.
+pub fn Hyperlink(text: &str, url: &str) -> String {
+    if HYPERLINKS.load(Ordering::SeqCst) {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        text.to_string()
+    }
+}
+
+// Like Hyperlink, padded to `width` visible columns. Format-string padding would count
+// the escape bytes and misalign tables.
+pub fn HyperlinkPadded(text: &str, url: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.chars().count());
+    format!("{}{}", Hyperlink(text, url), " ".repeat(padding))
+}
+
+pub fn LicensePageUrl(spdxId: &str) -> String {
+    format!("{}{}/", CHOOSEALICENSE_LICENSE_URL, spdxId.to_lowercase())
+}
@@ -0,0 +1,44 @@
+// Helpers shared by the unit tests of several modules.
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+// A scratch directory under the system temp dir, emptied on creation and removed on drop.
+// The process ID keeps parallel test runs apart; `name` keeps tests within one run apart.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn New(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("getlicense-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// Owned copies of string literals, for tag lists and the like
+pub fn Strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
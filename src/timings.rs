@@ -0,0 +1,112 @@
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Set by --timings. Nothing is recorded while this is false.
+pub static ENABLED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+
+static STARTED: Lazy<Instant> = Lazy::new(Instant::now);
+static PHASES: Lazy<Mutex<Vec<PhaseTiming>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    pub phase: String,
+    // File name or command for phases that run more than once, e.g. each fetch
+    pub detail: Option<String>,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTotal {
+    pub phase: String,
+    pub count: usize,
+    pub total: Duration,
+    pub slowest: Option<PhaseTiming>,
+}
+
+pub fn Enable() {
+    Lazy::force(&STARTED);
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+pub fn IsEnabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+// Records the time elapsed since `started`. Call sites still take the Instant when timings
+// are off; that is cheap enough not to guard everywhere.
+pub fn Record(phase: &str, detail: Option<&str>, started: Instant) {
+    if !IsEnabled() {
+        return;
+    }
+
+    let timing = PhaseTiming {
+        phase: phase.to_string(),
+        detail: detail.map(str::to_string),
+        duration: started.elapsed(),
+    };
+
+    if let Ok(mut phases) = PHASES.lock() {
+        phases.push(timing);
+    }
+}
+
+pub fn Recorded() -> Vec<PhaseTiming> {
+    PHASES.lock().map(|phases| phases.clone()).unwrap_or_default()
+}
+
+pub fn SinceStart() -> Duration {
+    STARTED.elapsed()
+}
+
+// One row per phase in first-seen order, so the report reads in the order things happened
+pub fn SummarizePhases(timings: &[PhaseTiming]) -> Vec<PhaseTotal> {
+    let mut totals: Vec<PhaseTotal> = Vec::new();
+
+    for timing in timings {
+        let index = match totals.iter().position(|t| t.phase == timing.phase) {
+            Some(index) => index,
+            None => {
+                totals.push(PhaseTotal { phase: timing.phase.clone(), count: 0, total: Duration::ZERO, slowest: None });
+                totals.len() - 1
+            }
+        };
+
+        let total = &mut totals[index];
+        total.count += 1;
+        total.total += timing.duration;
+        if total.slowest.as_ref().is_none_or(|s| s.duration < timing.duration) {
+            total.slowest = Some(timing.clone());
+        }
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn TestSummarizePhases() {
+        let timing = |phase: &str, detail: Option<&str>, millis| PhaseTiming {
+            phase: phase.to_string(),
+            detail: detail.map(str::to_string),
+            duration: Duration::from_millis(millis),
+        };
+        let timings = vec![
+            timing("cache load", None, 12),
+            timing("fetch", Some("mit.txt"), 40),
+            timing("fetch", Some("gpl-3.0.txt"), 90),
+            timing("parse", Some("mit.txt"), 1),
+        ];
+
+        let totals = SummarizePhases(&timings);
+
+        assert_eq!(totals.iter().map(|t| t.phase.as_str()).collect::<Vec<_>>(), ["cache load", "fetch", "parse"]);
+        assert_eq!(totals[1].count, 2);
+        assert_eq!(totals[1].total, Duration::from_millis(130));
+        assert_eq!(totals[1].slowest.as_ref().and_then(|s| s.detail.as_deref()), Some("gpl-3.0.txt"));
+    }
+}
@@ -0,0 +1,72 @@
+use colored::*;
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use crate::api;
+use crate::config::Config;
+use crate::error::{ActionError, AppError};
+use crate::models::{Cache, TranslationEntry};
+
+// Makes sure the cache holds the `lang` translation of each license, fetching it from its
+// [translations] source (or the built-in one) when missing, when the source changed, or on --refresh.
+// Returns whether the cache changed.
+pub async fn EnsureTranslations(
+    cache: &mut Cache,
+    spdxIds: &[String],
+    lang: &str,
+    appConfig: &Config,
+    refresh: bool,
+) -> Result<bool, AppError> {
+    let lang = lang.to_lowercase();
+    let mut fetched = false;
+
+    for spdxId in spdxIds {
+        let spdxIdLower = spdxId.to_lowercase();
+        let source = appConfig.TranslationSource(&spdxIdLower, &lang).ok_or_else(|| {
+            AppError::ActionErrorVariant(ActionError::InvalidInput(format!(
+                "no '{}' translation of {} is known; add it under [translations.\"{}\"] in config.toml",
+                lang, spdxId, spdxIdLower
+            )))
+        })?;
+
+        let cached = cache.translations.get(&spdxIdLower).and_then(|byLang| byLang.get(&lang));
+        if !refresh && cached.is_some_and(|entry| entry.source == source) {
+            continue;
+        }
+
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Translations] Fetching the {} translation of {} from {}", lang, spdxId, source);
+        }
+
+        let content = match ReadSource(&source).await {
+            Ok(content) => content,
+            // A stale copy beats failing, e.g. offline after the source moved
+            Err(e) if cached.is_some() => {
+                eprintln!("{} could not refresh the {} translation of {} ({}); using the cached copy.", "Warning:".yellow(), lang, spdxId, e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        cache.translations.entry(spdxIdLower).or_default().insert(
+            lang.clone(),
+            Arc::new(TranslationEntry {
+                source,
+                content,
+            }),
+        );
+        fetched = true;
+    }
+
+    Ok(fetched)
+}
+
+// URLs are downloaded, anything else is read as a local path
+async fn ReadSource(source: &str) -> Result<String, AppError> {
+    if source.starts_with("https://") || source.starts_with("http://") {
+        return Ok(api::FetchFileContent(source).await?);
+    }
+
+    fs::read_to_string(source).map_err(|e| AppError::Io(e, source.into()))
+}
@@ -0,0 +1,150 @@
+use chrono::Utc;
+use colored::*;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::constants::{
+    SELF_RELEASE_OWNER, SELF_RELEASE_REPO, UPDATE_CHECK_INTERVAL_HOURS, UPDATE_CHECK_TIMEOUT_SECS,
+};
+use crate::models::UpdateCheckState;
+
+// "v1.2.3" / "1.2.3" -> (1, 2, 3). Pre-release suffixes are ignored.
+pub fn ParseVersion(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v');
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+
+    Some((
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+pub fn IsNewerThanCurrent(tag: &str) -> bool {
+    match (ParseVersion(tag), ParseVersion(env!("CARGO_PKG_VERSION"))) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+// A few bytes next to the cache, so recording a check never rewrites the cache itself
+pub fn UpdateCheckPathFor(cachePath: &Path) -> PathBuf {
+    let stem = cachePath
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cache".to_string());
+
+    cachePath.with_file_name(format!("{}.update-check.json", stem))
+}
+
+// A missing or unreadable file just means the check is due
+fn LoadUpdateCheckState(statePath: &Path) -> Option<UpdateCheckState> {
+    serde_json::from_slice(&fs::read(statePath).ok()?).ok()
+}
+
+fn IsCheckDue(state: Option<&UpdateCheckState>, appConfig: &Config) -> bool {
+    if appConfig.updateCheck == Some(false)
+        || crate::OFFLINE.load(Ordering::SeqCst)
+        || !std::io::stderr().is_terminal()
+    {
+        return false;
+    }
+
+    state.is_none_or(|state| {
+        Utc::now() - state.checkedAt >= chrono::Duration::hours(UPDATE_CHECK_INTERVAL_HOURS)
+    })
+}
+
+// `statePath` is None when nothing may be written (--no-cache-write, --cache none): the
+// check could never be recorded, so it would run on every invocation and is skipped.
+// Never fails the command: network errors and timeouts are swallowed and retried on the
+// next day.
+pub async fn NotifyIfUpdateAvailable(statePath: Option<&Path>, appConfig: &Config) {
+    let Some(statePath) = statePath else {
+        return;
+    };
+
+    if !IsCheckDue(LoadUpdateCheckState(statePath).as_ref(), appConfig) {
+        return;
+    }
+
+    let fetch = crate::api::FetchLatestRelease(SELF_RELEASE_OWNER, SELF_RELEASE_REPO);
+    let latestVersion = match tokio::time::timeout(Duration::from_secs(UPDATE_CHECK_TIMEOUT_SECS), fetch).await {
+        Ok(Ok(release)) => Some(release.tagName),
+        Ok(Err(e)) => {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[Update] Could not check for a newer version: {}", e);
+            }
+            None
+        }
+        Err(_) => {
+            if crate::VERBOSE.load(Ordering::SeqCst) {
+                eprintln!("[Update] Version check timed out.");
+            }
+            None
+        }
+    };
+
+    if let Some(tag) = &latestVersion {
+        if IsNewerThanCurrent(tag) {
+            eprintln!(
+                "{}",
+                format!(
+                    "getlicense {} is available (you have {}). Set update_check = false in the config to silence this.",
+                    tag,
+                    env!("CARGO_PKG_VERSION")
+                )
+                .dimmed()
+            );
+        }
+    }
+
+    let state = UpdateCheckState {
+        checkedAt: Utc::now(),
+        latestVersion,
+    };
+    let written = serde_json::to_vec(&state)
+        .map_err(std::io::Error::other)
+        .and_then(|content| fs::write(statePath, content));
+
+    if let Err(e) = written {
+        if crate::VERBOSE.load(Ordering::SeqCst) {
+            eprintln!("[Update] Could not record the version check in {:?}: {}", statePath, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn TestParseVersion() {
+        assert_eq!(ParseVersion("v0.2.0"), Some((0, 2, 0)));
+        assert_eq!(ParseVersion("1.10"), Some((1, 10, 0)));
+        assert_eq!(ParseVersion("1.2.3-beta.1"), Some((1, 2, 3)));
+        assert_eq!(ParseVersion("latest"), None);
+    }
+
+    #[test]
+    fn TestUpdateCheckState() {
+        let dir = TempDir::New("update");
+        let statePath = UpdateCheckPathFor(&dir.join("license_cache_rs.json"));
+        assert_eq!(statePath.file_name().unwrap(), "license_cache_rs.update-check.json");
+        assert!(LoadUpdateCheckState(&statePath).is_none());
+
+        fs::write(&statePath, r#"{"checkedAt":"2026-01-01T00:00:00Z","latestVersion":"v0.3.0"}"#).unwrap();
+        let state = LoadUpdateCheckState(&statePath).unwrap();
+        assert_eq!(state.latestVersion.as_deref(), Some("v0.3.0"));
+
+        // Turned off in the config, a check is never due, however old the last one
+        let config = Config { updateCheck: Some(false), ..Config::default() };
+        assert!(!IsCheckDue(Some(&state), &config));
+    }
+}